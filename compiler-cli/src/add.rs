@@ -5,7 +5,11 @@ use gleam_core::{
     Error, Result,
 };
 
-use crate::{cli, dependencies::UseManifest, fs};
+use crate::{
+    cli,
+    dependencies::{Connectivity, UseManifest},
+    fs,
+};
 
 pub fn command(packages: Vec<String>, dev: bool) -> Result<()> {
     let paths = crate::project_paths_at_current_directory();
@@ -17,6 +21,7 @@ pub fn command(packages: Vec<String>, dev: bool) -> Result<()> {
         cli::Reporter::new(),
         Some((packages.to_vec(), dev)),
         UseManifest::Yes,
+        Connectivity::Online,
     )?;
 
     // Read gleam.toml so we can insert new deps into it
@@ -3,20 +3,35 @@ use std::{sync::Arc, time::Instant};
 use gleam_core::{
     build::{Built, Codegen, Options, ProjectCompiler},
     paths::ProjectPaths,
+    warning::WarningEmitterIO,
     Result,
 };
 
 use crate::{
     build_lock::BuildLock,
-    cli,
-    dependencies::UseManifest,
-    fs::{self, ConsoleWarningEmitter},
+    cli::{self, MessageFormat},
+    dependencies::{Connectivity, UseManifest},
+    fs::{self, ConsoleWarningEmitter, JsonWarningEmitter},
 };
 
 pub fn main(options: Options) -> Result<Built> {
+    main_with_message_format(options, MessageFormat::Human, Connectivity::Online)
+}
+
+/// Build the project, printing any warnings in the given format.
+pub fn main_with_message_format(
+    options: Options,
+    message_format: MessageFormat,
+    connectivity: Connectivity,
+) -> Result<Built> {
     let paths = crate::project_paths_at_current_directory();
-    let manifest =
-        crate::dependencies::download(&paths, cli::Reporter::new(), None, UseManifest::Yes)?;
+    let manifest = crate::dependencies::download(
+        &paths,
+        cli::Reporter::new(),
+        None,
+        UseManifest::Yes,
+        connectivity,
+    )?;
 
     let perform_codegen = options.codegen;
     let root_config = crate::config::root_config()?;
@@ -29,6 +44,10 @@ pub fn main(options: Options) -> Result<Built> {
         options.target.unwrap_or(root_config.target),
     )?;
     let current_dir = std::env::current_dir().expect("Failed to get current directory");
+    let warnings: Arc<dyn WarningEmitterIO> = match message_format {
+        MessageFormat::Human => Arc::new(ConsoleWarningEmitter),
+        MessageFormat::Json => Arc::new(JsonWarningEmitter),
+    };
 
     tracing::info!("Compiling packages");
     let compiled = {
@@ -38,7 +57,7 @@ pub fn main(options: Options) -> Result<Built> {
             options,
             manifest.packages,
             telemetry,
-            Arc::new(ConsoleWarningEmitter),
+            warnings,
             ProjectPaths::new(current_dir),
             io,
         );
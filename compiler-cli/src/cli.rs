@@ -1,5 +1,6 @@
 use gleam_core::{
     build::Telemetry,
+    diagnostic::Diagnostic,
     error::{Error, StandardIoAction},
 };
 use hexpm::version::Version;
@@ -7,7 +8,20 @@ use std::{
     io::Write,
     time::{Duration, Instant},
 };
-use termcolor::{BufferWriter, Color, ColorChoice, ColorSpec, WriteColor};
+use strum::{EnumString, EnumVariantNames};
+use termcolor::{Buffer, BufferWriter, Color, ColorChoice, ColorSpec, WriteColor};
+
+/// How errors and warnings are printed to standard error.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, EnumString, EnumVariantNames)]
+#[strum(serialize_all = "lowercase")]
+pub enum MessageFormat {
+    /// Coloured text with the source code of each problem, for people.
+    #[default]
+    Human,
+    /// One JSON object per line for each diagnostic, for editors and other
+    /// tools.
+    Json,
+}
 
 #[derive(Debug, Default, Clone)]
 pub struct Reporter;
@@ -83,6 +97,10 @@ pub fn print_unretired(package: &str, version: &str) {
     print_colourful_prefix("  Unretired", &format!("{package} {version}"))
 }
 
+pub fn print_vendored(count: usize) {
+    print_colourful_prefix("   Vendored", &format!("{count} packages"))
+}
+
 pub fn print_publishing_documentation() {
     print_colourful_prefix(" Publishing", "documentation");
 }
@@ -127,10 +145,30 @@ pub(crate) fn print_added(text: &str) {
     print_colourful_prefix("      Added", text)
 }
 
+pub(crate) fn print_watching() {
+    print_colourful_prefix("   Watching", "for changes...")
+}
+
+/// Clear the terminal and move the cursor to the top left so each watch mode
+/// rebuild starts with a clean screen. Nothing is printed if standard output
+/// is not a terminal, as the escape codes would only clutter a log file.
+pub(crate) fn clear_screen() {
+    if atty::is(atty::Stream::Stdout) {
+        print!("\x1b[2J\x1b[H");
+        std::io::stdout()
+            .flush()
+            .expect("clear_screen stdout flush");
+    }
+}
+
 pub(crate) fn print_generating_documentation() {
     print_colourful_prefix(" Generating", "documentation")
 }
 
+pub(crate) fn print_serving(url: &str) {
+    print_colourful_prefix("    Serving", &format!("documentation at {url}"))
+}
+
 fn print_packages_downloaded(start: Instant, count: usize) {
     let elapsed = seconds(start.elapsed());
     let msg = match count {
@@ -162,6 +200,25 @@ pub fn print_colourful_prefix(prefix: &str, text: &str) {
     buffer_writer.print(&buffer).expect("print_green_prefix");
 }
 
+pub(crate) fn print_error(error: &Error, format: MessageFormat) {
+    let buffer_writer = stderr_buffer_writer();
+    let mut buffer = buffer_writer.buffer();
+    match format {
+        MessageFormat::Human => error.pretty(&mut buffer),
+        MessageFormat::Json => write_json_diagnostics(&mut buffer, error.to_diagnostics()),
+    }
+    buffer_writer.print(&buffer).expect("Error writing");
+}
+
+pub(crate) fn write_json_diagnostics(
+    buffer: &mut Buffer,
+    diagnostics: impl IntoIterator<Item = Diagnostic>,
+) {
+    for diagnostic in diagnostics {
+        writeln!(buffer, "{}", diagnostic.to_json()).expect("Diagnostic writing");
+    }
+}
+
 pub fn stderr_buffer_writer() -> BufferWriter {
     // Don't add color codes to the output if standard error isn't connected to a terminal
     termcolor::BufferWriter::stderr(color_choice())
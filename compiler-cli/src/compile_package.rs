@@ -17,7 +17,7 @@ use std::{path::Path, sync::Arc};
 
 pub fn command(options: CompilePackage) -> Result<()> {
     let ids = UniqueIdGenerator::new();
-    let mut type_manifests = load_libraries(&ids, &options.libraries_directory)?;
+    let mut type_manifests = load_libraries(&ids, &options.libraries_directory, None)?;
     let mut defined_modules = im::HashMap::new();
     let warnings = WarningEmitter::new(Arc::new(ConsoleWarningEmitter));
     let paths = ProjectPaths::new(options.package_directory.clone());
@@ -49,10 +49,19 @@ pub fn command(options: CompilePackage) -> Result<()> {
     Ok(())
 }
 
-fn load_libraries(ids: &UniqueIdGenerator, lib: &Path) -> Result<im::HashMap<SmolStr, Module>> {
+/// Reads the metadata of the compiled packages in the lib directory, leaving
+/// out the package with the given name if there is one.
+pub(crate) fn load_libraries(
+    ids: &UniqueIdGenerator,
+    lib: &Path,
+    skip_package: Option<&str>,
+) -> Result<im::HashMap<SmolStr, Module>> {
     tracing::info!("Reading precompiled module metadata files");
     let mut manifests = im::HashMap::new();
     for lib in fs::read_dir(lib)?.filter_map(Result::ok) {
+        if skip_package.is_some_and(|package| lib.file_name() == package) {
+            continue;
+        }
         let path = lib.path().join(paths::ARTEFACT_DIRECTORY_NAME);
         if !path.is_dir() {
             continue;
@@ -1,9 +1,10 @@
 use std::path::PathBuf;
 
 use gleam_core::{
-    config::PackageConfig,
+    config::{PackageConfig, UserConfig},
     error::{Error, FileIoAction, FileKind},
-    paths::ProjectPaths,
+    hex::Repositories,
+    paths::{self, ProjectPaths},
 };
 
 pub fn root_config() -> Result<PackageConfig, Error> {
@@ -22,6 +23,63 @@ pub fn read(config_path: PathBuf) -> Result<PackageConfig, Error> {
     })
 }
 
+/// Reads the user's global config, which may not exist.
+pub fn user_config() -> Result<UserConfig, Error> {
+    read_user_config(paths::global_config())
+}
+
+fn read_user_config(path: PathBuf) -> Result<UserConfig, Error> {
+    if !path.is_file() {
+        return Ok(UserConfig::default());
+    }
+    let toml = crate::fs::read(&path)?;
+    toml::from_str(&toml).map_err(|e| Error::FileIo {
+        action: FileIoAction::Parse,
+        kind: FileKind::File,
+        path,
+        err: Some(e.to_string()),
+    })
+}
+
+/// The Hex repositories configured by the project and the user's global
+/// config.
+pub fn repositories(config: &PackageConfig) -> Result<Repositories, Error> {
+    Repositories::new(&config.repositories, &user_config()?.repositories)
+}
+
+#[test]
+fn read_missing_user_config() {
+    let tmp = tempfile::tempdir().unwrap();
+    assert_eq!(
+        read_user_config(tmp.path().join("config.toml")).unwrap(),
+        UserConfig::default()
+    );
+}
+
+#[test]
+fn read_user_config_repositories() {
+    let tmp = tempfile::tempdir().unwrap();
+    let path = tmp.path().join("config.toml");
+    std::fs::write(
+        &path,
+        r#"
+[[repositories]]
+name = "company"
+auth_key = "secret"
+"#,
+    )
+    .unwrap();
+    let config = read_user_config(path).unwrap();
+    assert_eq!(
+        config.repositories,
+        vec![gleam_core::config::HexRepositoryConfig {
+            name: "company".into(),
+            auth_key: Some("secret".into()),
+            ..Default::default()
+        }]
+    );
+}
+
 pub fn ensure_config_exists(paths: &ProjectPaths) -> Result<(), Error> {
     let path = paths.root_config();
     if !path.is_file() {
@@ -1,21 +1,26 @@
 use std::{
+    cell::RefCell,
     collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+    process::Command,
+    rc::Rc,
     time::Instant,
 };
 
 use flate2::read::GzDecoder;
 use futures::future;
 use gleam_core::{
-    build::{Mode, Telemetry},
+    build::{dep_tree::DependencyTree, Mode, Target, Telemetry},
     config::PackageConfig,
     error::{FileIoAction, FileKind, StandardIoAction},
-    hex::{self, HEXPM_PUBLIC_KEY},
+    hex::{self, Repositories, Repository, HEXPM_REPOSITORY},
     io::{HttpClient as _, TarUnpacker, WrappedReader},
     manifest::{Base16Checksum, Manifest, ManifestPackage, ManifestPackageSource},
     paths::ProjectPaths,
+    requirement::Requirement,
     Error, Result,
 };
-use hexpm::version::Version;
+use hexpm::version::{Range, Version};
 use itertools::Itertools;
 use smol_str::SmolStr;
 use strum::IntoEnumIterator;
@@ -39,6 +44,7 @@ pub fn list() -> Result<()> {
         &config,
         &cli::Reporter::new(),
         UseManifest::Yes,
+        Connectivity::Online,
     )?;
     list_manifest_packages(std::io::stdout(), manifest)
 }
@@ -68,6 +74,7 @@ fn list_manifest_format() {
                 requirements: vec![],
                 source: ManifestPackageSource::Hex {
                     outer_checksum: Base16Checksum(vec![1, 2, 3, 4]),
+                    repository: "hexpm".into(),
                 },
             },
             ManifestPackage {
@@ -78,6 +85,7 @@ fn list_manifest_format() {
                 requirements: vec!["zzz".into(), "gleam_stdlib".into()],
                 source: ManifestPackageSource::Hex {
                     outer_checksum: Base16Checksum(vec![3, 22]),
+                    repository: "hexpm".into(),
                 },
             },
             ManifestPackage {
@@ -88,9 +96,11 @@ fn list_manifest_format() {
                 requirements: vec![],
                 source: ManifestPackageSource::Hex {
                     outer_checksum: Base16Checksum(vec![3, 22]),
+                    repository: "hexpm".into(),
                 },
             },
         ],
+        local_requirements: HashMap::new(),
     };
     list_manifest_packages(&mut buffer, manifest).unwrap();
     assert_eq!(
@@ -102,15 +112,426 @@ zzz 0.4.0
     )
 }
 
+/// Which of the root package's dependencies to include in a dependency tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DependencyKind {
+    All,
+    Prod,
+    Dev,
+}
+
+impl DependencyKind {
+    pub fn from_flags(prod: bool, dev: bool) -> Self {
+        match (prod, dev) {
+            (true, false) => Self::Prod,
+            (false, true) => Self::Dev,
+            _ => Self::All,
+        }
+    }
+}
+
+pub fn tree(kind: DependencyKind) -> Result<()> {
+    let graph = read_dependency_graph(kind)?;
+    graph.write_tree(std::io::stdout())
+}
+
+pub fn why(package: &str) -> Result<()> {
+    let graph = read_dependency_graph(DependencyKind::All)?;
+    graph.write_paths_to(std::io::stdout(), package)
+}
+
+/// The manifest of the project, with the versions of its dependencies
+/// resolved again if gleam.toml has changed since the manifest was written.
+pub fn current_manifest(paths: &ProjectPaths, config: &PackageConfig) -> Result<Manifest> {
+    let runtime = tokio::runtime::Runtime::new().expect("Unable to start Tokio async runtime");
+    let (_, manifest) = get_manifest(
+        paths,
+        runtime.handle().clone(),
+        Mode::Dev,
+        config,
+        &cli::Reporter::new(),
+        UseManifest::Yes,
+        Connectivity::Online,
+    )?;
+    Ok(manifest)
+}
+
+fn read_dependency_graph(kind: DependencyKind) -> Result<DependencyGraph> {
+    let paths = crate::project_paths_at_current_directory();
+    let config = crate::config::root_config()?;
+    let manifest = current_manifest(&paths, &config)?;
+    Ok(DependencyGraph::new(&config, &manifest, kind, |package| {
+        let path = match &package.source {
+            ManifestPackageSource::Local { path } => paths.root().join(path).join("gleam.toml"),
+            ManifestPackageSource::Hex { .. } | ManifestPackageSource::Git { .. } => {
+                paths.build_packages_package_config(&package.name)
+            }
+        };
+        // Packages built with other tools have no gleam.toml, so the ranges
+        // they require their dependencies in are not known.
+        if path.is_file() {
+            crate::config::read(path).ok()
+        } else {
+            None
+        }
+    }))
+}
+
+/// The packages of the project and the dependencies between them, as locked
+/// in the manifest.
+#[derive(Debug)]
+struct DependencyGraph {
+    root: String,
+    tree: DependencyTree<String>,
+    versions: HashMap<String, Version>,
+    /// The requirement each package has on each of its dependencies, if known.
+    requirements: HashMap<(String, String), Requirement>,
+    dev_dependencies: HashSet<String>,
+}
+
+impl DependencyGraph {
+    fn new(
+        config: &PackageConfig,
+        manifest: &Manifest,
+        kind: DependencyKind,
+        read_config: impl Fn(&ManifestPackage) -> Option<PackageConfig>,
+    ) -> Self {
+        let root = config.name.to_string();
+        let mut root_dependencies = vec![];
+        let mut requirements = HashMap::new();
+        let mut dev_dependencies = HashSet::new();
+        if kind != DependencyKind::Dev {
+            for (name, requirement) in &config.dependencies {
+                root_dependencies.push(name.clone());
+                let _ = requirements.insert((root.clone(), name.clone()), requirement.clone());
+            }
+        }
+        if kind != DependencyKind::Prod {
+            for (name, requirement) in &config.dev_dependencies {
+                root_dependencies.push(name.clone());
+                let _ = requirements.insert((root.clone(), name.clone()), requirement.clone());
+                let _ = dev_dependencies.insert(name.clone());
+            }
+        }
+
+        let mut versions = HashMap::new();
+        let _ = versions.insert(root.clone(), config.version.clone());
+        let mut inputs = vec![(root.clone(), root_dependencies)];
+        for package in &manifest.packages {
+            let _ = versions.insert(package.name.clone(), package.version.clone());
+            if let Some(package_config) = read_config(package) {
+                for (name, requirement) in package_config.dependencies {
+                    let _ = requirements.insert((package.name.clone(), name), requirement);
+                }
+            }
+            inputs.push((package.name.clone(), package.requirements.clone()));
+        }
+
+        Self {
+            root,
+            tree: DependencyTree::new(inputs),
+            versions,
+            requirements,
+            dev_dependencies,
+        }
+    }
+
+    fn describe(&self, package: &str) -> String {
+        match self.versions.get(package) {
+            Some(version) => format!("{package} {version}"),
+            None => package.to_string(),
+        }
+    }
+
+    fn write_tree<W: std::io::Write>(&self, mut buffer: W) -> Result<()> {
+        let mut lines = vec![self.describe(&self.root)];
+        self.tree_lines(&self.root, "", &mut vec![self.root.as_str()], &mut lines);
+        lines
+            .into_iter()
+            .try_for_each(|line| writeln!(buffer, "{line}"))
+            .map_err(|e| Error::StandardIo {
+                action: StandardIoAction::Write,
+                err: Some(e.kind()),
+            })
+    }
+
+    fn tree_lines<'a>(
+        &'a self,
+        package: &str,
+        indent: &str,
+        ancestors: &mut Vec<&'a str>,
+        lines: &mut Vec<String>,
+    ) {
+        let dependencies = self
+            .tree
+            .dependencies(&package.to_string())
+            .into_iter()
+            .sorted()
+            .collect_vec();
+        let count = dependencies.len();
+        for (i, dependency) in dependencies.into_iter().enumerate() {
+            let last = i + 1 == count;
+            let (branch, child_indent) = if last {
+                ("└── ", "    ")
+            } else {
+                ("├── ", "│   ")
+            };
+            let mut line = format!("{indent}{branch}{}", self.describe(dependency));
+            let requirement = self
+                .requirements
+                .get(&(package.to_string(), dependency.clone()));
+            if let Some(requirement) = requirement {
+                line.push_str(&format!(" ({requirement})"));
+            }
+            if package == self.root && self.dev_dependencies.contains(dependency) {
+                line.push_str(" [dev]");
+            }
+            lines.push(line);
+
+            // Manifests should not contain cycles, but we make sure not to
+            // recurse forever if one does.
+            if ancestors.contains(&dependency.as_str()) {
+                continue;
+            }
+            ancestors.push(dependency);
+            self.tree_lines(
+                dependency,
+                &format!("{indent}{child_indent}"),
+                ancestors,
+                lines,
+            );
+            let _ = ancestors.pop();
+        }
+    }
+
+    fn write_paths_to<W: std::io::Write>(&self, mut buffer: W, package: &str) -> Result<()> {
+        let paths = self
+            .tree
+            .paths(&self.root, &package.to_string())
+            .into_iter()
+            .sorted()
+            .collect_vec();
+        if paths.is_empty() || package == self.root {
+            return Err(Error::UnknownDependency {
+                name: package.to_string(),
+            });
+        }
+        paths
+            .into_iter()
+            .map(|path| {
+                path.into_iter()
+                    .map(|name| self.describe(name))
+                    .join(" -> ")
+            })
+            .try_for_each(|line| writeln!(buffer, "{line}"))
+            .map_err(|e| Error::StandardIo {
+                action: StandardIoAction::Write,
+                err: Some(e.kind()),
+            })
+    }
+}
+
+#[cfg(test)]
+fn dependency_graph_test_packages() -> (PackageConfig, Manifest) {
+    let package = |name: &str, version, requirements: &[&str]| ManifestPackage {
+        name: name.into(),
+        version,
+        build_tools: ["gleam".into()].into(),
+        otp_app: None,
+        requirements: requirements.iter().map(|r| r.to_string()).collect(),
+        source: ManifestPackageSource::Hex {
+            outer_checksum: Base16Checksum(vec![1, 2, 3, 4]),
+            repository: HEXPM_REPOSITORY.into(),
+        },
+    };
+    let config = PackageConfig {
+        name: "app".into(),
+        version: Version::new(1, 0, 0),
+        dependencies: [
+            ("gleam_http".into(), Requirement::hex("~> 3.0")),
+            ("gleam_stdlib".into(), Requirement::hex("~> 0.29")),
+        ]
+        .into(),
+        dev_dependencies: [("gleeunit".into(), Requirement::hex("~> 0.10"))].into(),
+        ..Default::default()
+    };
+    let manifest = Manifest {
+        requirements: HashMap::new(),
+        packages: vec![
+            package("gleam_http", Version::new(3, 2, 0), &["gleam_stdlib"]),
+            package("gleam_stdlib", Version::new(0, 29, 2), &[]),
+            package("gleeunit", Version::new(0, 10, 1), &["gleam_stdlib"]),
+        ],
+        local_requirements: HashMap::new(),
+    };
+    (config, manifest)
+}
+
+#[test]
+fn dependency_tree_format() {
+    let (config, manifest) = dependency_graph_test_packages();
+    let read_config = |package: &ManifestPackage| {
+        let mut config = PackageConfig::default();
+        if package.name == "gleam_http" {
+            config.dependencies = [("gleam_stdlib".into(), Requirement::hex("~> 0.28"))].into();
+        }
+        Some(config)
+    };
+    let graph = DependencyGraph::new(&config, &manifest, DependencyKind::All, read_config);
+    let mut buffer = vec![];
+    graph.write_tree(&mut buffer).unwrap();
+    assert_eq!(
+        std::str::from_utf8(&buffer).unwrap(),
+        r#"app 1.0.0
+├── gleam_http 3.2.0 (~> 3.0)
+│   └── gleam_stdlib 0.29.2 (~> 0.28)
+├── gleam_stdlib 0.29.2 (~> 0.29)
+└── gleeunit 0.10.1 (~> 0.10) [dev]
+    └── gleam_stdlib 0.29.2
+"#
+    );
+}
+
+#[test]
+fn dependency_tree_kinds() {
+    let (config, manifest) = dependency_graph_test_packages();
+    let write = |kind| {
+        let graph = DependencyGraph::new(&config, &manifest, kind, |_| None);
+        let mut buffer = vec![];
+        graph.write_tree(&mut buffer).unwrap();
+        String::from_utf8(buffer).unwrap()
+    };
+    assert_eq!(
+        write(DependencyKind::Prod),
+        r#"app 1.0.0
+├── gleam_http 3.2.0 (~> 3.0)
+│   └── gleam_stdlib 0.29.2
+└── gleam_stdlib 0.29.2 (~> 0.29)
+"#
+    );
+    assert_eq!(
+        write(DependencyKind::Dev),
+        r#"app 1.0.0
+└── gleeunit 0.10.1 (~> 0.10) [dev]
+    └── gleam_stdlib 0.29.2
+"#
+    );
+}
+
+#[test]
+fn dependency_paths_format() {
+    let (config, manifest) = dependency_graph_test_packages();
+    let graph = DependencyGraph::new(&config, &manifest, DependencyKind::All, |_| None);
+    let mut buffer = vec![];
+    graph.write_paths_to(&mut buffer, "gleam_stdlib").unwrap();
+    assert_eq!(
+        std::str::from_utf8(&buffer).unwrap(),
+        r#"app 1.0.0 -> gleam_http 3.2.0 -> gleam_stdlib 0.29.2
+app 1.0.0 -> gleam_stdlib 0.29.2
+app 1.0.0 -> gleeunit 0.10.1 -> gleam_stdlib 0.29.2
+"#
+    );
+    assert!(matches!(
+        graph.write_paths_to(vec![], "wobble"),
+        Err(Error::UnknownDependency { .. })
+    ));
+    assert!(matches!(
+        graph.write_paths_to(vec![], "app"),
+        Err(Error::UnknownDependency { .. })
+    ));
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum UseManifest {
     Yes,
     No,
 }
 
+/// Whether packages may be looked up and downloaded from the network. When
+/// offline Hex packages are only taken from the vendor directory and the
+/// global package cache, and only the versions locked in the manifest can be
+/// used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Connectivity {
+    Online,
+    Offline,
+}
+
+impl Connectivity {
+    pub fn from_offline_flag(offline: bool) -> Self {
+        if offline {
+            Self::Offline
+        } else {
+            Self::Online
+        }
+    }
+}
+
 pub fn update() -> Result<()> {
     let paths = crate::project_paths_at_current_directory();
-    _ = download(&paths, cli::Reporter::new(), None, UseManifest::No)?;
+    _ = download(
+        &paths,
+        cli::Reporter::new(),
+        None,
+        UseManifest::No,
+        Connectivity::Online,
+    )?;
+    Ok(())
+}
+
+/// Copies the tarball of every Hex package in the manifest into the vendor
+/// directory so the project can be built with `--offline`. Each tarball is
+/// checked against the checksum recorded in the manifest, and tarballs of
+/// packages that are no longer in the manifest are removed.
+pub fn vendor() -> Result<()> {
+    let paths = crate::project_paths_at_current_directory();
+    let manifest = download(
+        &paths,
+        cli::Reporter::new(),
+        None,
+        UseManifest::Yes,
+        Connectivity::Online,
+    )?;
+
+    let config = crate::config::read(paths.root_config())?;
+    let fs = ProjectIO::boxed();
+    let downloader = hex::Downloader::new(
+        fs.clone(),
+        fs,
+        HttpClient::boxed(),
+        Untar::boxed(),
+        crate::config::repositories(&config)?,
+        paths.clone(),
+    );
+    let runtime = tokio::runtime::Runtime::new().expect("Unable to start Tokio async runtime");
+
+    fs::mkdir(paths.vendor_directory())?;
+    let mut vendored = HashSet::new();
+    for package in manifest
+        .packages
+        .iter()
+        .filter(|p| matches!(p.source, ManifestPackageSource::Hex { .. }))
+    {
+        let _ = runtime.block_on(downloader.ensure_package_downloaded(package))?;
+        let source = downloader.package_tarball(package)?;
+        let destination = paths.vendor_package_tarball(&package.name, &package.version.to_string());
+        let tarball = fs::read_bytes(&source)?;
+        hex::verify_outer_checksum(package, &source, &tarball)?;
+        if source != destination {
+            fs::write_bytes(&destination, &tarball)?;
+        }
+        let _ = vendored.insert(destination);
+    }
+
+    for entry in fs::read_dir(paths.vendor_directory())?.filter_map(|entry| entry.ok()) {
+        let path = entry.path();
+        let is_tarball = path.extension().is_some_and(|ext| ext == "tar");
+        if is_tarball && !vendored.contains(&path) {
+            fs::delete_file(&path)?;
+        }
+    }
+
+    cli::print_vendored(vendored.len());
     Ok(())
 }
 
@@ -122,6 +543,7 @@ pub fn download<Telem: Telemetry>(
     // manifest which will result in the latest versions of the dependency
     // packages being resolved (not the locked ones).
     use_manifest: UseManifest,
+    connectivity: Connectivity,
 ) -> Result<Manifest> {
     let span = tracing::info_span!("download_deps");
     let _enter = span.enter();
@@ -135,18 +557,25 @@ pub fn download<Telem: Telemetry>(
     let lock = BuildLock::new_packages(paths)?;
     let _guard = lock.lock(&telemetry);
 
-    let http = HttpClient::boxed();
-    let fs = ProjectIO::boxed();
-    let downloader = hex::Downloader::new(fs.clone(), fs, http, Untar::boxed(), paths.clone());
-
     // Read the project config
     let mut config = crate::config::read(paths.root_config())?;
     let project_name = config.name.clone();
 
+    let http = HttpClient::boxed();
+    let fs = ProjectIO::boxed();
+    let downloader = hex::Downloader::new(
+        fs.clone(),
+        fs,
+        http,
+        Untar::boxed(),
+        crate::config::repositories(&config)?,
+        paths.clone(),
+    );
+
     // Insert the new packages to add, if it exists
     if let Some((packages, dev)) = new_package {
         for package in packages {
-            let version = hexpm::version::Range::new(">= 0.0.0".into());
+            let version = Requirement::hex(">= 0.0.0");
             let _ = if dev {
                 config.dev_dependencies.insert(package.to_string(), version)
             } else {
@@ -166,20 +595,32 @@ pub fn download<Telem: Telemetry>(
         &config,
         &telemetry,
         use_manifest,
+        connectivity,
     )?;
     let local = LocalPackages::read_from_disc(paths)?;
 
     // Remove any packages that are no longer required due to gleam.toml changes
     remove_extra_packages(paths, &local, &manifest)?;
 
+    // Check out the locked commit of any git packages
+    checkout_git_packages(paths, &manifest, connectivity)?;
+
     // Download them from Hex to the local cache
-    runtime.block_on(download_missing_packages(
-        downloader,
-        &manifest,
-        &local,
-        project_name,
-        &telemetry,
-    ))?;
+    match connectivity {
+        Connectivity::Online => runtime.block_on(download_missing_packages(
+            downloader,
+            &manifest,
+            &local,
+            project_name,
+            &telemetry,
+        ))?,
+        Connectivity::Offline => downloader.extract_hex_packages(
+            local
+                .missing_local_packages(&manifest, &project_name)
+                .into_iter(),
+            &project_name,
+        )?,
+    }
 
     if manifest_updated {
         // If the manifest has changed then we need to blow away the build
@@ -288,6 +729,7 @@ impl LocalPackages {
         manifest
             .packages
             .iter()
+            .filter(|p| matches!(p.source, ManifestPackageSource::Hex { .. }))
             .filter(|p| p.name != root && self.packages.get(&p.name) != Some(&p.version))
             .collect()
     }
@@ -338,6 +780,7 @@ fn missing_local_packages() {
                 requirements: vec![],
                 source: ManifestPackageSource::Hex {
                     outer_checksum: Base16Checksum(vec![1, 2, 3, 4]),
+                    repository: "hexpm".into(),
                 },
             },
             ManifestPackage {
@@ -348,6 +791,7 @@ fn missing_local_packages() {
                 requirements: vec![],
                 source: ManifestPackageSource::Hex {
                     outer_checksum: Base16Checksum(vec![1, 2, 3, 4, 5]),
+                    repository: "hexpm".into(),
                 },
             },
             ManifestPackage {
@@ -358,9 +802,11 @@ fn missing_local_packages() {
                 requirements: vec![],
                 source: ManifestPackageSource::Hex {
                     outer_checksum: Base16Checksum(vec![1, 2, 3, 4, 5]),
+                    repository: "hexpm".into(),
                 },
             },
         ],
+        local_requirements: HashMap::new(),
     };
     let mut extra = LocalPackages {
         packages: [
@@ -382,6 +828,7 @@ fn missing_local_packages() {
                 requirements: vec![],
                 source: ManifestPackageSource::Hex {
                     outer_checksum: Base16Checksum(vec![1, 2, 3, 4, 5]),
+                    repository: "hexpm".into(),
                 },
             },
             &ManifestPackage {
@@ -392,6 +839,7 @@ fn missing_local_packages() {
                 requirements: vec![],
                 source: ManifestPackageSource::Hex {
                     outer_checksum: Base16Checksum(vec![1, 2, 3, 4, 5]),
+                    repository: "hexpm".into(),
                 },
             },
         ]
@@ -419,6 +867,7 @@ fn extra_local_packages() {
                 requirements: vec![],
                 source: ManifestPackageSource::Hex {
                     outer_checksum: Base16Checksum(vec![1, 2, 3, 4, 5]),
+                    repository: "hexpm".into(),
                 },
             },
             ManifestPackage {
@@ -429,9 +878,11 @@ fn extra_local_packages() {
                 requirements: vec![],
                 source: ManifestPackageSource::Hex {
                     outer_checksum: Base16Checksum(vec![4, 5]),
+                    repository: "hexpm".into(),
                 },
             },
         ],
+        local_requirements: HashMap::new(),
     });
     extra.sort();
     assert_eq!(
@@ -450,6 +901,7 @@ fn get_manifest<Telem: Telemetry>(
     config: &PackageConfig,
     telemetry: &Telem,
     use_manifest: UseManifest,
+    connectivity: Connectivity,
 ) -> Result<(bool, Manifest)> {
     // If there's no manifest (or we have been asked not to use it) then resolve
     // the versions anew
@@ -466,53 +918,294 @@ fn get_manifest<Telem: Telemetry>(
     };
 
     if should_resolve {
-        let manifest = resolve_versions(runtime, mode, config, None, telemetry)?;
+        let manifest =
+            resolve_versions(paths, runtime, mode, config, None, telemetry, connectivity)?;
         return Ok((true, manifest));
     }
 
     let manifest = read_manifest_from_disc(paths)?;
 
     // If the config has unchanged since the manifest was written then it is up
-    // to date so we can return it unmodified.
-    if manifest.requirements == config.all_dependencies()? {
+    // to date so we can return it unmodified. Local packages can change at any
+    // time, so their configs are checked too.
+    if manifest.requirements == config.all_dependencies()?
+        && !local_packages_changed(paths, &manifest)?
+    {
         tracing::debug!("manifest_up_to_date");
         Ok((false, manifest))
     } else {
         tracing::debug!("manifest_outdated");
-        let manifest = resolve_versions(runtime, mode, config, Some(&manifest), telemetry)?;
+        let manifest = resolve_versions(
+            paths,
+            runtime,
+            mode,
+            config,
+            Some(&manifest),
+            telemetry,
+            connectivity,
+        )?;
         Ok((true, manifest))
     }
 }
 
+fn local_packages_changed(paths: &ProjectPaths, manifest: &Manifest) -> Result<bool> {
+    for package in &manifest.packages {
+        let path = match &package.source {
+            ManifestPackageSource::Local { path } => paths.root().join(path),
+            ManifestPackageSource::Hex { .. } | ManifestPackageSource::Git { .. } => continue,
+        };
+        let config_path = path.join("gleam.toml");
+        if !config_path.is_file() {
+            return Ok(true);
+        }
+        let config = crate::config::read(config_path)?;
+        let requirements = manifest.local_requirements.get(&package.name);
+        if config.version != package.version || Some(&config.all_dependencies()?) != requirements {
+            tracing::debug!(package = package.name.as_str(), "local_package_changed");
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+#[test]
+fn local_packages_changed_test() {
+    let tmp = tempfile::tempdir().unwrap();
+    let write_shared = |dependencies: &str| {
+        std::fs::create_dir_all(tmp.path().join("shared")).unwrap();
+        std::fs::write(
+            tmp.path().join("shared/gleam.toml"),
+            format!("name = \"shared\"\nversion = \"1.0.0\"\n{dependencies}"),
+        )
+        .unwrap();
+    };
+    let paths = ProjectPaths::new(tmp.path().join("app"));
+    std::fs::create_dir_all(paths.root()).unwrap();
+    let manifest = Manifest {
+        requirements: HashMap::new(),
+        packages: vec![ManifestPackage {
+            name: "shared".into(),
+            version: Version::new(1, 0, 0),
+            build_tools: vec!["gleam".into()],
+            otp_app: None,
+            requirements: vec!["gleam_stdlib".into()],
+            source: ManifestPackageSource::Local {
+                path: "../shared".into(),
+            },
+        }],
+        local_requirements: [(
+            "shared".into(),
+            [
+                ("gleam_stdlib".into(), Requirement::hex("~> 0.28")),
+                ("gleeunit".into(), Requirement::hex("~> 0.10")),
+            ]
+            .into(),
+        )]
+        .into(),
+    };
+
+    write_shared(
+        "[dependencies]\ngleam_stdlib = \"~> 0.28\"\n[dev-dependencies]\ngleeunit = \"~> 0.10\"",
+    );
+    assert!(!local_packages_changed(&paths, &manifest).unwrap());
+
+    // A changed range
+    write_shared(
+        "[dependencies]\ngleam_stdlib = \"~> 0.30\"\n[dev-dependencies]\ngleeunit = \"~> 0.10\"",
+    );
+    assert!(local_packages_changed(&paths, &manifest).unwrap());
+
+    // A changed dev dependency
+    write_shared(
+        "[dependencies]\ngleam_stdlib = \"~> 0.28\"\n[dev-dependencies]\ngleeunit = \"~> 1.0\"",
+    );
+    assert!(local_packages_changed(&paths, &manifest).unwrap());
+
+    // Manifests written before requirements of path dependencies were recorded
+    let manifest = Manifest {
+        local_requirements: HashMap::new(),
+        ..manifest
+    };
+    write_shared(
+        "[dependencies]\ngleam_stdlib = \"~> 0.28\"\n[dev-dependencies]\ngleeunit = \"~> 0.10\"",
+    );
+    assert!(local_packages_changed(&paths, &manifest).unwrap());
+}
+
 fn resolve_versions<Telem: Telemetry>(
+    paths: &ProjectPaths,
     runtime: tokio::runtime::Handle,
     mode: Mode,
     config: &PackageConfig,
     manifest: Option<&Manifest>,
     telemetry: &Telem,
+    connectivity: Connectivity,
 ) -> Result<Manifest, Error> {
     telemetry.resolving_package_versions();
+    let repositories = crate::config::repositories(config)?;
+    let provided = provide_packages(paths, config, manifest, connectivity)?;
+
+    // Offline only the packages in the manifest can be used, so every Hex
+    // package required by the project or its path and git dependencies must
+    // already be in it.
+    let locked_packages = match connectivity {
+        Connectivity::Online => None,
+        Connectivity::Offline => {
+            let locked = locked_hex_packages(manifest);
+            let required = config.all_dependencies()?.into_keys().chain(
+                provided
+                    .values()
+                    .flat_map(|package| package.requirements.keys().cloned()),
+            );
+            for name in required {
+                if !provided.contains_key(&name) && !locked.contains_key(&name) {
+                    return Err(Error::PackageUnavailableOffline {
+                        package: name,
+                        version: None,
+                    });
+                }
+            }
+            Some(locked)
+        }
+    };
+
+    let provided_versions = provided
+        .iter()
+        .map(|(name, package)| (name.clone(), package.version.clone()))
+        .collect();
+    let package_repositories = requirement_repositories(&config.all_dependencies()?);
+    for repository in package_repositories.values() {
+        let _ = repositories.get(repository)?;
+    }
+    let package_repositories = Rc::new(RefCell::new(package_repositories));
     let resolved = hex::resolve_versions(
-        PackageFetcher::boxed(runtime.clone()),
+        PackageFetcher::boxed(
+            runtime.clone(),
+            repositories.clone(),
+            package_repositories.clone(),
+            &provided,
+            locked_packages
+                .as_ref()
+                .map(|locked| locked.values().map(locked_hex_package).collect()),
+        ),
         mode,
         config,
         manifest,
+        &provided_versions,
     )?;
-    let packages = runtime.block_on(future::try_join_all(
-        resolved
+    let (provided_packages, hex_packages): (Vec<_>, Vec<_>) = resolved
+        .into_iter()
+        .partition(|(name, _)| provided.contains_key(name));
+    let mut packages = match locked_packages {
+        Some(locked) => hex_packages
             .into_iter()
-            .map(|(name, version)| lookup_package(name, version)),
-    ))?;
+            .filter_map(|(name, _)| locked.get(&name).map(|package| (*package).clone()))
+            .collect(),
+        None => lookup_packages(&runtime, &repositories, &package_repositories, hex_packages)?,
+    };
+    packages.extend(provided_packages.into_iter().filter_map(|(name, _)| {
+        provided
+            .get(&name)
+            .map(|package| package.to_manifest_package(&name))
+    }));
+    let local_requirements = provided
+        .into_iter()
+        .filter(|(_, package)| matches!(package.source, ManifestPackageSource::Local { .. }))
+        .map(|(name, package)| (name, package.dependencies))
+        .collect();
     let manifest = Manifest {
         packages,
         requirements: config.all_dependencies()?,
+        local_requirements,
     };
     Ok(manifest)
 }
 
-async fn lookup_package(name: String, version: Version) -> Result<ManifestPackage> {
-    let config = hexpm::Config::new();
-    let release = hex::get_package_release(&name, &version, &config, &HttpClient::new()).await?;
+fn lookup_packages(
+    runtime: &tokio::runtime::Handle,
+    repositories: &Repositories,
+    package_repositories: &RefCell<HashMap<String, String>>,
+    hex_packages: Vec<(String, Version)>,
+) -> Result<Vec<ManifestPackage>> {
+    let package_repositories = package_repositories.borrow();
+    let hex_packages = hex_packages
+        .into_iter()
+        .map(|(name, version)| {
+            let repository = package_repositories
+                .get(&name)
+                .map(String::as_str)
+                .unwrap_or(HEXPM_REPOSITORY);
+            Ok((name, version, repositories.get(repository)?))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    runtime.block_on(future::try_join_all(hex_packages.into_iter().map(
+        |(name, version, repository)| lookup_package(name, version, repository),
+    )))
+}
+
+/// The Hex packages locked in the manifest, by name.
+fn locked_hex_packages(manifest: Option<&Manifest>) -> HashMap<String, &ManifestPackage> {
+    manifest
+        .into_iter()
+        .flat_map(|manifest| &manifest.packages)
+        .filter(|package| matches!(package.source, ManifestPackageSource::Hex { .. }))
+        .map(|package| (package.name.clone(), package))
+        .collect()
+}
+
+/// A package for the version resolver with only the locked version of a
+/// manifest package. The ranges of its requirements are not recorded in the
+/// manifest, but the locked versions satisfied them when it was written.
+fn locked_hex_package(package: &&ManifestPackage) -> (String, hexpm::Package) {
+    let requirements = package
+        .requirements
+        .iter()
+        .map(|name| {
+            let dependency = hexpm::Dependency {
+                requirement: Range::new(">= 0.0.0".into()),
+                optional: false,
+                app: None,
+                repository: None,
+            };
+            (name.clone(), dependency)
+        })
+        .collect();
+    let (outer_checksum, repository) = match &package.source {
+        ManifestPackageSource::Hex {
+            outer_checksum,
+            repository,
+        } => (outer_checksum.0.clone(), repository.clone()),
+        ManifestPackageSource::Git { .. } | ManifestPackageSource::Local { .. } => {
+            (vec![], HEXPM_REPOSITORY.into())
+        }
+    };
+    let locked = hexpm::Package {
+        name: package.name.clone(),
+        repository,
+        releases: vec![hexpm::Release {
+            version: package.version.clone(),
+            requirements,
+            retirement_status: None,
+            outer_checksum,
+            meta: (),
+        }],
+    };
+    (package.name.clone(), locked)
+}
+
+async fn lookup_package(
+    name: String,
+    version: Version,
+    repository: &Repository,
+) -> Result<ManifestPackage> {
+    let release = hex::get_package_release(
+        &name,
+        &version,
+        repository.auth_key.as_deref(),
+        &repository.config,
+        &HttpClient::new(),
+    )
+    .await?;
     let manifest = ManifestPackage {
         name,
         version,
@@ -521,23 +1214,669 @@ async fn lookup_package(name: String, version: Version) -> Result<ManifestPackag
         requirements: release.requirements.keys().cloned().collect_vec(),
         source: ManifestPackageSource::Hex {
             outer_checksum: Base16Checksum(release.outer_checksum),
+            repository: repository.name.clone(),
         },
     };
     Ok(manifest)
 }
 
+/// The repositories named by Hex requirements. Packages required without
+/// naming a repository are taken from the repository of the package that
+/// requires them, as Hex does.
+///
+fn requirement_repositories(
+    requirements: &HashMap<String, Requirement>,
+) -> HashMap<String, String> {
+    requirements
+        .iter()
+        .filter_map(|(name, requirement)| match requirement {
+            Requirement::Hex {
+                repository: Some(repository),
+                ..
+            } => Some((name.clone(), repository.clone())),
+            Requirement::Hex { .. } | Requirement::Path { .. } | Requirement::Git { .. } => None,
+        })
+        .collect()
+}
+
+/// A path or git dependency. These are used as they are on disc rather than
+/// being looked up on Hex.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ProvidedPackage {
+    version: Version,
+    source: ManifestPackageSource,
+    requirements: HashMap<String, Range>,
+    /// The repositories named by the package's Hex requirements.
+    repositories: HashMap<String, String>,
+    /// The dependencies and dev dependencies as written in the package's
+    /// gleam.toml.
+    dependencies: HashMap<String, Requirement>,
+}
+
+impl ProvidedPackage {
+    fn range(&self) -> Range {
+        Range::new(format!("== {}", self.version))
+    }
+
+    fn to_hex_package(&self, name: &str) -> hexpm::Package {
+        let requirements = self
+            .requirements
+            .iter()
+            .map(|(name, range)| {
+                let dependency = hexpm::Dependency {
+                    requirement: range.clone(),
+                    optional: false,
+                    app: None,
+                    repository: self.repositories.get(name).cloned(),
+                };
+                (name.clone(), dependency)
+            })
+            .collect();
+        hexpm::Package {
+            name: name.to_string(),
+            repository: "local".into(),
+            releases: vec![hexpm::Release {
+                version: self.version.clone(),
+                requirements,
+                retirement_status: None,
+                outer_checksum: vec![],
+                meta: (),
+            }],
+        }
+    }
+
+    fn to_manifest_package(&self, name: &str) -> ManifestPackage {
+        ManifestPackage {
+            name: name.to_string(),
+            version: self.version.clone(),
+            build_tools: vec!["gleam".into()],
+            otp_app: None,
+            requirements: self.requirements.keys().cloned().sorted().collect(),
+            source: self.source.clone(),
+        }
+    }
+}
+
+/// Reads the configs of all the path and git dependencies of the project,
+/// and of their path and git dependencies in turn, checking out the git
+/// repositories as needed.
+///
+fn provide_packages(
+    paths: &ProjectPaths,
+    config: &PackageConfig,
+    manifest: Option<&Manifest>,
+    connectivity: Connectivity,
+) -> Result<HashMap<String, ProvidedPackage>> {
+    let mut provider = PackageProvider {
+        paths,
+        manifest,
+        connectivity,
+        locked: config.locked(manifest)?,
+        provided: HashMap::new(),
+    };
+    for (name, requirement) in config.all_dependencies()? {
+        let _ = provider.provide(&name, &requirement, Path::new(""))?;
+    }
+    Ok(provider.provided)
+}
+
+struct PackageProvider<'a> {
+    paths: &'a ProjectPaths,
+    manifest: Option<&'a Manifest>,
+    connectivity: Connectivity,
+    locked: HashMap<String, Version>,
+    provided: HashMap<String, ProvidedPackage>,
+}
+
+impl PackageProvider<'_> {
+    /// Returns the version range to use for the requirement, providing the
+    /// package if it is a path or git dependency. Paths are relative to the
+    /// directory of the package that has the requirement.
+    ///
+    fn provide(&mut self, name: &str, requirement: &Requirement, parent: &Path) -> Result<Range> {
+        match requirement {
+            Requirement::Hex { version, .. } => Ok(version.clone()),
+
+            Requirement::Path { path } => {
+                let path = normalise_path(&parent.join(path));
+                let source = ManifestPackageSource::Local { path: path.clone() };
+                self.provide_package(name, self.paths.root().join(&path), path, source)
+            }
+
+            Requirement::Git { git, reference } => {
+                // The same repository may be required by several packages,
+                // in which case it only needs to be checked out once.
+                if let Some(provided) = self.provided.get(name) {
+                    if let ManifestPackageSource::Git { repo, .. } = &provided.source {
+                        if repo == git {
+                            return Ok(provided.range());
+                        }
+                    }
+                }
+
+                // Use the commit recorded in the manifest if the requirement
+                // has not changed since it was resolved.
+                let locked_commit = self
+                    .manifest
+                    .filter(|_| self.locked.contains_key(name))
+                    .into_iter()
+                    .flat_map(|manifest| manifest.packages.iter())
+                    .find_map(|package| match &package.source {
+                        ManifestPackageSource::Git { repo, commit }
+                            if package.name == name && repo == git =>
+                        {
+                            Some(commit.as_str())
+                        }
+                        _ => None,
+                    });
+
+                let package_path = self.paths.build_packages_package(name);
+                let commit = match (self.connectivity, locked_commit) {
+                    // The locked commit doesn't need fetching again if it is
+                    // already checked out.
+                    (_, Some(commit)) if is_checked_out(&package_path, commit)? => {
+                        commit.to_string()
+                    }
+                    (Connectivity::Online, _) => checkout_git_package(
+                        &package_path,
+                        git,
+                        locked_commit.unwrap_or(reference),
+                    )?,
+                    (Connectivity::Offline, commit) => {
+                        return Err(Error::PackageUnavailableOffline {
+                            package: name.into(),
+                            version: commit.map(String::from),
+                        })
+                    }
+                };
+                let source = ManifestPackageSource::Git {
+                    repo: git.clone(),
+                    commit,
+                };
+                self.provide_package(name, package_path.clone(), package_path, source)
+            }
+        }
+    }
+
+    fn provide_package(
+        &mut self,
+        name: &str,
+        package_path: PathBuf,
+        relative_path: PathBuf,
+        source: ManifestPackageSource,
+    ) -> Result<Range> {
+        if let Some(provided) = self.provided.get(name) {
+            return if provided.source == source {
+                Ok(provided.range())
+            } else {
+                Err(Error::ProvidedDependencyConflict {
+                    package: name.to_string(),
+                    source_1: describe_source(&provided.source),
+                    source_2: describe_source(&source),
+                })
+            };
+        }
+
+        let config = crate::config::read(package_path.join("gleam.toml"))?;
+        if config.name != name {
+            return Err(Error::WrongDependencyProvided {
+                path: package_path,
+                expected: name.to_string(),
+                found: config.name.to_string(),
+            });
+        }
+
+        // The package is recorded before its own dependencies are provided
+        // so that a dependency cycle does not recurse forever.
+        let package = ProvidedPackage {
+            version: config.version.clone(),
+            source,
+            requirements: HashMap::new(),
+            repositories: requirement_repositories(&config.dependencies),
+            dependencies: config.all_dependencies()?,
+        };
+        let range = package.range();
+        let _ = self.provided.insert(name.to_string(), package);
+
+        let mut requirements = HashMap::with_capacity(config.dependencies.len());
+        for (dependency, requirement) in &config.dependencies {
+            let range = self.provide(dependency, requirement, &relative_path)?;
+            let _ = requirements.insert(dependency.clone(), range);
+        }
+        self.provided
+            .get_mut(name)
+            .expect("Provided package missing")
+            .requirements = requirements;
+
+        Ok(range)
+    }
+}
+
+#[test]
+fn provide_path_packages() {
+    let tmp = tempfile::tempdir().unwrap();
+    let write = |package: &str, toml: &str| {
+        std::fs::create_dir_all(tmp.path().join(package)).unwrap();
+        std::fs::write(tmp.path().join(package).join("gleam.toml"), toml).unwrap();
+    };
+    write(
+        "app",
+        "name = \"app\"\n[dependencies]\nutil = { path = \"../util\" }\nshared = { path = \"../shared\" }",
+    );
+    write(
+        "util",
+        "name = \"util\"\nversion = \"0.2.0\"\n[dependencies]\nshared = { path = \"../shared\" }\ngleam_stdlib = \"~> 0.28\"",
+    );
+    write("shared", "name = \"shared\"\nversion = \"1.0.0\"");
+
+    let paths = ProjectPaths::new(tmp.path().join("app"));
+    let config = crate::config::read(paths.root_config()).unwrap();
+    let provided = provide_packages(&paths, &config, None, Connectivity::Online).unwrap();
+
+    assert_eq!(
+        provided
+            .get("shared")
+            .map(|package| package.to_manifest_package("shared")),
+        Some(ManifestPackage {
+            name: "shared".into(),
+            version: Version::new(1, 0, 0),
+            build_tools: vec!["gleam".into()],
+            otp_app: None,
+            requirements: vec![],
+            source: ManifestPackageSource::Local {
+                path: "../shared".into()
+            },
+        })
+    );
+    assert_eq!(
+        provided
+            .get("util")
+            .map(|package| package.to_manifest_package("util")),
+        Some(ManifestPackage {
+            name: "util".into(),
+            version: Version::new(0, 2, 0),
+            build_tools: vec!["gleam".into()],
+            otp_app: None,
+            requirements: vec!["gleam_stdlib".into(), "shared".into()],
+            source: ManifestPackageSource::Local {
+                path: "../util".into()
+            },
+        })
+    );
+    assert_eq!(
+        provided
+            .get("util")
+            .and_then(|package| package.requirements.get("shared")),
+        Some(&Range::new("== 1.0.0".into()))
+    );
+    assert_eq!(provided.len(), 2);
+}
+
+#[test]
+fn provide_path_package_with_wrong_name() {
+    let tmp = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(tmp.path().join("app")).unwrap();
+    std::fs::create_dir_all(tmp.path().join("shared")).unwrap();
+    std::fs::write(
+        tmp.path().join("app/gleam.toml"),
+        "name = \"app\"\n[dependencies]\nshared = { path = \"../shared\" }",
+    )
+    .unwrap();
+    std::fs::write(tmp.path().join("shared/gleam.toml"), "name = \"other\"").unwrap();
+
+    let paths = ProjectPaths::new(tmp.path().join("app"));
+    let config = crate::config::read(paths.root_config()).unwrap();
+    assert!(matches!(
+        provide_packages(&paths, &config, None, Connectivity::Online),
+        Err(Error::WrongDependencyProvided { .. })
+    ));
+}
+
+#[test]
+fn provided_package_requirement_repositories() {
+    let tmp = tempfile::tempdir().unwrap();
+    let write = |package: &str, toml: &str| {
+        std::fs::create_dir_all(tmp.path().join(package)).unwrap();
+        std::fs::write(tmp.path().join(package).join("gleam.toml"), toml).unwrap();
+    };
+    write(
+        "app",
+        "name = \"app\"\n[dependencies]\nutil = { path = \"../util\" }",
+    );
+    write(
+        "util",
+        "name = \"util\"\n[dependencies]\ninternal = { version = \"~> 1.0\", repository = \"company\" }\ngleam_stdlib = \"~> 0.28\"",
+    );
+
+    let paths = ProjectPaths::new(tmp.path().join("app"));
+    let config = crate::config::read(paths.root_config()).unwrap();
+    let provided = provide_packages(&paths, &config, None, Connectivity::Online).unwrap();
+    let package = provided.get("util").unwrap().to_hex_package("util");
+    let requirements = &package.releases.first().unwrap().requirements;
+    assert_eq!(
+        requirements
+            .get("internal")
+            .and_then(|dependency| dependency.repository.as_deref()),
+        Some("company")
+    );
+    assert_eq!(
+        requirements
+            .get("gleam_stdlib")
+            .map(|dependency| dependency.repository.as_deref()),
+        Some(None)
+    );
+}
+
+#[test]
+fn provide_git_packages() {
+    let tmp = tempfile::tempdir().unwrap();
+    let remote = tmp.path().join("remote");
+    std::fs::create_dir_all(&remote).unwrap();
+    let commit = |version: &str| {
+        std::fs::write(
+            remote.join("gleam.toml"),
+            format!("name = \"remote\"\nversion = \"{version}\"\n[dependencies]\ngleam_stdlib = \"~> 0.28\""),
+        )
+        .unwrap();
+        let _ = git(&["add", "gleam.toml"], &remote).unwrap();
+        let _ = git(
+            &[
+                "-c",
+                "user.name=Gleam",
+                "-c",
+                "user.email=gleam@example.com",
+                "commit",
+                "--quiet",
+                "--message",
+                version,
+            ],
+            &remote,
+        )
+        .unwrap();
+        git(&["rev-parse", "HEAD"], &remote).unwrap()
+    };
+    let _ = git(
+        &["-c", "init.defaultBranch=main", "init", "--quiet"],
+        &remote,
+    )
+    .unwrap();
+    let first_commit = commit("0.1.0");
+
+    std::fs::create_dir_all(tmp.path().join("app")).unwrap();
+    std::fs::write(
+        tmp.path().join("app/gleam.toml"),
+        format!(
+            "name = \"app\"\n[dependencies]\nremote = {{ git = {:?}, ref = \"main\" }}",
+            remote.to_string_lossy()
+        ),
+    )
+    .unwrap();
+    let paths = ProjectPaths::new(tmp.path().join("app"));
+    let config = crate::config::read(paths.root_config()).unwrap();
+
+    // The reference is checked out
+    let provided = provide_packages(&paths, &config, None, Connectivity::Online).unwrap();
+    let package = provided
+        .get("remote")
+        .unwrap()
+        .to_manifest_package("remote");
+    assert_eq!(package.version, Version::new(0, 1, 0));
+    assert_eq!(package.requirements, vec!["gleam_stdlib".to_string()]);
+    assert_eq!(
+        package.source,
+        ManifestPackageSource::Git {
+            repo: remote.to_string_lossy().into(),
+            commit: first_commit.clone(),
+        }
+    );
+    assert!(is_checked_out(&paths.build_packages_package("remote"), &first_commit).unwrap());
+
+    // Once locked in the manifest the same commit is used, even after the
+    // branch has moved on
+    let _ = commit("0.2.0");
+    let manifest = Manifest {
+        requirements: config.all_dependencies().unwrap(),
+        packages: vec![
+            package,
+            ManifestPackage {
+                name: "gleam_stdlib".into(),
+                version: Version::new(0, 28, 0),
+                build_tools: vec!["gleam".into()],
+                otp_app: None,
+                requirements: vec![],
+                source: ManifestPackageSource::Hex {
+                    outer_checksum: Base16Checksum(vec![1, 2, 3]),
+                    repository: HEXPM_REPOSITORY.into(),
+                },
+            },
+        ],
+        local_requirements: HashMap::new(),
+    };
+    let checked_out_marker = paths.build_packages_package("remote").join("marker");
+    std::fs::write(&checked_out_marker, "").unwrap();
+    let provided =
+        provide_packages(&paths, &config, Some(&manifest), Connectivity::Online).unwrap();
+    assert_eq!(
+        provided.get("remote").map(|package| &package.source),
+        Some(&ManifestPackageSource::Git {
+            repo: remote.to_string_lossy().into(),
+            commit: first_commit.clone(),
+        })
+    );
+    // The locked commit was already checked out so it was not fetched again
+    assert!(checked_out_marker.exists());
+
+    // Offline the locked commit can be used if it is already checked out
+    let provided =
+        provide_packages(&paths, &config, Some(&manifest), Connectivity::Offline).unwrap();
+    assert_eq!(
+        provided
+            .get("remote")
+            .map(|package| package.version.clone()),
+        Some(Version::new(0, 1, 0))
+    );
+    fs::delete_dir(&paths.build_packages_package("remote")).unwrap();
+    assert!(matches!(
+        provide_packages(&paths, &config, Some(&manifest), Connectivity::Offline),
+        Err(Error::PackageUnavailableOffline { package, version })
+            if package == "remote" && version == Some(first_commit)
+    ));
+}
+
+/// Removes the `.` and `..` components of a path where possible without
+/// looking at the file system, so that the same directory reached by
+/// different relative paths is recognised as the same package.
+///
+fn normalise_path(path: &Path) -> PathBuf {
+    let mut normalised = PathBuf::new();
+    for component in path.components() {
+        match component {
+            std::path::Component::CurDir => (),
+            std::path::Component::ParentDir
+                if matches!(
+                    normalised.components().next_back(),
+                    Some(std::path::Component::Normal(_))
+                ) =>
+            {
+                let _ = normalised.pop();
+            }
+            component => normalised.push(component),
+        }
+    }
+    normalised
+}
+
+#[test]
+fn normalise_path_test() {
+    assert_eq!(
+        normalise_path(Path::new("../util/../shared")),
+        Path::new("../shared")
+    );
+    assert_eq!(normalise_path(Path::new("./a/./b/..")), Path::new("a"));
+    assert_eq!(normalise_path(Path::new("../../a")), Path::new("../../a"));
+    assert_eq!(normalise_path(Path::new("/a/b/../c")), Path::new("/a/c"));
+}
+
+fn describe_source(source: &ManifestPackageSource) -> String {
+    match source {
+        ManifestPackageSource::Hex { .. } => "Hex".into(),
+        ManifestPackageSource::Git { repo, .. } => format!("the git repository {repo}"),
+        ManifestPackageSource::Local { path } => format!("the path {}", path.to_string_lossy()),
+    }
+}
+
+/// Ensures each git package is checked out at the commit recorded in the
+/// manifest. Any build of a package that had to be checked out again is
+/// deleted as it may be for a different commit.
+///
+fn checkout_git_packages(
+    paths: &ProjectPaths,
+    manifest: &Manifest,
+    connectivity: Connectivity,
+) -> Result<()> {
+    for package in &manifest.packages {
+        let (repo, commit) = match &package.source {
+            ManifestPackageSource::Git { repo, commit } => (repo, commit),
+            ManifestPackageSource::Hex { .. } | ManifestPackageSource::Local { .. } => continue,
+        };
+        let package_path = paths.build_packages_package(&package.name);
+        if is_checked_out(&package_path, commit)? {
+            continue;
+        }
+        if connectivity == Connectivity::Offline {
+            return Err(Error::PackageUnavailableOffline {
+                package: package.name.clone(),
+                version: Some(commit.clone()),
+            });
+        }
+
+        let _ = checkout_git_package(&package_path, repo, commit)?;
+        for mode in Mode::iter() {
+            for target in Target::iter() {
+                fs::delete_dir(&paths.build_directory_for_package(mode, target, &package.name))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Whether the git repository at the package path has the commit checked out.
+fn is_checked_out(package_path: &Path, commit: &str) -> Result<bool> {
+    Ok(package_path.join(".git").is_dir()
+        && git(&["rev-parse", "HEAD"], package_path)?.as_str() == commit)
+}
+
+/// Checks out the given branch, tag, or commit of a git repository into the
+/// package path, returning the hash of the commit that was checked out.
+///
+fn checkout_git_package(package_path: &Path, repo: &str, reference: &str) -> Result<String> {
+    tracing::info!(
+        repo = repo,
+        reference = reference,
+        "checking_out_git_package"
+    );
+    fs::delete_dir(package_path)?;
+    fs::mkdir(package_path)?;
+    let _ = git(&["init", "--quiet"], package_path)?;
+    let _ = git(&["remote", "add", "origin", repo], package_path)?;
+    let _ = git(
+        &["fetch", "--quiet", "--depth", "1", "origin", reference],
+        package_path,
+    )?;
+    let _ = git(&["checkout", "--quiet", "FETCH_HEAD"], package_path)?;
+    git(&["rev-parse", "HEAD"], package_path)
+}
+
+/// Runs git, returning what it printed to stdout.
+///
+fn git(args: &[&str], cwd: &Path) -> Result<String> {
+    tracing::trace!(args=?args.join(" "), cwd=?cwd, "git_exec");
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(cwd)
+        .stderr(std::process::Stdio::inherit())
+        .output()
+        .map_err(|error| match error.kind() {
+            std::io::ErrorKind::NotFound => Error::ShellProgramNotFound {
+                program: "git".into(),
+            },
+            other => Error::ShellCommand {
+                program: "git".into(),
+                err: Some(other),
+            },
+        })?;
+    if !output.status.success() {
+        return Err(Error::ShellCommand {
+            program: format!("git {}", args.join(" ")),
+            err: None,
+        });
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
 struct PackageFetcher {
     runtime: tokio::runtime::Handle,
     http: HttpClient,
+    repositories: Repositories,
+    /// The repository each package is looked up in, added to as the
+    /// dependencies of each package are fetched.
+    package_repositories: Rc<RefCell<HashMap<String, String>>>,
+    provided: HashMap<String, hexpm::Package>,
+    /// When offline, the packages locked in the manifest, which are the only
+    /// ones that can be used.
+    locked: Option<HashMap<String, hexpm::Package>>,
 }
 
 impl PackageFetcher {
-    pub fn boxed(runtime: tokio::runtime::Handle) -> Box<Self> {
+    fn boxed(
+        runtime: tokio::runtime::Handle,
+        repositories: Repositories,
+        package_repositories: Rc<RefCell<HashMap<String, String>>>,
+        provided: &HashMap<String, ProvidedPackage>,
+        locked: Option<HashMap<String, hexpm::Package>>,
+    ) -> Box<Self> {
+        let provided = provided
+            .iter()
+            .map(|(name, package)| (name.clone(), package.to_hex_package(name)))
+            .collect();
         Box::new(Self {
             runtime,
             http: HttpClient::new(),
+            repositories,
+            package_repositories,
+            provided,
+            locked,
         })
     }
+
+    fn fetch(&self, package: &str, repository: &Repository) -> Result<hexpm::Package> {
+        tracing::debug!(
+            package = package,
+            repository = repository.name.as_str(),
+            "looking_up_hex_package"
+        );
+        let request =
+            hexpm::get_package_request(package, repository.auth_key.as_deref(), &repository.config);
+        let response = self.runtime.block_on(self.http.send(request))?;
+        hexpm::get_package_response(response, &repository.public_key).map_err(Error::hex)
+    }
+
+    /// Records the repository of each dependency of the package, defaulting
+    /// to the repository the package itself came from.
+    fn record_repositories(&self, package: &hexpm::Package, repository: &str) {
+        let mut package_repositories = self.package_repositories.borrow_mut();
+        for release in &package.releases {
+            for (name, dependency) in &release.requirements {
+                let _ = package_repositories.entry(name.clone()).or_insert_with(|| {
+                    dependency
+                        .repository
+                        .clone()
+                        .unwrap_or_else(|| repository.into())
+                });
+            }
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -559,7 +1898,7 @@ impl TarUnpacker for Untar {
 
     fn io_result_unpack(
         &self,
-        path: &std::path::Path,
+        path: &Path,
         mut archive: tar::Archive<GzDecoder<tar::Entry<'_, WrappedReader>>>,
     ) -> std::io::Result<()> {
         archive.unpack(path)
@@ -571,13 +1910,28 @@ impl hexpm::version::PackageFetcher for PackageFetcher {
         &self,
         package: &str,
     ) -> Result<hexpm::Package, Box<dyn std::error::Error>> {
-        tracing::debug!(package = package, "looking_up_hex_package");
-        let config = hexpm::Config::new();
-        let request = hexpm::get_package_request(package, None, &config);
-        let response = self
-            .runtime
-            .block_on(self.http.send(request))
-            .map_err(Box::new)?;
-        hexpm::get_package_response(response, HEXPM_PUBLIC_KEY).map_err(|e| e.into())
+        if let Some(provided) = self.provided.get(package) {
+            self.record_repositories(provided, HEXPM_REPOSITORY);
+            return Ok(provided.clone());
+        }
+        if let Some(locked) = &self.locked {
+            return match locked.get(package) {
+                Some(locked) => Ok(locked.clone()),
+                None => Err(Box::new(Error::PackageUnavailableOffline {
+                    package: package.into(),
+                    version: None,
+                })),
+            };
+        }
+        let repository = self
+            .package_repositories
+            .borrow()
+            .get(package)
+            .cloned()
+            .unwrap_or_else(|| HEXPM_REPOSITORY.into());
+        let repository = self.repositories.get(&repository).map_err(Box::new)?;
+        let fetched = self.fetch(package, repository).map_err(Box::new)?;
+        self.record_repositories(&fetched, &repository.name);
+        Ok(fetched)
     }
 }
@@ -0,0 +1,354 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use gleam_core::{
+    api_diff::{ApiDiff, Bump},
+    build::{Codegen, Mode, Options, Origin, PackageCompiler, Target, TargetCodegenConfiguration},
+    config::PackageConfig,
+    error::InsufficientVersionBumpDetails,
+    hex,
+    io::HttpClient as _,
+    manifest::{Base16Checksum, ManifestPackage, ManifestPackageSource},
+    metadata,
+    paths::{self, ProjectPaths},
+    type_,
+    uid::UniqueIdGenerator,
+    warning::{NullWarningEmitterIO, WarningEmitter},
+    Error, Result,
+};
+use hexpm::version::Version;
+
+use crate::{
+    build,
+    dependencies::Untar,
+    fs::{self, ProjectIO},
+    http::HttpClient,
+};
+
+pub fn command(previous: Option<PathBuf>, repository: String) -> Result<()> {
+    let paths = crate::project_paths_at_current_directory();
+    let config = crate::config::root_config()?;
+
+    // Reset the build directory so that no modules that have since been
+    // removed are left over from earlier builds.
+    fs::delete_dir(&paths.build_directory_for_target(Mode::Prod, Target::Erlang))?;
+    let _ = build::main(Options {
+        warnings_as_errors: false,
+        mode: Mode::Prod,
+        target: Some(Target::Erlang),
+        codegen: Codegen::DepsOnly,
+    })?;
+
+    match ApiComparison::new(&paths, &config, previous.as_deref(), &repository)? {
+        Some(comparison) => print!("{}", comparison.report(&config.version)),
+        None => println!(
+            "{} has not been published before so there is no API to compare with.",
+            config.name
+        ),
+    }
+    Ok(())
+}
+
+/// The changes to the public API of the package since its previous release.
+#[derive(Debug)]
+pub struct ApiComparison {
+    pub previous_version: Version,
+    pub diff: ApiDiff,
+}
+
+impl ApiComparison {
+    /// Compares the API of the package, which must have been built for Erlang
+    /// in production mode, with that of its previous release. The previous
+    /// release is the package at the given path if there is one, and
+    /// otherwise the release on Hex with the highest version lower than the
+    /// current one. Returns `None` if there is no previous release.
+    pub fn new(
+        paths: &ProjectPaths,
+        config: &PackageConfig,
+        previous: Option<&Path>,
+        repository: &str,
+    ) -> Result<Option<Self>> {
+        let (previous_directory, previous_version) = match previous {
+            Some(path) => {
+                let previous_config = crate::config::read(path.join("gleam.toml"))?;
+                (path.to_path_buf(), previous_config.version)
+            }
+            None => match download_previous_release(paths, config, repository)? {
+                Some(release) => release,
+                None => return Ok(None),
+            },
+        };
+
+        let lib = paths.build_directory_for_target(Mode::Prod, Target::Erlang);
+        let out = paths
+            .build_api_diff_package(&config.name, &previous_version.to_string())
+            .join("build");
+        let previous = compile_interface(&previous_directory, &out, &lib)?;
+        let current = read_interface(
+            config,
+            &paths.build_directory_for_package(Mode::Prod, Target::Erlang, &config.name),
+            &UniqueIdGenerator::new(),
+        )?;
+        Ok(Some(Self {
+            previous_version,
+            diff: ApiDiff::new(&previous, &current),
+        }))
+    }
+
+    pub fn minimum_version(&self) -> Version {
+        self.diff.bump().minimum_version(&self.previous_version)
+    }
+
+    /// Errors if the version of the package is too low for the changes made
+    /// to its API.
+    pub fn check_version(&self, config: &PackageConfig) -> Result<()> {
+        let minimum_version = self.minimum_version();
+        if config.version >= minimum_version {
+            return Ok(());
+        }
+        Err(Error::InsufficientVersionBump {
+            details: Box::new(InsufficientVersionBumpDetails {
+                package: config.name.to_string(),
+                previous_version: self.previous_version.to_string(),
+                version: config.version.to_string(),
+                minimum_version: minimum_version.to_string(),
+                bump: self.diff.bump().to_string(),
+                changes: self
+                    .diff
+                    .changes
+                    .iter()
+                    .filter(|change| change.bump > Bump::Patch)
+                    .map(|change| change.description.clone())
+                    .collect(),
+            }),
+        })
+    }
+
+    pub fn report(&self, version: &Version) -> String {
+        let previous_version = &self.previous_version;
+        let minimum_version = self.minimum_version();
+        let mut report = if self.diff.changes.is_empty() {
+            format!("There are no changes to the API since {previous_version}.\n")
+        } else {
+            let mut report = format!("API changes since {previous_version}:\n");
+            for change in &self.diff.changes {
+                report.push_str(&format!("  - [{}] {}\n", change.bump, change.description));
+            }
+            report
+        };
+        report.push_str(&format!(
+            "\nA {} version bump is needed, so the new version must be {minimum_version} or \
+higher.\n",
+            self.diff.bump()
+        ));
+        if *version < minimum_version {
+            report.push_str(&format!(
+                "The version in gleam.toml, {version}, is too low.\n"
+            ));
+        }
+        report
+    }
+}
+
+/// Downloads the source of the release of the package on Hex with the
+/// highest version lower than the current one, returning the directory it was
+/// extracted into and its version.
+fn download_previous_release(
+    paths: &ProjectPaths,
+    config: &PackageConfig,
+    repository: &str,
+) -> Result<Option<(PathBuf, Version)>> {
+    let repositories = crate::config::repositories(config)?;
+    let repository = repositories.get(repository)?.clone();
+    let runtime = tokio::runtime::Runtime::new().expect("Unable to start Tokio async runtime");
+
+    let request = hexpm::get_package_request(
+        &config.name,
+        repository.auth_key.as_deref(),
+        &repository.config,
+    );
+    let response = runtime.block_on(HttpClient::new().send(request))?;
+    let package = match hexpm::get_package_response(response, &repository.public_key) {
+        Ok(package) => package,
+        Err(hexpm::ApiError::NotFound) => return Ok(None),
+        Err(error) => return Err(Error::hex(error)),
+    };
+    let Some(release) = package
+        .releases
+        .into_iter()
+        .filter(|release| release.version < config.version)
+        .max_by(|a, b| a.version.cmp(&b.version))
+    else {
+        return Ok(None);
+    };
+
+    let previous = ManifestPackage {
+        name: config.name.to_string(),
+        version: release.version.clone(),
+        build_tools: vec!["gleam".into()],
+        otp_app: None,
+        requirements: vec![],
+        source: ManifestPackageSource::Hex {
+            outer_checksum: Base16Checksum(release.outer_checksum),
+            repository: repository.name.clone(),
+        },
+    };
+    let fs = ProjectIO::boxed();
+    let downloader = hex::Downloader::new(
+        fs.clone(),
+        fs,
+        HttpClient::boxed(),
+        Untar::boxed(),
+        repositories,
+        paths.clone(),
+    );
+    let _ = runtime.block_on(downloader.ensure_package_downloaded(&previous))?;
+    let directory = paths
+        .build_api_diff_package(&config.name, &release.version.to_string())
+        .join("package");
+    let _ = downloader.extract_package(&previous, &directory)?;
+    Ok(Some((directory, release.version)))
+}
+
+/// Type checks the package in the given directory against the compiled
+/// packages in the lib directory, and reads the interfaces of its public
+/// modules from the metadata written to the out directory.
+fn compile_interface(package: &Path, out: &Path, lib: &Path) -> Result<Vec<type_::Module>> {
+    let config = crate::config::read(package.join("gleam.toml"))?;
+    fs::delete_dir(out)?;
+
+    let ids = UniqueIdGenerator::new();
+    let mut modules = crate::compile_package::load_libraries(&ids, lib, Some(&config.name))?;
+    let target = TargetCodegenConfiguration::Erlang { app_file: None };
+    let mut compiler = PackageCompiler::new(
+        &config,
+        Mode::Prod,
+        package,
+        out,
+        lib,
+        &target,
+        ids.clone(),
+        ProjectIO::new(),
+    );
+    compiler.write_metadata = true;
+    compiler.perform_codegen = false;
+    compiler.copy_native_files = false;
+    compiler.compile_beam_bytecode = false;
+    let warnings = WarningEmitter::new(Arc::new(NullWarningEmitterIO));
+    let _ = compiler.compile(&warnings, &mut modules, &mut im::HashMap::new())?;
+
+    read_interface(&config, out, &ids)
+}
+
+/// Reads the interfaces of the public modules of a compiled package.
+fn read_interface(
+    config: &PackageConfig,
+    out: &Path,
+    ids: &UniqueIdGenerator,
+) -> Result<Vec<type_::Module>> {
+    let mut modules = vec![];
+    for path in fs::module_caches_paths(out.join(paths::ARTEFACT_DIRECTORY_NAME))? {
+        let reader = fs::buffered_reader(path)?;
+        let module = metadata::ModuleDecoder::new(ids.clone()).read(reader)?;
+        if module.origin == Origin::Src && !config.is_internal_module(&module.name) {
+            modules.push(module);
+        }
+    }
+    Ok(modules)
+}
+
+#[cfg(test)]
+fn write_package(directory: &Path, version: &str, modules: &[(&str, &str)]) {
+    std::fs::create_dir_all(directory.join("src")).unwrap();
+    std::fs::write(
+        directory.join("gleam.toml"),
+        format!("name = \"wibble\"\nversion = \"{version}\"\n"),
+    )
+    .unwrap();
+    for (name, src) in modules {
+        let path = directory.join("src").join(format!("{name}.gleam"));
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, src).unwrap();
+    }
+}
+
+#[test]
+fn compare_packages_at_paths() {
+    let tmp = tempfile::tempdir().unwrap();
+    let lib = tmp.path().join("lib");
+    std::fs::create_dir_all(&lib).unwrap();
+
+    let previous = tmp.path().join("previous");
+    write_package(
+        &previous,
+        "1.2.0",
+        &[
+            ("wibble", "pub fn add(a: Int, b: Int) -> Int { a + b }"),
+            ("wibble/internal", "pub fn helper() { 1 }"),
+        ],
+    );
+    let current = tmp.path().join("current");
+    write_package(
+        &current,
+        "1.2.1",
+        &[
+            (
+                "wibble",
+                "pub fn add(a: Int, b: Int) -> Int { a + b }
+pub fn sub(a: Int, b: Int) -> Int { a - b }",
+            ),
+            ("wibble/internal", "pub fn helper() { 2.0 }"),
+        ],
+    );
+
+    let previous_interface =
+        compile_interface(&previous, &tmp.path().join("previous-out"), &lib).unwrap();
+    let current_interface =
+        compile_interface(&current, &tmp.path().join("current-out"), &lib).unwrap();
+    let comparison = ApiComparison {
+        previous_version: Version::new(1, 2, 0),
+        diff: ApiDiff::new(&previous_interface, &current_interface),
+    };
+
+    // Internal modules are not part of the API
+    assert_eq!(comparison.diff.bump(), Bump::Minor);
+    assert_eq!(comparison.minimum_version(), Version::new(1, 3, 0));
+    assert_eq!(
+        comparison.report(&Version::new(1, 2, 1)),
+        "API changes since 1.2.0:
+  - [minor] Added function `wibble.sub`
+
+A minor version bump is needed, so the new version must be 1.3.0 or higher.
+The version in gleam.toml, 1.2.1, is too low.
+"
+    );
+
+    let mut config = PackageConfig {
+        name: "wibble".into(),
+        version: Version::new(1, 2, 1),
+        ..Default::default()
+    };
+    assert!(matches!(
+        comparison.check_version(&config),
+        Err(Error::InsufficientVersionBump { .. })
+    ));
+    config.version = Version::new(1, 3, 0);
+    assert!(comparison.check_version(&config).is_ok());
+}
+
+#[test]
+fn report_without_changes() {
+    let comparison = ApiComparison {
+        previous_version: Version::new(0, 4, 0),
+        diff: ApiDiff::default(),
+    };
+    assert_eq!(
+        comparison.report(&Version::new(0, 4, 1)),
+        "There are no changes to the API since 0.4.0.
+
+A patch version bump is needed, so the new version must be 0.4.1 or higher.
+"
+    );
+}
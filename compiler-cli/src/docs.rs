@@ -1,3 +1,5 @@
+mod serve;
+
 use std::time::Instant;
 
 use crate::{cli, hex::ApiKeyCommand, http::HttpClient};
@@ -7,9 +9,12 @@ use gleam_core::{
     error::Error,
     hex,
     io::HttpClient as _,
+    paths::ProjectPaths,
     Result,
 };
 
+pub use serve::serve;
+
 pub fn remove(package: String, version: String) -> Result<()> {
     RemoveCommand::new(package, version).run()
 }
@@ -86,15 +91,24 @@ pub(crate) fn build_documentation(
     compiled.attach_doc_and_module_comments();
     cli::print_generating_documentation();
     let paths = crate::project_paths_at_current_directory();
+    let pages = documentation_pages(&paths, config);
+    let outputs =
+        gleam_core::docs::generate_html(&paths, config, compiled.modules.as_slice(), &pages);
+    Ok(outputs)
+}
+
+/// The README and any extra pages of the package at the given paths.
+fn documentation_pages(paths: &ProjectPaths, config: &PackageConfig) -> Vec<DocsPage> {
     let mut pages = vec![DocsPage {
         title: "README".into(),
         path: "index.html".into(),
         source: paths.readme(), // TODO: support non markdown READMEs. Or a default if there is none.
     }];
-    pages.extend(config.documentation.pages.iter().cloned());
-    let outputs =
-        gleam_core::docs::generate_html(&paths, config, compiled.modules.as_slice(), &pages);
-    Ok(outputs)
+    pages.extend(config.documentation.pages.iter().map(|page| DocsPage {
+        source: paths.root().join(&page.source),
+        ..page.clone()
+    }));
+    pages
 }
 
 struct PublishCommand {
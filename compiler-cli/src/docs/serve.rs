@@ -0,0 +1,425 @@
+use std::{
+    collections::{HashMap, HashSet},
+    io::{BufRead, BufReader, Write},
+    net::{TcpListener, TcpStream},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+use gleam_core::{
+    build::{Codegen, Mode, Module, Options, Package},
+    config::PackageConfig,
+    docs::{DocumentedModules, PackageDocumentation},
+    error::Error,
+    paths::{ProjectPaths, ARTEFACT_DIRECTORY_NAME},
+    Result,
+};
+use smol_str::SmolStr;
+
+use crate::cli::{self, MessageFormat};
+
+/// The path the live reload script polls to find out whether the
+/// documentation has been rendered again since the page was loaded.
+const GENERATION_PATH: &str = "/__gleam/generation";
+
+const LIVE_RELOAD_SCRIPT: &str = r#"<script>
+      (function poll(generation) {
+        fetch("/__gleam/generation")
+          .then((response) => response.text())
+          .then((latest) => {
+            if (generation !== undefined && latest !== generation) {
+              location.reload();
+            } else {
+              setTimeout(() => poll(latest), 1000);
+            }
+          })
+          .catch(() => setTimeout(() => poll(generation), 1000));
+      })();
+    </script>
+  </body>"#;
+
+/// Render the documentation for the project and all of its Gleam
+/// dependencies, serve it on localhost, and render it again each time the
+/// project changes. Pages open in a browser reload once the new
+/// documentation has been written.
+///
+/// This function only returns if the server or the file system watcher
+/// could not be started.
+pub fn serve(port: u16) -> Result<()> {
+    let paths = crate::project_paths_at_current_directory();
+    let site = paths.build_directory_for_mode(Mode::Dev).join("docs");
+    let address = format!("127.0.0.1:{port}");
+    let listener = TcpListener::bind(&address).map_err(|error| Error::UnableToStartDocsServer {
+        address: address.clone(),
+        error: error.to_string(),
+    })?;
+    let generation = Arc::new(AtomicU64::new(0));
+
+    let server_site = site.clone();
+    let server_generation = generation.clone();
+    let _ = std::thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let site = server_site.clone();
+            let generation = server_generation.clone();
+            let _ = std::thread::spawn(move || {
+                if let Err(error) = respond(stream, &site, &generation) {
+                    tracing::debug!(error = %error, "docs_server_response_failed");
+                }
+            });
+        }
+    });
+
+    let url = format!("http://localhost:{port}/");
+    let mut documentation = Site::new(site);
+    crate::watch::watch(MessageFormat::Human, || {
+        documentation.render(&paths)?;
+        let _ = generation.fetch_add(1, Ordering::SeqCst);
+        cli::print_serving(&url);
+        Ok(())
+    })
+}
+
+/// The documentation of every package being served, each in a directory
+/// named after the package.
+#[derive(Debug)]
+struct Site {
+    directory: PathBuf,
+    root_package: SmolStr,
+    sources: HashMap<SmolStr, PackageSources>,
+    packages: HashMap<SmolStr, PackageDocumentation>,
+    documented_modules: DocumentedModules,
+}
+
+impl Site {
+    fn new(directory: PathBuf) -> Self {
+        Self {
+            directory,
+            root_package: SmolStr::default(),
+            sources: HashMap::new(),
+            packages: HashMap::new(),
+            documented_modules: DocumentedModules::default(),
+        }
+    }
+
+    fn render(&mut self, paths: &ProjectPaths) -> Result<()> {
+        let config = crate::config::root_config()?;
+
+        // The package compiler only returns the modules it compiled, so the
+        // first time the Gleam packages are built again from scratch to get
+        // every module. After that the modules are kept between renders and
+        // only the ones that were compiled again are replaced.
+        if self.sources.is_empty() {
+            crate::fs::delete_dir(&self.directory)?;
+            delete_gleam_package_builds(
+                &paths.build_directory_for_target(Mode::Prod, config.target),
+            )?;
+        }
+
+        let built = crate::build::main(Options {
+            mode: Mode::Prod,
+            target: None,
+            codegen: Codegen::All,
+            warnings_as_errors: false,
+        })?;
+
+        // The root package is always documented again as its README and
+        // pages may have changed. Dependencies are only documented again if
+        // some of their modules were compiled, which for Hex packages is
+        // only the first time and for local packages is whenever they change.
+        let mut changed = vec![];
+        let compiled = std::iter::once((paths.root().to_path_buf(), built.root_package))
+            .chain(built.compiled_dependencies);
+        for (root, mut package) in compiled {
+            let name = package.config.name.clone();
+            let is_documented = self.sources.contains_key(&name);
+            if name != config.name && is_documented && package.modules.is_empty() {
+                continue;
+            }
+            package.attach_doc_and_module_comments();
+            match self.sources.get_mut(&name) {
+                Some(sources) => sources.update(root, package),
+                None => {
+                    let _ = self.sources.insert(
+                        name.clone(),
+                        PackageSources {
+                            root,
+                            config: package.config,
+                            modules: package.modules,
+                        },
+                    );
+                }
+            }
+            changed.push(name);
+        }
+
+        let changed: Vec<_> = self
+            .sources
+            .iter()
+            .filter(|(name, _)| changed.contains(name))
+            .collect();
+        for (_, sources) in &changed {
+            self.documented_modules
+                .insert_package(&sources.config, &sources.modules);
+        }
+
+        cli::print_generating_documentation();
+        for (name, sources) in changed {
+            let paths = ProjectPaths::new(sources.root.clone());
+            let pages = super::documentation_pages(&paths, &sources.config);
+            let documentation = gleam_core::docs::generate_package_html(
+                &paths,
+                &sources.config,
+                &sources.modules,
+                &pages,
+                &self.documented_modules,
+            );
+            let directory = self.directory.join(name.as_str());
+            crate::fs::delete_dir(&directory)?;
+            crate::fs::write_outputs_under(&documentation.files, &directory)?;
+            let _ = self.packages.insert(name.clone(), documentation);
+        }
+        self.root_package = config.name;
+
+        // Every package searches the documentation of all the others, so
+        // the search data of each one is written again.
+        let packages = self.packages_root_first();
+        for package in &packages {
+            let search_data = gleam_core::docs::search_data(&package.name, &packages);
+            let directory = self.directory.join(package.name.as_str());
+            crate::fs::write_outputs_under(&[search_data], &directory)?;
+        }
+        let index = gleam_core::docs::packages_index(&packages);
+        crate::fs::write_outputs_under(&[index], &self.directory)
+    }
+
+    fn packages_root_first(&self) -> Vec<&PackageDocumentation> {
+        let mut packages: Vec<_> = self.packages.values().collect();
+        packages.sort_by_key(|package| (package.name != self.root_package, &package.name));
+        packages
+    }
+}
+
+/// The compiled modules of a package, kept so that it can be documented
+/// again without compiling every one of its modules.
+#[derive(Debug)]
+struct PackageSources {
+    root: PathBuf,
+    config: PackageConfig,
+    modules: Vec<Module>,
+}
+
+impl PackageSources {
+    /// Replace the modules that were compiled again, dropping any whose
+    /// source file has since been removed.
+    fn update(&mut self, root: PathBuf, package: Package) {
+        let compiled: HashSet<_> = package
+            .modules
+            .iter()
+            .map(|module| module.name.clone())
+            .collect();
+        self.modules
+            .retain(|module| !compiled.contains(&module.name) && module.input_path.is_file());
+        self.modules.extend(package.modules);
+        self.modules.sort_by(|a, b| a.name.cmp(&b.name));
+        self.root = root;
+        self.config = package.config;
+    }
+}
+
+/// Delete the build directory of each Gleam package, leaving the packages
+/// built by other build tools in place.
+fn delete_gleam_package_builds(build_directory: &Path) -> Result<()> {
+    if !build_directory.is_dir() {
+        return Ok(());
+    }
+    for entry in crate::fs::read_dir(build_directory)?.flatten() {
+        let path = entry.path();
+        if path.join(ARTEFACT_DIRECTORY_NAME).is_dir() {
+            crate::fs::delete_dir(&path)?;
+        }
+    }
+    Ok(())
+}
+
+fn respond(stream: TcpStream, site: &Path, generation: &AtomicU64) -> std::io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut request_line = String::new();
+    let _ = reader.read_line(&mut request_line)?;
+
+    // The headers are not needed, but are read so that the client has
+    // finished sending its request before the response is written.
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? <= 2 {
+            break;
+        }
+    }
+
+    let target = request_line.split_whitespace().nth(1).unwrap_or("/");
+    let path = target.split(['?', '#']).next().unwrap_or("/");
+    let response = if path == GENERATION_PATH {
+        Response::ok(
+            "text/plain",
+            generation.load(Ordering::SeqCst).to_string().into_bytes(),
+        )
+    } else {
+        site_response(site, path)
+    };
+    response.write(stream)
+}
+
+fn site_response(site: &Path, path: &str) -> Response {
+    let file = match site_file(site, path) {
+        Some(file) => file,
+        None => return Response::not_found(),
+    };
+
+    // Pages use paths relative to the directory they are in, so a directory
+    // must be requested with a trailing slash for those paths to work.
+    if file.is_dir() && !path.ends_with('/') {
+        return Response::redirect(format!("{path}/"));
+    }
+    let file = if file.is_dir() {
+        file.join("index.html")
+    } else {
+        file
+    };
+
+    match std::fs::read(&file) {
+        Ok(body) => match file.extension().and_then(|extension| extension.to_str()) {
+            Some("html") => Response::ok(
+                "text/html; charset=utf-8",
+                with_live_reload(&String::from_utf8_lossy(&body)).into_bytes(),
+            ),
+            extension => Response::ok(content_type(extension), body),
+        },
+        Err(_) => Response::not_found(),
+    }
+}
+
+/// The file in the site for a request path, or `None` if the path would
+/// lead outside of the site.
+fn site_file(site: &Path, path: &str) -> Option<PathBuf> {
+    let mut file = site.to_path_buf();
+    for segment in path.split('/').filter(|segment| !segment.is_empty()) {
+        if segment == "." || segment == ".." || segment.contains('\\') {
+            return None;
+        }
+        file.push(segment);
+    }
+    Some(file)
+}
+
+fn with_live_reload(html: &str) -> String {
+    match html.rfind("</body>") {
+        Some(index) => {
+            let (page, end) = html.split_at(index);
+            format!(
+                "{page}{LIVE_RELOAD_SCRIPT}{}",
+                end.trim_start_matches("</body>")
+            )
+        }
+        None => html.to_string(),
+    }
+}
+
+fn content_type(extension: Option<&str>) -> &'static str {
+    match extension {
+        Some("css") => "text/css",
+        Some("js") => "text/javascript",
+        Some("json") => "application/json",
+        Some("svg") => "image/svg+xml",
+        Some("png") => "image/png",
+        Some("woff2") => "font/woff2",
+        _ => "application/octet-stream",
+    }
+}
+
+#[derive(Debug)]
+struct Response {
+    status: &'static str,
+    headers: Vec<(&'static str, String)>,
+    body: Vec<u8>,
+}
+
+impl Response {
+    fn ok(content_type: &str, body: Vec<u8>) -> Self {
+        Self {
+            status: "200 OK",
+            headers: vec![("Content-Type", content_type.into())],
+            body,
+        }
+    }
+
+    fn not_found() -> Self {
+        Self {
+            status: "404 Not Found",
+            headers: vec![("Content-Type", "text/plain".into())],
+            body: b"Not found".to_vec(),
+        }
+    }
+
+    fn redirect(location: String) -> Self {
+        Self {
+            status: "301 Moved Permanently",
+            headers: vec![("Location", location)],
+            body: vec![],
+        }
+    }
+
+    fn write(self, mut stream: TcpStream) -> std::io::Result<()> {
+        let mut head = format!("HTTP/1.1 {}\r\n", self.status);
+        for (name, value) in self.headers {
+            head.push_str(&format!("{name}: {value}\r\n"));
+        }
+        head.push_str(&format!(
+            "Content-Length: {}\r\nCache-Control: no-cache\r\nConnection: close\r\n\r\n",
+            self.body.len()
+        ));
+        stream.write_all(head.as_bytes())?;
+        stream.write_all(&self.body)?;
+        stream.flush()
+    }
+}
+
+#[test]
+fn site_file_test() {
+    let site = Path::new("/build/dev/docs");
+    assert_eq!(site_file(site, "/"), Some(site.to_path_buf()));
+    assert_eq!(
+        site_file(site, "/wibble/gleam/wobble.html"),
+        Some(site.join("wibble/gleam/wobble.html"))
+    );
+    assert_eq!(
+        site_file(site, "//wibble//index.html"),
+        Some(site.join("wibble/index.html"))
+    );
+    assert_eq!(site_file(site, "/wibble/../../secret"), None);
+    assert_eq!(site_file(site, "/./wibble"), None);
+}
+
+#[test]
+fn with_live_reload_test() {
+    let html = with_live_reload("<html><body><p>Hi</p></body></html>");
+    assert!(html.starts_with("<html><body><p>Hi</p><script>"));
+    assert!(html.contains(GENERATION_PATH));
+    assert!(html.ends_with("</script>\n  </body></html>"));
+    assert_eq!(with_live_reload("<p>Hi</p>"), "<p>Hi</p>");
+}
+
+#[test]
+fn delete_gleam_package_builds_test() {
+    let tmp = tempfile::tempdir().unwrap();
+    let build = tmp.path().join("prod/erlang");
+    std::fs::create_dir_all(build.join("app").join(ARTEFACT_DIRECTORY_NAME)).unwrap();
+    std::fs::create_dir_all(build.join("certifi/ebin")).unwrap();
+    delete_gleam_package_builds(&build).unwrap();
+    assert!(!build.join("app").exists());
+    assert!(build.join("certifi/ebin").is_dir());
+
+    // There is nothing to delete before the project has been built
+    delete_gleam_package_builds(&tmp.path().join("dev/erlang")).unwrap();
+}
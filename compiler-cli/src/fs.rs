@@ -21,7 +21,10 @@ use std::{
     time::SystemTime,
 };
 
-use crate::{dependencies::UseManifest, lsp::LspLocker};
+use crate::{
+    dependencies::{Connectivity, UseManifest},
+    lsp::LspLocker,
+};
 
 #[cfg(test)]
 mod tests;
@@ -193,7 +196,13 @@ impl MakeLocker for ProjectIO {
 
 impl DownloadDependencies for ProjectIO {
     fn download_dependencies(&self, paths: &ProjectPaths) -> Result<Manifest> {
-        crate::dependencies::download(paths, NullTelemetry, None, UseManifest::Yes)
+        crate::dependencies::download(
+            paths,
+            NullTelemetry,
+            None,
+            UseManifest::Yes,
+            Connectivity::Online,
+        )
     }
 }
 
@@ -631,3 +640,18 @@ impl WarningEmitterIO for ConsoleWarningEmitter {
             .expect("Writing warning to stderr");
     }
 }
+
+/// Prints warnings to stderr as JSON, one object per line.
+#[derive(Debug, Clone, Copy)]
+pub struct JsonWarningEmitter;
+
+impl WarningEmitterIO for JsonWarningEmitter {
+    fn emit_warning(&self, warning: Warning) {
+        let buffer_writer = crate::cli::stderr_buffer_writer();
+        let mut buffer = buffer_writer.buffer();
+        crate::cli::write_json_diagnostics(&mut buffer, [warning.to_diagnostic()]);
+        buffer_writer
+            .print(&buffer)
+            .expect("Writing warning to stderr");
+    }
+}
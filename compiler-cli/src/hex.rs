@@ -1,5 +1,5 @@
 use gleam_core::{
-    hex::{self, RetirementReason},
+    hex::{self, Repositories, Repository, RetirementReason, HEXPM_REPOSITORY},
     Result,
 };
 
@@ -20,10 +20,23 @@ pub trait ApiKeyCommand {
         api_key: &str,
     ) -> Result<()>;
 
+    /// The name of the Hex repository the command is run against.
+    fn repository_name(&self) -> &str {
+        HEXPM_REPOSITORY
+    }
+
     fn run(&mut self) -> Result<()> {
         let runtime = tokio::runtime::Runtime::new().expect("Unable to start Tokio async runtime");
+        let repository = repository(self.repository_name())?;
+        let hex_config = repository.config;
+
+        // A repository configured with an auth key uses it rather than an API
+        // key created with the user's credentials.
+        if let Some(auth_key) = repository.auth_key {
+            return self.with_api_key(runtime.handle(), &hex_config, &auth_key);
+        }
+
         let hostname = crate::publish::get_hostname();
-        let hex_config = hexpm::Config::new();
         let http = HttpClient::new();
 
         // Get login creds from user
@@ -57,11 +70,25 @@ pub trait ApiKeyCommand {
     }
 }
 
+/// The Hex repository with the given name, as configured by the project in
+/// the current directory, if there is one, and by the user's global config.
+pub fn repository(name: &str) -> Result<Repository> {
+    let paths = crate::project_paths_at_current_directory();
+    let project = if paths.root_config().is_file() {
+        crate::config::read(paths.root_config())?.repositories
+    } else {
+        vec![]
+    };
+    let user = crate::config::user_config()?.repositories;
+    Repositories::new(&project, &user)?.get(name).cloned()
+}
+
 pub struct RetireCommand {
     package: String,
     version: String,
     reason: RetirementReason,
     message: Option<String>,
+    repository: String,
 }
 
 impl RetireCommand {
@@ -70,17 +97,23 @@ impl RetireCommand {
         version: String,
         reason: RetirementReason,
         message: Option<String>,
+        repository: String,
     ) -> Self {
         Self {
             package,
             version,
             reason,
             message,
+            repository,
         }
     }
 }
 
 impl ApiKeyCommand for RetireCommand {
+    fn repository_name(&self) -> &str {
+        &self.repository
+    }
+
     fn with_api_key(
         &mut self,
         handle: &tokio::runtime::Handle,
@@ -104,15 +137,24 @@ impl ApiKeyCommand for RetireCommand {
 pub struct UnretireCommand {
     package: String,
     version: String,
+    repository: String,
 }
 
 impl UnretireCommand {
-    pub fn new(package: String, version: String) -> Self {
-        Self { package, version }
+    pub fn new(package: String, version: String, repository: String) -> Self {
+        Self {
+            package,
+            version,
+            repository,
+        }
     }
 }
 
 impl ApiKeyCommand for UnretireCommand {
+    fn repository_name(&self) -> &str {
+        &self.repository
+    }
+
     fn with_api_key(
         &mut self,
         handle: &tokio::runtime::Handle,
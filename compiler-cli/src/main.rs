@@ -57,6 +57,7 @@ mod cli;
 mod compile_package;
 mod config;
 mod dependencies;
+mod diff_api;
 mod docs;
 mod export;
 mod format;
@@ -65,13 +66,17 @@ mod hex;
 mod http;
 mod lsp;
 mod new;
+mod outdated;
 mod panic;
 mod publish;
 mod run;
 mod shell;
+mod test_runner;
+mod watch;
 
+use cli::MessageFormat;
 use config::root_config;
-use dependencies::UseManifest;
+use dependencies::{Connectivity, DependencyKind, UseManifest};
 pub use gleam_core::{
     error::{Error, Result},
     warning::Warning,
@@ -79,6 +84,7 @@ pub use gleam_core::{
 
 use gleam_core::{
     build::{Codegen, Mode, Options, Runtime, Target},
+    explanation,
     hex::RetirementReason,
     paths::ProjectPaths,
 };
@@ -103,10 +109,30 @@ enum Command {
         /// The platform to target
         #[clap(long, ignore_case = true)]
         target: Option<Target>,
+
+        /// Rebuild the project each time a file changes
+        #[clap(long)]
+        watch: bool,
+
+        /// The format to print errors and warnings in: `human` or `json`
+        #[clap(long, default_value = "human", possible_values = MessageFormat::VARIANTS)]
+        message_format: MessageFormat,
+
+        /// Use only vendored and cached packages, without connecting to Hex
+        #[clap(long)]
+        offline: bool,
     },
 
     /// Type check the project
-    Check,
+    Check {
+        /// The format to print errors and warnings in: `human` or `json`
+        #[clap(long, default_value = "human", possible_values = MessageFormat::VARIANTS)]
+        message_format: MessageFormat,
+
+        /// Use only vendored and cached packages, without connecting to Hex
+        #[clap(long)]
+        offline: bool,
+    },
 
     /// Publish the project to the Hex package manager
     ///
@@ -120,6 +146,25 @@ enum Command {
         replace: bool,
         #[clap(short, long)]
         yes: bool,
+        /// Publish even if the version is too low for the changes made to the
+        /// package's API since its previous release
+        #[clap(long)]
+        force: bool,
+        /// The Hex repository to publish to
+        #[clap(long, default_value = "hexpm")]
+        repository: String,
+    },
+
+    /// Show the changes to the package's API since its previous release and
+    /// the version bump they need
+    DiffApi {
+        /// The directory of the previous version of the package, to compare
+        /// with instead of its previous release on Hex
+        #[clap(long)]
+        previous: Option<PathBuf>,
+        /// The Hex repository the package is published to
+        #[clap(long, default_value = "hexpm")]
+        repository: String,
     },
 
     /// Render HTML documentation
@@ -185,6 +230,26 @@ enum Command {
         #[clap(long, ignore_case = true)]
         runtime: Option<Runtime>,
 
+        /// Only run the tests whose name contains this pattern
+        #[clap(long)]
+        filter: Option<String>,
+
+        /// Write a JUnit XML report of the results to this file
+        #[clap(long)]
+        junit: Option<PathBuf>,
+
+        /// Rerun the tests each time a file changes
+        #[clap(long)]
+        watch: bool,
+
+        /// The format to print errors and warnings in: `human` or `json`
+        #[clap(long, default_value = "human", possible_values = MessageFormat::VARIANTS)]
+        message_format: MessageFormat,
+
+        /// Use only vendored and cached packages, without connecting to Hex
+        #[clap(long)]
+        offline: bool,
+
         arguments: Vec<String>,
     },
 
@@ -217,6 +282,9 @@ enum Command {
     /// Export something useful from the Gleam project
     #[clap(subcommand)]
     Export(ExportTarget),
+
+    /// Explain an error or warning, given its code such as E0103
+    Explain { code: String },
 }
 
 #[derive(Subcommand, Debug, Clone, Copy)]
@@ -284,8 +352,36 @@ enum Dependencies {
     /// List all dependency packages
     List,
 
+    /// Show the dependency packages as a tree
+    Tree {
+        /// Only show the dependencies needed in production
+        #[clap(long, conflicts_with = "dev")]
+        prod: bool,
+
+        /// Only show the dev dependencies and the packages they need
+        #[clap(long)]
+        dev: bool,
+    },
+
+    /// Show every chain of dependencies that leads to a package
+    Why {
+        /// The name of the package
+        package: String,
+    },
+
+    /// List the dependencies that have newer releases or have been retired
+    Outdated,
+
     /// Download all dependency packages
-    Download,
+    Download {
+        /// Use only vendored and cached packages, without connecting to Hex
+        #[clap(long)]
+        offline: bool,
+    },
+
+    /// Copy the Hex packages in manifest.toml into the vendor directory so
+    /// the project can be built with `--offline`
+    Vendor,
 
     /// Update dependency packages to their latest versions
     Update,
@@ -309,6 +405,10 @@ enum Hex {
         reason: RetirementReason,
 
         message: Option<String>,
+
+        /// The Hex repository the release was published to
+        #[clap(long, default_value = "hexpm")]
+        repository: String,
     },
 
     /// Un-retire a release from Hex
@@ -318,7 +418,15 @@ enum Hex {
     /// - HEXPM_USER: (optional) The Hex username to authenticate with.
     /// - HEXPM_PASS: (optional) The Hex password to authenticate with.
     #[clap(verbatim_doc_comment)]
-    Unretire { package: String, version: String },
+    Unretire {
+        package: String,
+
+        version: String,
+
+        /// The Hex repository the release was published to
+        #[clap(long, default_value = "hexpm")]
+        repository: String,
+    },
 }
 
 #[derive(Subcommand, Debug)]
@@ -326,6 +434,14 @@ enum Docs {
     /// Render HTML docs locally
     Build,
 
+    /// Render HTML docs for the project and its dependencies and serve them
+    /// on localhost, rendering them again when the project changes
+    Serve {
+        /// The port to serve the documentation on
+        #[clap(long, default_value = "8000")]
+        port: u16,
+    },
+
     /// Publish HTML docs to HexDocs
     ///
     /// This command uses this environment variables:
@@ -356,18 +472,33 @@ enum Docs {
 fn main() {
     initialise_logger();
     panic::add_handler();
-    let stderr = cli::stderr_buffer_writer();
 
-    let result = match Command::parse() {
+    let command = Command::parse();
+    let message_format = command.message_format();
+    let result = match command {
         Command::Build {
             target,
             warnings_as_errors,
-        } => command_build(target, warnings_as_errors),
+            watch,
+            message_format,
+            offline,
+        } => command_build(
+            target,
+            warnings_as_errors,
+            watch,
+            message_format,
+            Connectivity::from_offline_flag(offline),
+        ),
 
-        Command::Check => command_check(),
+        Command::Check {
+            message_format,
+            offline,
+        } => command_check(message_format, Connectivity::from_offline_flag(offline)),
 
         Command::Docs(Docs::Build) => docs::build(),
 
+        Command::Docs(Docs::Serve { port }) => docs::serve(port),
+
         Command::Docs(Docs::Publish) => docs::publish(),
 
         Command::Docs(Docs::Remove { package, version }) => docs::remove(package, version),
@@ -380,7 +511,19 @@ fn main() {
 
         Command::Deps(Dependencies::List) => dependencies::list(),
 
-        Command::Deps(Dependencies::Download) => download_dependencies(),
+        Command::Deps(Dependencies::Tree { prod, dev }) => {
+            dependencies::tree(DependencyKind::from_flags(prod, dev))
+        }
+
+        Command::Deps(Dependencies::Why { package }) => dependencies::why(&package),
+
+        Command::Deps(Dependencies::Outdated) => outdated::command(),
+
+        Command::Deps(Dependencies::Download { offline }) => {
+            download_dependencies(Connectivity::from_offline_flag(offline))
+        }
+
+        Command::Deps(Dependencies::Vendor) => dependencies::vendor(),
 
         Command::Deps(Dependencies::Update) => dependencies::update(),
 
@@ -393,17 +536,47 @@ fn main() {
             arguments,
             runtime,
             module,
-        } => run::command(arguments, target, runtime, module, run::Which::Src),
+        } => run::command(arguments, target, runtime, module),
 
         Command::Test {
             target,
-            arguments,
             runtime,
-        } => run::command(arguments, target, runtime, None, run::Which::Test),
+            filter,
+            junit,
+            watch,
+            message_format,
+            offline,
+            arguments,
+        } => {
+            let options = test_runner::TestOptions {
+                target,
+                runtime,
+                filter,
+                junit,
+                message_format,
+                connectivity: Connectivity::from_offline_flag(offline),
+                arguments,
+            };
+            if watch {
+                test_runner::watch(options)
+            } else {
+                test_runner::command(options)
+            }
+        }
 
         Command::CompilePackage(opts) => compile_package::command(opts),
 
-        Command::Publish { replace, yes } => publish::command(replace, yes),
+        Command::Publish {
+            replace,
+            yes,
+            force,
+            repository,
+        } => publish::command(replace, yes, force, repository),
+
+        Command::DiffApi {
+            previous,
+            repository,
+        } => diff_api::command(previous, repository),
 
         Command::PrintConfig => print_config(),
 
@@ -412,11 +585,14 @@ fn main() {
             version,
             reason,
             message,
-        }) => hex::RetireCommand::new(package, version, reason, message).run(),
+            repository,
+        }) => hex::RetireCommand::new(package, version, reason, message, repository).run(),
 
-        Command::Hex(Hex::Unretire { package, version }) => {
-            hex::UnretireCommand::new(package, version).run()
-        }
+        Command::Hex(Hex::Unretire {
+            package,
+            version,
+            repository,
+        }) => hex::UnretireCommand::new(package, version, repository).run(),
 
         Command::Add { packages, dev } => add::command(packages, dev),
 
@@ -428,6 +604,8 @@ fn main() {
 
         Command::Export(ExportTarget::ErlangShipment) => export::erlang_shipment(),
         Command::Export(ExportTarget::HexTarball) => export::hex_tarball(),
+
+        Command::Explain { code } => explain(&code),
     };
 
     match result {
@@ -436,40 +614,80 @@ fn main() {
         }
         Err(error) => {
             tracing::error!(error = ?error, "Failed");
-            let mut buffer = stderr.buffer();
-            error.pretty(&mut buffer);
-            stderr.print(&buffer).expect("Final result error writing");
+            cli::print_error(&error, message_format);
             std::process::exit(1);
         }
     }
 }
 
-fn command_check() -> Result<(), Error> {
-    let _ = build::main(Options {
-        warnings_as_errors: false,
-        codegen: Codegen::DepsOnly,
-        mode: Mode::Dev,
-        target: None,
-    })?;
-    Ok(())
+impl Command {
+    /// The format errors are to be printed in. Only the commands that build
+    /// the project can print them as JSON.
+    fn message_format(&self) -> MessageFormat {
+        match self {
+            Command::Build { message_format, .. }
+            | Command::Check { message_format, .. }
+            | Command::Test { message_format, .. } => *message_format,
+            _ => MessageFormat::Human,
+        }
+    }
 }
 
-fn command_build(target: Option<Target>, warnings_as_errors: bool) -> Result<(), Error> {
-    let _ = build::main(Options {
-        warnings_as_errors,
-        codegen: Codegen::All,
-        mode: Mode::Dev,
-        target,
-    })?;
+fn command_check(message_format: MessageFormat, connectivity: Connectivity) -> Result<(), Error> {
+    let _ = build::main_with_message_format(
+        Options {
+            warnings_as_errors: false,
+            codegen: Codegen::DepsOnly,
+            mode: Mode::Dev,
+            target: None,
+        },
+        message_format,
+        connectivity,
+    )?;
     Ok(())
 }
 
+fn command_build(
+    target: Option<Target>,
+    warnings_as_errors: bool,
+    watch: bool,
+    message_format: MessageFormat,
+    connectivity: Connectivity,
+) -> Result<(), Error> {
+    let build = || {
+        let _ = build::main_with_message_format(
+            Options {
+                warnings_as_errors,
+                codegen: Codegen::All,
+                mode: Mode::Dev,
+                target,
+            },
+            message_format,
+            connectivity,
+        )?;
+        Ok(())
+    };
+
+    if watch {
+        watch::watch(message_format, build)
+    } else {
+        build()
+    }
+}
+
 fn print_config() -> Result<()> {
     let config = root_config()?;
     println!("{config:#?}");
     Ok(())
 }
 
+fn explain(code: &str) -> Result<()> {
+    let explanation = explanation::explanation(code)
+        .ok_or_else(|| Error::UnknownErrorCode { code: code.into() })?;
+    print!("{explanation}");
+    Ok(())
+}
+
 fn clean() -> Result<()> {
     let paths = project_paths_at_current_directory();
     fs::delete_dir(&paths.build_directory())
@@ -491,8 +709,14 @@ fn project_paths_at_current_directory() -> ProjectPaths {
     ProjectPaths::new(current_dir)
 }
 
-fn download_dependencies() -> Result<(), Error> {
+fn download_dependencies(connectivity: Connectivity) -> Result<(), Error> {
     let paths = project_paths_at_current_directory();
-    _ = dependencies::download(&paths, cli::Reporter::new(), None, UseManifest::Yes)?;
+    _ = dependencies::download(
+        &paths,
+        cli::Reporter::new(),
+        None,
+        UseManifest::Yes,
+        connectivity,
+    )?;
     Ok(())
 }
@@ -0,0 +1,489 @@
+use futures::future;
+use gleam_core::{
+    config::PackageConfig,
+    hex::Repositories,
+    manifest::{Manifest, ManifestPackageSource},
+    requirement::Requirement,
+    Error, Result,
+};
+use hexpm::{
+    version::{Range, Version},
+    RetirementReason, RetirementStatus,
+};
+use termcolor::{Color, ColorSpec, WriteColor};
+
+use crate::{cli, http::HttpClient};
+
+pub fn command() -> Result<()> {
+    let paths = crate::project_paths_at_current_directory();
+    let config = crate::config::root_config()?;
+    let manifest = crate::dependencies::current_manifest(&paths, &config)?;
+    let repositories = crate::config::repositories(&config)?;
+
+    let runtime = tokio::runtime::Runtime::new().expect("Unable to start Tokio async runtime");
+    let dependencies = runtime.block_on(outdated_dependencies(
+        &config,
+        &manifest,
+        &repositories,
+        &HttpClient::new(),
+    ))?;
+
+    let buffer_writer = cli::stdout_buffer_writer();
+    let mut buffer = buffer_writer.buffer();
+    write_report(&mut buffer, &dependencies).map_err(|e| Error::StandardIo {
+        action: gleam_core::error::StandardIoAction::Write,
+        err: Some(e.kind()),
+    })?;
+    buffer_writer.print(&buffer).map_err(|e| Error::StandardIo {
+        action: gleam_core::error::StandardIoAction::Write,
+        err: Some(e.kind()),
+    })
+}
+
+/// A Hex dependency of the project along with the newer releases of it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outdated {
+    pub name: String,
+    /// The version locked in the manifest.
+    pub current: Version,
+    /// The latest release allowed by the version range in gleam.toml.
+    pub compatible: Version,
+    /// The latest release of the package.
+    pub latest: Version,
+    /// Set if the current version has been retired.
+    pub retirement: Option<RetirementStatus>,
+    /// Whether the latest release is not allowed by the version range in
+    /// gleam.toml, so gleam.toml must be changed to upgrade to it.
+    pub needs_range_change: bool,
+}
+
+impl Outdated {
+    fn new(name: String, current: Version, range: &Range, package: &hexpm::Package) -> Self {
+        let allowed = range.to_pubgrub().ok();
+        let retirement = package
+            .releases
+            .iter()
+            .find(|release| release.version == current)
+            .and_then(|release| release.retirement_status.clone());
+
+        // Retired releases and pre-releases are not suggested as upgrades.
+        let candidates = package
+            .releases
+            .iter()
+            .filter(|release| release.retirement_status.is_none() && !release.version.is_pre())
+            .map(|release| &release.version);
+        let latest = candidates
+            .clone()
+            .max()
+            .cloned()
+            .unwrap_or_else(|| current.clone())
+            .max(current.clone());
+        let compatible = candidates
+            .filter(|version| {
+                allowed
+                    .as_ref()
+                    .is_some_and(|range| range.contains(version))
+            })
+            .max()
+            .cloned()
+            .unwrap_or_else(|| current.clone())
+            .max(current.clone());
+        let needs_range_change = latest != compatible;
+
+        Self {
+            name,
+            current,
+            compatible,
+            latest,
+            retirement,
+            needs_range_change,
+        }
+    }
+
+    fn is_outdated(&self) -> bool {
+        self.latest != self.current || self.retirement.is_some()
+    }
+
+    /// Whether upgrading to the latest release is a breaking change according
+    /// to semantic versioning, in which case it needs a change to gleam.toml.
+    /// Before 1.0.0 minor releases are breaking.
+    pub fn is_major_bump(&self) -> bool {
+        let (current, latest) = (&self.current, &self.latest);
+        self.needs_range_change
+            && if current.major == 0 {
+                latest.major > 0 || latest.minor > current.minor
+            } else {
+                latest.major > current.major
+            }
+    }
+
+    fn status(&self) -> String {
+        let mut status = vec![];
+        if let Some(retirement) = &self.retirement {
+            let reason = match retirement.reason {
+                RetirementReason::Other => "other",
+                RetirementReason::Invalid => "invalid",
+                RetirementReason::Security => "security",
+                RetirementReason::Deprecated => "deprecated",
+                RetirementReason::Renamed => "renamed",
+            };
+            if retirement.message.is_empty() {
+                status.push(format!("retired ({reason})"));
+            } else {
+                status.push(format!("retired ({reason}): {}", retirement.message));
+            }
+        }
+        if self.is_major_bump() {
+            status.push("major version, update gleam.toml".into());
+        } else if self.needs_range_change {
+            status.push("update gleam.toml".into());
+        }
+        status.join(", ")
+    }
+}
+
+/// Looks up the releases of each of the project's Hex dependencies and
+/// returns those that have newer releases or have been retired.
+pub async fn outdated_dependencies(
+    config: &PackageConfig,
+    manifest: &Manifest,
+    repositories: &Repositories,
+    http: &impl gleam_core::io::HttpClient,
+) -> Result<Vec<Outdated>> {
+    let mut lookups = vec![];
+    for (name, requirement) in config.dependencies.iter().chain(&config.dev_dependencies) {
+        let Requirement::Hex { version: range, .. } = requirement else {
+            continue;
+        };
+        let Some(package) = manifest.packages.iter().find(|p| &p.name == name) else {
+            continue;
+        };
+        let ManifestPackageSource::Hex { repository, .. } = &package.source else {
+            continue;
+        };
+        let repository = repositories.get(repository)?;
+        lookups.push(async move {
+            tracing::debug!(package = name.as_str(), "looking_up_hex_package");
+            let request = hexpm::get_package_request(
+                name,
+                repository.auth_key.as_deref(),
+                &repository.config,
+            );
+            let response = http.send(request).await?;
+            let hex_package = hexpm::get_package_response(response, &repository.public_key)
+                .map_err(Error::hex)?;
+            Ok::<_, Error>(Outdated::new(
+                name.clone(),
+                package.version.clone(),
+                range,
+                &hex_package,
+            ))
+        });
+    }
+
+    let mut outdated: Vec<Outdated> = future::try_join_all(lookups)
+        .await?
+        .into_iter()
+        .filter(Outdated::is_outdated)
+        .collect();
+    outdated.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(outdated)
+}
+
+fn write_report(buffer: &mut impl WriteColor, dependencies: &[Outdated]) -> std::io::Result<()> {
+    if dependencies.is_empty() {
+        return writeln!(buffer, "All dependencies are up to date");
+    }
+
+    let header = ["Package", "Current", "Compatible", "Latest", "Status"];
+    let rows: Vec<[String; 5]> = dependencies
+        .iter()
+        .map(|dependency| {
+            [
+                dependency.name.clone(),
+                dependency.current.to_string(),
+                dependency.compatible.to_string(),
+                dependency.latest.to_string(),
+                dependency.status(),
+            ]
+        })
+        .collect();
+    let mut widths = header.map(str::len);
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+
+    let write_row = |buffer: &mut dyn WriteColor, row: [&str; 5], highlight: bool| {
+        let [name, current, compatible, latest, status] = row;
+        let [name_width, current_width, compatible_width, latest_width, _] = widths;
+        write!(
+            buffer,
+            "{name:name_width$}  {current:current_width$}  {compatible:compatible_width$}  "
+        )?;
+        if highlight {
+            buffer.set_color(ColorSpec::new().set_bold(true).set_fg(Some(Color::Red)))?;
+        }
+        write!(buffer, "{latest:latest_width$}")?;
+        buffer.set_color(&ColorSpec::new())?;
+        if status.is_empty() {
+            writeln!(buffer)
+        } else {
+            writeln!(buffer, "  {status}")
+        }
+    };
+
+    write_row(buffer, header, false)?;
+    for (row, dependency) in rows.iter().zip(dependencies) {
+        let [name, current, compatible, latest, status] = row;
+        write_row(
+            buffer,
+            [name, current, compatible, latest, status],
+            dependency.is_major_bump(),
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+fn package(releases: Vec<(&str, Option<RetirementStatus>)>) -> hexpm::Package {
+    hexpm::Package {
+        name: "wibble".into(),
+        repository: "hexpm".into(),
+        releases: releases
+            .into_iter()
+            .map(|(version, retirement_status)| hexpm::Release {
+                version: Version::parse(version).unwrap(),
+                requirements: [].into(),
+                retirement_status,
+                outer_checksum: vec![],
+                meta: (),
+            })
+            .collect(),
+    }
+}
+
+#[test]
+fn outdated_minor_release() {
+    let package = package(vec![("1.0.0", None), ("1.1.0", None), ("1.2.0-rc1", None)]);
+    let outdated = Outdated::new(
+        "wibble".into(),
+        Version::new(1, 0, 0),
+        &Range::new("~> 1.0".into()),
+        &package,
+    );
+    assert_eq!(outdated.compatible, Version::new(1, 1, 0));
+    assert_eq!(outdated.latest, Version::new(1, 1, 0));
+    assert!(outdated.is_outdated());
+    assert!(!outdated.needs_range_change);
+    assert!(!outdated.is_major_bump());
+    assert_eq!(outdated.status(), "");
+}
+
+#[test]
+fn outdated_major_release() {
+    let package = package(vec![("1.0.0", None), ("1.1.0", None), ("2.0.0", None)]);
+    let outdated = Outdated::new(
+        "wibble".into(),
+        Version::new(1, 0, 0),
+        &Range::new("~> 1.0".into()),
+        &package,
+    );
+    assert_eq!(outdated.compatible, Version::new(1, 1, 0));
+    assert_eq!(outdated.latest, Version::new(2, 0, 0));
+    assert!(outdated.is_major_bump());
+    assert_eq!(outdated.status(), "major version, update gleam.toml");
+}
+
+#[test]
+fn outdated_minor_release_before_1_0_0_is_major() {
+    let package = package(vec![("0.29.0", None), ("0.30.0", None)]);
+    let outdated = Outdated::new(
+        "wibble".into(),
+        Version::new(0, 29, 0),
+        &Range::new("~> 0.29.0".into()),
+        &package,
+    );
+    assert_eq!(outdated.compatible, Version::new(0, 29, 0));
+    assert_eq!(outdated.latest, Version::new(0, 30, 0));
+    assert!(outdated.is_major_bump());
+}
+
+#[test]
+fn outdated_non_breaking_release_outside_range() {
+    let package = package(vec![("1.0.0", None), ("1.1.0", None)]);
+    let outdated = Outdated::new(
+        "wibble".into(),
+        Version::new(1, 0, 0),
+        &Range::new("== 1.0.0".into()),
+        &package,
+    );
+    assert!(outdated.needs_range_change);
+    assert!(!outdated.is_major_bump());
+    assert_eq!(outdated.status(), "update gleam.toml");
+}
+
+#[test]
+fn outdated_retired_releases() {
+    let retired = || {
+        Some(RetirementStatus {
+            reason: RetirementReason::Security,
+            message: "Leaks secrets".into(),
+        })
+    };
+    let package = package(vec![("1.0.0", retired()), ("1.1.0", retired())]);
+    let outdated = Outdated::new(
+        "wibble".into(),
+        Version::new(1, 0, 0),
+        &Range::new("~> 1.0".into()),
+        &package,
+    );
+    // Retired releases are not suggested as upgrades
+    assert_eq!(outdated.compatible, Version::new(1, 0, 0));
+    assert_eq!(outdated.latest, Version::new(1, 0, 0));
+    assert!(outdated.is_outdated());
+    assert_eq!(outdated.status(), "retired (security): Leaks secrets");
+}
+
+#[test]
+fn outdated_up_to_date() {
+    let package = package(vec![("0.9.0", None), ("1.0.0", None)]);
+    let outdated = Outdated::new(
+        "wibble".into(),
+        Version::new(1, 0, 0),
+        &Range::new("~> 1.0".into()),
+        &package,
+    );
+    assert!(!outdated.is_outdated());
+}
+
+#[test]
+fn outdated_report_format() {
+    let dependencies = vec![
+        Outdated {
+            name: "gleam_http".into(),
+            current: Version::new(1, 0, 0),
+            compatible: Version::new(1, 1, 0),
+            latest: Version::new(2, 0, 0),
+            retirement: None,
+            needs_range_change: true,
+        },
+        Outdated {
+            name: "lustre".into(),
+            current: Version::new(0, 3, 0),
+            compatible: Version::new(0, 3, 1),
+            latest: Version::new(0, 3, 1),
+            retirement: Some(RetirementStatus {
+                reason: RetirementReason::Invalid,
+                message: "".into(),
+            }),
+            needs_range_change: false,
+        },
+    ];
+    let mut buffer = termcolor::Buffer::no_color();
+    write_report(&mut buffer, &dependencies).unwrap();
+    assert_eq!(
+        std::str::from_utf8(buffer.as_slice()).unwrap(),
+        "\
+Package     Current  Compatible  Latest  Status
+gleam_http  1.0.0    1.1.0       2.0.0   major version, update gleam.toml
+lustre      0.3.0    0.3.1       0.3.1   retired (invalid)
+"
+    );
+
+    let mut buffer = termcolor::Buffer::no_color();
+    write_report(&mut buffer, &[]).unwrap();
+    assert_eq!(
+        std::str::from_utf8(buffer.as_slice()).unwrap(),
+        "All dependencies are up to date\n"
+    );
+}
+
+/// Serves the signed package information for `exfmt` from the hexpm crate's
+/// test suite, which was signed with the key in `test/hexpm/public_key`.
+#[cfg(test)]
+struct FixtureRepository;
+
+#[cfg(test)]
+#[async_trait::async_trait]
+impl gleam_core::io::HttpClient for FixtureRepository {
+    async fn send(&self, request: http::Request<Vec<u8>>) -> Result<http::Response<Vec<u8>>> {
+        let (status, body) = match request.uri().path() {
+            "/repo/packages/exfmt" => (
+                http::StatusCode::OK,
+                include_bytes!("../test/hexpm/package_exfmt").to_vec(),
+            ),
+            _ => (http::StatusCode::FORBIDDEN, vec![]),
+        };
+        Ok(http::Response::builder()
+            .status(status)
+            .body(body)
+            .expect("Response"))
+    }
+}
+
+#[test]
+fn outdated_dependencies_from_repository() {
+    use gleam_core::{
+        config::HexRepositoryConfig,
+        manifest::{Base16Checksum, ManifestPackage},
+    };
+
+    let repository = HexRepositoryConfig {
+        name: "fixtures".into(),
+        api_url: Some("https://hex.example.com/api/".into()),
+        repo_url: Some("https://hex.example.com/repo/".into()),
+        auth_key: None,
+        public_key: Some(include_str!("../test/hexpm/public_key").into()),
+    };
+    let repositories = Repositories::new(&[repository], &[]).unwrap();
+
+    let config = PackageConfig {
+        dependencies: [(
+            "exfmt".into(),
+            Requirement::Hex {
+                version: Range::new("~> 0.2.0".into()),
+                repository: Some("fixtures".into()),
+            },
+        )]
+        .into(),
+        ..Default::default()
+    };
+    let manifest = Manifest {
+        requirements: config.dependencies.clone(),
+        packages: vec![ManifestPackage {
+            name: "exfmt".into(),
+            version: Version::new(0, 2, 1),
+            build_tools: ["mix".into()].into(),
+            otp_app: None,
+            requirements: vec![],
+            source: ManifestPackageSource::Hex {
+                outer_checksum: Base16Checksum(vec![]),
+                repository: "fixtures".into(),
+            },
+        }],
+        local_requirements: Default::default(),
+    };
+
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let outdated = runtime
+        .block_on(outdated_dependencies(
+            &config,
+            &manifest,
+            &repositories,
+            &FixtureRepository,
+        ))
+        .unwrap();
+    assert_eq!(
+        outdated,
+        vec![Outdated {
+            name: "exfmt".into(),
+            current: Version::new(0, 2, 1),
+            compatible: Version::new(0, 2, 4),
+            latest: Version::new(0, 5, 0),
+            retirement: None,
+            needs_range_change: true,
+        }]
+    );
+}
@@ -4,6 +4,7 @@ use gleam_core::{
     config::{PackageConfig, SpdxLicense},
     hex, paths,
     paths::ProjectPaths,
+    requirement::Requirement,
     Error, Result,
 };
 use hexpm::version::{Range, Version};
@@ -15,10 +16,10 @@ use std::{
     time::Instant,
 };
 
-use crate::{build, cli, docs, fs, hex::ApiKeyCommand, http::HttpClient};
+use crate::{build, cli, diff_api::ApiComparison, docs, fs, hex::ApiKeyCommand, http::HttpClient};
 
-pub fn command(replace: bool, yes: bool) -> Result<()> {
-    PublishCommand::setup(replace, yes)?.run()
+pub fn command(replace: bool, yes: bool, force: bool, repository: String) -> Result<()> {
+    PublishCommand::setup(replace, yes, force, repository)?.run()
 }
 
 pub struct PublishCommand {
@@ -26,10 +27,11 @@ pub struct PublishCommand {
     package_tarball: Vec<u8>,
     docs_tarball: Vec<u8>,
     replace: bool,
+    repository: String,
 }
 
 impl PublishCommand {
-    pub fn setup(replace: bool, i_am_sure: bool) -> Result<Self> {
+    pub fn setup(replace: bool, i_am_sure: bool, force: bool, repository: String) -> Result<Self> {
         let paths = crate::project_paths_at_current_directory();
         let config = crate::config::root_config()?;
         let Tarball {
@@ -39,6 +41,17 @@ impl PublishCommand {
             generated_files_added,
         } = do_build_hex_tarball(&paths, &config)?;
 
+        // Check the version is high enough for the changes to the API since
+        // the previous release
+        let api_comparison = if force {
+            None
+        } else {
+            ApiComparison::new(&paths, &config, None, &repository)?
+        };
+        if let Some(comparison) = &api_comparison {
+            comparison.check_version(&config)?;
+        }
+
         // Build HTML documentation
         let docs_tarball =
             fs::create_tar_archive(docs::build_documentation(&config, &mut compile_result)?)?;
@@ -54,6 +67,9 @@ impl PublishCommand {
         for file in src_files_added.iter().sorted() {
             println!("  - {}", file.to_string_lossy());
         }
+        if let Some(comparison) = &api_comparison {
+            print!("\n{}", comparison.report(&config.version));
+        }
         println!("\nName: {}", config.name);
         println!("Version: {}", config.version);
 
@@ -71,11 +87,16 @@ impl PublishCommand {
             docs_tarball,
             package_tarball,
             replace,
+            repository,
         })
     }
 }
 
 impl ApiKeyCommand for PublishCommand {
+    fn repository_name(&self) -> &str {
+        &self.repository
+    }
+
     fn with_api_key(
         &mut self,
         runtime: &tokio::runtime::Handle,
@@ -171,6 +192,17 @@ fn do_build_hex_tarball(paths: &ProjectPaths, config: &PackageConfig) -> Result<
 }
 
 fn check_config_for_publishing(config: &PackageConfig) -> Result<()> {
+    // Hex packages can only depend on other Hex packages.
+    if config
+        .dependencies
+        .values()
+        .any(|requirement| !matches!(requirement, Requirement::Hex { .. }))
+    {
+        return Err(Error::PublishNonHexDependencies {
+            package: config.name.to_string(),
+        });
+    }
+
     // These fields are required to publish a Hex package. Hex will reject
     // packages without them.
     if config.description.is_empty() || config.licences.is_empty() {
@@ -205,7 +237,17 @@ fn metadata_config(
         requirements: config
             .dependencies
             .iter()
-            .map(|(name, requirement)| ReleaseRequirement { name, requirement })
+            .filter_map(|(name, requirement)| match requirement {
+                Requirement::Hex {
+                    version,
+                    repository,
+                } => Some(ReleaseRequirement {
+                    name,
+                    requirement: version,
+                    repository: repository.as_deref(),
+                }),
+                Requirement::Path { .. } | Requirement::Git { .. } => None,
+            })
             .collect(),
         build_tools: vec!["gleam"],
     }
@@ -397,17 +439,23 @@ struct ReleaseRequirement<'a> {
     name: &'a str,
     // optional: bool,
     requirement: &'a Range,
-    // Support alternate repositories at a later date.
-    // repository: String,
+    repository: Option<&'a str>,
 }
 impl<'a> ReleaseRequirement<'a> {
     pub fn as_erlang(&self) -> String {
+        let repository = match self.repository {
+            Some(repository) => format!(
+                r#",
+    {{<<"repository">>, <<"{repository}">>}}"#
+            ),
+            None => String::new(),
+        };
         format!(
             r#"
   {{<<"{app}">>, [
     {{<<"app">>, <<"{app}">>}},
     {{<<"optional">>, false}},
-    {{<<"requirement">>, <<"{requirement}">>}}
+    {{<<"requirement">>, <<"{requirement}">>}}{repository}
   ]}}"#,
             app = self.name,
             requirement = self.requirement,
@@ -450,10 +498,12 @@ fn release_metadata_as_erlang() {
             ReleaseRequirement {
                 name: "wibble",
                 requirement: &req1,
+                repository: None,
             },
             ReleaseRequirement {
                 name: "wobble",
                 requirement: &req2,
+                repository: Some("company"),
             },
         ],
         build_tools: vec!["gleam", "rebar3"],
@@ -479,7 +529,8 @@ fn release_metadata_as_erlang() {
   {<<"wobble">>, [
     {<<"app">>, <<"wobble">>},
     {<<"optional">>, false},
-    {<<"requirement">>, <<"~> 1.2">>}
+    {<<"requirement">>, <<"~> 1.2">>},
+    {<<"repository">>, <<"company">>}
   ]}
 ]}.
 {<<"files">>, [
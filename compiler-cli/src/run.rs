@@ -6,27 +6,43 @@ use gleam_core::{
     error::Error,
     io::{CommandExecutor, Stdio},
     paths::ProjectPaths,
+    type_::ModuleFunction,
 };
 use lazy_static::lazy_static;
 use smol_str::SmolStr;
 
 use crate::fs::ProjectIO;
 
-#[derive(Debug, Clone, Copy)]
-pub enum Which {
-    Src,
-    Test,
-}
-
 // TODO: test
 pub fn command(
     arguments: Vec<String>,
     target: Option<Target>,
     runtime: Option<Runtime>,
     module: Option<String>,
-    which: Which,
 ) -> Result<(), Error> {
     let paths = crate::project_paths_at_current_directory();
+    let (config, module, main_function) = build(target, module)?;
+
+    // Don't exit on ctrl+c as it is used by child erlang shell
+    ctrlc::set_handler(move || {}).expect("Error setting Ctrl-C handler");
+
+    let status = run(
+        &paths,
+        &config,
+        target,
+        runtime,
+        &module,
+        &main_function,
+        arguments,
+    )?;
+
+    std::process::exit(status);
+}
+
+fn build(
+    target: Option<Target>,
+    module: Option<String>,
+) -> Result<(PackageConfig, String, ModuleFunction), Error> {
     // Validate the module to make sure it is a gleam module path
     match &module {
         Some(module_name) if !is_gleam_module(module_name) => Err(Error::InvalidModuleName {
@@ -38,10 +54,7 @@ pub fn command(
     let config = crate::config::root_config()?;
 
     // Determine which module to run
-    let module = module.unwrap_or(match which {
-        Which::Src => config.name.to_string(),
-        Which::Test => format!("{}_test", &config.name),
-    });
+    let module = module.unwrap_or_else(|| config.name.to_string());
 
     // Build project so we have bytecode to run
     let built = crate::build::main(Options {
@@ -54,31 +67,38 @@ pub fn command(
     // A module can not be run if it does not exist or does not have a public main function.
     let main_function = built.get_main_function(&SmolStr::from(module.to_owned()))?;
 
-    // Don't exit on ctrl+c as it is used by child erlang shell
-    ctrlc::set_handler(move || {}).expect("Error setting Ctrl-C handler");
+    Ok((config, module, main_function))
+}
 
+pub(crate) fn run(
+    paths: &ProjectPaths,
+    config: &PackageConfig,
+    target: Option<Target>,
+    runtime: Option<Runtime>,
+    module: &str,
+    main_function: &ModuleFunction,
+    arguments: Vec<String>,
+) -> Result<i32, Error> {
     crate::cli::print_running(&format!("{module}.main"));
 
     // Run the command
-    let status = match target.unwrap_or(config.target) {
+    match target.unwrap_or(config.target) {
         Target::Erlang => match runtime {
             Some(r) => Err(Error::InvalidRuntime {
                 target: Target::Erlang,
                 invalid_runtime: r,
             }),
-            _ => run_erlang(&paths, &config.name, &module, arguments),
+            _ => run_erlang(paths, &config.name, module, arguments),
         },
         Target::JavaScript => match runtime.unwrap_or(config.javascript.runtime) {
             Runtime::Deno => {
-                run_javascript_deno(&paths, &config, &main_function.package, &module, arguments)
+                run_javascript_deno(paths, config, &main_function.package, module, arguments)
             }
             Runtime::NodeJs => {
-                run_javascript_node(&paths, &main_function.package, &module, arguments)
+                run_javascript_node(paths, &main_function.package, module, arguments)
             }
         },
-    }?;
-
-    std::process::exit(status);
+    }
 }
 
 fn is_gleam_module(module: &str) -> bool {
@@ -101,15 +121,7 @@ fn run_erlang(
     module: &str,
     arguments: Vec<String>,
 ) -> Result<i32, Error> {
-    let mut args = vec![];
-
-    // Specify locations of Erlang applications
-    let packages = paths.build_directory_for_target(Mode::Dev, Target::Erlang);
-
-    for entry in crate::fs::read_dir(packages)?.filter_map(Result::ok) {
-        args.push("-pa".into());
-        args.push(entry.path().join("ebin").to_string_lossy().into());
-    }
+    let mut args = erlang_code_path_arguments(paths)?;
 
     // gleam modules are seperated by `/`. Erlang modules are seperated by `@`.
     let module = module.replace('/', "@");
@@ -129,6 +141,20 @@ fn run_erlang(
     ProjectIO::new().exec("erl", &args, &[], None, Stdio::Inherit)
 }
 
+/// Arguments for `erl` that specify the locations of the Erlang applications
+/// in the build directory.
+pub(crate) fn erlang_code_path_arguments(paths: &ProjectPaths) -> Result<Vec<String>, Error> {
+    let mut args = vec![];
+    let packages = paths.build_directory_for_target(Mode::Dev, Target::Erlang);
+
+    for entry in crate::fs::read_dir(packages)?.filter_map(Result::ok) {
+        args.push("-pa".into());
+        args.push(entry.path().join("ebin").to_string_lossy().into());
+    }
+
+    Ok(args)
+}
+
 fn run_javascript_node(
     paths: &ProjectPaths,
     package: &str,
@@ -147,17 +173,27 @@ fn run_javascript_node(
     ProjectIO::new().exec("node", &args, &[], None, Stdio::Inherit)
 }
 
-fn write_javascript_entrypoint(
+/// The path of a file in the JavaScript build directory of the given package,
+/// relative to the root of the project.
+pub(crate) fn javascript_entrypoint_path(
     paths: &ProjectPaths,
     package: &str,
-    module: &str,
-) -> Result<String, Error> {
+    name: &str,
+) -> String {
     let entry = paths
         .build_directory_for_package(Mode::Dev, Target::JavaScript, package)
         .strip_prefix(paths.root())
         .expect("Failed to strip prefix from path")
         .to_path_buf();
-    let entrypoint = format!("./{}/gleam.main.mjs", entry.to_string_lossy());
+    format!("./{}/{name}", entry.to_string_lossy())
+}
+
+fn write_javascript_entrypoint(
+    paths: &ProjectPaths,
+    package: &str,
+    module: &str,
+) -> Result<String, Error> {
+    let entrypoint = javascript_entrypoint_path(paths, package, "gleam.main.mjs");
     let module = format!(
         r#"import {{ main }} from "./{module}.mjs";
 main();
@@ -180,6 +216,22 @@ fn run_javascript_deno(
     args.push("run".into());
 
     // Set deno permissions
+    args.extend(deno_permission_arguments(config));
+
+    let entrypoint = write_javascript_entrypoint(paths, package, module)?;
+    args.push(entrypoint);
+
+    for argument in arguments.into_iter() {
+        args.push(argument);
+    }
+
+    ProjectIO::new().exec("deno", &args, &[], None, Stdio::Inherit)
+}
+
+/// The permission flags for `deno run` given in the project configuration.
+pub(crate) fn deno_permission_arguments(config: &PackageConfig) -> Vec<String> {
+    let mut args = vec![];
+
     if config.javascript.deno.allow_all {
         // Allow all
         args.push("--allow-all".into())
@@ -223,14 +275,7 @@ fn run_javascript_deno(
         );
     }
 
-    let entrypoint = write_javascript_entrypoint(paths, package, module)?;
-    args.push(entrypoint);
-
-    for argument in arguments.into_iter() {
-        args.push(argument);
-    }
-
-    ProjectIO::new().exec("deno", &args, &[], None, Stdio::Inherit)
+    args
 }
 
 fn add_deno_flag(args: &mut Vec<String>, flag: &str, flags: &DenoFlag) {
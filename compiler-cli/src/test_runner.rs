@@ -0,0 +1,261 @@
+use std::{
+    io::{BufRead, BufReader},
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    time::{Duration, Instant},
+};
+
+use gleam_core::{
+    build::{Codegen, Mode, Options, Runtime, Target},
+    config::PackageConfig,
+    error::{Error, StandardIoAction},
+    paths::ProjectPaths,
+    test_runner::{self, TestFunction, TestOutcome, TestResult},
+    Result,
+};
+use smol_str::SmolStr;
+
+use crate::{
+    cli::{self, MessageFormat},
+    dependencies::Connectivity,
+};
+
+#[derive(Debug)]
+pub struct TestOptions {
+    pub target: Option<Target>,
+    pub runtime: Option<Runtime>,
+    pub filter: Option<String>,
+    pub junit: Option<PathBuf>,
+    pub message_format: MessageFormat,
+    pub connectivity: Connectivity,
+    pub arguments: Vec<String>,
+}
+
+pub fn command(options: TestOptions) -> Result<()> {
+    if build_and_run(&options)? {
+        Ok(())
+    } else {
+        std::process::exit(1);
+    }
+}
+
+/// Rebuild the project and run its tests each time the project's files
+/// change.
+pub fn watch(options: TestOptions) -> Result<()> {
+    crate::watch::watch(options.message_format, || {
+        let _ = build_and_run(&options)?;
+        Ok(())
+    })
+}
+
+/// Build the project and run its tests, returning whether they all passed.
+fn build_and_run(options: &TestOptions) -> Result<bool> {
+    let paths = crate::project_paths_at_current_directory();
+    let config = crate::config::root_config()?;
+
+    let built = crate::build::main_with_message_format(
+        Options {
+            warnings_as_errors: false,
+            codegen: Codegen::All,
+            mode: Mode::Dev,
+            target: options.target,
+        },
+        options.message_format,
+        options.connectivity,
+    )?;
+
+    let tests = built.test_functions();
+
+    // Projects without any test functions may have their own way of running
+    // their tests, so run the `main` function of the `{package}_test` module
+    // as `gleam test` did before it could find tests itself.
+    if tests.is_empty() {
+        let module = format!("{}_test", config.name);
+        let main_function = built.get_main_function(&SmolStr::from(module.as_str()))?;
+        let status = crate::run::run(
+            &paths,
+            &config,
+            options.target,
+            options.runtime,
+            &module,
+            &main_function,
+            options.arguments.clone(),
+        )?;
+        return Ok(status == 0);
+    }
+
+    let tests = test_runner::filter(tests, options.filter.as_deref());
+    let start = Instant::now();
+    let results = match tests.as_slice() {
+        [] => vec![],
+        _ => {
+            let runner = runner_command(&paths, &config, options, &tests)?;
+            run_tests(runner, &tests)?
+        }
+    };
+
+    print_summary(&results, start.elapsed());
+    if let Some(path) = &options.junit {
+        crate::fs::write(path, &test_runner::junit_xml(&config.name, &results))?;
+    }
+
+    Ok(results.iter().all(|result| result.outcome.is_passed()))
+}
+
+/// The command that runs the given tests on the target of the project.
+fn runner_command(
+    paths: &ProjectPaths,
+    config: &PackageConfig,
+    options: &TestOptions,
+    tests: &[TestFunction],
+) -> Result<Command> {
+    let (program, mut args) = match options.target.unwrap_or(config.target) {
+        Target::Erlang => match options.runtime {
+            Some(runtime) => {
+                return Err(Error::InvalidRuntime {
+                    target: Target::Erlang,
+                    invalid_runtime: runtime,
+                })
+            }
+            None => {
+                let mut args = crate::run::erlang_code_path_arguments(paths)?;
+                args.push("-eval".into());
+                args.push(test_runner::erlang_runner_expression(&config.name, tests));
+                // Don't run the Erlang shell
+                args.push("-noshell".into());
+                // Tell the BEAM that any following argument are for the program
+                args.push("-extra".into());
+                ("erl", args)
+            }
+        },
+
+        Target::JavaScript => {
+            let entrypoint =
+                crate::run::javascript_entrypoint_path(paths, &config.name, "gleam.test.mjs");
+            crate::fs::write(
+                Path::new(&entrypoint),
+                &test_runner::javascript_runner_module(tests),
+            )?;
+            match options.runtime.unwrap_or(config.javascript.runtime) {
+                Runtime::NodeJs => ("node", vec![entrypoint]),
+                Runtime::Deno => {
+                    let mut args = vec!["run".into()];
+                    args.extend(crate::run::deno_permission_arguments(config));
+                    args.push(entrypoint);
+                    ("deno", args)
+                }
+            }
+        }
+    };
+
+    args.extend(options.arguments.iter().cloned());
+    let mut command = Command::new(program);
+    let _ = command.args(args);
+    Ok(command)
+}
+
+/// Run the test runner, passing through anything the tests print and
+/// reporting each result as it arrives.
+fn run_tests(mut command: Command, tests: &[TestFunction]) -> Result<Vec<TestResult>> {
+    let program = command.get_program().to_string_lossy().to_string();
+    cli::print_running(&match tests.len() {
+        1 => "1 test".into(),
+        count => format!("{count} tests"),
+    });
+
+    tracing::info!("Running OS process {:?}", command);
+    let mut child =
+        command
+            .stdout(Stdio::piped())
+            .spawn()
+            .map_err(|error| Error::ShellCommand {
+                program: program.clone(),
+                err: Some(error.kind()),
+            })?;
+    let stdout = child.stdout.take().expect("Test runner stdout");
+
+    let mut results = Vec::with_capacity(tests.len());
+    for line in BufReader::new(stdout).split(b'\n') {
+        let line = line.map_err(|error| Error::StandardIo {
+            action: StandardIoAction::Read,
+            err: Some(error.kind()),
+        })?;
+        let line = String::from_utf8_lossy(&line);
+        let (output, result) = test_runner::parse_output_line(&line);
+        if result.is_none() || !output.is_empty() {
+            println!("{output}");
+        }
+        if let Some(result) = result {
+            print_result(&result);
+            results.push(result);
+        }
+    }
+
+    let _ = child.wait().map_err(|error| Error::ShellCommand {
+        program,
+        err: Some(error.kind()),
+    })?;
+
+    // If the runner itself stopped, for example because a test halted the
+    // virtual machine, then the tests that did not report a result crashed.
+    for test in tests.iter().skip(results.len()) {
+        let result = TestResult {
+            test: test.clone(),
+            outcome: TestOutcome::Crashed {
+                message: "The test runner stopped before this test finished".into(),
+            },
+            duration: Duration::ZERO,
+        };
+        print_result(&result);
+        results.push(result);
+    }
+
+    Ok(results)
+}
+
+fn print_result(result: &TestResult) {
+    let text = format!(
+        "{} in {:.2}ms",
+        result.test.full_name(),
+        result.duration.as_secs_f64() * 1000.0
+    );
+    match &result.outcome {
+        TestOutcome::Passed => cli::print_colourful_prefix("     Passed", &text),
+        TestOutcome::Failed { message } => {
+            cli::print_colourful_prefix("     Failed", &text);
+            print_indented(message);
+        }
+        TestOutcome::Crashed { message } => {
+            cli::print_colourful_prefix("    Crashed", &text);
+            print_indented(message);
+        }
+    }
+}
+
+fn print_indented(message: &str) {
+    for line in message.trim_end().lines() {
+        println!("            {line}");
+    }
+}
+
+fn print_summary(results: &[TestResult], duration: Duration) {
+    let (mut passed, mut failed, mut crashed) = (0, 0, 0);
+    for result in results {
+        match result.outcome {
+            TestOutcome::Passed => passed += 1,
+            TestOutcome::Failed { .. } => failed += 1,
+            TestOutcome::Crashed { .. } => crashed += 1,
+        }
+    }
+    let tests = match results.len() {
+        1 => "1 test".into(),
+        count => format!("{count} tests"),
+    };
+    cli::print_colourful_prefix(
+        "   Finished",
+        &format!(
+            "{tests} in {}: {passed} passed, {failed} failed, {crashed} crashed",
+            cli::seconds(duration)
+        ),
+    );
+}
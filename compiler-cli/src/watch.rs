@@ -0,0 +1,200 @@
+use std::{
+    path::Path,
+    sync::mpsc::{self, Receiver},
+    time::Duration,
+};
+
+use crate::cli::MessageFormat;
+use gleam_core::{
+    error::{Error, FileIoAction, FileKind},
+    paths::ProjectPaths,
+    Result,
+};
+use notify::{event::EventKind, Event, RecommendedWatcher, RecursiveMode, Watcher};
+
+/// How long the file system must be quiet for before the project is rebuilt.
+/// Editors often write a file several times when saving it, and a formatter
+/// or a `git checkout` can touch many files at once, so we wait for these
+/// bursts of changes to settle rather than rebuilding for each one.
+const DEBOUNCE: Duration = Duration::from_millis(100);
+
+/// Run the given action, and then run it again each time a source file,
+/// native file, or the `gleam.toml` of the project changes.
+///
+/// Errors returned by the action are printed rather than returned so that
+/// the user can fix them and carry on watching. Each run is compiled through
+/// the usual `ProjectCompiler`, so the `.cache` metadata from previous runs
+/// means only the changed modules and their dependents get recompiled.
+///
+/// This function only returns if the file system watcher fails.
+pub fn watch(message_format: MessageFormat, mut action: impl FnMut() -> Result<()>) -> Result<()> {
+    let paths = crate::project_paths_at_current_directory();
+    let (sender, receiver) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(move |event| {
+        // The receiver is only dropped once we stop watching, at which point
+        // there is no-one left to tell about changes.
+        let _ = sender.send(event);
+    })
+    .map_err(|error| watch_error(paths.root(), error))?;
+
+    watch_path(&mut watcher, paths.root(), RecursiveMode::NonRecursive)?;
+    for directory in [paths.src_directory(), paths.test_directory()] {
+        if directory.is_dir() {
+            watch_path(&mut watcher, &directory, RecursiveMode::Recursive)?;
+        }
+    }
+
+    loop {
+        crate::cli::clear_screen();
+        if let Err(error) = action() {
+            crate::cli::print_error(&error, message_format);
+        }
+        crate::cli::print_watching();
+        wait_for_changes(&paths, &receiver)?;
+    }
+}
+
+fn watch_path(watcher: &mut RecommendedWatcher, path: &Path, mode: RecursiveMode) -> Result<()> {
+    watcher
+        .watch(path, mode)
+        .map_err(|error| watch_error(path, error))
+}
+
+/// Block until a relevant file has changed, and then until the changes have
+/// stopped for the debounce period.
+fn wait_for_changes(
+    paths: &ProjectPaths,
+    receiver: &Receiver<notify::Result<Event>>,
+) -> Result<()> {
+    loop {
+        let event = receiver
+            .recv()
+            .expect("File watcher stopped sending events")
+            .map_err(|error| watch_error(paths.root(), error))?;
+        if is_relevant_event(paths, &event) {
+            tracing::debug!(paths = ?event.paths, "watched_file_changed");
+            break;
+        }
+    }
+
+    while receiver.recv_timeout(DEBOUNCE).is_ok() {}
+    Ok(())
+}
+
+fn is_relevant_event(paths: &ProjectPaths, event: &Event) -> bool {
+    match event.kind {
+        // Reading the files while compiling must not trigger another build
+        EventKind::Access(_) => false,
+        _ => event.paths.iter().any(|path| is_watched_file(paths, path)),
+    }
+}
+
+/// Whether a change to the file at the given path could change the result of
+/// building the project.
+fn is_watched_file(paths: &ProjectPaths, path: &Path) -> bool {
+    if path == paths.root_config() {
+        return true;
+    }
+
+    let in_source_directory =
+        path.starts_with(paths.src_directory()) || path.starts_with(paths.test_directory());
+    let extension = path
+        .extension()
+        .unwrap_or_default()
+        .to_str()
+        .unwrap_or_default();
+
+    in_source_directory
+        && matches!(
+            extension,
+            "gleam" | "mjs" | "js" | "ts" | "hrl" | "erl" | "ex"
+        )
+}
+
+fn watch_error(path: &Path, error: notify::Error) -> Error {
+    Error::FileIo {
+        kind: FileKind::Directory,
+        action: FileIoAction::Watch,
+        path: path.to_path_buf(),
+        err: Some(error.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use notify::event::{AccessKind, CreateKind, ModifyKind};
+    use std::path::PathBuf;
+
+    fn paths() -> ProjectPaths {
+        ProjectPaths::new(PathBuf::from("/app"))
+    }
+
+    fn event(kind: EventKind, path: &str) -> Event {
+        Event::new(kind).add_path(PathBuf::from(path))
+    }
+
+    #[test]
+    fn gleam_source_files_are_watched() {
+        assert!(is_watched_file(&paths(), Path::new("/app/src/app.gleam")));
+        assert!(is_watched_file(
+            &paths(),
+            Path::new("/app/test/app/nested_test.gleam")
+        ));
+    }
+
+    #[test]
+    fn native_files_are_watched() {
+        assert!(is_watched_file(&paths(), Path::new("/app/src/ffi.erl")));
+        assert!(is_watched_file(&paths(), Path::new("/app/src/ffi.hrl")));
+        assert!(is_watched_file(&paths(), Path::new("/app/src/ffi.mjs")));
+        assert!(is_watched_file(&paths(), Path::new("/app/test/ffi.ex")));
+    }
+
+    #[test]
+    fn config_is_watched() {
+        assert!(is_watched_file(&paths(), Path::new("/app/gleam.toml")));
+    }
+
+    #[test]
+    fn other_files_are_not_watched() {
+        assert!(!is_watched_file(&paths(), Path::new("/app/README.md")));
+        assert!(!is_watched_file(&paths(), Path::new("/app/manifest.toml")));
+        assert!(!is_watched_file(
+            &paths(),
+            Path::new("/app/src/.app.gleam.swp")
+        ));
+        assert!(!is_watched_file(
+            &paths(),
+            Path::new("/app/build/dev/erlang/app/_gleam_artefacts/app.erl")
+        ));
+        assert!(!is_watched_file(&paths(), Path::new("/app/src/gleam.toml")));
+    }
+
+    #[test]
+    fn access_events_are_ignored() {
+        assert!(!is_relevant_event(
+            &paths(),
+            &event(EventKind::Access(AccessKind::Any), "/app/src/app.gleam")
+        ));
+    }
+
+    #[test]
+    fn changes_to_watched_files_are_relevant() {
+        assert!(is_relevant_event(
+            &paths(),
+            &event(EventKind::Modify(ModifyKind::Any), "/app/src/app.gleam")
+        ));
+        assert!(is_relevant_event(
+            &paths(),
+            &event(
+                EventKind::Create(CreateKind::File),
+                "/app/test/app_test.gleam"
+            )
+        ));
+        assert!(!is_relevant_event(
+            &paths(),
+            &event(EventKind::Modify(ModifyKind::Any), "/app/build/app.beam")
+        ));
+    }
+}
@@ -3,10 +3,10 @@ mod tests;
 
 use crate::{
     ast::{
-        self, BitStringSegmentOption, CustomType, DefinitionLocation, ExternalFunction,
-        ExternalType, Function, GroupedStatements, Import, Layer, ModuleConstant, ModuleFunction,
-        ModuleStatement, RecordConstructor, RecordConstructorArg, SrcSpan, TypeAlias, TypeAst,
-        TypedModule, TypedModuleStatement, UnqualifiedImport, UntypedModule,
+        self, BitStringSegmentOption, CustomType, DefinitionLocation, Deprecation,
+        ExternalFunction, ExternalType, Function, GroupedStatements, Import, Layer, ModuleConstant,
+        ModuleFunction, ModuleStatement, RecordConstructor, RecordConstructorArg, SrcSpan,
+        TypeAlias, TypeAst, TypedModule, TypedModuleStatement, UnqualifiedImport, UntypedModule,
     },
     build::{Origin, Target},
     call_graph::into_dependency_order,
@@ -27,6 +27,7 @@ use crate::{
 use itertools::Itertools;
 use smol_str::SmolStr;
 use std::{collections::HashMap, sync::Arc};
+use vec1::{vec1, Vec1};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Inferred<T> {
@@ -62,7 +63,11 @@ impl Inferred<PatternConstructor> {
 
 // TODO: This takes too many arguments.
 /// Crawl the AST, annotating each node with the inferred type or
-/// returning an error.
+/// returning the errors found.
+///
+/// An error in a top level definition does not stop the rest of the module
+/// from being checked, so that all of its errors can be reported at once.
+/// They are returned in the order they appear in the module.
 ///
 pub fn infer_module(
     target: Target,
@@ -72,78 +77,107 @@ pub fn infer_module(
     package: &SmolStr,
     modules: &im::HashMap<SmolStr, Module>,
     warnings: &TypeWarningEmitter,
-) -> Result<TypedModule, Error> {
+) -> Result<TypedModule, Vec1<Error>> {
     let name = module.name.clone();
     let documentation = std::mem::take(&mut module.documentation);
     let mut env = Environment::new(ids.clone(), &name, modules, warnings);
-    validate_module_name(&name)?;
+    validate_module_name(&name).map_err(|e| vec1![e])?;
 
     let mut type_names = HashMap::with_capacity(module.statements.len());
     let mut value_names = HashMap::with_capacity(module.statements.len());
     let mut hydrators = HashMap::with_capacity(module.statements.len());
+    let mut errors = vec![];
 
-    let statements = GroupedStatements::new(module.into_iter_statements(target));
+    let mut statements = GroupedStatements::new(module.into_iter_statements(target));
     let statements_count = statements.len();
 
+    // A definition that could not be registered is not checked any further,
+    // as the later passes rely on what registering it would have recorded.
+
     // Register any modules, types, and values being imported
     // We process imports first so that anything imported can be referenced
     // anywhere in the module.
     // TODO: Extract an ImportRegistrar class to perform this.
-    for s in &statements.imports {
-        register_import(s, &name, origin, &mut env)?;
-    }
+    statements
+        .imports
+        .retain(|s| registered(register_import(s, &name, origin, &mut env), &mut errors));
 
     // Register types so they can be used in constructors and functions
     // earlier in the module.
     // TODO: Extract a TypeRegistrar class to perform all this.
-    for t in &statements.external_types {
-        register_types_from_external_type(t, &mut type_names, &mut env, &name)?;
-    }
-    for t in &statements.custom_types {
-        register_types_from_custom_type(t, &mut type_names, &mut env, &name, &mut hydrators)?;
-    }
+    statements.external_types.retain(|t| {
+        let result = register_types_from_external_type(t, &mut type_names, &mut env, &name);
+        registered(result, &mut errors)
+    });
+    statements.custom_types.retain(|t| {
+        let result =
+            register_types_from_custom_type(t, &mut type_names, &mut env, &name, &mut hydrators);
+        registered(result, &mut errors)
+    });
     // TODO: Extract a Type alias class to perform this.
     // TODO: Sort type aliases by dependency order so they don't have to be
     // ordered in the file
-    for t in &statements.type_aliases {
-        register_type_alias(t, &mut type_names, &mut env, &name)?;
-    }
+    statements.type_aliases.retain(|t| {
+        let result = register_type_alias(t, &mut type_names, &mut env, &name);
+        registered(result, &mut errors)
+    });
 
     // Register values so they can be used in functions earlier in the module.
-    for c in &statements.constants {
-        assert_unique_name(&mut value_names, &c.name, c.location)?;
-    }
-    for f in &statements.functions {
-        register_value_from_function(f, &mut value_names, &mut env, &mut hydrators, &name)?;
-    }
-    for ef in &statements.external_functions {
-        register_external_function(ef, &mut value_names, &mut env)?;
-    }
-    for t in &statements.custom_types {
-        register_values_from_custom_type(t, &mut hydrators, &mut env, &mut value_names, &name)?;
-    }
+    statements.constants.retain(|c| {
+        let result = assert_unique_name(&mut value_names, &c.name, c.location);
+        registered(result, &mut errors)
+    });
+    statements.functions.retain(|f| {
+        let result =
+            register_value_from_function(f, &mut value_names, &mut env, &mut hydrators, &name);
+        registered(result, &mut errors)
+    });
+    statements.external_functions.retain(|ef| {
+        let result = register_external_function(ef, &mut value_names, &mut env);
+        registered(result, &mut errors)
+    });
+    statements.custom_types.retain(|t| {
+        let result =
+            register_values_from_custom_type(t, &mut hydrators, &mut env, &mut value_names, &name);
+        registered(result, &mut errors)
+    });
 
     // Infer the types of each statement in the module
     let mut typed_statements = Vec::with_capacity(statements_count);
     for i in statements.imports {
-        let statement = record_imported_items_for_use_detection(i, &mut env)?;
-        typed_statements.push(statement);
+        match record_imported_items_for_use_detection(i, &mut env) {
+            Ok(statement) => typed_statements.push(statement),
+            Err(error) => errors.push(error),
+        }
     }
     for t in statements.custom_types {
-        let statement = infer_custom_type(t, &mut env)?;
-        typed_statements.push(statement);
+        match infer_custom_type(t, &mut env) {
+            Ok(statement) => typed_statements.push(statement),
+            Err(error) => errors.push(error),
+        }
     }
     for t in statements.external_types {
-        let statement = hydrate_external_type(t, &mut env)?;
-        typed_statements.push(statement);
+        match hydrate_external_type(t, &mut env) {
+            Ok(statement) => typed_statements.push(statement),
+            Err(error) => errors.push(error),
+        }
     }
     for t in statements.type_aliases {
-        let statement = insert_type_alias(t, &mut env)?;
-        typed_statements.push(statement);
+        match insert_type_alias(t, &mut env) {
+            Ok(statement) => typed_statements.push(statement),
+            Err(error) => errors.push(error),
+        }
     }
     for c in statements.constants {
-        let statement = infer_module_constant(c, &mut env, &name)?;
-        typed_statements.push(statement);
+        let constant_name = c.name.clone();
+        let location = c.location;
+        match infer_module_constant(c, &mut env, &name) {
+            Ok(statement) => typed_statements.push(statement),
+            Err(error) => {
+                errors.push(error);
+                treat_as_unknown_constant(constant_name, location, &mut env);
+            }
+        }
     }
 
     // Sort the functions into dependency order for inference. Functions that do
@@ -158,18 +192,31 @@ pub fn infer_module(
         .into_iter()
         .map(ModuleFunction::External);
     let functions = functions.chain(external_functions).collect_vec();
-    let function_groups = into_dependency_order(functions)?;
+    let function_groups = match into_dependency_order(functions) {
+        Ok(function_groups) => function_groups,
+        Err(error) => {
+            errors.push(error);
+            return Err(into_sorted_errors(errors));
+        }
+    };
     let mut working_group = vec![];
 
     for group in function_groups {
         // A group may have multiple functions that depend on each other through
         // mutual recursion.
         for function in group {
+            let function_name = function.name().clone();
             let inferred = match function {
-                ModuleFunction::Internal(f) => infer_function(f, &mut env, &mut hydrators, &name)?,
-                ModuleFunction::External(f) => infer_external_function(f, &mut env)?,
+                ModuleFunction::Internal(f) => infer_function(f, &mut env, &mut hydrators, &name),
+                ModuleFunction::External(f) => infer_external_function(f, &mut env),
             };
-            working_group.push(inferred);
+            match inferred {
+                Ok(inferred) => working_group.push(inferred),
+                Err(error) => {
+                    errors.push(error);
+                    treat_as_generalised(&function_name, &mut env);
+                }
+            }
         }
 
         // Now that the entire group has been inferred, generalise their types.
@@ -179,6 +226,10 @@ pub fn infer_module(
         }
     }
 
+    if !errors.is_empty() {
+        return Err(into_sorted_errors(errors));
+    }
+
     // Generate warnings for unused items
     env.convert_unused_to_warnings();
 
@@ -191,12 +242,15 @@ pub fn infer_module(
     // Ensure no exported values have private types in their type signature
     for value in env.module_values.values() {
         if let Some(leaked) = value.type_.find_private_type() {
-            return Err(Error::PrivateTypeLeak {
+            errors.push(Error::PrivateTypeLeak {
                 location: value.variant.definition_location(),
                 leaked,
             });
         }
     }
+    if !errors.is_empty() {
+        return Err(into_sorted_errors(errors));
+    }
 
     let Environment {
         module_types: types,
@@ -222,6 +276,56 @@ pub fn infer_module(
     })
 }
 
+/// An error in one function does not stop the others from being checked, so
+/// that all the errors in a module can be reported at once. The function that
+/// failed keeps the type it was registered with, which is as general as its
+/// annotations allow, and is generalised so that it can be used by the other
+/// functions without causing further errors.
+///
+fn treat_as_generalised(name: &SmolStr, environment: &mut Environment<'_>) {
+    let _ = environment.ungeneralised_functions.remove(name);
+    if let Some(ValueConstructor {
+        public,
+        variant,
+        type_,
+        ..
+    }) = environment.get_variable(name).cloned()
+    {
+        environment.insert_variable(name.clone(), variant, type_::generalise(type_), public);
+    }
+}
+
+fn registered(result: Result<(), Error>, errors: &mut Vec<Error>) -> bool {
+    match result {
+        Ok(()) => true,
+        Err(error) => {
+            errors.push(error);
+            false
+        }
+    }
+}
+
+/// A constant with an error is given a fresh generic type, so that using it
+/// elsewhere in the module does not cause further errors.
+///
+fn treat_as_unknown_constant(name: SmolStr, location: SrcSpan, environment: &mut Environment<'_>) {
+    let type_ = environment.new_generic_var();
+    environment.insert_variable(
+        name,
+        ValueConstructorVariant::LocalVariable { location },
+        type_,
+        false,
+    );
+}
+
+/// Errors are collected one pass over the module at a time, so they are put
+/// back into the order they appear in the source.
+///
+fn into_sorted_errors(mut errors: Vec<Error>) -> Vec1<Error> {
+    errors.sort_by_key(|error| error.location().map(|location| location.start));
+    Vec1::try_from_vec(errors).expect("into_sorted_errors called with no errors")
+}
+
 pub fn register_import(
     import: &Import<()>,
     current_module: &str,
@@ -288,12 +392,7 @@ pub fn register_import(
 
         // Register the unqualified import if it is a value
         if let Some(value) = module_info.values.get(name) {
-            environment.insert_variable(
-                imported_name.clone(),
-                value.variant.clone(),
-                value.type_.clone(),
-                true,
-            );
+            environment.insert_imported_variable(imported_name.clone(), value.clone());
             variant = Some(&value.variant);
             value_imported = true;
         }
@@ -403,6 +502,7 @@ fn register_type_alias(
         parameters: args,
         alias: name,
         type_ast: resolved_type,
+        deprecation,
         ..
     } = t;
     assert_unique_type_name(names, name, *location)?;
@@ -418,6 +518,7 @@ fn register_type_alias(
             public: *public,
             parameters,
             typ,
+            deprecation: deprecation.clone(),
         },
     )?;
     if !public {
@@ -439,6 +540,7 @@ fn register_types_from_custom_type<'a>(
         parameters,
         location,
         constructors,
+        deprecation,
         ..
     } = t;
     assert_unique_type_name(names, name, *location)?;
@@ -459,6 +561,7 @@ fn register_types_from_custom_type<'a>(
             public: *public,
             parameters,
             typ,
+            deprecation: deprecation.clone(),
         },
     )?;
     let constructor_names = constructors.iter().map(|c| c.name.clone()).collect();
@@ -499,6 +602,7 @@ fn register_types_from_external_type(
             public: *public,
             parameters,
             typ,
+            deprecation: Deprecation::NotDeprecated,
         },
     )?;
     if !public {
@@ -520,6 +624,7 @@ fn register_values_from_custom_type(
         opaque,
         name,
         constructors,
+        deprecation,
         ..
     } = t;
     let mut hydrator = hydrators
@@ -581,6 +686,7 @@ fn register_values_from_custom_type(
                 constructor.name.clone(),
                 ValueConstructor {
                     public: *public,
+                    deprecation: deprecation.clone(),
                     type_: typ.clone(),
                     variant: constructor_info.clone(),
                 },
@@ -614,6 +720,7 @@ fn register_external_function(
         module,
         fun,
         documentation,
+        deprecation,
         ..
     } = f;
     assert_unique_name(names, name, *location)?;
@@ -634,6 +741,7 @@ fn register_external_function(
         name.clone(),
         ValueConstructor {
             public: *public,
+            deprecation: deprecation.clone(),
             type_: typ.clone(),
             variant: ValueConstructorVariant::ModuleFn {
                 documentation: documentation.clone(),
@@ -730,6 +838,7 @@ fn infer_function(
         body,
         return_annotation,
         end_position: end_location,
+        deprecation,
         ..
     } = f;
     let preregistered_fn = environment
@@ -796,6 +905,7 @@ fn infer_function(
             .return_type()
             .expect("Could not find return type for fn"),
         body,
+        deprecation,
     }))
 }
 
@@ -812,6 +922,7 @@ fn infer_external_function(
         return_: retrn,
         module,
         fun,
+        deprecation,
         ..
     } = f;
     let preregistered_fn = environment
@@ -836,6 +947,7 @@ fn infer_external_function(
         return_: retrn,
         module,
         fun,
+        deprecation,
     }))
 }
 
@@ -850,6 +962,7 @@ fn insert_type_alias(
         alias,
         parameters: args,
         type_ast: resolved_type,
+        deprecation,
         ..
     } = t;
     let typ = environment
@@ -865,6 +978,7 @@ fn insert_type_alias(
         parameters: args,
         type_ast: resolved_type,
         type_: typ,
+        deprecation,
     }))
 }
 
@@ -880,6 +994,7 @@ fn infer_custom_type(
         name,
         parameters,
         constructors,
+        deprecation,
         ..
     } = t;
     let constructors = constructors
@@ -947,6 +1062,7 @@ fn infer_custom_type(
         parameters,
         constructors,
         typed_parameters,
+        deprecation,
     }))
 }
 
@@ -1030,12 +1146,14 @@ fn infer_module_constant(
         annotation,
         public,
         value,
+        deprecation,
         ..
     } = c;
     let typed_expr = ExprTyper::new(environment).infer_const(&annotation, *value)?;
     let type_ = typed_expr.type_();
     let variant = ValueConstructor {
         public,
+        deprecation: deprecation.clone(),
         variant: ValueConstructorVariant::ModuleConstant {
             documentation: doc.clone(),
             location,
@@ -1060,6 +1178,7 @@ fn infer_module_constant(
         public,
         value: Box::new(typed_expr),
         type_,
+        deprecation,
     }))
 }
 
@@ -1165,6 +1284,7 @@ fn generalise_function(
         return_annotation,
         end_position: end_location,
         return_type,
+        deprecation,
     } = function;
 
     // Lookup the inferred function information
@@ -1186,6 +1306,7 @@ fn generalise_function(
         name.clone(),
         ValueConstructor {
             public,
+            deprecation: deprecation.clone(),
             type_: typ,
             variant: ValueConstructorVariant::ModuleFn {
                 documentation: doc.clone(),
@@ -1208,6 +1329,7 @@ fn generalise_function(
         return_annotation,
         return_type,
         body,
+        deprecation,
     })
 }
 
@@ -1281,7 +1403,7 @@ fn custom_type_accessors<A>(
 
 /// Returns the fields that have the same label and type across all variants of
 /// the given type.
-fn get_compatible_record_fields<A>(
+pub(crate) fn get_compatible_record_fields<A>(
     constructors: &[RecordConstructor<A>],
 ) -> Vec<(usize, &SmolStr, &TypeAst)> {
     let mut compatible = vec![];
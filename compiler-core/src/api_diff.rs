@@ -0,0 +1,472 @@
+//! Comparison of the public interfaces of two versions of a package, used to
+//! work out which semantic version bump the changes between them require.
+
+use std::{collections::HashMap, sync::Arc};
+
+use hexpm::version::Version;
+use itertools::Itertools;
+use smol_str::SmolStr;
+use strum::Display;
+
+use crate::type_::{
+    pretty::Printer, FieldMap, Module, Type, TypeVar, ValueConstructor, ValueConstructorVariant,
+};
+
+#[cfg(test)]
+use pretty_assertions::assert_eq;
+
+/// The part of the version number that must be incremented for a change.
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[strum(serialize_all = "lowercase")]
+pub enum Bump {
+    Patch,
+    Minor,
+    Major,
+}
+
+impl Bump {
+    /// The lowest version that can follow the previous one for a change of
+    /// this kind. Before 1.0.0 breaking changes only need a minor bump, and
+    /// additions a patch bump.
+    ///
+    pub fn minimum_version(self, previous: &Version) -> Version {
+        let Version {
+            major,
+            minor,
+            patch,
+            ..
+        } = *previous;
+        match (self, major) {
+            (Bump::Major, 0) | (Bump::Minor, 1..) => Version::new(major, minor + 1, 0),
+            (Bump::Major, _) => Version::new(major + 1, 0, 0),
+            (Bump::Minor, 0) | (Bump::Patch, _) => Version::new(major, minor, patch + 1),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiChange {
+    pub bump: Bump,
+    pub description: String,
+}
+
+/// The changes to the public interface of a package between two versions.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ApiDiff {
+    pub changes: Vec<ApiChange>,
+}
+
+impl ApiDiff {
+    /// Compare the interfaces of the public modules of two versions of a
+    /// package.
+    ///
+    pub fn new(previous: &[Module], current: &[Module]) -> Self {
+        let mut diff = Self::default();
+        let current: HashMap<_, _> = current.iter().map(|m| (&m.name, m)).collect();
+        let previous: HashMap<_, _> = previous.iter().map(|m| (&m.name, m)).collect();
+
+        for (name, previous_module) in previous.iter().sorted_by_key(|(name, _)| **name) {
+            match current.get(name) {
+                Some(current_module) => diff.compare_modules(previous_module, current_module),
+                None => diff.major(format!("Removed module `{name}`")),
+            }
+        }
+        for name in current.keys().sorted() {
+            if !previous.contains_key(name) {
+                diff.minor(format!("Added module `{name}`"));
+            }
+        }
+        diff
+    }
+
+    /// The bump required by the most significant change.
+    ///
+    pub fn bump(&self) -> Bump {
+        self.changes
+            .iter()
+            .map(|change| change.bump)
+            .max()
+            .unwrap_or(Bump::Patch)
+    }
+
+    fn major(&mut self, description: String) {
+        self.changes.push(ApiChange {
+            bump: Bump::Major,
+            description,
+        });
+    }
+
+    fn minor(&mut self, description: String) {
+        self.changes.push(ApiChange {
+            bump: Bump::Minor,
+            description,
+        });
+    }
+
+    fn compare_modules(&mut self, previous: &Module, current: &Module) {
+        let module = &previous.name;
+
+        for (name, previous_type) in previous.types.iter().sorted_by_key(|(name, _)| *name) {
+            let Some(current_type) = current.types.get(name) else {
+                self.major(format!("Removed type `{module}.{name}`"));
+                continue;
+            };
+            if previous_type.parameters.len() != current_type.parameters.len() {
+                self.major(format!(
+                    "Changed the number of parameters of type `{module}.{name}` from {} to {}",
+                    previous_type.parameters.len(),
+                    current_type.parameters.len()
+                ));
+            }
+
+            // Once the constructors of a type are public, adding another
+            // breaks any case expression that matches on all of them.
+            let previous_constructors = public_constructors(previous, name);
+            if previous_constructors.is_empty() {
+                continue;
+            }
+            for constructor in public_constructors(current, name) {
+                if !previous_constructors.contains(&constructor) {
+                    self.major(format!(
+                        "Added constructor `{module}.{constructor}` to type `{module}.{name}`"
+                    ));
+                }
+            }
+        }
+        for name in current.types.keys().sorted() {
+            if !previous.types.contains_key(name) {
+                self.minor(format!("Added type `{module}.{name}`"));
+            }
+        }
+
+        for (name, previous_value) in previous.values.iter().sorted_by_key(|(name, _)| *name) {
+            let kind = value_kind(previous_value);
+            let Some(current_value) = current.values.get(name) else {
+                self.major(format!("Removed {kind} `{module}.{name}`"));
+                continue;
+            };
+            if signature(&previous_value.type_) != signature(&current_value.type_) {
+                self.major(format!(
+                    "Changed the type of {kind} `{module}.{name}` from `{}` to `{}`",
+                    Printer::new().pretty_print(&previous_value.type_, 0),
+                    Printer::new().pretty_print(&current_value.type_, 0)
+                ));
+            }
+            let previous_labels = labels(previous_value);
+            let current_labels = labels(current_value);
+            if previous_labels
+                .iter()
+                .any(|label| !current_labels.contains(label))
+            {
+                self.major(format!("Changed the labels of {kind} `{module}.{name}`"));
+            } else if current_labels.len() > previous_labels.len() {
+                self.minor(format!("Added labels to {kind} `{module}.{name}`"));
+            }
+        }
+        for (name, current_value) in current.values.iter().sorted_by_key(|(name, _)| *name) {
+            let is_added_constructor = || match constructed_type_name(current_value) {
+                Some(type_name) => {
+                    previous.types.contains_key(type_name)
+                        && !public_constructors(previous, type_name).is_empty()
+                }
+                None => false,
+            };
+            // Constructors added to a type with public constructors have
+            // already been recorded as breaking changes.
+            if !previous.values.contains_key(name) && !is_added_constructor() {
+                let kind = value_kind(current_value);
+                self.minor(format!("Added {kind} `{module}.{name}`"));
+            }
+        }
+
+        for (type_name, previous_accessors) in
+            previous.accessors.iter().sorted_by_key(|(name, _)| *name)
+        {
+            // The removal of the type itself has already been recorded
+            if !current.types.contains_key(type_name) {
+                continue;
+            }
+            let current_accessors = current.accessors.get(type_name);
+            for (label, accessor) in previous_accessors
+                .accessors
+                .iter()
+                .sorted_by_key(|(label, _)| *label)
+            {
+                let current_accessor = current_accessors.and_then(|a| a.accessors.get(label));
+                match current_accessor {
+                    None => self.major(format!(
+                        "Removed field `{label}` from type `{module}.{type_name}`"
+                    )),
+                    Some(current_accessor)
+                        if signature(&accessor.type_) != signature(&current_accessor.type_) =>
+                    {
+                        self.major(format!(
+                            "Changed the type of field `{label}` of type `{module}.{type_name}`"
+                        ))
+                    }
+                    Some(_) => (),
+                }
+            }
+        }
+    }
+}
+
+/// The names of the constructors of a type that can be used outside of the
+/// module, which is none of them for opaque types.
+fn public_constructors(module: &Module, type_name: &str) -> Vec<SmolStr> {
+    module
+        .types_constructors
+        .get(type_name)
+        .into_iter()
+        .flatten()
+        .filter(|constructor| module.values.contains_key(*constructor))
+        .cloned()
+        .collect()
+}
+
+fn constructed_type_name(value: &ValueConstructor) -> Option<&SmolStr> {
+    if !matches!(value.variant, ValueConstructorVariant::Record { .. }) {
+        return None;
+    }
+    let mut type_ = value.type_.as_ref();
+    if let Type::Fn { retrn, .. } = type_ {
+        type_ = retrn.as_ref();
+    }
+    match type_ {
+        Type::App { name, .. } => Some(name),
+        _ => None,
+    }
+}
+
+fn value_kind(value: &ValueConstructor) -> &'static str {
+    match value.variant {
+        ValueConstructorVariant::ModuleConstant { .. } => "constant",
+        ValueConstructorVariant::ModuleFn { .. } => "function",
+        ValueConstructorVariant::Record { .. } => "constructor",
+        ValueConstructorVariant::LocalVariable { .. } => "value",
+    }
+}
+
+/// The labelled arguments of a function or constructor and their positions.
+fn labels(value: &ValueConstructor) -> Vec<(&SmolStr, u32)> {
+    match &value.variant {
+        ValueConstructorVariant::ModuleFn {
+            field_map: Some(FieldMap { fields, .. }),
+            ..
+        }
+        | ValueConstructorVariant::Record {
+            field_map: Some(FieldMap { fields, .. }),
+            ..
+        } => fields
+            .iter()
+            .map(|(label, index)| (label, *index))
+            .collect(),
+        _ => vec![],
+    }
+}
+
+/// A rendering of a type for comparison, with type names qualified by their
+/// module and type variables numbered in the order they first appear, so
+/// that the types of the two versions can be compared even though they were
+/// inferred separately.
+fn signature(type_: &Type) -> String {
+    fn render(type_: &Type, variables: &mut HashMap<u64, usize>, buffer: &mut String) {
+        let render_all = |types: &[Arc<Type>], variables: &mut _, buffer: &mut String| {
+            for (i, type_) in types.iter().enumerate() {
+                if i > 0 {
+                    buffer.push_str(", ");
+                }
+                render(type_, variables, buffer);
+            }
+        };
+        match type_ {
+            Type::App {
+                module, name, args, ..
+            } => {
+                buffer.push_str(&format!("{module}.{name}("));
+                render_all(args, variables, buffer);
+                buffer.push(')');
+            }
+            Type::Fn { args, retrn } => {
+                buffer.push_str("fn(");
+                render_all(args, variables, buffer);
+                buffer.push_str(") -> ");
+                render(retrn, variables, buffer);
+            }
+            Type::Tuple { elems } => {
+                buffer.push_str("#(");
+                render_all(elems, variables, buffer);
+                buffer.push(')');
+            }
+            Type::Var { type_ } => match &*type_.borrow() {
+                TypeVar::Link { type_ } => render(type_, variables, buffer),
+                TypeVar::Unbound { id } | TypeVar::Generic { id } => {
+                    let next = variables.len();
+                    let number = *variables.entry(*id).or_insert(next);
+                    buffer.push_str(&format!("_{number}"));
+                }
+            },
+        }
+    }
+    let mut buffer = String::new();
+    render(type_, &mut HashMap::new(), &mut buffer);
+    buffer
+}
+
+#[cfg(test)]
+fn interface(src: &str) -> Module {
+    let mut module = crate::type_::tests::compile_module(src, None, vec![])
+        .expect("compile")
+        .type_info;
+    module.name = "wibble".into();
+    module
+}
+
+#[cfg(test)]
+fn diff(previous: &str, current: &str) -> Vec<(Bump, String)> {
+    ApiDiff::new(&[interface(previous)], &[interface(current)])
+        .changes
+        .into_iter()
+        .map(|change| (change.bump, change.description))
+        .collect()
+}
+
+#[test]
+fn minimum_version_test() {
+    let version = Version::new(1, 2, 3);
+    assert_eq!(Bump::Major.minimum_version(&version), Version::new(2, 0, 0));
+    assert_eq!(Bump::Minor.minimum_version(&version), Version::new(1, 3, 0));
+    assert_eq!(Bump::Patch.minimum_version(&version), Version::new(1, 2, 4));
+
+    let version = Version::new(0, 2, 3);
+    assert_eq!(Bump::Major.minimum_version(&version), Version::new(0, 3, 0));
+    assert_eq!(Bump::Minor.minimum_version(&version), Version::new(0, 2, 4));
+    assert_eq!(Bump::Patch.minimum_version(&version), Version::new(0, 2, 4));
+}
+
+#[test]
+fn unchanged_interface_is_a_patch() {
+    let src = "pub fn add(a: Int, b: Int) -> Int { a + b }
+fn private() { 1 }";
+    let changed_body = "pub fn add(a: Int, b: Int) -> Int { b + a }
+fn other_private() { 2 }";
+    let changes = ApiDiff::new(&[interface(src)], &[interface(changed_body)]);
+    assert_eq!(changes.changes, vec![]);
+    assert_eq!(changes.bump(), Bump::Patch);
+}
+
+#[test]
+fn added_values_and_types_are_minor() {
+    assert_eq!(
+        diff(
+            "pub fn add(a, b) { a + b }",
+            "pub fn add(a, b) { a + b }
+pub fn sub(a, b) { a - b }
+pub const one = 1
+pub type Thing { Thing }"
+        ),
+        vec![
+            (Bump::Minor, "Added type `wibble.Thing`".into()),
+            (Bump::Minor, "Added constructor `wibble.Thing`".into()),
+            (Bump::Minor, "Added constant `wibble.one`".into()),
+            (Bump::Minor, "Added function `wibble.sub`".into()),
+        ]
+    );
+}
+
+#[test]
+fn removed_and_changed_values_are_major() {
+    assert_eq!(
+        diff(
+            "pub fn add(a: Int, b: Int) -> Int { a + b }
+pub fn id(x) { x }
+pub fn sub(a, b) { a - b }",
+            "pub fn add(a: Float, b: Float) -> Float { a +. b }
+pub fn id(y) { y }"
+        ),
+        vec![
+            (
+                Bump::Major,
+                "Changed the type of function `wibble.add` from `fn(Int, Int) -> Int` to \
+`fn(Float, Float) -> Float`"
+                    .into()
+            ),
+            (Bump::Major, "Removed function `wibble.sub`".into()),
+        ]
+    );
+}
+
+#[test]
+fn changed_labels_are_major() {
+    assert_eq!(
+        diff(
+            "pub fn greet(name name: String) { name }
+pub fn wave(name: String) { name }",
+            "pub fn greet(person name: String) { name }
+pub fn wave(to name: String) { name }"
+        ),
+        vec![
+            (
+                Bump::Major,
+                "Changed the labels of function `wibble.greet`".into()
+            ),
+            (Bump::Minor, "Added labels to function `wibble.wave`".into()),
+        ]
+    );
+}
+
+#[test]
+fn type_changes() {
+    assert_eq!(
+        diff(
+            "pub type Box(a) { Box(inner: a) }
+pub type Animal { Cat Dog }
+pub opaque type Id { Id(Int) }
+pub type Gone { Gone }",
+            "pub type Box(a, b) { Box(inner: a, other: b) }
+pub type Animal { Cat Dog Cow }
+pub opaque type Id { Id(Int) NoId }"
+        ),
+        vec![
+            (
+                Bump::Major,
+                "Added constructor `wibble.Cow` to type `wibble.Animal`".into()
+            ),
+            (
+                Bump::Major,
+                "Changed the number of parameters of type `wibble.Box` from 1 to 2".into()
+            ),
+            (Bump::Major, "Removed type `wibble.Gone`".into()),
+            (
+                Bump::Major,
+                "Changed the type of constructor `wibble.Box` from `fn(a) -> Box(a)` to \
+`fn(a, b) -> Box(a, b)`"
+                    .into()
+            ),
+            (
+                Bump::Minor,
+                "Added labels to constructor `wibble.Box`".into()
+            ),
+            (Bump::Major, "Removed constructor `wibble.Gone`".into()),
+        ]
+    );
+}
+
+#[test]
+fn removed_and_added_modules() {
+    let mut other = interface("pub fn main() { 1 }");
+    other.name = "wibble/other".into();
+    let changes = ApiDiff::new(&[interface("pub fn main() { 1 }"), other.clone()], &[]);
+    assert_eq!(changes.bump(), Bump::Major);
+    assert_eq!(
+        changes
+            .changes
+            .iter()
+            .map(|change| change.description.as_str())
+            .collect_vec(),
+        vec!["Removed module `wibble`", "Removed module `wibble/other`"]
+    );
+
+    let changes = ApiDiff::new(&[], &[other]);
+    assert_eq!(changes.bump(), Bump::Minor);
+}
@@ -384,6 +384,7 @@ pub struct ExternalFunction<T> {
     pub module: SmolStr,
     pub fun: SmolStr,
     pub documentation: Option<SmolStr>,
+    pub deprecation: Deprecation,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -407,6 +408,7 @@ pub struct Function<T, Expr> {
     pub return_annotation: Option<TypeAst>,
     pub return_type: T,
     pub documentation: Option<SmolStr>,
+    pub deprecation: Deprecation,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -457,6 +459,7 @@ pub struct ModuleConstant<T, ConstantRecordTag> {
     pub annotation: Option<TypeAst>,
     pub value: Box<Constant<T, ConstantRecordTag>>,
     pub type_: T,
+    pub deprecation: Deprecation,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -484,6 +487,7 @@ pub struct CustomType<T> {
     pub documentation: Option<SmolStr>,
     pub opaque: bool,
     pub typed_parameters: Vec<T>,
+    pub deprecation: Deprecation,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -521,6 +525,7 @@ pub struct TypeAlias<T> {
     pub type_: T,
     pub public: bool,
     pub documentation: Option<SmolStr>,
+    pub deprecation: Deprecation,
 }
 
 pub type TypedModuleStatement = ModuleStatement<Arc<Type>, TypedExpr, SmolStr, SmolStr>;
@@ -635,6 +640,37 @@ impl Layer {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+/// Whether a definition has been marked as deprecated with the
+/// `@deprecated("message")` attribute.
+///
+/// # Example(s)
+///
+/// ```gleam
+/// @deprecated("Use new_function instead")
+/// pub fn old_function() { ... }
+/// ```
+pub enum Deprecation {
+    #[default]
+    NotDeprecated,
+    Deprecated {
+        message: SmolStr,
+    },
+}
+
+impl Deprecation {
+    pub fn is_deprecated(&self) -> bool {
+        matches!(self, Deprecation::Deprecated { .. })
+    }
+
+    pub fn message(&self) -> Option<&str> {
+        match self {
+            Deprecation::NotDeprecated => None,
+            Deprecation::Deprecated { message } => Some(message),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ExternalFnArg<T> {
     pub location: SrcSpan,
@@ -998,7 +1034,7 @@ impl TypedClauseGuard {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Default, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, Hash, Default, Clone, Copy)]
 pub struct SrcSpan {
     pub start: u32,
     pub end: u32,
@@ -1054,6 +1090,7 @@ pub enum Pattern<Type> {
     VarUsage {
         location: SrcSpan,
         name: SmolStr,
+        constructor: Option<Box<ValueConstructor>>,
         type_: Type,
     },
 
@@ -1,7 +1,7 @@
 use std::sync::Arc;
 
 use crate::{
-    ast::{SrcSpan, TypedExpr},
+    ast::{Deprecation, SrcSpan, TypedExpr},
     build::Located,
     type_::{
         self, AccessorsMap, Environment, ExprTyper, FieldMap, ModuleValueConstructor,
@@ -182,6 +182,7 @@ wibble}"#,
                 location: SrcSpan { start: 5, end: 11 },
             },
             type_: type_::int(),
+            deprecation: Deprecation::NotDeprecated,
         },
         name: "wibble".into(),
     };
@@ -314,6 +315,7 @@ fn find_node_module_select() {
             name: "function".into(),
             location: SrcSpan { start: 1, end: 55 },
             documentation: None,
+            deprecation: Deprecation::NotDeprecated,
         },
     };
 
@@ -472,6 +474,7 @@ fn find_node_bool() {
                 module: "".into(),
             },
             type_: type_::bool(),
+            deprecation: Deprecation::NotDeprecated,
         },
         name: "True".into(),
     };
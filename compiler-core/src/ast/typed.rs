@@ -133,6 +133,8 @@ pub enum TypedExpr {
     RecordUpdate {
         location: SrcSpan,
         typ: Arc<Type>,
+        /// The record constructor named before the spread, as in `Name(..x)`.
+        constructor: Box<Self>,
         spread: Box<Self>,
         args: Vec<TypedRecordUpdateArg>,
     },
@@ -417,6 +419,33 @@ impl TypedExpr {
         }
     }
 
+    pub(crate) fn get_deprecation_message(&self) -> Option<&str> {
+        match self {
+            TypedExpr::Var { constructor, .. } => constructor.get_deprecation_message(),
+            TypedExpr::ModuleSelect { constructor, .. } => constructor.get_deprecation_message(),
+
+            TypedExpr::Int { .. }
+            | TypedExpr::Float { .. }
+            | TypedExpr::String { .. }
+            | TypedExpr::Block { .. }
+            | TypedExpr::Pipeline { .. }
+            | TypedExpr::Fn { .. }
+            | TypedExpr::List { .. }
+            | TypedExpr::Call { .. }
+            | TypedExpr::BinOp { .. }
+            | TypedExpr::Case { .. }
+            | TypedExpr::Tuple { .. }
+            | TypedExpr::TupleIndex { .. }
+            | TypedExpr::Todo { .. }
+            | TypedExpr::Panic { .. }
+            | TypedExpr::BitString { .. }
+            | TypedExpr::RecordUpdate { .. }
+            | TypedExpr::RecordAccess { .. }
+            | TypedExpr::NegateBool { .. }
+            | TypedExpr::NegateInt { .. } => None,
+        }
+    }
+
     /// Returns `true` if the typed expr is [`Case`].
     ///
     /// [`Case`]: TypedExpr::Case
@@ -1,6 +1,6 @@
 #![allow(warnings)]
 
-mod dep_tree;
+pub mod dep_tree;
 mod module_loader;
 mod native_file_copier;
 pub mod package_compiler;
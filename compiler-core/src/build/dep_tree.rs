@@ -13,6 +13,77 @@ pub struct DependencyTree<T> {
     values: HashMap<NodeIndex, T>,
 }
 
+impl<T> DependencyTree<T>
+where
+    T: Clone + Eq + Hash,
+{
+    /// Build a graph from a sequence of values and their deps.
+    ///
+    /// Any deps that are not themselves values are ignored.
+    ///
+    pub fn new(inputs: Vec<(T, Vec<T>)>) -> Self {
+        let mut graph = petgraph::Graph::with_capacity(inputs.len(), inputs.len() * 5);
+        let mut indexes = HashMap::with_capacity(inputs.len());
+        let mut values = HashMap::with_capacity(inputs.len());
+
+        for (value, _deps) in &inputs {
+            let index = graph.add_node(value.clone());
+            let _ = indexes.insert(value.clone(), index);
+            let _ = values.insert(index, value.clone());
+        }
+
+        for (value, deps) in inputs {
+            let &from_index = indexes.get(&value).expect("Finding index for value");
+            for &to_index in deps.iter().filter_map(|dep| indexes.get(dep)) {
+                let _ = graph.update_edge(from_index, to_index, ());
+            }
+        }
+
+        Self {
+            graph,
+            indexes,
+            values,
+        }
+    }
+
+    /// The direct deps of a value. Unknown values have no deps.
+    ///
+    pub fn dependencies(&self, value: &T) -> Vec<&T> {
+        let Some(&index) = self.indexes.get(value) else {
+            return vec![];
+        };
+        self.graph
+            .neighbors_directed(index, Direction::Outgoing)
+            .filter_map(|index| self.values.get(&index))
+            .collect()
+    }
+
+    /// Every path through the graph from one value to another that does not
+    /// visit any value twice. Each path starts with `from` and ends with `to`.
+    ///
+    pub fn paths(&self, from: &T, to: &T) -> Vec<Vec<&T>> {
+        let (Some(&from_index), Some(&to_index)) = (self.indexes.get(from), self.indexes.get(to))
+        else {
+            return vec![];
+        };
+        if from_index == to_index {
+            return self
+                .values
+                .get(&from_index)
+                .into_iter()
+                .map(|value| vec![value])
+                .collect();
+        }
+        petgraph::algo::all_simple_paths::<Vec<_>, _>(&self.graph, from_index, to_index, 0, None)
+            .map(|path| {
+                path.into_iter()
+                    .filter_map(|index| self.values.get(&index))
+                    .collect()
+            })
+            .collect()
+    }
+}
+
 /// Take a sequence of values and their deps, and return the values in
 /// order so that deps come before the dependants.
 ///
@@ -123,6 +194,43 @@ fn toposort_deps_test() {
     );
 }
 
+#[test]
+fn dependency_tree_dependencies_test() {
+    let tree = DependencyTree::new(vec![
+        ("a", vec!["b", "c", "z"]),
+        ("b", vec!["c"]),
+        ("c", vec![]),
+    ]);
+    let mut deps = tree.dependencies(&"a");
+    deps.sort();
+    assert_eq!(deps, vec![&"b", &"c"]);
+    assert_eq!(tree.dependencies(&"c"), Vec::<&&str>::new());
+    assert_eq!(tree.dependencies(&"z"), Vec::<&&str>::new());
+}
+
+#[test]
+fn dependency_tree_paths_test() {
+    let tree = DependencyTree::new(vec![
+        ("a", vec!["b", "c"]),
+        ("b", vec!["c", "d"]),
+        ("c", vec!["d"]),
+        ("d", vec![]),
+    ]);
+    let mut paths = tree.paths(&"a", &"d");
+    paths.sort();
+    assert_eq!(
+        paths,
+        vec![
+            vec![&"a", &"b", &"c", &"d"],
+            vec![&"a", &"b", &"d"],
+            vec![&"a", &"c", &"d"],
+        ]
+    );
+    assert_eq!(tree.paths(&"a", &"a"), vec![vec![&"a"]]);
+    assert_eq!(tree.paths(&"d", &"a"), Vec::<Vec<&&str>>::new());
+    assert_eq!(tree.paths(&"a", &"z"), Vec::<Vec<&&str>>::new());
+}
+
 #[derive(Debug, PartialEq)]
 pub enum Error {
     Cycle(Vec<SmolStr>),
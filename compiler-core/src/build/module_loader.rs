@@ -9,6 +9,7 @@ use std::{
 
 use serde::{Deserialize, Serialize};
 use smol_str::SmolStr;
+use vec1::vec1;
 
 use super::{
     package_compiler::{module_name, CacheMetadata, CachedModule, Input, UncompiledModule},
@@ -156,7 +157,7 @@ where
     let (mut ast, extra) = crate::parse::parse_module(&code).map_err(|error| Error::Parse {
         path: path.clone(),
         src: code.clone(),
-        error,
+        errors: vec1![error],
     })?;
 
     let dependencies = ast.dependencies(target);
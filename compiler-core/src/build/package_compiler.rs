@@ -339,23 +339,38 @@ where
         out: &Path,
         modules_to_compile: &mut HashSet<PathBuf>,
     ) -> Result<(), Error> {
-        let name = format!("{name}@@main.erl", name = self.config.name);
+        let main = ErlangEntrypointModule {
+            application: &self.config.name,
+        };
+        self.render_erlang_entrypoint(out, "main", main, modules_to_compile)?;
+
+        let test = ErlangTestRunnerModule {
+            application: &self.config.name,
+        };
+        self.render_erlang_entrypoint(out, "test", test, modules_to_compile)
+    }
+
+    fn render_erlang_entrypoint(
+        &mut self,
+        out: &Path,
+        kind: &str,
+        template: impl Template,
+        modules_to_compile: &mut HashSet<PathBuf>,
+    ) -> Result<(), Error> {
+        let name = format!("{name}@@{kind}.erl", name = self.config.name);
         let path = out.join(&name);
 
         // If the entrypoint module has already been created then we don't need
         // to write and compile it again.
         if self.io.is_file(&path) {
-            tracing::debug!("erlang_entrypoint_already_exists");
+            tracing::debug!(kind, "erlang_entrypoint_already_exists");
             return Ok(());
         }
 
-        let template = ErlangEntrypointModule {
-            application: &self.config.name,
-        };
         let module = template.render().expect("Erlang entrypoint rendering");
         self.io.write(&path, &module)?;
         let _ = modules_to_compile.insert(name.into());
-        tracing::debug!("erlang_entrypoint_written");
+        tracing::debug!(kind, "erlang_entrypoint_written");
         Ok(())
     }
 }
@@ -400,10 +415,10 @@ fn analyse(
             module_types,
             &TypeWarningEmitter::new(path.clone(), code.clone(), warnings.clone()),
         )
-        .map_err(|error| Error::Type {
+        .map_err(|errors| Error::Type {
             path: path.clone(),
             src: code.clone(),
-            error,
+            errors,
         })?;
 
         // Register the types from this module so they can be imported into
@@ -628,3 +643,9 @@ pub(crate) struct UncompiledModule {
 struct ErlangEntrypointModule<'a> {
     application: &'a str,
 }
+
+#[derive(Template)]
+#[template(path = "gleam@@test.erl", escape = "none")]
+struct ErlangTestRunnerModule<'a> {
+    application: &'a str,
+}
@@ -139,7 +139,11 @@ where
             .join(info.name.replace('/', "@"))
             .with_extension("cache");
         let bytes = self.io.read_bytes(&path)?;
-        metadata::ModuleDecoder::new(self.ids.clone()).read(bytes.as_slice())
+        let mut module = metadata::ModuleDecoder::new(self.ids.clone()).read(bytes.as_slice())?;
+        // The origin is not part of the cached metadata, but we know it from
+        // the directory the source file is in.
+        module.origin = info.origin;
+        Ok(module)
     }
 
     fn read_source_files(&self) -> Result<HashMap<SmolStr, Input>> {
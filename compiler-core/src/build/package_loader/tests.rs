@@ -12,6 +12,7 @@ use std::time::Duration;
 struct LoaderTestOutput {
     to_compile: Vec<SmolStr>,
     cached: Vec<SmolStr>,
+    cached_origins: Vec<Origin>,
 }
 
 const TEST_SOURCE_1: &'static str = "const x = 1";
@@ -69,7 +70,8 @@ fn run_loader(fs: InMemoryFileSystem, root: &Path, artefact: &Path) -> LoaderTes
 
     LoaderTestOutput {
         to_compile: loaded.to_compile.into_iter().map(|m| m.name).collect(),
-        cached: loaded.cached.into_iter().map(|m| m.name).collect(),
+        cached: loaded.cached.iter().map(|m| m.name.clone()).collect(),
+        cached_origins: loaded.cached.iter().map(|m| m.origin).collect(),
     }
 }
 
@@ -185,3 +187,75 @@ fn module_is_stale_if_deps_are_stale() {
     );
     assert_eq!(loaded.cached, vec![SmolStr::new("three")]);
 }
+
+#[test]
+fn module_is_stale_if_transitive_deps_are_stale() {
+    let fs = InMemoryFileSystem::new();
+    let root = Path::new("/");
+    let artefact = Path::new("/artefact");
+
+    // Cache is stale
+    write_src(&fs, "/src/one.gleam", 1, TEST_SOURCE_2);
+    write_cache(&fs, "one", 0, vec![], TEST_SOURCE_1);
+
+    // Cache is fresh but dep is stale
+    write_src(&fs, "/src/two.gleam", 1, "import one");
+    write_cache(&fs, "two", 2, vec![SmolStr::new("one")], "import one");
+
+    // Cache is fresh but dep of dep is stale
+    write_src(&fs, "/src/three.gleam", 1, "import two");
+    write_cache(&fs, "three", 2, vec![SmolStr::new("two")], "import two");
+
+    let loaded = run_loader(fs, root, artefact);
+    assert_eq!(
+        loaded.to_compile,
+        vec![
+            SmolStr::new("one"),
+            SmolStr::new("two"),
+            SmolStr::new("three")
+        ]
+    );
+    assert!(loaded.cached.is_empty());
+}
+
+#[test]
+fn module_is_not_stale_if_only_the_timestamp_changed() {
+    let fs = InMemoryFileSystem::new();
+    let root = Path::new("/");
+    let artefact = Path::new("/artefact");
+
+    // The file has been saved again without any changes to its content
+    write_src(&fs, "/src/one.gleam", 1, TEST_SOURCE_1);
+    write_cache(&fs, "one", 0, vec![], TEST_SOURCE_1);
+
+    write_src(&fs, "/src/two.gleam", 1, "import one");
+    write_cache(&fs, "two", 2, vec![SmolStr::new("one")], "import one");
+
+    let loaded = run_loader(fs, root, artefact);
+    assert!(loaded.to_compile.is_empty());
+    assert_eq!(
+        loaded.cached,
+        vec![SmolStr::new("one"), SmolStr::new("two")]
+    );
+}
+
+#[test]
+fn cached_test_module_has_test_origin() {
+    let fs = InMemoryFileSystem::new();
+    let root = Path::new("/");
+    let artefact = Path::new("/artefact");
+
+    write_src(&fs, "/src/one.gleam", 0, TEST_SOURCE_1);
+    write_cache(&fs, "one", 0, vec![], TEST_SOURCE_1);
+
+    write_src(&fs, "/test/two.gleam", 0, "import one");
+    write_cache(&fs, "two", 0, vec![SmolStr::new("one")], "import one");
+
+    let loaded = run_loader(fs, root, artefact);
+    assert!(loaded.to_compile.is_empty());
+    assert_eq!(
+        loaded.cached,
+        vec![SmolStr::new("one"), SmolStr::new("two")]
+    );
+    assert_eq!(loaded.cached_origins, vec![Origin::Src, Origin::Test]);
+}
@@ -7,9 +7,10 @@ use crate::{
     config::PackageConfig,
     error::{FileIoAction, FileKind},
     io::{CommandExecutor, FileSystemReader, FileSystemWriter, Stdio},
-    manifest::ManifestPackage,
+    manifest::{ManifestPackage, ManifestPackageSource},
     metadata,
     paths::{self, ProjectPaths},
+    test_runner::{self, TestFunction},
     type_::{self, ModuleFunction},
     uid::UniqueIdGenerator,
     version::COMPILER_VERSION,
@@ -52,6 +53,11 @@ pub struct Options {
 #[derive(Debug)]
 pub struct Built {
     pub root_package: Package,
+    /// The Gleam dependencies that were compiled during this build, along
+    /// with the directory each one was compiled from. Only the modules that
+    /// needed compiling are included, so dependencies loaded from the build
+    /// cache are absent and local dependencies may be missing modules.
+    pub compiled_dependencies: Vec<(PathBuf, Package)>,
     module_interfaces: im::HashMap<SmolStr, type_::Module>,
 }
 
@@ -64,6 +70,19 @@ impl Built {
             }),
         }
     }
+
+    /// The test functions defined in the test modules of the root package,
+    /// sorted by module and then by name.
+    pub fn test_functions(&self) -> Vec<TestFunction> {
+        self.module_interfaces
+            .values()
+            .filter(|module| {
+                module.origin == Origin::Test && module.package == self.root_package.config.name
+            })
+            .flat_map(test_runner::module_test_functions)
+            .sorted()
+            .collect()
+    }
 }
 
 #[derive(Debug)]
@@ -71,6 +90,7 @@ pub struct ProjectCompiler<IO> {
     // The gleam.toml config for the root package of the project
     config: PackageConfig,
     packages: HashMap<String, ManifestPackage>,
+    compiled_dependencies: Vec<(PathBuf, Package)>,
     importable_modules: im::HashMap<SmolStr, type_::Module>,
     defined_modules: im::HashMap<SmolStr, PathBuf>,
     warnings: WarningEmitter,
@@ -106,6 +126,7 @@ where
             .collect();
 
         Self {
+            compiled_dependencies: Vec::new(),
             importable_modules: im::HashMap::new(),
             defined_modules: im::HashMap::new(),
             ids: UniqueIdGenerator::new(),
@@ -124,6 +145,10 @@ where
         &self.importable_modules
     }
 
+    pub fn get_importable_modules_mut(&mut self) -> &mut im::HashMap<SmolStr, type_::Module> {
+        &mut self.importable_modules
+    }
+
     // TODO: test
     pub fn checkpoint(&self) -> CheckpointState {
         CheckpointState {
@@ -146,6 +171,22 @@ where
         self.options.target.unwrap_or(self.config.target)
     }
 
+    pub fn config(&self) -> &PackageConfig {
+        &self.config
+    }
+
+    pub fn ids(&self) -> &UniqueIdGenerator {
+        &self.ids
+    }
+
+    pub fn paths(&self) -> &ProjectPaths {
+        &self.paths
+    }
+
+    pub fn io(&self) -> &IO {
+        &self.io
+    }
+
     /// Returns the compiled information from the root package
     pub fn compile(mut self) -> Result<Built> {
         self.check_gleam_version()?;
@@ -167,6 +208,7 @@ where
 
         Ok(Built {
             root_package,
+            compiled_dependencies: self.compiled_dependencies,
             module_interfaces: self.importable_modules,
         })
     }
@@ -228,7 +270,11 @@ where
         let build_path =
             self.paths
                 .build_directory_for_package(self.mode(), self.target(), &package.name);
-        if self.io.is_directory(&build_path) {
+        // Local packages can be edited at any time, so rather than loading
+        // their precompiled modules they are always given to the package
+        // compiler, which only recompiles the modules that have changed.
+        let is_local = matches!(package.source, ManifestPackageSource::Local { .. });
+        if !is_local && self.io.is_directory(&build_path) {
             tracing::debug!(package=%package.name, "loading_precompiled_package");
             return self.load_cached_package(build_path, package);
         }
@@ -397,11 +443,16 @@ where
     }
 
     fn compile_gleam_dep_package(&mut self, package: &ManifestPackage) -> Result<(), Error> {
-        let config_path = self.paths.build_packages_package_config(&package.name);
-        let config = PackageConfig::read(config_path, &self.io)?;
-        let root = self.paths.build_packages_package(&package.name);
-        self.compile_gleam_package(&config, false, root)
-            .map(|_| ())?;
+        let root = match &package.source {
+            ManifestPackageSource::Local { path } => self.paths.root().join(path),
+            ManifestPackageSource::Hex { .. } | ManifestPackageSource::Git { .. } => {
+                self.paths.build_packages_package(&package.name)
+            }
+        };
+        let config = PackageConfig::read(root.join("gleam.toml"), &self.io)?;
+        let modules = self.compile_gleam_package(&config, false, root.clone())?;
+        self.compiled_dependencies
+            .push((root, Package { config, modules }));
         Ok(())
     }
 
@@ -1,6 +1,20 @@
-use crate::{manifest::ManifestPackage, Error};
+use std::sync::Arc;
 
-use super::project_compiler::{usable_build_tool, BuildTool};
+use smol_str::SmolStr;
+
+use crate::{
+    config::PackageConfig,
+    io::{memory::InMemoryFileSystem, FileSystemWriter},
+    manifest::{Base16Checksum, ManifestPackage, ManifestPackageSource},
+    paths::ProjectPaths,
+    warning::NullWarningEmitterIO,
+    Error,
+};
+
+use super::{
+    project_compiler::{usable_build_tool, BuildTool},
+    Codegen, Mode, NullTelemetry, Options, ProjectCompiler, Target,
+};
 
 #[test]
 fn usable_build_tool_unknown() {
@@ -56,3 +70,65 @@ fn usable_build_tool_mix_then_rebar3() {
         Ok(BuildTool::Rebar3)
     )
 }
+
+/// Compiles a dependency that has been built before but has nothing in its
+/// build directory, returning whether its module can then be imported.
+fn compile_dependency(source: ManifestPackageSource) -> bool {
+    let io = InMemoryFileSystem::new();
+    let paths = ProjectPaths::new("/app".into());
+    let package_root = match &source {
+        ManifestPackageSource::Local { path } => paths.root().join(path),
+        ManifestPackageSource::Hex { .. } | ManifestPackageSource::Git { .. } => {
+            paths.build_packages_package("shared")
+        }
+    };
+    io.write(
+        &package_root.join("gleam.toml"),
+        "name = \"shared\"\nversion = \"1.0.0\"",
+    )
+    .unwrap();
+    io.write(&package_root.join("src/shared.gleam"), "pub fn one() { 1 }")
+        .unwrap();
+    let build_directory =
+        paths.build_directory_for_package(Mode::Dev, Target::JavaScript, "shared");
+    io.write(&build_directory.join("shared.mjs"), "").unwrap();
+
+    let package = ManifestPackage {
+        name: "shared".into(),
+        source,
+        ..ManifestPackage::default().with_build_tools(&["gleam"])
+    };
+    let mut compiler = ProjectCompiler::new(
+        PackageConfig::default(),
+        Options {
+            mode: Mode::Dev,
+            target: Some(Target::JavaScript),
+            codegen: Codegen::None,
+            warnings_as_errors: false,
+        },
+        vec![package],
+        Box::new(NullTelemetry),
+        Arc::new(NullWarningEmitterIO),
+        paths,
+        io,
+    );
+    compiler.compile_dependencies().unwrap();
+    compiler
+        .get_importable_modules()
+        .contains_key(&SmolStr::from("shared"))
+}
+
+#[test]
+fn local_packages_are_always_compiled() {
+    assert!(compile_dependency(ManifestPackageSource::Local {
+        path: "shared".into(),
+    }));
+}
+
+#[test]
+fn hex_packages_are_loaded_from_the_build_cache() {
+    assert!(!compile_dependency(ManifestPackageSource::Hex {
+        outer_checksum: Base16Checksum(vec![]),
+        repository: "hexpm".into(),
+    }));
+}
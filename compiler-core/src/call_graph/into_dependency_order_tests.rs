@@ -1,5 +1,5 @@
 use super::*;
-use crate::ast::{Arg, ExternalFunction, Function, ModuleFunction, TypeAst};
+use crate::ast::{Arg, Deprecation, ExternalFunction, Function, ModuleFunction, TypeAst};
 use smol_str::SmolStr;
 
 enum Input {
@@ -29,6 +29,7 @@ fn parse_and_order(functions: &[Input]) -> Result<Vec<Vec<SmolStr>>, Error> {
                 end_position: src.len() as u32,
                 return_type: (),
                 documentation: None,
+                deprecation: Deprecation::NotDeprecated,
             }),
             Input::External(name) => ModuleFunction::External(ExternalFunction {
                 name: name.into(),
@@ -43,6 +44,7 @@ fn parse_and_order(functions: &[Input]) -> Result<Vec<Vec<SmolStr>>, Error> {
                 },
                 return_type: (),
                 documentation: None,
+                deprecation: Deprecation::NotDeprecated,
             }),
         })
         .collect_vec();
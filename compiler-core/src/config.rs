@@ -1,9 +1,10 @@
 use crate::error::{FileIoAction, FileKind};
 use crate::io::FileSystemReader;
 use crate::manifest::Manifest;
+use crate::requirement::Requirement;
 use crate::{Error, Result};
 use globset::{Glob, GlobSetBuilder};
-use hexpm::version::{Range, Version};
+use hexpm::version::Version;
 use http::Uri;
 use serde::Deserialize;
 use smol_str::SmolStr;
@@ -29,7 +30,7 @@ fn default_javascript_runtime() -> Runtime {
     Runtime::NodeJs
 }
 
-pub type Dependencies = HashMap<String, Range>;
+pub type Dependencies = HashMap<String, Requirement>;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct SpdxLicense {
@@ -93,6 +94,8 @@ pub struct PackageConfig {
     pub target: Target,
     #[serde(default)]
     pub internal_modules: Option<Vec<Glob>>,
+    #[serde(default)]
+    pub repositories: Vec<HexRepositoryConfig>,
 }
 
 impl PackageConfig {
@@ -184,7 +187,7 @@ struct StalePackageRemover<'a> {
 
 impl<'a> StalePackageRemover<'a> {
     pub fn fresh_and_locked(
-        requirements: &'a HashMap<String, Range>,
+        requirements: &'a HashMap<String, Requirement>,
         manifest: &'a Manifest,
     ) -> HashMap<String, Version> {
         let locked = manifest
@@ -201,7 +204,7 @@ impl<'a> StalePackageRemover<'a> {
 
     fn run(
         &mut self,
-        requirements: &'a HashMap<String, Range>,
+        requirements: &'a HashMap<String, Requirement>,
         manifest: &'a Manifest,
     ) -> HashMap<String, Version> {
         // Record all the requirements that have not changed
@@ -249,13 +252,13 @@ impl<'a> StalePackageRemover<'a> {
 fn locked_no_manifest() {
     let mut config = PackageConfig::default();
     config.dependencies = [
-        ("prod1".into(), Range::new("~> 1.0".into())),
-        ("prod2".into(), Range::new("~> 2.0".into())),
+        ("prod1".into(), Requirement::hex("~> 1.0")),
+        ("prod2".into(), Requirement::hex("~> 2.0")),
     ]
     .into();
     config.dev_dependencies = [
-        ("dev1".into(), Range::new("~> 1.0".into())),
-        ("dev2".into(), Range::new("~> 2.0".into())),
+        ("dev1".into(), Requirement::hex("~> 1.0")),
+        ("dev2".into(), Requirement::hex("~> 2.0")),
     ]
     .into();
     assert_eq!(config.locked(None).unwrap(), [].into());
@@ -265,13 +268,13 @@ fn locked_no_manifest() {
 fn locked_no_changes() {
     let mut config = PackageConfig::default();
     config.dependencies = [
-        ("prod1".into(), Range::new("~> 1.0".into())),
-        ("prod2".into(), Range::new("~> 2.0".into())),
+        ("prod1".into(), Requirement::hex("~> 1.0")),
+        ("prod2".into(), Requirement::hex("~> 2.0")),
     ]
     .into();
     config.dev_dependencies = [
-        ("dev1".into(), Range::new("~> 1.0".into())),
-        ("dev2".into(), Range::new("~> 2.0".into())),
+        ("dev1".into(), Requirement::hex("~> 1.0")),
+        ("dev2".into(), Requirement::hex("~> 2.0")),
     ]
     .into();
     let manifest = Manifest {
@@ -282,6 +285,7 @@ fn locked_no_changes() {
             manifest_package("dev1", "1.1.0", &[]),
             manifest_package("dev2", "1.2.0", &[]),
         ],
+        local_requirements: HashMap::new(),
     };
     assert_eq!(
         config.locked(Some(&manifest)).unwrap(),
@@ -298,8 +302,8 @@ fn locked_no_changes() {
 #[test]
 fn locked_some_removed() {
     let mut config = PackageConfig::default();
-    config.dependencies = [("prod1".into(), Range::new("~> 1.0".into()))].into();
-    config.dev_dependencies = [("dev2".into(), Range::new("~> 2.0".into()))].into();
+    config.dependencies = [("prod1".into(), Requirement::hex("~> 1.0"))].into();
+    config.dev_dependencies = [("dev2".into(), Requirement::hex("~> 2.0"))].into();
     let manifest = Manifest {
         requirements: config.all_dependencies().unwrap(),
         packages: vec![
@@ -308,6 +312,7 @@ fn locked_some_removed() {
             manifest_package("dev1", "1.1.0", &[]),  // Not in config
             manifest_package("dev2", "1.2.0", &[]),
         ],
+        local_requirements: HashMap::new(),
     };
     assert_eq!(
         config.locked(Some(&manifest)).unwrap(),
@@ -325,21 +330,21 @@ fn locked_some_removed() {
 fn locked_some_changed() {
     let mut config = PackageConfig::default();
     config.dependencies = [
-        ("prod1".into(), Range::new("~> 3.0".into())), // Does not match manifest
-        ("prod2".into(), Range::new("~> 2.0".into())),
+        ("prod1".into(), Requirement::hex("~> 3.0")), // Does not match manifest
+        ("prod2".into(), Requirement::hex("~> 2.0")),
     ]
     .into();
     config.dev_dependencies = [
-        ("dev1".into(), Range::new("~> 3.0".into())), // Does not match manifest
-        ("dev2".into(), Range::new("~> 2.0".into())),
+        ("dev1".into(), Requirement::hex("~> 3.0")), // Does not match manifest
+        ("dev2".into(), Requirement::hex("~> 2.0")),
     ]
     .into();
     let manifest = Manifest {
         requirements: [
-            ("prod1".into(), Range::new("~> 1.0".into())),
-            ("prod2".into(), Range::new("~> 2.0".into())),
-            ("dev1".into(), Range::new("~> 1.0".into())),
-            ("dev2".into(), Range::new("~> 2.0".into())),
+            ("prod1".into(), Requirement::hex("~> 1.0")),
+            ("prod2".into(), Requirement::hex("~> 2.0")),
+            ("dev1".into(), Requirement::hex("~> 1.0")),
+            ("dev2".into(), Requirement::hex("~> 2.0")),
         ]
         .into(),
         packages: vec![
@@ -348,6 +353,7 @@ fn locked_some_changed() {
             manifest_package("dev1", "1.1.0", &[]),
             manifest_package("dev2", "1.2.0", &[]),
         ],
+        local_requirements: HashMap::new(),
     };
     assert_eq!(
         config.locked(Some(&manifest)).unwrap(),
@@ -365,15 +371,15 @@ fn locked_some_changed() {
 fn locked_nested_are_removed_too() {
     let mut config = PackageConfig::default();
     config.dependencies = [
-        ("1".into(), Range::new("~> 2.0".into())), // Does not match manifest
-        ("2".into(), Range::new("~> 1.0".into())),
+        ("1".into(), Requirement::hex("~> 2.0")), // Does not match manifest
+        ("2".into(), Requirement::hex("~> 1.0")),
     ]
     .into();
     config.dev_dependencies = [].into();
     let manifest = Manifest {
         requirements: [
-            ("1".into(), Range::new("~> 1.0".into())),
-            ("2".into(), Range::new("~> 1.0".into())),
+            ("1".into(), Requirement::hex("~> 1.0")),
+            ("2".into(), Requirement::hex("~> 1.0")),
         ]
         .into(),
         packages: vec![
@@ -393,6 +399,7 @@ fn locked_nested_are_removed_too() {
             manifest_package("2.2.2", "2.1.0", &[]),
             manifest_package("shared", "2.1.0", &[]),
         ],
+        local_requirements: HashMap::new(),
     };
     assert_eq!(
         config.locked(Some(&manifest)).unwrap(),
@@ -527,6 +534,7 @@ fn manifest_package(
         requirements: requirements.iter().map(|e| (*e).to_string()).collect(),
         source: crate::manifest::ManifestPackageSource::Hex {
             outer_checksum: Base16Checksum(vec![]),
+            repository: "hexpm".into(),
         },
     }
 }
@@ -551,6 +559,7 @@ impl Default for PackageConfig {
             licences: Default::default(),
             links: Default::default(),
             internal_modules: Default::default(),
+            repositories: Default::default(),
             target: Target::Erlang,
         }
     }
@@ -677,6 +686,77 @@ impl Default for Repository {
     }
 }
 
+/// A Hex repository that packages can be downloaded from, as configured in
+/// the `repositories` section of `gleam.toml` or of the user's global config.
+///
+/// Other repositories must give all of the URLs and the public key, but a
+/// repository named `hexpm` can give only the settings that differ from those
+/// of the public Hex repository, which is how a mirror of it is used.
+///
+/// ```toml
+/// [[repositories]]
+/// name = "internal"
+/// repo_url = "https://hex.example.com/repo/"
+/// api_url = "https://hex.example.com/api/"
+/// auth_key = "..."
+/// public_key = """
+/// -----BEGIN PUBLIC KEY-----
+/// ...
+/// -----END PUBLIC KEY-----
+/// """
+/// ```
+///
+#[derive(Deserialize, Default, Debug, PartialEq, Eq, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct HexRepositoryConfig {
+    pub name: String,
+    #[serde(default)]
+    pub api_url: Option<String>,
+    #[serde(default)]
+    pub repo_url: Option<String>,
+    #[serde(default)]
+    pub auth_key: Option<String>,
+    #[serde(default)]
+    pub public_key: Option<String>,
+}
+
+impl HexRepositoryConfig {
+    /// Fills in any settings not given here from another config for the same
+    /// repository. This is used to take secrets such as the auth key from the
+    /// user's global config rather than from the project.
+    ///
+    /// The other config's auth key is only used if the URLs it would be sent
+    /// to are the ones given in that config, so that a project cannot have a
+    /// key from the user's config sent to a host of its choosing.
+    ///
+    pub fn or(self, other: &HexRepositoryConfig) -> Self {
+        let overrides =
+            |url: &Option<String>, other_url: &Option<String>| url.is_some() && url != other_url;
+        let keeps_urls = !overrides(&self.api_url, &other.api_url)
+            && !overrides(&self.repo_url, &other.repo_url);
+        let other_auth_key = if keeps_urls {
+            other.auth_key.clone()
+        } else {
+            None
+        };
+        Self {
+            name: self.name,
+            api_url: self.api_url.or_else(|| other.api_url.clone()),
+            repo_url: self.repo_url.or_else(|| other.repo_url.clone()),
+            auth_key: self.auth_key.or(other_auth_key),
+            public_key: self.public_key.or_else(|| other.public_key.clone()),
+        }
+    }
+}
+
+/// The user's global config, which applies to every project.
+///
+#[derive(Deserialize, Default, Debug, PartialEq, Eq, Clone)]
+pub struct UserConfig {
+    #[serde(default)]
+    pub repositories: Vec<HexRepositoryConfig>,
+}
+
 #[derive(Deserialize, Default, Debug, PartialEq, Eq, Clone)]
 pub struct Docs {
     #[serde(default)]
@@ -2,10 +2,11 @@ use std::path::PathBuf;
 
 pub use codespan_reporting::diagnostic::{LabelStyle, Severity};
 use codespan_reporting::{diagnostic::Label as CodespanLabel, files::SimpleFile};
+use serde_json::json;
 use smol_str::SmolStr;
 use termcolor::Buffer;
 
-use crate::ast::SrcSpan;
+use crate::{ast::SrcSpan, line_numbers::LineNumbers};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Level {
@@ -41,6 +42,7 @@ pub struct Diagnostic {
     pub level: Level,
     pub location: Option<Location>,
     pub hint: Option<String>,
+    pub code: Option<&'static str>,
 }
 
 impl Diagnostic {
@@ -85,9 +87,12 @@ impl Diagnostic {
             Level::Warning => Severity::Warning,
         };
 
-        let diagnostic = codespan_reporting::diagnostic::Diagnostic::new(severity)
+        let mut diagnostic = codespan_reporting::diagnostic::Diagnostic::new(severity)
             .with_message(&self.title)
             .with_labels(labels);
+        if let Some(code) = self.code {
+            diagnostic = diagnostic.with_code(code);
+        }
         let config = codespan_reporting::term::Config::default();
         codespan_reporting::term::emit(buffer, &config, &file, &diagnostic)
             .expect("write_diagnostic");
@@ -104,6 +109,9 @@ impl Diagnostic {
             .set_color(ColorSpec::new().set_bold(true).set_fg(Some(colour)))
             .expect("write_title_color1");
         write!(buffer, "{kind}").expect("write_title_kind");
+        if let Some(code) = self.code {
+            write!(buffer, "[{code}]").expect("write_title_code");
+        }
         buffer
             .set_color(ColorSpec::new().set_bold(true))
             .expect("write_title_color2");
@@ -113,9 +121,138 @@ impl Diagnostic {
             .expect("write_title_reset");
     }
 
+    /// The diagnostic as a JSON object, for editors and other tools that would
+    /// otherwise have to scrape the output of `write`.
+    ///
+    /// The first label is the primary one. Spans are given both as byte
+    /// offsets into the file and as line and column numbers, which start at 1.
+    pub fn to_json(&self) -> serde_json::Value {
+        let severity = match self.level {
+            Level::Error => "error",
+            Level::Warning => "warning",
+        };
+        let (file, labels) = match &self.location {
+            Some(location) => {
+                let line_numbers = LineNumbers::new(&location.src);
+                let labels = location
+                    .labels()
+                    .enumerate()
+                    .map(|(index, label)| {
+                        json!({
+                            "text": label.text,
+                            "primary": index == 0,
+                            "start": position_json(&line_numbers, label.span.start),
+                            "end": position_json(&line_numbers, label.span.end),
+                        })
+                    })
+                    .collect();
+                (Some(location.path.to_string_lossy()), labels)
+            }
+            None => (None, vec![]),
+        };
+        json!({
+            "severity": severity,
+            "code": self.code,
+            "title": self.title,
+            "text": self.text,
+            "hint": self.hint,
+            "file": file,
+            "labels": labels,
+        })
+    }
+
     pub fn pretty_string(&self) -> String {
         let mut nocolor = Buffer::no_color();
         self.write(&mut nocolor);
         String::from_utf8(nocolor.into_inner()).expect("Error printing produced invalid utf8")
     }
 }
+
+fn position_json(line_numbers: &LineNumbers, byte_index: u32) -> serde_json::Value {
+    let position = line_numbers.line_and_column_number(byte_index);
+    json!({
+        "byte": byte_index,
+        "line": position.line,
+        "column": position.column,
+    })
+}
+
+#[test]
+fn type_error_json() {
+    let src = "pub fn main() {\n  wibble\n}\n";
+    let errors = crate::type_::tests::compile_module(src, None, vec![]).expect_err("type error");
+    let error = crate::Error::Type {
+        path: PathBuf::from("src/app.gleam"),
+        src: src.into(),
+        errors,
+    };
+    assert_eq!(
+        error.to_diagnostic().to_json(),
+        json!({
+            "severity": "error",
+            "code": "E0103",
+            "title": "Unknown variable",
+            "text": "The name `wibble` is not in scope here.",
+            "hint": null,
+            "file": "src/app.gleam",
+            "labels": [{
+                "text": "Did you mean `Nil`?",
+                "primary": true,
+                "start": {"byte": 18, "line": 2, "column": 3},
+                "end": {"byte": 24, "line": 2, "column": 9},
+            }],
+        })
+    );
+}
+
+#[test]
+fn parse_error_json() {
+    let src = "pub fn main() {\n  1 +\n}\n";
+    let error = crate::parse::parse_module(src).expect_err("parse error");
+    let error = crate::Error::Parse {
+        path: PathBuf::from("src/app.gleam"),
+        src: src.into(),
+        errors: vec1::vec1![error],
+    };
+    assert_eq!(
+        error.to_diagnostic().to_json(),
+        json!({
+            "severity": "error",
+            "code": "E0024",
+            "title": "Syntax error",
+            "text": "",
+            "hint": "Remove it or put a value after it.",
+            "file": "src/app.gleam",
+            "labels": [{
+                "text": "This operator has no value on its right side.",
+                "primary": true,
+                "start": {"byte": 20, "line": 2, "column": 5},
+                "end": {"byte": 21, "line": 2, "column": 6},
+            }],
+        })
+    );
+}
+
+#[test]
+fn unlocated_json() {
+    let diagnostic = Diagnostic {
+        title: "Dependency resolution failed".into(),
+        text: "No versions satisfy the requirements.".into(),
+        level: Level::Warning,
+        location: None,
+        hint: Some("Loosen the requirements.".into()),
+        code: None,
+    };
+    assert_eq!(
+        diagnostic.to_json(),
+        json!({
+            "severity": "warning",
+            "code": null,
+            "title": "Dependency resolution failed",
+            "text": "No versions satisfy the requirements.",
+            "hint": "Loosen the requirements.",
+            "file": null,
+            "labels": [],
+        })
+    );
+}
@@ -1,6 +1,6 @@
 mod source_links;
 
-use std::{path::PathBuf, time::SystemTime};
+use std::{collections::HashMap, path::PathBuf, sync::Arc, time::SystemTime};
 
 use crate::{
     ast::{
@@ -15,6 +15,7 @@ use crate::{
     io::OutputFile,
     paths::ProjectPaths,
     pretty,
+    type_::TypeVar,
 };
 use askama::Template;
 use itertools::Itertools;
@@ -31,10 +32,59 @@ pub fn generate_html(
     analysed: &[Module],
     docs_pages: &[DocsPage],
 ) -> Vec<OutputFile> {
-    let modules = analysed
-        .iter()
-        .filter(|module| !module.is_test())
-        .filter(|module| !config.is_internal_module(&module.name));
+    let documentation = generate_package_html(
+        paths,
+        config,
+        analysed,
+        docs_pages,
+        &DocumentedModules::default(),
+    );
+    let search_data = search_data(&config.name, &[&documentation]);
+    let mut files = documentation.files;
+    files.push(search_data);
+    files
+}
+
+/// The rendered documentation of a single package.
+#[derive(Debug)]
+pub struct PackageDocumentation {
+    pub name: SmolStr,
+    pub version: String,
+    /// The pages of the package and the assets they use. The search data is
+    /// not included as it may cover other packages, see [`search_data`].
+    pub files: Vec<OutputFile>,
+    search_indexes: Vec<SearchIndex>,
+}
+
+/// The modules of each package being documented together, so that types in
+/// the signatures of one package can link to their documentation in another.
+#[derive(Debug, Default, Clone)]
+pub struct DocumentedModules {
+    packages: HashMap<SmolStr, SmolStr>,
+}
+
+impl DocumentedModules {
+    pub fn insert_package(&mut self, config: &PackageConfig, analysed: &[Module]) {
+        for module in modules_to_document(config, analysed) {
+            let _ = self
+                .packages
+                .insert(module.name.clone(), config.name.clone());
+        }
+    }
+}
+
+/// Render the documentation of a package, linking any types it mentions
+/// that are defined in one of the other documented modules to the pages of
+/// their package, which are expected to be in a sibling directory named
+/// after that package.
+pub fn generate_package_html(
+    paths: &ProjectPaths,
+    config: &PackageConfig,
+    analysed: &[Module],
+    docs_pages: &[DocsPage],
+    documented_modules: &DocumentedModules,
+) -> PackageDocumentation {
+    let modules = modules_to_document(config, analysed);
 
     let rendering_timestamp = SystemTime::now()
         .duration_since(SystemTime::UNIX_EPOCH)
@@ -131,6 +181,11 @@ pub fn generate_html(
 
         // Read module src & create line number lookup structure
         let source_links = SourceLinker::new(paths, config, module);
+        let type_links = TypeLinker {
+            documented_modules,
+            package: &config.name,
+            unnest: &unnest,
+        };
 
         let documentation_content = module.ast.documentation.iter().join("\n");
         let rendered_documentation = render_markdown(&documentation_content.clone());
@@ -139,7 +194,7 @@ pub fn generate_html(
             .ast
             .statements
             .iter()
-            .flat_map(|statement| function(&source_links, statement))
+            .flat_map(|statement| function(&source_links, &type_links, statement))
             .sorted()
             .collect();
 
@@ -147,7 +202,7 @@ pub fn generate_html(
             .ast
             .statements
             .iter()
-            .flat_map(|statement| type_(&source_links, statement))
+            .flat_map(|statement| type_(&source_links, &type_links, statement))
             .sorted()
             .collect();
 
@@ -155,7 +210,7 @@ pub fn generate_html(
             .ast
             .statements
             .iter()
-            .flat_map(|statement| constant(&source_links, statement))
+            .flat_map(|statement| constant(&source_links, &type_links, statement))
             .sorted()
             .collect();
 
@@ -326,15 +381,6 @@ pub fn generate_html(
         content: Content::Text(std::include_str!("../templates/docs-js/lunr.min.js").to_string()),
     });
 
-    files.push(OutputFile {
-        path: PathBuf::from("search-data.js"),
-        content: Content::Text(format!(
-            "window.Gleam.initSearch({});",
-            serde_to_string(&escape_html_contents(search_indexes))
-                .expect("search index serialization")
-        )),
-    });
-
     files.push(OutputFile {
         path: PathBuf::from("js/index.js"),
         content: Content::Text(std::include_str!("../templates/docs-js/index.js").to_string()),
@@ -416,7 +462,81 @@ pub fn generate_html(
         ),
     });
 
-    files
+    PackageDocumentation {
+        name: config.name.clone(),
+        version: config.version.to_string(),
+        files,
+        search_indexes,
+    }
+}
+
+/// Render the search data for the documentation of the named package,
+/// covering all of the given packages. Results from the other packages link
+/// to the sibling directory named after their package.
+pub fn search_data(package: &str, packages: &[&PackageDocumentation]) -> OutputFile {
+    let search_indexes = packages
+        .iter()
+        .flat_map(|documentation| {
+            let prefix = if documentation.name == package {
+                "".into()
+            } else {
+                format!("../{}/", documentation.name)
+            };
+            documentation
+                .search_indexes
+                .iter()
+                .map(move |index| SearchIndex {
+                    url: format!("{prefix}{}", index.url),
+                    ..index.clone()
+                })
+        })
+        .collect();
+
+    OutputFile {
+        path: PathBuf::from("search-data.js"),
+        content: Content::Text(format!(
+            "window.Gleam.initSearch({});",
+            serde_to_string(&escape_html_contents(search_indexes))
+                .expect("search index serialization")
+        )),
+    }
+}
+
+/// Render an index page for a site documenting several packages, each in a
+/// directory named after the package. The first package is listed first and
+/// provides the stylesheet.
+pub fn packages_index(packages: &[&PackageDocumentation]) -> OutputFile {
+    let links: Vec<_> = packages
+        .iter()
+        .map(|documentation| Link {
+            name: format!("{} v{}", documentation.name, documentation.version),
+            path: documentation.name.to_string(),
+        })
+        .collect();
+    let template = PackagesTemplate {
+        gleam_version: VERSION,
+        page_title: "Packages",
+        stylesheet_package: packages
+            .first()
+            .map(|documentation| documentation.name.as_str())
+            .unwrap_or_default(),
+        packages: &links,
+    };
+
+    OutputFile {
+        path: PathBuf::from("index.html"),
+        content: Content::Text(template.render().expect("Packages template rendering")),
+    }
+}
+
+fn modules_to_document<'a>(
+    config: &'a PackageConfig,
+    analysed: &'a [Module],
+) -> impl Iterator<Item = &'a Module> + Clone {
+    analysed
+        .iter()
+        .filter(|module| !module.is_test())
+        .filter(|module| !config.is_internal_module(&module.name))
 }
 
 fn page_unnest(path: &str) -> String {
@@ -448,6 +568,97 @@ fn page_unnest_test() {
     assert_eq!(page_unnest("gleam/string/inspect"), "../..");
 }
 
+#[cfg(test)]
+fn named_type(module: &str, name: &str) -> Arc<crate::type_::Type> {
+    Arc::new(crate::type_::Type::App {
+        public: true,
+        module: module.into(),
+        name: name.into(),
+        args: vec![],
+    })
+}
+
+#[test]
+fn type_linker_links_types_from_other_packages() {
+    let mut documented_modules = DocumentedModules::default();
+    let _ = documented_modules
+        .packages
+        .insert("wibble/option".into(), "wibble".into());
+    let _ = documented_modules
+        .packages
+        .insert("wobble".into(), "wobble".into());
+    let linker = TypeLinker {
+        documented_modules: &documented_modules,
+        package: "wobble",
+        unnest: "..",
+    };
+    let option = named_type("wibble/option", "Option");
+    let wobble = named_type("wobble", "Wobble");
+    let function = Arc::new(crate::type_::Type::Fn {
+        args: vec![option],
+        retrn: wobble,
+    });
+
+    assert_eq!(
+        linker.link(
+            "pub fn go(f: fn(Option) -> Wobble) -> \"Option\\\"Option\"",
+            [&function]
+        ),
+        "pub fn go(f: fn(<a href=\"../../wibble/wibble/option.html#Option\">Option</a>) \
+-&gt; Wobble) -&gt; &quot;Option\\&quot;Option&quot;"
+    );
+}
+
+#[test]
+fn type_linker_does_not_link_ambiguous_names() {
+    let mut documented_modules = DocumentedModules::default();
+    let _ = documented_modules
+        .packages
+        .insert("wibble".into(), "wibble".into());
+    let linker = TypeLinker {
+        documented_modules: &documented_modules,
+        package: "wobble",
+        unnest: ".",
+    };
+    let wibble = named_type("wibble", "Thing");
+    let wobble = named_type("wobble", "Thing");
+
+    assert_eq!(
+        linker.link("Thing(Thing)", [&wibble, &wobble]),
+        "Thing(Thing)"
+    );
+}
+
+#[test]
+fn search_data_links_to_other_packages() {
+    let index = |doc: &str, url: &str| SearchIndex {
+        doc: doc.into(),
+        title: doc.into(),
+        content: "".into(),
+        url: url.into(),
+    };
+    let wibble = PackageDocumentation {
+        name: "wibble".into(),
+        version: "1.0.0".into(),
+        files: vec![],
+        search_indexes: vec![index("wibble", "wibble.html")],
+    };
+    let wobble = PackageDocumentation {
+        name: "wobble".into(),
+        version: "1.0.0".into(),
+        files: vec![],
+        search_indexes: vec![index("wobble/woo", "wobble/woo.html#woo")],
+    };
+
+    assert_eq!(
+        search_data("wibble", &[&wibble, &wobble]).content,
+        Content::Text(
+            r#"window.Gleam.initSearch([{"doc":"wibble","title":"wibble","content":"","url":"wibble.html"},{"doc":"wobble/woo","title":"wobble/woo","content":"","url":"../wobble/wobble/woo.html#woo"}]);"#
+                .into()
+        )
+    );
+}
+
 fn escape_html_content(it: String) -> String {
     it.replace('&', "&amp;")
         .replace('<', "&lt;")
@@ -474,6 +685,7 @@ fn import_synonyms(parent: &str, child: &str) -> String {
 
 fn function<'a>(
     source_links: &SourceLinker,
+    type_links: &TypeLinker<'_>,
     statement: &'a TypedModuleStatement,
 ) -> Option<DocsFunction<'a>> {
     let mut formatter = format::Formatter::new();
@@ -484,16 +696,27 @@ fn function<'a>(
             name,
             documentation: doc,
             return_: retrn,
+            return_type,
             arguments: args,
             location,
+            deprecation,
             ..
-        }) => Some(DocsFunction {
-            name,
-            documentation: markdown_documentation(doc),
-            text_documentation: text_documentation(doc),
-            signature: print(formatter.external_fn_signature(true, name, args, retrn)),
-            source_url: source_links.url(location),
-        }),
+        }) => {
+            let signature = print(formatter.external_fn_signature(true, name, args, retrn));
+            let types = args
+                .iter()
+                .map(|arg| &arg.type_)
+                .chain(std::iter::once(return_type));
+            Some(DocsFunction {
+                name,
+                documentation: markdown_documentation(doc),
+                text_documentation: text_documentation(doc),
+                deprecation_message: deprecation.message(),
+                signature_html: type_links.link(&signature, types),
+                signature,
+                source_url: source_links.url(location),
+            })
+        }
 
         ModuleStatement::Function(Function {
             public: true,
@@ -502,14 +725,24 @@ fn function<'a>(
             arguments: args,
             return_type: ret,
             location,
+            deprecation,
             ..
-        }) => Some(DocsFunction {
-            name,
-            documentation: markdown_documentation(doc),
-            text_documentation: text_documentation(doc),
-            signature: print(formatter.docs_fn_signature(true, name, args, ret.clone())),
-            source_url: source_links.url(location),
-        }),
+        }) => {
+            let signature = print(formatter.docs_fn_signature(true, name, args, ret.clone()));
+            let types = args
+                .iter()
+                .map(|arg| &arg.type_)
+                .chain(std::iter::once(ret));
+            Some(DocsFunction {
+                name,
+                documentation: markdown_documentation(doc),
+                text_documentation: text_documentation(doc),
+                deprecation_message: deprecation.message(),
+                signature_html: type_links.link(&signature, types),
+                signature,
+                source_url: source_links.url(location),
+            })
+        }
 
         _ => None,
     }
@@ -536,7 +769,11 @@ fn render_markdown(text: &str) -> String {
     s
 }
 
-fn type_<'a>(source_links: &SourceLinker, statement: &'a TypedModuleStatement) -> Option<Type<'a>> {
+fn type_<'a>(
+    source_links: &SourceLinker,
+    type_links: &TypeLinker<'_>,
+    statement: &'a TypedModuleStatement,
+) -> Option<Type<'a>> {
     let mut formatter = format::Formatter::new();
 
     match statement {
@@ -546,14 +783,19 @@ fn type_<'a>(source_links: &SourceLinker, statement: &'a TypedModuleStatement) -
             documentation: doc,
             arguments: args,
             location,
-        }) => Some(Type {
-            name,
-            definition: print(formatter.external_type(true, name, args)),
-            documentation: markdown_documentation(doc),
-            text_documentation: text_documentation(doc),
-            constructors: vec![],
-            source_url: source_links.url(location),
-        }),
+        }) => {
+            let definition = print(formatter.external_type(true, name, args));
+            Some(Type {
+                name,
+                definition_html: type_links.link(&definition, []),
+                definition,
+                documentation: markdown_documentation(doc),
+                text_documentation: text_documentation(doc),
+                deprecation_message: None,
+                constructors: vec![],
+                source_url: source_links.url(location),
+            })
+        }
 
         ModuleStatement::CustomType(CustomType {
             public: true,
@@ -563,35 +805,51 @@ fn type_<'a>(source_links: &SourceLinker, statement: &'a TypedModuleStatement) -
             documentation: doc,
             constructors: cs,
             location,
+            deprecation,
             ..
-        }) => Some(Type {
-            name,
+        }) => {
             // TODO: Don't use the same printer for docs as for the formatter.
             // We are not interested in showing the exact implementation in the
             // documentation and we could add things like colours, etc.
-            definition: print(formatter.custom_type(true, false, name, parameters, cs, location)),
-            documentation: markdown_documentation(doc),
-            text_documentation: text_documentation(doc),
-            constructors: cs
+            let definition =
+                print(formatter.custom_type(true, false, name, parameters, cs, location));
+            let types = cs
                 .iter()
-                .map(|constructor| TypeConstructor {
-                    definition: print(formatter.record_constructor(constructor)),
-                    documentation: markdown_documentation(&constructor.documentation),
-                    text_documentation: text_documentation(&constructor.documentation),
-                    arguments: constructor
-                        .arguments
-                        .iter()
-                        .filter_map(|arg| arg.label.as_ref().map(|label| (arg, label)))
-                        .map(|(argument, label)| TypeConstructorArg {
-                            name: label.trim_end().to_string(),
-                            doc: markdown_documentation(&argument.doc),
-                        })
-                        .filter(|arg| !arg.doc.is_empty())
-                        .collect(),
-                })
-                .collect(),
-            source_url: source_links.url(location),
-        }),
+                .flat_map(|constructor| &constructor.arguments)
+                .map(|argument| &argument.type_);
+            Some(Type {
+                name,
+                definition_html: type_links.link(&definition, types),
+                definition,
+                documentation: markdown_documentation(doc),
+                text_documentation: text_documentation(doc),
+                deprecation_message: deprecation.message(),
+                constructors: cs
+                    .iter()
+                    .map(|constructor| {
+                        let definition = print(formatter.record_constructor(constructor));
+                        let types = constructor.arguments.iter().map(|argument| &argument.type_);
+                        TypeConstructor {
+                            definition_html: type_links.link(&definition, types),
+                            definition,
+                            documentation: markdown_documentation(&constructor.documentation),
+                            text_documentation: text_documentation(&constructor.documentation),
+                            arguments: constructor
+                                .arguments
+                                .iter()
+                                .filter_map(|arg| arg.label.as_ref().map(|label| (arg, label)))
+                                .map(|(argument, label)| TypeConstructorArg {
+                                    name: label.trim_end().to_string(),
+                                    doc: markdown_documentation(&argument.doc),
+                                })
+                                .filter(|arg| !arg.doc.is_empty())
+                                .collect(),
+                        }
+                    })
+                    .collect(),
+                source_url: source_links.url(location),
+            })
+        }
 
         ModuleStatement::CustomType(CustomType {
             public: true,
@@ -600,32 +858,45 @@ fn type_<'a>(source_links: &SourceLinker, statement: &'a TypedModuleStatement) -
             parameters,
             documentation: doc,
             location,
+            deprecation,
             ..
-        }) => Some(Type {
-            name,
-            definition: print(formatter.docs_opaque_custom_type(true, name, parameters, location)),
-            documentation: markdown_documentation(doc),
-            text_documentation: text_documentation(doc),
-            constructors: vec![],
-            source_url: source_links.url(location),
-        }),
+        }) => {
+            let definition =
+                print(formatter.docs_opaque_custom_type(true, name, parameters, location));
+            Some(Type {
+                name,
+                definition_html: type_links.link(&definition, []),
+                definition,
+                documentation: markdown_documentation(doc),
+                text_documentation: text_documentation(doc),
+                deprecation_message: deprecation.message(),
+                constructors: vec![],
+                source_url: source_links.url(location),
+            })
+        }
 
         ModuleStatement::TypeAlias(TypeAlias {
             public: true,
             alias: name,
             type_ast: typ,
+            type_,
             documentation: doc,
             parameters: args,
             location,
-            ..
-        }) => Some(Type {
-            name,
-            definition: print(formatter.type_alias(true, name, args, typ)),
-            documentation: markdown_documentation(doc),
-            text_documentation: text_documentation(doc),
-            constructors: vec![],
-            source_url: source_links.url(location),
-        }),
+            deprecation,
+        }) => {
+            let definition = print(formatter.type_alias(true, name, args, typ));
+            Some(Type {
+                name,
+                definition_html: type_links.link(&definition, [type_]),
+                definition,
+                documentation: markdown_documentation(doc),
+                text_documentation: text_documentation(doc),
+                deprecation_message: deprecation.message(),
+                constructors: vec![],
+                source_url: source_links.url(location),
+            })
+        }
 
         _ => None,
     }
@@ -633,6 +904,7 @@ fn type_<'a>(source_links: &SourceLinker, statement: &'a TypedModuleStatement) -
 
 fn constant<'a>(
     source_links: &SourceLinker,
+    type_links: &TypeLinker<'_>,
     statement: &'a TypedModuleStatement,
 ) -> Option<Constant<'a>> {
     let mut formatter = format::Formatter::new();
@@ -642,15 +914,22 @@ fn constant<'a>(
             documentation: doc,
             name,
             value,
+            type_,
             location,
+            deprecation,
             ..
-        }) => Some(Constant {
-            name,
-            definition: print(formatter.docs_const_expr(true, name, value)),
-            documentation: markdown_documentation(doc),
-            text_documentation: text_documentation(doc),
-            source_url: source_links.url(location),
-        }),
+        }) => {
+            let definition = print(formatter.docs_const_expr(true, name, value));
+            Some(Constant {
+                name,
+                definition_html: type_links.link(&definition, [type_]),
+                definition,
+                documentation: markdown_documentation(doc),
+                text_documentation: text_documentation(doc),
+                deprecation_message: deprecation.message(),
+                source_url: source_links.url(location),
+            })
+        }
 
         _ => None,
     }
@@ -660,6 +939,110 @@ fn print(doc: pretty::Document<'_>) -> String {
     doc.to_pretty_string(MAX_COLUMNS)
 }
 
+/// Renders signatures and definitions as HTML, linking the names of types
+/// defined in other documented packages to their documentation.
+struct TypeLinker<'a> {
+    documented_modules: &'a DocumentedModules,
+    package: &'a str,
+    unnest: &'a str,
+}
+
+impl TypeLinker<'_> {
+    fn link<'a>(
+        &self,
+        text: &str,
+        types: impl IntoIterator<Item = &'a Arc<crate::type_::Type>>,
+    ) -> String {
+        let mut named_types = vec![];
+        for type_ in types {
+            collect_named_types(type_, &mut named_types);
+        }
+
+        // Types are printed by name, so a name can only be linked if all the
+        // types with that name come from the same module.
+        let mut links: HashMap<SmolStr, Option<String>> = HashMap::new();
+        for (module, name) in named_types {
+            let href = match self.documented_modules.packages.get(&module) {
+                Some(package) if package != self.package => {
+                    Some(format!("{}/../{package}/{module}.html#{name}", self.unnest))
+                }
+                _ => None,
+            };
+            let _ = links
+                .entry(name)
+                .and_modify(|existing| {
+                    if *existing != href {
+                        *existing = None
+                    }
+                })
+                .or_insert(href);
+        }
+
+        let mut html = String::with_capacity(text.len());
+        let mut plain = String::new();
+        let mut in_string = false;
+        let mut escaped = false;
+        let mut chars = text.chars().peekable();
+        while let Some(char) = chars.next() {
+            if in_string || !is_name_char(char) {
+                match char {
+                    _ if escaped => escaped = false,
+                    '\\' if in_string => escaped = true,
+                    '"' => in_string = !in_string,
+                    _ => (),
+                }
+                plain.push(char);
+                continue;
+            }
+
+            let mut name = String::from(char);
+            while let Some(char) = chars.next_if(|char| is_name_char(*char)) {
+                name.push(char);
+            }
+            match links.get(name.as_str()) {
+                Some(Some(href)) => {
+                    html.push_str(&escape_html(&plain));
+                    plain.clear();
+                    html.push_str(&format!("<a href=\"{}\">{name}</a>", escape_html(href)));
+                }
+                _ => plain.push_str(&name),
+            }
+        }
+        html.push_str(&escape_html(&plain));
+        html
+    }
+}
+
+fn is_name_char(char: char) -> bool {
+    char == '_' || char.is_alphanumeric()
+}
+
+fn collect_named_types(type_: &crate::type_::Type, names: &mut Vec<(SmolStr, SmolStr)>) {
+    match type_ {
+        crate::type_::Type::App {
+            module, name, args, ..
+        } => {
+            names.push((module.clone(), name.clone()));
+            args.iter().for_each(|arg| collect_named_types(arg, names));
+        }
+        crate::type_::Type::Fn { args, retrn } => {
+            args.iter().for_each(|arg| collect_named_types(arg, names));
+            collect_named_types(retrn, names);
+        }
+        crate::type_::Type::Var { type_ } => match &*type_.borrow() {
+            TypeVar::Link { type_ } => collect_named_types(type_, names),
+            TypeVar::Unbound { .. } | TypeVar::Generic { .. } => (),
+        },
+        crate::type_::Type::Tuple { elems } => elems
+            .iter()
+            .for_each(|elem| collect_named_types(elem, names)),
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    askama::MarkupDisplay::new_unsafe(text, askama::Html).to_string()
+}
+
 #[derive(PartialEq, Eq, PartialOrd, Ord, Clone)]
 struct Link {
     name: String,
@@ -670,14 +1053,17 @@ struct Link {
 struct DocsFunction<'a> {
     name: &'a str,
     signature: String,
+    signature_html: String,
     documentation: String,
     text_documentation: String,
+    deprecation_message: Option<&'a str>,
     source_url: String,
 }
 
 #[derive(PartialEq, Eq, PartialOrd, Ord, Debug)]
 struct TypeConstructor {
     definition: String,
+    definition_html: String,
     documentation: String,
     text_documentation: String,
     arguments: Vec<TypeConstructorArg>,
@@ -693,9 +1079,11 @@ struct TypeConstructorArg {
 struct Type<'a> {
     name: &'a str,
     definition: String,
+    definition_html: String,
     documentation: String,
     constructors: Vec<TypeConstructor>,
     text_documentation: String,
+    deprecation_message: Option<&'a str>,
     source_url: String,
 }
 
@@ -703,8 +1091,10 @@ struct Type<'a> {
 struct Constant<'a> {
     name: &'a str,
     definition: String,
+    definition_html: String,
     documentation: String,
     text_documentation: String,
+    deprecation_message: Option<&'a str>,
     source_url: String,
 }
 
@@ -744,7 +1134,16 @@ struct ModuleTemplate<'a> {
     rendering_timestamp: &'a str,
 }
 
-#[derive(Serialize, PartialEq, Eq, PartialOrd, Ord, Clone)]
+#[derive(Template)]
+#[template(path = "documentation_packages.html")]
+struct PackagesTemplate<'a> {
+    gleam_version: &'a str,
+    page_title: &'a str,
+    stylesheet_package: &'a str,
+    packages: &'a [Link],
+}
+
+#[derive(Serialize, PartialEq, Eq, PartialOrd, Ord, Clone, Debug)]
 struct SearchIndex {
     doc: String,
     title: String,
@@ -180,7 +180,7 @@ pub fn tail(list) { case list { [x, ..xs] -> xs z -> list } }
 
 #[test]
 fn integration_test5() {
-    assert_erl!("pub fn tail(list) { case list { [x, ..] -> x } }");
+    assert_erl!("pub fn tail(list) { case list { [x, ..] -> x [] -> panic } }");
 }
 
 #[test]
@@ -532,6 +532,7 @@ fn guard_variable_rewriting() {
       let a = a
       a
     }
+    a -> a
   }
 }
 "
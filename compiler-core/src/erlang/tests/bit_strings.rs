@@ -7,8 +7,8 @@ fn bit_strings() {
   let a = 1
   let simple = <<1, a>>
   let complex = <<4:int-big, 5.0:little-float, 6:native-int>>
-  let <<7:2, 8:size(3), b:binary-size(4)>> = <<1>>
-  let <<c:8-unit(1), d:binary-size(2)-unit(2)>> = <<1>>
+  let assert <<7:2, 8:size(3), b:binary-size(4)>> = <<1>>
+  let assert <<c:8-unit(1), d:binary-size(2)-unit(2)>> = <<1>>
 
   simple
 }
@@ -22,7 +22,7 @@ fn bit_strings_float() {
         r#"pub fn main() {
   let b = 16
   let floats = <<1.0:16-float, 5.0:float-32, 6.0:float-64-little, 1.0:float-size(b)>>
-  let <<1.0:16-float, 5.0:float-32, 6.0:float-64-little, 1.0:float-size(b)>> = floats 
+  let assert <<1.0:16-float, 5.0:float-32, 6.0:float-64-little, 1.0:float-size(b)>> = floats 
 }"#
     );
 }
@@ -46,7 +46,7 @@ fn bit_strings2() {
     assert_erl!(
         r#"pub fn main() {
   let a = 1
-  let <<b, 1>> = <<1, a>>
+  let assert <<b, 1>> = <<1, a>>
   b
 }
 "#
@@ -58,7 +58,7 @@ fn bit_strings3() {
     assert_erl!(
         r#"pub fn main() {
   let a = <<"test":utf8>>
-  let <<b:utf8_codepoint, "st":utf8>> = a
+  let assert <<b:utf8_codepoint, "st":utf8>> = a
   b
 }
 "#
@@ -111,8 +111,10 @@ pub fn bitstring_discard(x) -> Bool {
 fn bit_string_declare_and_use_var() {
     assert_erl!(
         r#"pub fn go(x) {
-  let <<name_size:8, name:binary-size(name_size)>> = x
-  name
+  case x {
+    <<name_size:8, name:binary-size(name_size)>> -> name
+    _ -> x
+  }
 }"#
     );
 }
@@ -31,8 +31,8 @@ fn numbers_with_underscores2() {
     assert_erl!(
         r#"
 pub fn main() {
-  let 100_000 = 1
-  let 100_000.00101 = 1.
+  let assert 100_000 = 1
+  let assert 100_000.00101 = 1.
   1
 }
 "#
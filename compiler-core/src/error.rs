@@ -18,6 +18,7 @@ use std::fmt::Debug;
 use std::path::{Path, PathBuf};
 use termcolor::Buffer;
 use thiserror::Error;
+use vec1::{vec1, Vec1};
 
 pub type Name = SmolStr;
 
@@ -38,20 +39,30 @@ pub struct UnknownImportDetails {
     pub modules: Vec<SmolStr>,
 }
 
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct InsufficientVersionBumpDetails {
+    pub package: String,
+    pub previous_version: String,
+    pub version: String,
+    pub minimum_version: String,
+    pub bump: String,
+    pub changes: Vec<String>,
+}
+
 #[derive(Debug, Eq, PartialEq, Error, Clone)]
 pub enum Error {
     #[error("failed to parse Gleam source code")]
     Parse {
         path: PathBuf,
         src: SmolStr,
-        error: crate::parse::error::ParseError,
+        errors: Vec1<crate::parse::error::ParseError>,
     },
 
     #[error("type checking failed")]
     Type {
         path: PathBuf,
         src: SmolStr,
-        error: crate::type_::Error,
+        errors: Vec1<crate::type_::Error>,
     },
 
     #[error("unknown import {import}")]
@@ -186,6 +197,23 @@ pub enum Error {
     #[error("The package {0} is listed in dependencies and dev-dependencies")]
     DuplicateDependency(String),
 
+    #[error("Expected package {expected} at path {path} but found {found} instead")]
+    WrongDependencyProvided {
+        path: PathBuf,
+        expected: String,
+        found: String,
+    },
+
+    #[error("The package {package} is provided by both {source_1} and {source_2}")]
+    ProvidedDependencyConflict {
+        package: String,
+        source_1: String,
+        source_2: String,
+    },
+
+    #[error("The package {package} cannot be published as it has path or git dependencies")]
+    PublishNonHexDependencies { package: String },
+
     #[error("The package was missing required fields for publishing")]
     MissingHexPublishFields {
         description_missing: bool,
@@ -197,6 +225,41 @@ pub enum Error {
         package: String,
         build_tools: Vec<String>,
     },
+
+    #[error("There is no error or warning with the code {code}")]
+    UnknownErrorCode { code: String },
+
+    #[error("The documentation server could not listen on {address}")]
+    UnableToStartDocsServer { address: String, error: String },
+
+    #[error("Unknown Hex repository {name}")]
+    UnknownHexRepository { name: String },
+
+    #[error("Invalid Hex repository config for {name}")]
+    InvalidHexRepository { name: String, reason: String },
+
+    #[error("Package {package} is not available offline")]
+    PackageUnavailableOffline {
+        package: String,
+        version: Option<String>,
+    },
+
+    #[error("Package {name} is not a dependency")]
+    UnknownDependency { name: String },
+
+    #[error("Version {} is too low for the changes to the package API", details.version)]
+    InsufficientVersionBump {
+        // Boxed to prevent this variant from being overly large
+        details: Box<InsufficientVersionBumpDetails>,
+    },
+    #[error("The tarball of {package} {version} does not match its checksum")]
+    TarballChecksumMismatch {
+        package: String,
+        version: String,
+        path: PathBuf,
+        expected: String,
+        found: String,
+    },
 }
 
 impl Error {
@@ -316,6 +379,7 @@ pub enum FileIoAction {
     UpdatePermissions,
     FindParent,
     ReadMetadata,
+    Watch,
 }
 
 impl FileIoAction {
@@ -334,6 +398,7 @@ impl FileIoAction {
             FileIoAction::Canonicalise => "canonicalise",
             FileIoAction::UpdatePermissions => "update permissions of",
             FileIoAction::ReadMetadata => "read metadata of",
+            FileIoAction::Watch => "watch",
         }
     }
 }
@@ -354,27 +419,101 @@ impl FileKind {
 }
 
 fn did_you_mean(name: &str, options: &[SmolStr]) -> Option<String> {
-    // Find best match
+    closest_match(name, options).map(|option| format!("Did you mean `{option}`?"))
+}
+
+fn parse_error_diagnostic(
+    path: &Path,
+    src: &SmolStr,
+    error: &crate::parse::error::ParseError,
+) -> Diagnostic {
+    let (label, extra) = error.details();
+    let text = extra.join("\n");
+
+    let adjusted_location = if error.error == ParseErrorType::UnexpectedEof {
+        crate::ast::SrcSpan {
+            start: (src.len() - 1) as u32,
+            end: (src.len() - 1) as u32,
+        }
+    } else {
+        error.location
+    };
+
+    Diagnostic {
+        title: "Syntax error".into(),
+        text,
+        hint: error.hint(),
+        level: Level::Error,
+        location: Some(Location {
+            label: Label {
+                text: Some(label.to_string()),
+                span: adjusted_location,
+            },
+            path: path.to_path_buf(),
+            src: src.clone(),
+            extra_labels: vec![],
+        }),
+        code: Some(error.error.code()),
+    }
+}
+
+/// The option most similar to the given name, as suggested in error messages.
+pub(crate) fn closest_match<'a>(name: &str, options: &'a [SmolStr]) -> Option<&'a SmolStr> {
     options
         .iter()
         .filter(|&option| option != crate::ast::CAPTURE_VARIABLE)
         .sorted()
         .min_by_key(|option| strsim::levenshtein(option, name))
-        .map(|option| format!("Did you mean `{option}`?"))
 }
 
 impl Error {
     pub fn pretty_string(&self) -> String {
-        self.to_diagnostic().pretty_string()
+        self.to_diagnostics()
+            .iter()
+            .map(|diagnostic| diagnostic.pretty_string())
+            .join("\n")
     }
 
     pub fn pretty(&self, buffer: &mut Buffer) {
-        self.to_diagnostic().write(buffer)
+        use std::io::Write;
+        for (index, diagnostic) in self.to_diagnostics().iter().enumerate() {
+            if index > 0 {
+                writeln!(buffer).expect("write new line");
+            }
+            diagnostic.write(buffer)
+        }
     }
 
+    /// A diagnostic for each of the problems this error is made up of. Most
+    /// errors are a single problem, but parsing with error recovery and type
+    /// checking can find many.
+    ///
+    pub fn to_diagnostics(&self) -> Vec<Diagnostic> {
+        match self {
+            Error::Parse { path, src, errors } => errors
+                .iter()
+                .map(|error| parse_error_diagnostic(path, src, error))
+                .collect(),
+            Error::Type { path, src, errors } => errors
+                .iter()
+                .map(|error| {
+                    Error::Type {
+                        path: path.clone(),
+                        src: src.clone(),
+                        errors: vec1![error.clone()],
+                    }
+                    .to_diagnostic()
+                })
+                .collect(),
+            _ => vec![self.to_diagnostic()],
+        }
+    }
+
+    /// The diagnostic for the first problem this error is made up of.
+    ///
     pub fn to_diagnostic(&self) -> Diagnostic {
         use crate::type_::Error as TypeError;
-        match self {
+        let diagnostic = match self {
             Error::MetadataDecodeError { error } => {
                 let mut text = "A problem was encountered when decoding the metadata for one \
 of the Gleam dependency modules."
@@ -390,6 +529,7 @@ of the Gleam dependency modules."
                     level: Level::Error,
                     location: None,
                     hint: None,
+                    code: None,
                 }
             }
 
@@ -424,6 +564,7 @@ This prefix is intended for official Gleam packages only.",
                     hint: None,
                     level: Level::Error,
                     location: None,
+                    code: None,
                 }
             }
 
@@ -437,6 +578,7 @@ forward slash and must not end with a slash."
                 level: Level::Error,
                 location: None,
                 hint: None,
+                code: None,
             },
 
             Error::ModuleDoesNotExist { module } => Diagnostic {
@@ -448,6 +590,7 @@ forward slash and must not end with a slash."
                     format!("Try creating the file `src/{}.gleam`.",
                     module)
                 ),
+                code: None,
             },
 
             Error::ModuleDoesNotHaveMainFunction { module } => Diagnostic {
@@ -456,6 +599,7 @@ forward slash and must not end with a slash."
                 level: Level::Error,
                 location: None,
                 hint: Some(format!("Add a function with the singature `pub fn main() {{}}` to `src/{module}.gleam`")),
+                code: None,
             },
 
             Error::MainFunctionHasWrongArity { module, arity } => Diagnostic {
@@ -464,6 +608,7 @@ forward slash and must not end with a slash."
                 level: Level::Error,
                 location: None,
                 hint: Some("Change the function signature of main to `pub fn main() {}`".into()),
+                code: None,
             },
 
             Error::ProjectRootAlreadyExist { path } => Diagnostic {
@@ -472,6 +617,7 @@ forward slash and must not end with a slash."
                 level: Level::Error,
                 hint: None,
                 location: None,
+                code: None,
             },
 
             Error::UnableToFindProjectRoot { path } => Diagnostic {
@@ -480,6 +626,7 @@ forward slash and must not end with a slash."
                 hint: None,
                 level: Level::Error,
                 location: None,
+                code: None,
             },
 
             Error::VersionDoesNotMatch { toml_ver, app_ver } => {
@@ -493,6 +640,7 @@ your app.src file \"{app_ver}\""
                     text,
                     level: Level::Error,
                     location: None,
+                    code: None,
                 }
             }
 
@@ -531,6 +679,7 @@ You can also install rebar3 via homebrew using \"brew install rebar3\"",
                     hint: None,
                     level: Level::Error,
                     location: None,
+                    code: None,
                 }
             }
 
@@ -546,6 +695,7 @@ You can also install rebar3 via homebrew using \"brew install rebar3\"",
                     hint: None,
                     level: Level::Error,
                     location: None,
+                    code: None,
                 }
             }
 
@@ -568,6 +718,7 @@ The error from the shell command library was:
                     hint: None,
                     level: Level::Error,
                     location: None,
+                    code: None,
                 }
             }
 
@@ -585,6 +736,7 @@ This was error from the gzip library:
                     hint: None,
                     level: Level::Error,
                     location: None,
+                    code: None,
                 }
             }
 
@@ -605,6 +757,7 @@ This was error from the tar library:
                     hint: None,
                     level: Level::Error,
                     location: None,
+                    code: None,
                 }
             }
 
@@ -622,6 +775,7 @@ This was error from the tar library:
                     hint: None,
                     level: Level::Error,
                     location: None,
+                    code: None,
                 }
             }
 
@@ -639,6 +793,7 @@ This was error from the tar library:
                     hint: None,
                     level: Level::Error,
                     location: None,
+                    code: None,
                 }
             }
 
@@ -656,6 +811,7 @@ This was error from the Hex client library:
                     hint: None,
                     level: Level::Error,
                     location: None,
+                    code: None,
                 }
             }
 
@@ -680,6 +836,7 @@ Second: {}",
                     hint: None,
                     level: Level::Error,
                     location: None,
+                    code: None,
                 }
             }
 
@@ -689,6 +846,7 @@ Second: {}",
                 hint: None,
                 level: Level::Error,
                 location: None,
+                code: None,
             },
 
             Error::FileIo {
@@ -719,6 +877,7 @@ Second: {}",
                     hint: None,
                     level: Level::Error,
                     location: None,
+                    code: None,
                 }
             }
 
@@ -734,10 +893,11 @@ Second: {}",
                     hint: None,
                     level: Level::Error,
                     location: None,
+                    code: None,
                 }
             }
 
-            Error::Type { path, src, error } => match error {
+            Error::Type { path, src, errors } => match errors.first() {
                 TypeError::SrcImportingTest {
                     location,
                     src_module,
@@ -764,6 +924,7 @@ Test modules are not included in production builds so test modules cannot import
                             src: src.clone(),
                             extra_labels: vec![],
                         }),
+                        code: None,
                     }
                 }
 
@@ -820,6 +981,7 @@ constructor accepts."
                             src: src.clone(),
                             extra_labels,
                         }),
+                        code: None,
                     }
                 }
 
@@ -842,6 +1004,7 @@ not expect any. Please remove the label `{label}`."
                             src: src.clone(),
                             extra_labels: vec![],
                         }),
+                        code: None,
                     }
                 }
 
@@ -865,6 +1028,7 @@ also be labelled."
                             src: src.clone(),
                             extra_labels: vec![],
                         }),
+                        code: None,
                     }
                 }
 
@@ -894,6 +1058,7 @@ Names in a Gleam module must be unique so one will need to be renamed."
                                 span: *previous_location,
                             }],
                         }),
+                        code: None,
                     }
                 }
 
@@ -929,6 +1094,7 @@ Names in a Gleam module must be unique so one will need to be renamed."
                                 span: *first_location,
                             }],
                         }),
+                        code: None,
                     }
                 }
 
@@ -959,6 +1125,7 @@ Names in a Gleam module must be unique so one will need to be renamed."
                                 span: *previous_location,
                             }],
                         }),
+                        code: None,
                     }
                 }
 
@@ -979,6 +1146,7 @@ Names in a Gleam module must be unique so one will need to be renamed."
                             src: src.clone(),
                             extra_labels: vec![],
                         }),
+                        code: None,
                     }
                 }
 
@@ -999,19 +1167,18 @@ Names in a Gleam module must be unique so one will need to be renamed."
                             src: src.clone(),
                             extra_labels: vec![],
                         }),
+                        code: None,
                     }
                 }
 
                 TypeError::RecursiveType { location } => {
                     let text = "I don't know how to work out what type this value has. It seems
-to be defined in terms of itself.
-
-Hint: Add some type annotations and try again."
+to be defined in terms of itself."
                         .into();
                     Diagnostic {
                         title: "Recursive type".into(),
                         text,
-                        hint: None,
+                        hint: Some("Add some type annotations and try again.".into()),
                         level: Level::Error,
                         location: Some(Location {
                             label: Label {
@@ -1022,6 +1189,7 @@ Hint: Add some type annotations and try again."
                             src: src.clone(),
                             extra_labels: vec![],
                         }),
+                        code: None,
                     }
                 }
 
@@ -1045,6 +1213,7 @@ Hint: Add some type annotations and try again."
                             src: src.clone(),
                             extra_labels: vec![],
                         }),
+                        code: None,
                     }
                 }
 
@@ -1107,6 +1276,7 @@ to call a method on this value you may want to use the function syntax instead."
                             src: src.clone(),
                             extra_labels: vec![],
                         }),
+                        code: None,
                     }
                 }
 
@@ -1119,7 +1289,7 @@ to call a method on this value you may want to use the function syntax instead."
                 } => {
                     let mut printer = Printer::new();
                     printer.with_names(annotated_names.clone());
-                    let mut text = format!(
+                    let text = format!(
                         "The {op} operator expects arguments of this type:
 
 {expected}
@@ -1131,15 +1301,10 @@ But this argument has this type:
                         expected = printer.pretty_print(expected, 4),
                         given = printer.pretty_print(given, 4),
                     );
-                    if let Some(hint) = hint_alternative_operator(op, given) {
-                        text.push('\n');
-                        text.push_str("Hint: ");
-                        text.push_str(&hint);
-                    }
                     Diagnostic {
                         title: "Type mismatch".into(),
                         text,
-                        hint: None,
+                        hint: hint_alternative_operator(op, given),
                         level: Level::Error,
                         location: Some(Location {
                             label: Label {
@@ -1150,6 +1315,7 @@ But this argument has this type:
                             src: src.clone(),
                             extra_labels: vec![],
                         }),
+                        code: None,
                     }
                 }
 
@@ -1201,6 +1367,7 @@ But function expects:
                             src: src.clone(),
                             extra_labels: vec![],
                         }),
+                        code: None,
                     }
                 }
 
@@ -1240,6 +1407,7 @@ But function expects:
                             src: src.clone(),
                             extra_labels: vec![],
                         }),
+                        code: None,
                     }
                 }
 
@@ -1271,6 +1439,7 @@ number of arguments."
                             src: src.clone(),
                             extra_labels: vec![],
                         }),
+                        code: None,
                     }
                 }
 
@@ -1312,6 +1481,7 @@ number of arguments."
                             src: src.clone(),
                             extra_labels: vec![],
                         }),
+                        code: None,
                     }
                 }
 
@@ -1334,6 +1504,7 @@ assigned variables to all of them."
                             src: src.clone(),
                             extra_labels: vec![],
                         }),
+                        code: None,
                     }
                 }
 
@@ -1362,6 +1533,7 @@ constructing a new record with its values."
                             src: src.clone(),
                             extra_labels: vec![],
                         }),
+                        code: None,
                     }
                 }
 
@@ -1387,6 +1559,7 @@ constructing a new record with its values."
                             src: src.clone(),
                             extra_labels: vec![],
                         }),
+                        code: None,
                     }
                 }
 
@@ -1408,6 +1581,7 @@ constructing a new record with its values."
                         src: src.clone(),
                         extra_labels: vec![],
                     }),
+                    code: None,
                 },
 
                 TypeError::PrivateTypeLeak { location, leaked } => {
@@ -1440,6 +1614,7 @@ Private types can only be used within the module that defines them.",
                             src: src.clone(),
                             extra_labels: vec![],
                         }),
+                        code: None,
                     }
                 }
 
@@ -1461,6 +1636,7 @@ Private types can only be used within the module that defines them.",
                         src: src.clone(),
                         extra_labels: vec![],
                     }),
+                    code: None,
                 },
 
                 TypeError::UnknownModuleType {
@@ -1485,6 +1661,7 @@ Private types can only be used within the module that defines them.",
                             src: src.clone(),
                             extra_labels: vec![],
                         }),
+                        code: None,
                     }
                 }
 
@@ -1510,6 +1687,7 @@ Private types can only be used within the module that defines them.",
                             src: src.clone(),
                             extra_labels: vec![],
                         }),
+                        code: None,
                     }
                 }
 
@@ -1541,6 +1719,7 @@ Private types can only be used within the module that defines them.",
                             src: src.clone(),
                             extra_labels: vec![],
                         }),
+                        code: None,
                     }
                 }
 
@@ -1569,6 +1748,7 @@ Each clause must have a pattern for every subject value.",
                             src: src.clone(),
                             extra_labels: vec![],
                         }),
+                        code: None,
                     }
                 }
 
@@ -1591,6 +1771,7 @@ Each clause must have a pattern for every subject value.",
                             src: src.clone(),
                             extra_labels: vec![],
                         }),
+                        code: None,
                     }
                 }
 
@@ -1614,6 +1795,7 @@ This variable `{}` has not been previously defined.",
                             src: src.clone(),
                             extra_labels: vec![],
                         }),
+                        code: None,
                     }
                 }
 
@@ -1637,6 +1819,7 @@ as the initial pattern, but the `{}` variable is missing.",
                             src: src.clone(),
                             extra_labels: vec![],
                         }),
+                        code: None,
                     }
                 }
 
@@ -1663,6 +1846,7 @@ e.g. (x, y) if x == y -> ...",
                             src: src.clone(),
                             extra_labels: vec![],
                         }),
+                        code: None,
                     }
                 }
 
@@ -1682,6 +1866,7 @@ e.g. (x, y) if x == y -> ...",
                         src: src.clone(),
                         extra_labels: vec![],
                     }),
+                    code: None,
                 },
 
                 TypeError::OutOfBoundsTupleIndex {
@@ -1710,6 +1895,7 @@ tuple has {} elements so the highest valid index is {}.",
                             src: src.clone(),
                             extra_labels: vec![],
                         }),
+                        code: None,
                     }
                 }
 
@@ -1735,6 +1921,7 @@ tuple has {} elements so the highest valid index is {}.",
                             src: src.clone(),
                             extra_labels: vec![],
                         }),
+                        code: None,
                     }
                 }
 
@@ -1757,6 +1944,7 @@ we can continue."
                             src: src.clone(),
                             extra_labels: vec![],
                         }),
+                        code: None,
                     }
                 }
 
@@ -1779,90 +1967,86 @@ function and try again."
                             src: src.clone(),
                             extra_labels: vec![],
                         }),
+                        code: None,
                     }
                 }
 
                 TypeError::BitStringSegmentError { error, location } => {
-                    let (label, mut extra) = match error {
+                    let (label, hint) = match error {
                         bit_string::ErrorType::ConflictingTypeOptions { existing_type } => (
                             "This is an extra type specifier.",
-                            vec![format!("Hint: This segment already has the type {existing_type}.")],
+                            format!("This segment already has the type {existing_type}."),
                         ),
 
                         bit_string::ErrorType::ConflictingSignednessOptions {
                             existing_signed
                         } => (
                             "This is an extra signedness specifier.",
-                            vec![format!(
-                                "Hint: This segment already has a signedness of {existing_signed}."
-                            )],
+                            format!("This segment already has a signedness of {existing_signed}."),
                         ),
 
                         bit_string::ErrorType::ConflictingEndiannessOptions {
                             existing_endianness
                         } => (
                             "This is an extra endianness specifier.",
-                            vec![format!(
-                                "Hint: This segment already has an endianness of {existing_endianness}."
-                            )],
+                            format!("This segment already has an endianness of {existing_endianness}."),
                         ),
 
                         bit_string::ErrorType::ConflictingSizeOptions => (
                             "This is an extra size specifier.",
-                            vec!["Hint: This segment already has a size.".into()],
+                            "This segment already has a size.".into(),
                         ),
 
                         bit_string::ErrorType::ConflictingUnitOptions => (
                             "This is an extra unit specifier.",
-                            vec!["Hint: A BitString segment can have at most 1 unit.".into()],
+                            "A BitString segment can have at most 1 unit.".into(),
                         ),
 
                         bit_string::ErrorType::FloatWithSize => (
                             "Invalid float size.",
-                            vec!["Hint: floats have an exact size of 16/32/64 bits.".into()],
+                            "floats have an exact size of 16/32/64 bits.".into(),
                         ),
 
                         bit_string::ErrorType::InvalidEndianness => (
                             "This option is invalid here.",
-                            vec![wrap("Hint: signed and unsigned can only be used with int, float, utf16 and utf32 types.")],
+                            wrap("signed and unsigned can only be used with int, float, utf16 and utf32 types."),
                         ),
 
                         bit_string::ErrorType::OptionNotAllowedInValue => (
                             "This option is only allowed in BitString patterns.",
-                            vec!["Hint: This option has no effect in BitString values.".into()],
+                            "This option has no effect in BitString values.".into(),
                         ),
 
                         bit_string::ErrorType::SignednessUsedOnNonInt { typ } => (
                             "Signedness is only valid with int types.",
-                            vec![format!("Hint: This segment has a type of {typ}")],
+                            format!("This segment has a type of {typ}"),
                         ),
                         bit_string::ErrorType::TypeDoesNotAllowSize { typ } => (
                             "Size cannot be specified here",
-                            vec![format!("Hint: {typ} segments have an autoatic size.")],
+                            format!("{typ} segments have an autoatic size."),
                         ),
                         bit_string::ErrorType::TypeDoesNotAllowUnit { typ } => (
                             "Unit cannot be specified here",
-                            vec![wrap(&format!("Hint: {typ} segments are sized based on their value and cannot have a unit."))],
+                            wrap(&format!("{typ} segments are sized based on their value and cannot have a unit.")),
                         ),
                         bit_string::ErrorType::VariableUtfSegmentInPattern => (
                             "This cannot be a variable",
-                            vec![wrap("Hint: in patterns utf8, utf16, and utf32  must be an exact string.")],
+                            wrap("in patterns utf8, utf16, and utf32  must be an exact string."),
                         ),
                         bit_string::ErrorType::SegmentMustHaveSize => (
                             "This segment has no size",
-                            vec![wrap("Hint: Bit string segments without a size are only allowed at the end of a bin pattern.")],
+                            wrap("Bit string segments without a size are only allowed at the end of a bin pattern."),
                         ),
                         bit_string::ErrorType::UnitMustHaveSize => (
                             "This needs an explicit size",
-                            vec!["Hint: If you specify unit() you must also specify size().".into()],
+                            "If you specify unit() you must also specify size().".into(),
                         ),
                     };
-                    extra.push("See: https://gleam.run/book/tour/bit-strings.html".into());
-                    let text = extra.join("\n");
+                    let text = "See: https://gleam.run/book/tour/bit-strings.html".into();
                     Diagnostic {
                         title: "Invalid bit string segment".into(),
                         text,
-                        hint: None,
+                        hint: Some(hint),
                         level: Level::Error,
                         location: Some(Location {
                             label: Label {
@@ -1873,6 +2057,7 @@ function and try again."
                             src: src.clone(),
                             extra_labels: vec![],
                         }),
+                        code: None,
                     }
                 }
 
@@ -1890,6 +2075,7 @@ function and try again."
                         src: src.clone(),
                         extra_labels: vec![],
                     }),
+                    code: None,
                 },
 
                 TypeError::UnexpectedTypeHole { location } => Diagnostic {
@@ -1907,6 +2093,7 @@ function and try again."
                         src: src.clone(),
                         extra_labels: vec![],
                     }),
+                    code: None,
                 },
 
                 TypeError::ReservedModuleName { name } => {
@@ -1920,6 +2107,7 @@ Try a different name for this module."
                         hint: None,
                         location: None,
                         level: Level::Error,
+                        code: None,
                     }
                 }
 
@@ -1935,6 +2123,7 @@ Try a different name for this module."
                         hint: None,
                         location: None,
                         level: Level::Error,
+                        code: None,
                     }
                 }
 
@@ -1977,6 +2166,7 @@ value, or use `assert` rather than `let`."
                             src: src.clone(),
                             extra_labels: vec![],
                         }),
+                        code: None,
                     }
                 }
 
@@ -1994,6 +2184,7 @@ value, or use `assert` rather than `let`."
                         src: src.clone(),
                         extra_labels: vec![],
                     }),
+                    code: None,
                 },
 
                 TypeError::UnlabelledAfterlabelled { location } => Diagnostic {
@@ -2010,37 +2201,12 @@ value, or use `assert` rather than `let`."
                         src: src.clone(),
                         extra_labels: vec![],
                     }),
+                    code: None,
                 },
             },
 
-            Error::Parse { path, src, error } => {
-                let (label, extra) = error.details();
-                let text = extra.join("\n");
-
-                let adjusted_location = if error.error == ParseErrorType::UnexpectedEof {
-                    crate::ast::SrcSpan {
-                        start: (src.len() - 1) as u32,
-                        end: (src.len() - 1) as u32,
-                    }
-                } else {
-                    error.location
-                };
-
-                Diagnostic {
-                    title: "Syntax error".into(),
-                    text,
-                    hint: None,
-                    level: Level::Error,
-                    location: Some(Location {
-                        label: Label {
-                            text: Some(label.to_string()),
-                            span: adjusted_location,
-                        },
-                        path: path.clone(),
-                        src: src.clone(),
-                        extra_labels: vec![],
-                    }),
-                }
+            Error::Parse { path, src, errors } => {
+                parse_error_diagnostic(path, src, errors.first())
             }
 
             Error::ImportCycle { modules } => {
@@ -2058,6 +2224,7 @@ cycle to continue.",
                     hint: None,
                     level: Level::Error,
                     location: None,
+                    code: None,
                 }
             }
 
@@ -2076,6 +2243,7 @@ cycle to continue.",
                     hint: None,
                     level: Level::Error,
                     location: None,
+                    code: None,
                 }
             }
 
@@ -2104,6 +2272,7 @@ cycle to continue.",
                         src: src.clone(),
                         extra_labels: vec![],
                     }),
+                    code: None,
                 }
             }
 
@@ -2127,6 +2296,7 @@ cycle to continue.",
                     hint: None,
                     location: None,
                     level: Level::Error,
+                    code: None,
                 }
             }
 
@@ -2145,6 +2315,7 @@ cycle to continue.",
                     hint: None,
                     location: None,
                     level: Level::Error,
+                    code: None,
                 }
             }
 
@@ -2162,6 +2333,7 @@ Fix the warnings and try again."
                     hint: None,
                     location: None,
                     level: Level::Error,
+                    code: None,
                 }
             }
 
@@ -2180,6 +2352,7 @@ Fix the warnings and try again."
                         src: src.clone(),
                         extra_labels: vec![],
                     }),
+                    code: None,
                 },
             },
 
@@ -2200,6 +2373,7 @@ The error from the package manager client was:
                     hint: None,
                     location: None,
                     level: Level::Error,
+                    code: None,
                 }
             }
 
@@ -2216,6 +2390,7 @@ The error from the HTTP client was:
                     hint: None,
                     location: None,
                     level: Level::Error,
+                    code: None,
                 }
             }
 
@@ -2232,6 +2407,7 @@ The error from the parser was:
                     hint: None,
                     location: None,
                     level: Level::Error,
+                    code: None,
                 }
             }
 
@@ -2250,6 +2426,7 @@ The error from the version resolver library was:
                     hint: None,
                     location: None,
                     level: Level::Error,
+                    code: None,
                 }
             }
 
@@ -2264,6 +2441,69 @@ dev-dependencies sections of the gleam.toml file."
                     hint: None,
                     location: None,
                     level: Level::Error,
+                    code: None,
+                }
+            }
+
+            Error::WrongDependencyProvided {
+                path,
+                expected,
+                found,
+            } => {
+                let text = format!(
+                    "The package {expected} was expected at the path {path}
+but the gleam.toml file there is for the package {found}.",
+                    path = path.to_string_lossy(),
+                );
+                Diagnostic {
+                    title: "Wrong dependency provided".into(),
+                    text,
+                    hint: Some(format!(
+                        "Change the name of the dependency in gleam.toml to {found}."
+                    )),
+                    location: None,
+                    level: Level::Error,
+                    code: None,
+                }
+            }
+
+            Error::ProvidedDependencyConflict {
+                package,
+                source_1,
+                source_2,
+            } => {
+                let text = format!(
+                    "The package {package} is required from two different places:
+
+    {source_1}
+    {source_2}
+
+A package can only be used from one place in a project."
+                );
+                Diagnostic {
+                    title: "Conflicting provided dependencies".into(),
+                    text,
+                    hint: None,
+                    location: None,
+                    level: Level::Error,
+                    code: None,
+                }
+            }
+
+            Error::PublishNonHexDependencies { package } => {
+                let text = format!(
+                    "The package {package} cannot be published to Hex because it has
+path or git dependencies. Hex packages can only depend on other Hex packages."
+                );
+                Diagnostic {
+                    title: "Unpublishable dependencies".into(),
+                    text,
+                    hint: Some(
+                        "Publish the dependencies to Hex and depend on them by version.".into(),
+                    ),
+                    location: None,
+                    level: Level::Error,
+                    code: None,
                 }
             }
 
@@ -2295,6 +2535,7 @@ licences = ["Apache-2.0"]"#
                     hint: None,
                     location: None,
                     level: Level::Error,
+                    code: None,
                 }
             }
 
@@ -2317,6 +2558,7 @@ issue in our tracker: https://github.com/gleam-lang/gleam/issues",
                     hint: None,
                     location: None,
                     level: Level::Error,
+                    code: None,
                 }
             }
             Error::InvalidRuntime {
@@ -2341,8 +2583,229 @@ issue in our tracker: https://github.com/gleam-lang/gleam/issues",
                     hint,
                     location: None,
                     level: Level::Error,
+                    code: None,
+                }
+            }
+
+            Error::UnknownErrorCode { code } => Diagnostic {
+                title: "Unknown error code".into(),
+                text: format!("There is no error or warning with the code `{code}`."),
+                hint: Some(
+                    "Codes are shown in square brackets next to the title of \
+an error or warning, for example `error[E0103]`."
+                        .into(),
+                ),
+                location: None,
+                level: Level::Error,
+                code: None,
+            },
+
+            Error::UnableToStartDocsServer { address, error } => Diagnostic {
+                title: "Unable to start documentation server".into(),
+                text: format!(
+                    "The documentation server could not listen on {address}.
+
+The error message was:
+
+    {error}
+"
+                ),
+                hint: Some(
+                    "If another program is using this port you can choose a \
+different one with the `--port` flag."
+                        .into(),
+                ),
+                location: None,
+                level: Level::Error,
+                code: None,
+            },
+
+            Error::UnknownHexRepository { name } => Diagnostic {
+                title: "Unknown Hex repository".into(),
+                text: format!(
+                    "A package is to be downloaded from the Hex repository `{name}`,
+but no repository with that name has been configured."
+                ),
+                hint: Some(
+                    "Add the repository to the `repositories` section of gleam.toml \
+or of your global Gleam config."
+                        .into(),
+                ),
+                location: None,
+                level: Level::Error,
+                code: None,
+            },
+
+            Error::InvalidHexRepository { name, reason } => Diagnostic {
+                title: "Invalid Hex repository".into(),
+                text: format!("The config for the Hex repository `{name}` is invalid. {reason}"),
+                hint: Some(
+                    "Repositories other than `hexpm` must have a `repo_url`, \
+an `api_url`, and a `public_key`."
+                        .into(),
+                ),
+                location: None,
+                level: Level::Error,
+                code: None,
+            },
+
+            Error::PackageUnavailableOffline { package, version } => {
+                let text = match version {
+                    Some(version) => format!(
+                        "The package `{package}` {version} is not in the vendor directory or
+the package cache, and packages cannot be downloaded offline."
+                    ),
+                    None => format!(
+                        "The package `{package}` is not in manifest.toml, and its versions
+cannot be looked up offline."
+                    ),
+                };
+                Diagnostic {
+                    title: "Package not available offline".into(),
+                    text,
+                    hint: Some(
+                        "Run `gleam deps vendor` while online to copy the packages \
+the project needs into the vendor directory."
+                            .into(),
+                    ),
+                    location: None,
+                    level: Level::Error,
+                    code: None,
                 }
             }
+
+            Error::InsufficientVersionBump { details } => {
+                let InsufficientVersionBumpDetails {
+                    package,
+                    previous_version,
+                    version,
+                    minimum_version,
+                    bump,
+                    changes,
+                } = details.as_ref();
+                let mut text = format!(
+                    "The public API of {package} has changed since version {previous_version}
+in ways that need a {bump} version bump, so the new version must be
+{minimum_version} or higher, not {version}.
+"
+                );
+                for change in changes {
+                    text.push_str(&format!("\n  - {change}"));
+                }
+                Diagnostic {
+                    title: "Version too low for API changes".into(),
+                    text,
+                    hint: Some(format!(
+                        "Set the version in gleam.toml to {minimum_version} or higher."
+                    )),
+                    location: None,
+                    level: Level::Error,
+                    code: None,
+                }
+            }
+
+            Error::UnknownDependency { name } => Diagnostic {
+                title: "Unknown dependency".into(),
+                text: format!(
+                    "The package `{name}` is not a dependency of this project, either
+directly or through another package."
+                ),
+                hint: Some("Run `gleam deps list` to see the packages the project depends on.".into()),
+                location: None,
+                level: Level::Error,
+                code: None,
+            },
+
+            Error::TarballChecksumMismatch {
+                package,
+                version,
+                path,
+                expected,
+                found,
+            } => Diagnostic {
+                title: "Package checksum mismatch".into(),
+                text: format!(
+                    "The tarball of {package} {version} at
+
+    {path}
+
+does not match the checksum recorded for it in manifest.toml.
+
+Expected: {expected}
+Found:    {found}
+
+The file may have been modified or replaced, so it will not be used.",
+                    path = path.display()
+                ),
+                hint: Some(
+                    "Delete the file and run `gleam deps vendor` or `gleam deps download` \
+while online to fetch the package again."
+                        .into(),
+                ),
+                location: None,
+                level: Level::Error,
+                code: None,
+            },
+        };
+        Diagnostic {
+            code: Some(self.code()),
+            ..diagnostic
+        }
+    }
+
+    /// The code identifying this kind of error. Syntax and type errors have
+    /// the code of the first problem they are made up of. See
+    /// [`crate::explanation`] for how codes are assigned.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Error::Parse { errors, .. } => errors.first().error.code(),
+            Error::Type { errors, .. } => errors.first().code(),
+            Error::UnknownImport { .. } => "E0300",
+            Error::DuplicateModule { .. } => "E0301",
+            Error::DuplicateSourceFile { .. } => "E0302",
+            Error::ImportCycle { .. } => "E0303",
+            Error::PackageCycle { .. } => "E0304",
+            Error::FileIo { .. } => "E0305",
+            Error::GitInitialization { .. } => "E0306",
+            Error::StandardIo { .. } => "E0307",
+            Error::Format { .. } => "E0308",
+            Error::Hex(..) => "E0309",
+            Error::ExpandTar { .. } => "E0310",
+            Error::AddTar { .. } => "E0311",
+            Error::TarFinish(..) => "E0312",
+            Error::Gzip(..) => "E0313",
+            Error::ShellProgramNotFound { .. } => "E0314",
+            Error::ShellCommand { .. } => "E0315",
+            Error::InvalidProjectName { .. } => "E0316",
+            Error::InvalidModuleName { .. } => "E0317",
+            Error::ModuleDoesNotExist { .. } => "E0318",
+            Error::ModuleDoesNotHaveMainFunction { .. } => "E0319",
+            Error::MainFunctionHasWrongArity { .. } => "E0320",
+            Error::InvalidVersionFormat { .. } => "E0321",
+            Error::ProjectRootAlreadyExist { .. } => "E0322",
+            Error::UnableToFindProjectRoot { .. } => "E0323",
+            Error::VersionDoesNotMatch { .. } => "E0324",
+            Error::MetadataDecodeError { .. } => "E0325",
+            Error::ForbiddenWarnings { .. } => "E0326",
+            Error::JavaScript { .. } => "E0327",
+            Error::InvalidRuntime { .. } => "E0328",
+            Error::DownloadPackageError { .. } => "E0329",
+            Error::Http(..) => "E0330",
+            Error::DependencyResolutionFailed(..) => "E0331",
+            Error::DuplicateDependency(..) => "E0332",
+            Error::WrongDependencyProvided { .. } => "E0333",
+            Error::ProvidedDependencyConflict { .. } => "E0334",
+            Error::PublishNonHexDependencies { .. } => "E0335",
+            Error::MissingHexPublishFields { .. } => "E0336",
+            Error::UnsupportedBuildTool { .. } => "E0337",
+            Error::UnknownErrorCode { .. } => "E0338",
+            Error::UnableToStartDocsServer { .. } => "E0339",
+            Error::UnknownHexRepository { .. } => "E0340",
+            Error::InvalidHexRepository { .. } => "E0341",
+            Error::PackageUnavailableOffline { .. } => "E0342",
+            Error::UnknownDependency { .. } => "E0343",
+            Error::InsufficientVersionBump { .. } => "E0344",
+            Error::TarballChecksumMismatch { .. } => "E0345",
         }
     }
 }
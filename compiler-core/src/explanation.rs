@@ -0,0 +1,90 @@
+//! Long-form explanations of the errors and warnings the compiler emits,
+//! printed by `gleam explain`.
+//!
+//! Each explanation is a markdown file in the `explanations` directory named
+//! after its code, and is embedded in the binary when it is compiled.
+//!
+//! Codes are shown to users and included in machine readable output, so they
+//! are stable. Syntax errors use `E00xx`, type errors `E01xx`, warnings
+//! `E02xx`, and all other errors `E03xx`. Codes are never reused, so new
+//! variants must be given a new code rather than renumbering the others.
+
+macro_rules! explanations {
+    ($($code:literal,)*) => {
+        const EXPLANATIONS: &[(&str, &str)] = &[
+            $(($code, include_str!(concat!("../explanations/", $code, ".md"))),)*
+        ];
+    };
+}
+
+explanations!(
+    "E0001", "E0002", "E0003", "E0004", "E0005", "E0006", "E0007", "E0008", "E0009", "E0010",
+    "E0011", "E0012", "E0013", "E0014", "E0015", "E0017", "E0018", "E0019", "E0020", "E0021",
+    "E0022", "E0023", "E0024", "E0025", "E0026", "E0027", "E0028", "E0029", "E0030", "E0031",
+    "E0032", "E0033", "E0034", "E0035", "E0036", "E0037", "E0038", "E0039", "E0050", "E0051",
+    "E0052", "E0053", "E0054", "E0055", "E0056", "E0057", "E0058", "E0100", "E0101", "E0102",
+    "E0103", "E0104", "E0105", "E0106", "E0107", "E0108", "E0109", "E0110", "E0111", "E0112",
+    "E0113", "E0114", "E0115", "E0116", "E0117", "E0118", "E0119", "E0120", "E0121", "E0122",
+    "E0123", "E0124", "E0125", "E0126", "E0127", "E0128", "E0129", "E0130", "E0131", "E0132",
+    "E0133", "E0134", "E0135", "E0136", "E0137", "E0138", "E0139", "E0140", "E0200", "E0201",
+    "E0202", "E0203", "E0204", "E0205", "E0206", "E0207", "E0208", "E0209", "E0210", "E0211",
+    "E0212", "E0213", "E0214", "E0215", "E0300", "E0301", "E0302", "E0303", "E0304", "E0305",
+    "E0306", "E0307", "E0308", "E0309", "E0310", "E0311", "E0312", "E0313", "E0314", "E0315",
+    "E0316", "E0317", "E0318", "E0319", "E0320", "E0321", "E0322", "E0323", "E0324", "E0325",
+    "E0326", "E0327", "E0328", "E0329", "E0330", "E0331", "E0332", "E0333", "E0334", "E0335",
+    "E0336", "E0337", "E0338", "E0339", "E0340", "E0341", "E0342", "E0343", "E0344", "E0345",
+);
+
+/// The explanation of the error or warning with the given code, such as
+/// `E0042`. The code is not case sensitive.
+pub fn explanation(code: &str) -> Option<&'static str> {
+    let code = code.to_uppercase();
+    EXPLANATIONS
+        .iter()
+        .find(|(known, _)| *known == code)
+        .map(|(_, explanation)| *explanation)
+}
+
+#[cfg(test)]
+fn codes_in_source(src: &str) -> Vec<&str> {
+    src.match_indices("=> \"E")
+        .map(|(index, _)| &src[index + 4..index + 9])
+        .collect()
+}
+
+#[test]
+fn every_code_has_an_explanation() {
+    let sources = [
+        include_str!("parse/error.rs"),
+        include_str!("type_/error.rs"),
+        include_str!("error.rs"),
+    ];
+    let codes: Vec<_> = sources.into_iter().flat_map(codes_in_source).collect();
+    assert!(codes.len() > 100);
+    for code in codes {
+        assert!(explanation(code).is_some(), "{code} has no explanation");
+    }
+}
+
+#[test]
+fn explanations_are_titled_with_their_code() {
+    for (code, explanation) in EXPLANATIONS {
+        assert!(
+            explanation.starts_with(&format!("# {code}: ")),
+            "{code} has the wrong title"
+        );
+        assert!(explanation.contains("\n```"), "{code} has no example");
+    }
+}
+
+#[test]
+fn codes_are_not_case_sensitive() {
+    assert_eq!(explanation("e0103"), explanation("E0103"));
+    assert!(explanation("E0103").is_some());
+}
+
+#[test]
+fn unknown_code() {
+    assert_eq!(explanation("E9999"), None);
+    assert_eq!(explanation("wibble"), None);
+}
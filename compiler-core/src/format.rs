@@ -16,7 +16,7 @@ use crate::{
 use itertools::Itertools;
 use smol_str::SmolStr;
 use std::{path::Path, sync::Arc};
-use vec1::Vec1;
+use vec1::{vec1, Vec1};
 
 const INDENT: isize = 2;
 
@@ -24,7 +24,7 @@ pub fn pretty(writer: &mut impl Utf8Writer, src: &SmolStr, path: &Path) -> Resul
     let (module, extra) = crate::parse::parse_module(src).map_err(|error| Error::Parse {
         path: path.to_path_buf(),
         src: src.clone(),
-        error,
+        errors: vec1![error],
     })?;
     let intermediate = Intermediate::from_extra(&extra, src);
     Formatter::with_comments(&intermediate)
@@ -423,7 +423,10 @@ impl<'comments> Formatter<'comments> {
 
     fn documented_statement<'a>(&mut self, s: &'a UntypedModuleStatement) -> Document<'a> {
         let comments = self.doc_comments(s.location().start);
-        comments.append(self.module_statement(s).group()).group()
+        comments
+            .append(attributes(s))
+            .append(self.module_statement(s).group())
+            .group()
     }
 
     fn doc_comments<'a>(&mut self, limit: u32) -> Document<'a> {
@@ -1914,3 +1917,20 @@ fn is_breakable_expr(expr: &UntypedExpr) -> bool {
             | UntypedExpr::BitString { .. }
     )
 }
+
+fn attributes(statement: &UntypedModuleStatement) -> Document<'_> {
+    let deprecation = match statement {
+        ModuleStatement::Function(Function { deprecation, .. })
+        | ModuleStatement::ExternalFunction(ExternalFunction { deprecation, .. })
+        | ModuleStatement::CustomType(CustomType { deprecation, .. })
+        | ModuleStatement::TypeAlias(TypeAlias { deprecation, .. })
+        | ModuleStatement::ModuleConstant(ModuleConstant { deprecation, .. }) => deprecation,
+        ModuleStatement::ExternalType(_) | ModuleStatement::Import(_) => return nil(),
+    };
+    match deprecation {
+        Deprecation::NotDeprecated => nil(),
+        Deprecation::Deprecated { message } => {
+            docvec!["@deprecated(\"", message.as_str(), "\")", line()].force_break()
+        }
+    }
+}
@@ -5098,3 +5098,23 @@ fn empty_line_after_fn_with_return_annotation() {
 "#
     );
 }
+
+#[test]
+fn deprecated_attribute() {
+    assert_format!(
+        r#"/// Some documentation
+@deprecated("Use new_function instead")
+pub fn old_function() {
+  1
+}
+
+@deprecated("Use NewType instead")
+pub type OldType {
+  OldType
+}
+
+@deprecated("Use new_constant instead")
+pub const old_constant = 1
+"#
+    );
+}
@@ -1,16 +1,18 @@
 use debug_ignore::DebugIgnore;
 use flate2::read::GzDecoder;
 use futures::future;
-use hexpm::version::{PackageVersions, Version};
-use std::path::Path;
+use hexpm::version::{PackageVersions, Range, Version};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use tar::Archive;
 
 use crate::{
     build::Mode,
-    config::PackageConfig,
-    io::{FileSystemReader, FileSystemWriter, HttpClient, TarUnpacker},
-    manifest::{Manifest, ManifestPackage, ManifestPackageSource},
+    config::{HexRepositoryConfig, PackageConfig},
+    io::{FileSystemReader, FileSystemWriter, HttpClient, TarUnpacker, WrappedReader},
+    manifest::{Base16Checksum, Manifest, ManifestPackage, ManifestPackageSource},
     paths::{self, ProjectPaths},
+    requirement::Requirement,
     Error, Result,
 };
 
@@ -25,19 +27,183 @@ J1i2xWFndWa6nfFnRxZmCStCOZWYYPlaxr+FZceFbpMwzTNs4g3d4tLNUcbKAIH4
 -----END PUBLIC KEY-----
 ";
 
+/// The name of the public Hex repository at hex.pm.
+pub const HEXPM_REPOSITORY: &str = "hexpm";
+
+/// A Hex repository that packages can be looked up in, downloaded from, and
+/// published to.
+///
+#[derive(Debug, Clone)]
+pub struct Repository {
+    pub name: String,
+    pub config: hexpm::Config,
+    /// Sent with each request to the repository when set, and used instead
+    /// of creating an API key when publishing.
+    pub auth_key: Option<String>,
+    /// Used to verify that the package information downloaded from the
+    /// repository was signed by it.
+    pub public_key: Vec<u8>,
+}
+
+impl Repository {
+    fn hexpm() -> Self {
+        Self {
+            name: HEXPM_REPOSITORY.into(),
+            config: hexpm::Config::new(),
+            auth_key: None,
+            public_key: HEXPM_PUBLIC_KEY.to_vec(),
+        }
+    }
+
+    fn from_config(config: HexRepositoryConfig) -> Result<Self> {
+        let default = Self::hexpm();
+        let is_hexpm = config.name == HEXPM_REPOSITORY;
+        let invalid = |reason: String| Error::InvalidHexRepository {
+            name: config.name.clone(),
+            reason,
+        };
+        let url = |url: &Option<String>, key: &str, default: &http::Uri| match url {
+            Some(url) => parse_repository_url(url).map_err(invalid),
+            None if is_hexpm => Ok(default.clone()),
+            None => Err(invalid(format!("It has no `{key}`."))),
+        };
+
+        let repository_base = url(
+            &config.repo_url,
+            "repo_url",
+            &default.config.repository_base,
+        )?;
+        let api_base = url(&config.api_url, "api_url", &default.config.api_base)?;
+        let public_key = match &config.public_key {
+            Some(key) => key.clone().into_bytes(),
+            None if is_hexpm => default.public_key,
+            None => return Err(invalid("It has no `public_key`.".into())),
+        };
+
+        Ok(Self {
+            config: hexpm::Config {
+                api_base,
+                repository_base,
+            },
+            auth_key: config.auth_key,
+            public_key,
+            name: config.name,
+        })
+    }
+}
+
+fn parse_repository_url(url: &str) -> Result<http::Uri, String> {
+    // Request paths are appended to the URL, so it must end with a slash.
+    let url = if url.ends_with('/') {
+        url.to_string()
+    } else {
+        format!("{url}/")
+    };
+    let uri: http::Uri = url
+        .parse()
+        .map_err(|_| format!("`{url}` is not a valid URL."))?;
+    if uri.scheme().is_none() || uri.host().is_none() {
+        return Err(format!("`{url}` is not a valid URL."));
+    }
+    Ok(uri)
+}
+
+/// The Hex repositories configured for a project, always including the
+/// public Hex repository.
+///
+#[derive(Debug, Clone)]
+pub struct Repositories {
+    repositories: HashMap<String, Repository>,
+}
+
+impl Repositories {
+    /// Repositories configured by the project take precedence over those in
+    /// the user's global config, with any settings the project does not give
+    /// taken from the global config.
+    ///
+    pub fn new(project: &[HexRepositoryConfig], user: &[HexRepositoryConfig]) -> Result<Self> {
+        let mut configs: HashMap<String, HexRepositoryConfig> = user
+            .iter()
+            .map(|config| (config.name.clone(), config.clone()))
+            .collect();
+        for config in project {
+            let config = match configs.get(&config.name) {
+                Some(user) => config.clone().or(user),
+                None => config.clone(),
+            };
+            let _ = configs.insert(config.name.clone(), config);
+        }
+
+        let mut repositories = Self::default().repositories;
+        for config in configs.into_values() {
+            let repository = Repository::from_config(config)?;
+            let _ = repositories.insert(repository.name.clone(), repository);
+        }
+        Ok(Self { repositories })
+    }
+
+    pub fn get(&self, name: &str) -> Result<&Repository> {
+        self.repositories
+            .get(name)
+            .ok_or_else(|| Error::UnknownHexRepository { name: name.into() })
+    }
+
+    pub fn hexpm(&self) -> &Repository {
+        self.get(HEXPM_REPOSITORY)
+            .expect("The hexpm repository is always configured")
+    }
+}
+
+impl Default for Repositories {
+    fn default() -> Self {
+        Self {
+            repositories: HashMap::from([(HEXPM_REPOSITORY.into(), Repository::hexpm())]),
+        }
+    }
+}
+
+/// Resolves the versions of the Hex packages required by the config.
+///
+/// Path and git dependencies are not looked up on Hex. Instead the package
+/// fetcher is expected to provide them, and the versions given in
+/// `provided_packages` are required exactly.
+///
 pub fn resolve_versions(
     package_fetcher: Box<dyn hexpm::version::PackageFetcher>,
     mode: Mode,
     config: &PackageConfig,
     manifest: Option<&Manifest>,
+    provided_packages: &HashMap<String, Version>,
 ) -> Result<PackageVersions> {
-    let specified_dependencies = config.dependencies_for(mode)?.into_iter();
-    let locked = config.locked(manifest)?;
+    let specified_dependencies = config
+        .dependencies_for(mode)?
+        .into_iter()
+        .map(|(name, requirement)| {
+            let range = match requirement {
+                Requirement::Hex { version, .. } => version,
+                Requirement::Path { .. } | Requirement::Git { .. } => {
+                    let version = provided_packages.get(&name).ok_or_else(|| {
+                        Error::DependencyResolutionFailed(format!(
+                            "The path or git dependency {name} was not provided"
+                        ))
+                    })?;
+                    Range::new(format!("== {version}"))
+                }
+            };
+            Ok((name, range))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    // Provided packages are always used as they currently are, so the
+    // versions previously recorded for them in the manifest are not locked.
+    let mut locked = config.locked(manifest)?;
+    locked.retain(|name, _| !provided_packages.contains_key(name));
+
     tracing::info!("resolving_versions");
     hexpm::version::resolve_versions(
         package_fetcher,
         config.name.to_string(),
-        specified_dependencies,
+        specified_dependencies.into_iter(),
         &locked,
     )
     .map_err(Error::dependency_resolution_failed)
@@ -148,7 +314,7 @@ pub struct Downloader {
     fs_writer: DebugIgnore<Box<dyn FileSystemWriter>>,
     http: DebugIgnore<Box<dyn HttpClient>>,
     untar: DebugIgnore<Box<dyn TarUnpacker>>,
-    hex_config: hexpm::Config,
+    repositories: Repositories,
     paths: ProjectPaths,
 }
 
@@ -158,6 +324,7 @@ impl Downloader {
         fs_writer: Box<dyn FileSystemWriter>,
         http: Box<dyn HttpClient>,
         untar: Box<dyn TarUnpacker>,
+        repositories: Repositories,
         paths: ProjectPaths,
     ) -> Self {
         Self {
@@ -165,7 +332,7 @@ impl Downloader {
             fs_writer: DebugIgnore(fs_writer),
             http: DebugIgnore(http),
             untar: DebugIgnore(untar),
-            hex_config: hexpm::Config::new(),
+            repositories,
             paths,
         }
     }
@@ -174,10 +341,22 @@ impl Downloader {
         &self,
         package: &ManifestPackage,
     ) -> Result<bool, Error> {
+        let (outer_checksum, repository) = hex_source(package)?;
         let tarball_path = paths::global_package_cache_package_tarball(
+            repository,
             &package.name,
             &package.version.to_string(),
         );
+        let vendored = self.vendored_tarball(package);
+        if self.fs_reader.is_file(&vendored) {
+            tracing::info!(
+                package = package.name.as_str(),
+                version = %package.version,
+                "package_vendored"
+            );
+            verify_outer_checksum(package, &vendored, &self.fs_reader.read_bytes(&vendored)?)?;
+            return Ok(false);
+        }
         if self.fs_reader.is_file(&tarball_path) {
             tracing::info!(
                 package = package.name.as_str(),
@@ -189,19 +368,19 @@ impl Downloader {
         tracing::info!(
             package = &package.name.as_str(),
             version = %package.version,
+            repository = repository,
             "downloading_package_to_cache"
         );
 
+        let repository = self.repositories.get(repository)?;
         let request = hexpm::get_package_tarball_request(
             &package.name,
             &package.version.to_string(),
-            None,
-            &self.hex_config,
+            repository.auth_key.as_deref(),
+            &repository.config,
         );
         let response = self.http.send(request).await?;
 
-        let ManifestPackageSource::Hex { outer_checksum } = &package.source;
-
         let tarball =
             hexpm::get_package_tarball_response(response, &outer_checksum.0).map_err(|error| {
                 Error::DownloadPackageError {
@@ -219,23 +398,52 @@ impl Downloader {
         package: &ManifestPackage,
     ) -> Result<bool> {
         let _ = self.ensure_package_downloaded(package).await?;
-        self.extract_package_from_cache(&package.name, &package.version)
+        self.extract_package_from_cache(package)
+    }
+
+    fn vendored_tarball(&self, package: &ManifestPackage) -> PathBuf {
+        self.paths
+            .vendor_package_tarball(&package.name, &package.version.to_string())
+    }
+
+    /// The tarball of a Hex package, taken from the project's vendor directory
+    /// if it is there and from the global package cache otherwise.
+    pub fn package_tarball(&self, package: &ManifestPackage) -> Result<PathBuf> {
+        let (_, repository) = hex_source(package)?;
+        let vendored = self.vendored_tarball(package);
+        if self.fs_reader.is_file(&vendored) {
+            return Ok(vendored);
+        }
+        Ok(paths::global_package_cache_package_tarball(
+            repository,
+            &package.name,
+            &package.version.to_string(),
+        ))
     }
 
     // It would be really nice if this was async but the library is sync
-    pub fn extract_package_from_cache(&self, name: &str, version: &Version) -> Result<bool> {
+    pub fn extract_package_from_cache(&self, package: &ManifestPackage) -> Result<bool> {
+        let destination = self.paths.build_packages_package(&package.name);
+        self.extract_package(package, &destination)
+    }
+
+    /// Extracts the source code of a downloaded package into a directory,
+    /// doing nothing if the directory already exists.
+    pub fn extract_package(&self, package: &ManifestPackage, destination: &Path) -> Result<bool> {
+        let name = package.name.as_str();
         let contents_path = Path::new("contents.tar.gz");
-        let destination = self.paths.build_packages_package(name);
 
         // If the directory already exists then there's nothing for us to do
-        if self.fs_reader.is_directory(&destination) {
+        if self.fs_reader.is_directory(destination) {
             tracing::info!(package = name, "Package already in build directory");
             return Ok(false);
         }
 
         tracing::info!(package = name, "writing_package_to_target");
-        let tarball = paths::global_package_cache_package_tarball(name, &version.to_string());
-        let reader = self.fs_reader.reader(&tarball)?;
+        let tarball = self.package_tarball(package)?;
+        let bytes = self.fs_reader.read_bytes(&tarball)?;
+        verify_outer_checksum(package, &tarball, &bytes)?;
+        let reader = WrappedReader::new(&tarball, Box::new(std::io::Cursor::new(bytes)));
         let mut archive = Archive::new(reader);
 
         // Find the source code from within the outer tarball
@@ -246,7 +454,7 @@ impl Downloader {
             if path.as_ref() == contents_path {
                 // Expand this inner source code and write to the file system
                 let archive = Archive::new(GzDecoder::new(file));
-                let result = self.untar.unpack(&destination, archive);
+                let result = self.untar.unpack(destination, archive);
 
                 // If we failed to expand the tarball remove any source code
                 // that was partially written so that we don't mistakenly think
@@ -254,7 +462,7 @@ impl Downloader {
                 return match result {
                     Ok(()) => Ok(true),
                     Err(err) => {
-                        self.fs_writer.delete(&destination)?;
+                        self.fs_writer.delete(destination)?;
                         Err(err)
                     }
                 };
@@ -273,6 +481,7 @@ impl Downloader {
     ) -> Result<()> {
         let futures = packages
             .filter(|package| project_name != package.name)
+            .filter(|package| matches!(package.source, ManifestPackageSource::Hex { .. }))
             .map(|package| self.ensure_package_in_build_directory(package));
 
         // Run the futures to download the packages concurrently
@@ -284,6 +493,63 @@ impl Downloader {
         }
         Ok(())
     }
+
+    /// Extracts Hex packages into the build directory without downloading
+    /// them, failing if any of them are not vendored or cached.
+    pub fn extract_hex_packages<'a, Packages: Iterator<Item = &'a ManifestPackage>>(
+        &self,
+        packages: Packages,
+        project_name: &str,
+    ) -> Result<()> {
+        let packages = packages
+            .filter(|package| project_name != package.name)
+            .filter(|package| matches!(package.source, ManifestPackageSource::Hex { .. }));
+        for package in packages {
+            if !self.fs_reader.is_file(&self.package_tarball(package)?) {
+                return Err(Error::PackageUnavailableOffline {
+                    package: package.name.clone(),
+                    version: Some(package.version.to_string()),
+                });
+            }
+            let _ = self.extract_package_from_cache(package)?;
+        }
+        Ok(())
+    }
+}
+
+/// Checks that the tarball of a Hex package matches the checksum recorded
+/// for it in the manifest.
+pub fn verify_outer_checksum(package: &ManifestPackage, path: &Path, tarball: &[u8]) -> Result<()> {
+    use sha2::Digest;
+    let (outer_checksum, _) = hex_source(package)?;
+    let checksum = sha2::Sha256::digest(tarball);
+    if checksum.as_slice() == outer_checksum.0.as_slice() {
+        return Ok(());
+    }
+    Err(Error::TarballChecksumMismatch {
+        package: package.name.clone(),
+        version: package.version.to_string(),
+        path: path.to_path_buf(),
+        expected: outer_checksum.to_string(),
+        found: base16::encode_upper(&checksum),
+    })
+}
+
+/// The checksum and repository of a package downloaded from Hex.
+fn hex_source(package: &ManifestPackage) -> Result<(&Base16Checksum, &str)> {
+    match &package.source {
+        ManifestPackageSource::Hex {
+            outer_checksum,
+            repository,
+        } => Ok((outer_checksum, repository)),
+        ManifestPackageSource::Git { .. } | ManifestPackageSource::Local { .. } => {
+            Err(Error::DownloadPackageError {
+                package_name: package.name.to_string(),
+                package_version: package.version.to_string(),
+                error: "Only Hex packages can be downloaded from Hex".into(),
+            })
+        }
+    }
 }
 
 pub async fn publish_documentation<Http: HttpClient>(
@@ -304,6 +570,7 @@ pub async fn publish_documentation<Http: HttpClient>(
 pub async fn get_package_release<Http: HttpClient>(
     name: &str,
     version: &Version,
+    api_key: Option<&str>,
     config: &hexpm::Config,
     http: &Http,
 ) -> Result<hexpm::Release<hexpm::ReleaseMeta>> {
@@ -313,7 +580,363 @@ pub async fn get_package_release<Http: HttpClient>(
         version = version.as_str(),
         "looking_up_package_release"
     );
-    let request = hexpm::get_package_release_request(name, &version, None, config);
+    let request = hexpm::get_package_release_request(name, &version, api_key, config);
     let response = http.send(request).await?;
     hexpm::get_package_release_response(response).map_err(Error::hex)
 }
+
+#[cfg(test)]
+fn repository_config(name: &str) -> HexRepositoryConfig {
+    HexRepositoryConfig {
+        name: name.into(),
+        api_url: Some("https://hex.example.com/api".into()),
+        repo_url: Some("https://hex.example.com/repo/".into()),
+        auth_key: None,
+        public_key: Some("company public key".into()),
+    }
+}
+
+#[test]
+fn repositories_include_hexpm() {
+    let repositories = Repositories::new(&[], &[]).unwrap();
+    let hexpm = repositories.get(HEXPM_REPOSITORY).unwrap();
+    assert_eq!(hexpm.config.repository_base, "https://repo.hex.pm/");
+    assert_eq!(hexpm.public_key, HEXPM_PUBLIC_KEY);
+    assert!(matches!(
+        repositories.get("company"),
+        Err(Error::UnknownHexRepository { name }) if name == "company"
+    ));
+}
+
+#[test]
+fn repository_urls_end_with_a_slash() {
+    let repositories = Repositories::new(&[repository_config("company")], &[]).unwrap();
+    let company = repositories.get("company").unwrap();
+    assert_eq!(company.config.api_base, "https://hex.example.com/api/");
+    assert_eq!(
+        company.config.repository_base,
+        "https://hex.example.com/repo/"
+    );
+    assert_eq!(company.public_key, b"company public key");
+}
+
+#[test]
+fn project_repositories_take_settings_from_user_config() {
+    let project = HexRepositoryConfig {
+        name: "company".into(),
+        public_key: Some("project key".into()),
+        ..Default::default()
+    };
+    let user = HexRepositoryConfig {
+        auth_key: Some("secret".into()),
+        ..repository_config("company")
+    };
+    let repositories = Repositories::new(&[project], &[user]).unwrap();
+    let company = repositories.get("company").unwrap();
+    assert_eq!(company.config.api_base, "https://hex.example.com/api/");
+    assert_eq!(company.public_key, b"project key");
+    assert_eq!(company.auth_key.as_deref(), Some("secret"));
+}
+
+#[test]
+fn user_auth_key_is_not_sent_to_project_urls() {
+    let project = HexRepositoryConfig {
+        auth_key: None,
+        repo_url: Some("https://evil.example.com/repo/".into()),
+        ..repository_config("company")
+    };
+    let user = HexRepositoryConfig {
+        auth_key: Some("secret".into()),
+        ..repository_config("company")
+    };
+    let repositories = Repositories::new(&[project], &[user]).unwrap();
+    let company = repositories.get("company").unwrap();
+    assert_eq!(
+        company.config.repository_base,
+        "https://evil.example.com/repo/"
+    );
+    assert_eq!(company.auth_key, None);
+
+    let project = HexRepositoryConfig {
+        name: HEXPM_REPOSITORY.into(),
+        api_url: Some("https://evil.example.com/api/".into()),
+        ..Default::default()
+    };
+    let user = HexRepositoryConfig {
+        name: HEXPM_REPOSITORY.into(),
+        auth_key: Some("secret".into()),
+        ..Default::default()
+    };
+    let repositories = Repositories::new(&[project], &[user]).unwrap();
+    assert_eq!(repositories.hexpm().auth_key, None);
+}
+
+#[test]
+fn hexpm_mirror_only_needs_its_repo_url() {
+    let mirror = HexRepositoryConfig {
+        name: HEXPM_REPOSITORY.into(),
+        repo_url: Some("https://mirror.example.com".into()),
+        ..Default::default()
+    };
+    let repositories = Repositories::new(&[], &[mirror]).unwrap();
+    let hexpm = repositories.get(HEXPM_REPOSITORY).unwrap();
+    assert_eq!(hexpm.config.repository_base, "https://mirror.example.com/");
+    assert_eq!(hexpm.config.api_base, "https://hex.pm/api/");
+    assert_eq!(hexpm.public_key, HEXPM_PUBLIC_KEY);
+}
+
+#[test]
+fn repositories_must_have_all_settings() {
+    let config = HexRepositoryConfig {
+        public_key: None,
+        ..repository_config("company")
+    };
+    assert!(matches!(
+        Repositories::new(&[config], &[]),
+        Err(Error::InvalidHexRepository { name, .. }) if name == "company"
+    ));
+
+    let config = HexRepositoryConfig {
+        repo_url: Some("not a url".into()),
+        ..repository_config("company")
+    };
+    assert!(matches!(
+        Repositories::new(&[config], &[]),
+        Err(Error::InvalidHexRepository { name, .. }) if name == "company"
+    ));
+}
+
+/// A Hex repository served from a directory, each request being answered
+/// with the file at the same path. Requests without the repository's auth
+/// key are refused.
+#[cfg(test)]
+#[derive(Debug)]
+struct DirectoryRepository {
+    directory: PathBuf,
+    auth_key: Option<String>,
+}
+
+#[cfg(test)]
+#[async_trait::async_trait]
+impl HttpClient for DirectoryRepository {
+    async fn send(
+        &self,
+        request: http::Request<Vec<u8>>,
+    ) -> Result<http::Response<Vec<u8>>, Error> {
+        let auth_key = request
+            .headers()
+            .get("authorization")
+            .and_then(|key| key.to_str().ok());
+        let path = self
+            .directory
+            .join(request.uri().path().trim_start_matches('/'));
+        let (status, body) = if auth_key != self.auth_key.as_deref() {
+            (http::StatusCode::UNAUTHORIZED, vec![])
+        } else {
+            match std::fs::read(path) {
+                Ok(body) => (http::StatusCode::OK, body),
+                Err(_) => (http::StatusCode::NOT_FOUND, vec![]),
+            }
+        };
+        Ok(http::Response::builder()
+            .status(status)
+            .body(body)
+            .expect("Response"))
+    }
+}
+
+#[cfg(test)]
+struct Untar;
+
+#[cfg(test)]
+impl TarUnpacker for Untar {
+    fn io_result_entries<'a>(
+        &self,
+        archive: &'a mut Archive<WrappedReader>,
+    ) -> std::io::Result<tar::Entries<'a, WrappedReader>> {
+        archive.entries()
+    }
+
+    fn io_result_unpack(
+        &self,
+        path: &Path,
+        mut archive: Archive<GzDecoder<tar::Entry<'_, WrappedReader>>>,
+    ) -> std::io::Result<()> {
+        archive.unpack(path)
+    }
+}
+
+#[cfg(test)]
+fn directory_repository_downloader(
+    fs: &crate::io::memory::InMemoryFileSystem,
+    repository: &tempfile::TempDir,
+) -> Downloader {
+    let config = HexRepositoryConfig {
+        auth_key: Some("secret".into()),
+        ..repository_config("company")
+    };
+    Downloader::new(
+        Box::new(fs.clone()),
+        Box::new(fs.clone()),
+        Box::new(DirectoryRepository {
+            directory: repository.path().to_path_buf(),
+            auth_key: Some("secret".into()),
+        }),
+        Box::new(Untar),
+        Repositories::new(&[config], &[]).unwrap(),
+        ProjectPaths::new("/app".into()),
+    )
+}
+
+#[cfg(test)]
+fn internal_package(repository: &str) -> ManifestPackage {
+    ManifestPackage {
+        name: "internal".into(),
+        version: Version::new(1, 0, 0),
+        build_tools: vec!["gleam".into()],
+        otp_app: None,
+        requirements: vec![],
+        source: ManifestPackageSource::Hex {
+            outer_checksum: Base16Checksum(
+                base16::decode("eb69d45eb5f243eb79e4c6f5b2372f0399d31dac15c211bb39ba26b95297c06a")
+                    .unwrap(),
+            ),
+            repository: repository.into(),
+        },
+    }
+}
+
+#[test]
+fn download_from_configured_repository() {
+    let repository = tempfile::tempdir().unwrap();
+    let tarballs = repository.path().join("repo/tarballs");
+    std::fs::create_dir_all(&tarballs).unwrap();
+    std::fs::write(
+        tarballs.join("internal-1.0.0.tar"),
+        "internal-1.0.0 tarball",
+    )
+    .unwrap();
+
+    let fs = crate::io::memory::InMemoryFileSystem::new();
+    let downloader = directory_repository_downloader(&fs, &repository);
+    let package = internal_package("company");
+    assert!(futures::executor::block_on(downloader.ensure_package_downloaded(&package)).unwrap());
+
+    let cached = paths::global_package_cache_package_tarball("company", "internal", "1.0.0");
+    assert_eq!(fs.read_bytes(&cached).unwrap(), b"internal-1.0.0 tarball");
+
+    // Once cached the package is not downloaded again
+    assert!(!futures::executor::block_on(downloader.ensure_package_downloaded(&package)).unwrap());
+}
+
+#[test]
+fn download_from_unknown_repository() {
+    let repository = tempfile::tempdir().unwrap();
+    let fs = crate::io::memory::InMemoryFileSystem::new();
+    let downloader = directory_repository_downloader(&fs, &repository);
+    let package = internal_package("elsewhere");
+    assert!(matches!(
+        futures::executor::block_on(downloader.ensure_package_downloaded(&package)),
+        Err(Error::UnknownHexRepository { name }) if name == "elsewhere"
+    ));
+}
+
+#[test]
+fn vendored_packages_are_not_downloaded() {
+    let repository = tempfile::tempdir().unwrap();
+    let fs = crate::io::memory::InMemoryFileSystem::new();
+    let downloader = directory_repository_downloader(&fs, &repository);
+    let package = internal_package("company");
+    let vendored = PathBuf::from("/app/vendor/internal-1.0.0.tar");
+    fs.write_bytes(&vendored, b"internal-1.0.0 tarball")
+        .unwrap();
+
+    assert!(!futures::executor::block_on(downloader.ensure_package_downloaded(&package)).unwrap());
+    assert_eq!(downloader.package_tarball(&package).unwrap(), vendored);
+}
+
+#[test]
+fn extract_packages_unavailable_offline() {
+    let repository = tempfile::tempdir().unwrap();
+    let fs = crate::io::memory::InMemoryFileSystem::new();
+    let downloader = directory_repository_downloader(&fs, &repository);
+    let package = internal_package("company");
+    assert!(matches!(
+        downloader.extract_hex_packages([package].iter(), "app"),
+        Err(Error::PackageUnavailableOffline { package, version })
+            if package == "internal" && version.as_deref() == Some("1.0.0")
+    ));
+}
+
+#[test]
+fn tampered_vendored_packages_are_rejected() {
+    let repository = tempfile::tempdir().unwrap();
+    let fs = crate::io::memory::InMemoryFileSystem::new();
+    let downloader = directory_repository_downloader(&fs, &repository);
+    let package = internal_package("company");
+    let vendored = PathBuf::from("/app/vendor/internal-1.0.0.tar");
+    fs.write_bytes(&vendored, b"tampered tarball").unwrap();
+
+    assert!(matches!(
+        futures::executor::block_on(downloader.ensure_package_downloaded(&package)),
+        Err(Error::TarballChecksumMismatch { path, .. }) if path == vendored
+    ));
+    assert!(matches!(
+        downloader.extract_hex_packages([package].iter(), "app"),
+        Err(Error::TarballChecksumMismatch { path, .. }) if path == vendored
+    ));
+    assert!(!fs.is_directory(Path::new("/app/build/packages/internal")));
+}
+
+#[test]
+fn verify_outer_checksum_test() {
+    let path = Path::new("internal-1.0.0.tar");
+    let mut package = internal_package("company");
+    assert!(verify_outer_checksum(&package, path, b"internal-1.0.0 tarball").is_ok());
+
+    package.source = ManifestPackageSource::Hex {
+        outer_checksum: Base16Checksum(vec![1, 2, 3]),
+        repository: "company".into(),
+    };
+    assert!(matches!(
+        verify_outer_checksum(&package, path, b"internal-1.0.0 tarball"),
+        Err(Error::TarballChecksumMismatch { expected, .. }) if expected == "010203"
+    ));
+}
+
+#[cfg(test)]
+struct NoPackages;
+
+#[cfg(test)]
+impl hexpm::version::PackageFetcher for NoPackages {
+    fn get_dependencies(
+        &self,
+        package: &str,
+    ) -> Result<hexpm::Package, Box<dyn std::error::Error>> {
+        Err(format!("Unexpected lookup of {package}").into())
+    }
+}
+
+#[test]
+fn resolve_versions_without_provided_package() {
+    let config = PackageConfig {
+        dependencies: [(
+            "shared".into(),
+            Requirement::Path {
+                path: "../shared".into(),
+            },
+        )]
+        .into(),
+        ..Default::default()
+    };
+    assert!(matches!(
+        resolve_versions(
+            Box::new(NoPackages),
+            Mode::Dev,
+            &config,
+            None,
+            &HashMap::new()
+        ),
+        Err(Error::DependencyResolutionFailed(_))
+    ));
+}
@@ -127,6 +127,30 @@ impl<'a> Generator<'a> {
             self.register_prelude_usage(&mut imports, "float64Bits", None);
         };
 
+        if self.tracker.sized_float_segment_used {
+            self.register_prelude_usage(&mut imports, "sizedFloat", None);
+        };
+
+        if self.tracker.sized_bit_string_segment_used {
+            self.register_prelude_usage(&mut imports, "sizedBitString", None);
+        };
+
+        if self.tracker.utf16_string_bit_string_segment_used {
+            self.register_prelude_usage(&mut imports, "stringToUtf16", None);
+        };
+
+        if self.tracker.utf32_string_bit_string_segment_used {
+            self.register_prelude_usage(&mut imports, "stringToUtf32", None);
+        };
+
+        if self.tracker.utf16_codepoint_bit_string_segment_used {
+            self.register_prelude_usage(&mut imports, "codepointToUtf16", None);
+        };
+
+        if self.tracker.utf32_codepoint_bit_string_segment_used {
+            self.register_prelude_usage(&mut imports, "codepointToUtf32", None);
+        };
+
         // Put it all together
 
         if imports.is_empty() && statements.is_empty() {
@@ -717,4 +741,10 @@ pub(crate) struct UsageTracker {
     pub string_bit_string_segment_used: bool,
     pub codepoint_bit_string_segment_used: bool,
     pub float_bit_string_segment_used: bool,
+    pub sized_float_segment_used: bool,
+    pub sized_bit_string_segment_used: bool,
+    pub utf16_string_bit_string_segment_used: bool,
+    pub utf32_string_bit_string_segment_used: bool,
+    pub utf16_codepoint_bit_string_segment_used: bool,
+    pub utf32_codepoint_bit_string_segment_used: bool,
 }
@@ -6,6 +6,7 @@ use super::{
 };
 use crate::{
     ast::*,
+    bit_string::GetLiteralValue,
     line_numbers::LineNumbers,
     pretty::*,
     type_::{ModuleValueConstructor, Type, ValueConstructor, ValueConstructorVariant},
@@ -211,49 +212,15 @@ impl<'module> Generator<'module> {
     fn bit_string<'a>(&mut self, segments: &'a [TypedExprBitStringSegment]) -> Output<'a> {
         self.tracker.bit_string_literal_used = true;
 
-        use BitStringSegmentOption as Opt;
-
         // Collect all the values used in segments.
         let segments_array = array(segments.iter().map(|segment| {
             let value = self.not_in_tail_position(|gen| gen.wrap_expression(&segment.value))?;
-            match segment.options.as_slice() {
-                // Ints
-                [] | [Opt::Int { .. }] => Ok(value),
-
-                // Sized ints
-                [Opt::Size { value: size, .. }] => {
-                    self.tracker.sized_integer_segment_used = true;
-                    let size = self.not_in_tail_position(|gen| gen.wrap_expression(size))?;
-                    Ok(docvec!["sizedInt(", value, ", ", size, ")"])
-                }
-
-                // Floats
-                [Opt::Float { .. }] => {
-                    self.tracker.float_bit_string_segment_used = true;
-                    Ok(docvec!["float64Bits(", value, ")"])
-                }
-
-                // UTF8 strings
-                [Opt::Utf8 { .. }] => {
-                    self.tracker.string_bit_string_segment_used = true;
-                    Ok(docvec!["stringBits(", value, ")"])
-                }
-
-                // UTF8 codepoints
-                [Opt::Utf8Codepoint { .. }] => {
-                    self.tracker.codepoint_bit_string_segment_used = true;
-                    Ok(docvec!["codepointBits(", value, ")"])
-                }
-
-                // Bit strings
-                [Opt::BitString { .. }] => Ok(docvec![value, ".buffer"]),
-
-                // Anything else
-                _ => Err(Error::Unsupported {
-                    feature: "This bit string segment option".into(),
-                    location: segment.location,
-                }),
-            }
+            let details = segment_details(&segment.options);
+            let size = match details.size {
+                Some(size) => Some(self.not_in_tail_position(|gen| gen.wrap_expression(size))?),
+                None => None,
+            };
+            bit_string_segment(self.tracker, value, &details, size, segment.location)
         }))?;
 
         Ok(docvec!["toBitString(", segments_array, ")"])
@@ -1230,10 +1197,19 @@ pub(crate) fn constant_expression<'a>(
             Ok(construct_record(module.as_deref(), tag, field_values))
         }
 
-        Constant::BitString { location, .. } => Err(Error::Unsupported {
-            feature: "Bit string syntax".into(),
-            location: *location,
-        }),
+        Constant::BitString { segments, .. } => {
+            tracker.bit_string_literal_used = true;
+            let segments_array = array(segments.iter().map(|segment| {
+                let value = constant_expression(tracker, &segment.value)?;
+                let details = segment_details(&segment.options);
+                let size = match details.size {
+                    Some(size) => Some(constant_expression(tracker, size)?),
+                    None => None,
+                };
+                bit_string_segment(tracker, value, &details, size, segment.location)
+            }))?;
+            Ok(docvec!["toBitString(", segments_array, ")"])
+        }
 
         Constant::Var { name, module, .. } => Ok({
             match module {
@@ -1378,6 +1354,206 @@ impl BinOp {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SegmentType {
+    Int,
+    Float,
+    Binary,
+    BitString,
+    Utf8,
+    Utf16,
+    Utf32,
+    Utf8Codepoint,
+    Utf16Codepoint,
+    Utf32Codepoint,
+}
+
+/// The options of a bit string segment, with the defaults filled in for
+/// any that were not given.
+///
+#[derive(Debug)]
+pub(crate) struct SegmentDetails<'a, Value> {
+    pub type_: SegmentType,
+    /// The value of the size option. This is a number of units, not bits.
+    pub size: Option<&'a Value>,
+    pub unit: u8,
+    pub is_big_endian: bool,
+    pub is_signed: bool,
+}
+
+impl<Value: GetLiteralValue> SegmentDetails<'_, Value> {
+    /// The size of the segment in bits, if it is known at compile time.
+    ///
+    pub fn literal_size_in_bits(&self) -> Option<i64> {
+        match self.size {
+            Some(size) => size.as_int_literal().map(|size| size * self.unit as i64),
+            None => match self.type_ {
+                SegmentType::Int => Some(8),
+                SegmentType::Float => Some(64),
+                _ => None,
+            },
+        }
+    }
+}
+
+pub(crate) fn segment_details<Value>(
+    options: &[BitStringSegmentOption<Value>],
+) -> SegmentDetails<'_, Value> {
+    use BitStringSegmentOption as Opt;
+
+    let mut details = SegmentDetails {
+        type_: SegmentType::Int,
+        size: None,
+        unit: 1,
+        is_big_endian: true,
+        is_signed: false,
+    };
+    let mut unit = None;
+
+    for option in options {
+        match option {
+            Opt::Int { .. } => details.type_ = SegmentType::Int,
+            Opt::Float { .. } => details.type_ = SegmentType::Float,
+            Opt::Binary { .. } => details.type_ = SegmentType::Binary,
+            Opt::BitString { .. } => details.type_ = SegmentType::BitString,
+            Opt::Utf8 { .. } => details.type_ = SegmentType::Utf8,
+            Opt::Utf16 { .. } => details.type_ = SegmentType::Utf16,
+            Opt::Utf32 { .. } => details.type_ = SegmentType::Utf32,
+            Opt::Utf8Codepoint { .. } => details.type_ = SegmentType::Utf8Codepoint,
+            Opt::Utf16Codepoint { .. } => details.type_ = SegmentType::Utf16Codepoint,
+            Opt::Utf32Codepoint { .. } => details.type_ = SegmentType::Utf32Codepoint,
+            Opt::Signed { .. } => details.is_signed = true,
+            Opt::Unsigned { .. } => details.is_signed = false,
+            Opt::Big { .. } => details.is_big_endian = true,
+            // JavaScript has no way to ask for the endianness of the machine,
+            // so native is taken to be little endian like nearly all of them.
+            Opt::Little { .. } | Opt::Native { .. } => details.is_big_endian = false,
+            Opt::Size { value, .. } => details.size = Some(value.as_ref()),
+            Opt::Unit { value, .. } => unit = Some(*value),
+        }
+    }
+
+    details.unit = unit.unwrap_or(match details.type_ {
+        SegmentType::Binary => 8,
+        _ => 1,
+    });
+    details
+}
+
+/// Renders a segment of a bit string expression or constant, which is then
+/// passed to the `toBitString` prelude function.
+///
+fn bit_string_segment<'a, Value: GetLiteralValue>(
+    tracker: &mut UsageTracker,
+    value: Document<'a>,
+    details: &SegmentDetails<'_, Value>,
+    size: Option<Document<'a>>,
+    location: SrcSpan,
+) -> Output<'a> {
+    let size = size.map(|size| match details.literal_size_in_bits() {
+        Some(bits) => bits.to_doc(),
+        None if details.unit == 1 => size,
+        None => docvec![size, " * ", details.unit],
+    });
+    let is_big_endian = if details.is_big_endian {
+        "true"
+    } else {
+        "false"
+    };
+
+    match (details.type_, size) {
+        (SegmentType::Int, None) => Ok(value),
+
+        (SegmentType::Int, Some(size)) => {
+            tracker.sized_integer_segment_used = true;
+            Ok(docvec![
+                "sizedInt(",
+                value,
+                ", ",
+                size,
+                ", ",
+                is_big_endian,
+                ")"
+            ])
+        }
+
+        (SegmentType::Float, None) if details.is_big_endian => {
+            tracker.float_bit_string_segment_used = true;
+            Ok(docvec!["float64Bits(", value, ")"])
+        }
+
+        (SegmentType::Float, _) if details.literal_size_in_bits() == Some(16) => {
+            Err(Error::Unsupported {
+                feature: "16 bit float segments".into(),
+                location,
+            })
+        }
+
+        (SegmentType::Float, size) => {
+            tracker.sized_float_segment_used = true;
+            let size = size.unwrap_or_else(|| "64".to_doc());
+            Ok(docvec![
+                "sizedFloat(",
+                value,
+                ", ",
+                size,
+                ", ",
+                is_big_endian,
+                ")"
+            ])
+        }
+
+        (SegmentType::Binary | SegmentType::BitString, None) => Ok(docvec![value, ".buffer"]),
+
+        (SegmentType::Binary | SegmentType::BitString, Some(size)) => {
+            tracker.sized_bit_string_segment_used = true;
+            Ok(docvec!["sizedBitString(", value, ", ", size, ")"])
+        }
+
+        (SegmentType::Utf8, _) => {
+            tracker.string_bit_string_segment_used = true;
+            Ok(docvec!["stringBits(", value, ")"])
+        }
+
+        (SegmentType::Utf16, _) => {
+            tracker.utf16_string_bit_string_segment_used = true;
+            Ok(docvec!["stringToUtf16(", value, ", ", is_big_endian, ")"])
+        }
+
+        (SegmentType::Utf32, _) => {
+            tracker.utf32_string_bit_string_segment_used = true;
+            Ok(docvec!["stringToUtf32(", value, ", ", is_big_endian, ")"])
+        }
+
+        (SegmentType::Utf8Codepoint, _) => {
+            tracker.codepoint_bit_string_segment_used = true;
+            Ok(docvec!["codepointBits(", value, ")"])
+        }
+
+        (SegmentType::Utf16Codepoint, _) => {
+            tracker.utf16_codepoint_bit_string_segment_used = true;
+            Ok(docvec![
+                "codepointToUtf16(",
+                value,
+                ", ",
+                is_big_endian,
+                ")"
+            ])
+        }
+
+        (SegmentType::Utf32Codepoint, _) => {
+            tracker.utf32_codepoint_bit_string_segment_used = true;
+            Ok(docvec![
+                "codepointToUtf32(",
+                value,
+                ", ",
+                is_big_endian,
+                ")"
+            ])
+        }
+    }
+}
+
 pub fn is_js_scalar(t: Arc<Type>) -> bool {
     t.is_int() || t.is_float() || t.is_bool() || t.is_nil() || t.is_string()
 }
@@ -1,4 +1,7 @@
-use super::{expression::is_js_scalar, *};
+use super::{
+    expression::{is_js_scalar, segment_details, SegmentType},
+    *,
+};
 use crate::{
     analyse::Inferred,
     type_::{FieldMap, PatternConstructor},
@@ -15,10 +18,22 @@ lazy_static! {
 enum Index<'a> {
     Int(usize),
     String(&'a str),
-    ByteAt(usize),
-    IntFromSlice(usize, usize),
-    FloatAt(usize),
-    SliceAfter(usize),
+    ByteAt(Document<'a>),
+    IntFromSlice {
+        start: Document<'a>,
+        end: Document<'a>,
+        is_big_endian: bool,
+        is_signed: bool,
+    },
+    FloatAt(Document<'a>),
+    FloatFromSlice {
+        start: Document<'a>,
+        end: Document<'a>,
+        is_big_endian: bool,
+    },
+    BinaryFromSlice(Document<'a>, Document<'a>),
+    SliceAfter(Document<'a>),
+    CodepointAt(SegmentType, Document<'a>),
     StringPrefixSlice(usize),
 }
 
@@ -36,15 +51,19 @@ pub(crate) struct Generator<'module_ctx, 'expression_gen, 'a> {
     assignments: Vec<Assignment<'a>>,
 }
 
-struct Offset {
+struct Offset<'a> {
     bytes: usize,
+    /// The sizes of earlier segments that are only known at runtime, such as
+    /// those given by a variable, in bytes.
+    dynamic: Vec<Document<'a>>,
     open_ended: bool,
 }
 
-impl Offset {
+impl<'a> Offset<'a> {
     pub fn new() -> Self {
         Self {
             bytes: 0,
+            dynamic: vec![],
             open_ended: false,
         }
     }
@@ -53,9 +72,23 @@ impl Offset {
     pub fn increment(&mut self, step: usize) {
         self.bytes += step
     }
+    pub fn increment_at_runtime(&mut self, step: Document<'a>) {
+        self.dynamic.push(step)
+    }
     pub fn set_open_ended(&mut self) {
         self.open_ended = true
     }
+    pub fn to_doc(&self) -> Document<'a> {
+        if self.dynamic.is_empty() {
+            return self.bytes.to_doc();
+        }
+        let dynamic = join(self.dynamic.iter().cloned(), " + ".to_doc());
+        if self.bytes == 0 {
+            dynamic
+        } else {
+            docvec![self.bytes, " + ", dynamic]
+        }
+    }
 }
 
 impl<'module_ctx, 'expression_gen, 'a> Generator<'module_ctx, 'expression_gen, 'a> {
@@ -90,22 +123,54 @@ impl<'module_ctx, 'expression_gen, 'a> Generator<'module_ctx, 'expression_gen, '
         self.path.push(Index::StringPrefixSlice(i));
     }
 
-    fn push_byte_at(&mut self, i: usize) {
+    fn push_byte_at(&mut self, i: Document<'a>) {
         self.path.push(Index::ByteAt(i));
     }
 
-    fn push_int_from_slice(&mut self, start: usize, end: usize) {
-        self.path.push(Index::IntFromSlice(start, end));
+    fn push_int_from_slice(
+        &mut self,
+        start: Document<'a>,
+        end: Document<'a>,
+        is_big_endian: bool,
+        is_signed: bool,
+    ) {
+        self.path.push(Index::IntFromSlice {
+            start,
+            end,
+            is_big_endian,
+            is_signed,
+        });
     }
 
-    fn push_float_at(&mut self, i: usize) {
+    fn push_float_at(&mut self, i: Document<'a>) {
         self.path.push(Index::FloatAt(i));
     }
 
-    fn push_rest_from(&mut self, i: usize) {
+    fn push_float_from_slice(
+        &mut self,
+        start: Document<'a>,
+        end: Document<'a>,
+        is_big_endian: bool,
+    ) {
+        self.path.push(Index::FloatFromSlice {
+            start,
+            end,
+            is_big_endian,
+        });
+    }
+
+    fn push_binary_from_slice(&mut self, start: Document<'a>, end: Document<'a>) {
+        self.path.push(Index::BinaryFromSlice(start, end));
+    }
+
+    fn push_rest_from(&mut self, i: Document<'a>) {
         self.path.push(Index::SliceAfter(i));
     }
 
+    fn push_codepoint_at(&mut self, type_: SegmentType, index: Document<'a>) {
+        self.path.push(Index::CodepointAt(type_, index));
+    }
+
     fn push_string_times(&mut self, s: &'a str, times: usize) {
         for _ in 0..times {
             self.push_string(s);
@@ -127,10 +192,48 @@ impl<'module_ctx, 'expression_gen, 'a> Generator<'module_ctx, 'expression_gen, '
             Index::Int(i) => Document::String(format!("[{i}]")),
             // TODO: escape string if needed
             Index::String(s) => docvec!(".", s),
-            Index::ByteAt(i) => docvec!(".byteAt(", i, ")"),
-            Index::IntFromSlice(start, end) => docvec!(".intFromSlice(", start, ", ", end, ")"),
-            Index::FloatAt(i) => docvec!(".floatAt(", i, ")"),
-            Index::SliceAfter(i) => docvec!(".sliceAfter(", i, ")"),
+            Index::ByteAt(i) => docvec!(".byteAt(", i.clone(), ")"),
+            Index::IntFromSlice {
+                start,
+                end,
+                is_big_endian,
+                is_signed,
+            } => docvec!(
+                ".intFromSlice(",
+                start.clone(),
+                ", ",
+                end.clone(),
+                ", ",
+                bool(*is_big_endian),
+                ", ",
+                bool(*is_signed),
+                ")"
+            ),
+            Index::FloatAt(i) => docvec!(".floatAt(", i.clone(), ")"),
+            Index::FloatFromSlice {
+                start,
+                end,
+                is_big_endian,
+            } => docvec!(
+                ".floatFromSlice(",
+                start.clone(),
+                ", ",
+                end.clone(),
+                ", ",
+                bool(*is_big_endian),
+                ")"
+            ),
+            Index::BinaryFromSlice(start, end) => {
+                docvec!(".binaryFromSlice(", start.clone(), ", ", end.clone(), ")")
+            }
+            Index::SliceAfter(i) => docvec!(".sliceAfter(", i.clone(), ")"),
+            Index::CodepointAt(SegmentType::Utf8Codepoint, i) => {
+                docvec!(".utf8CodepointAt(", i.clone(), ")")
+            }
+            Index::CodepointAt(SegmentType::Utf16Codepoint, i) => {
+                docvec!(".utf16CodepointAt(", i.clone(), ")")
+            }
+            Index::CodepointAt(_, i) => docvec!(".utf32CodepointAt(", i.clone(), ")"),
             Index::StringPrefixSlice(i) => docvec!(".slice(", i, ")"),
         }))
     }
@@ -432,63 +535,16 @@ impl<'module_ctx, 'expression_gen, 'a> Generator<'module_ctx, 'expression_gen, '
             }
 
             Pattern::BitString { segments, .. } => {
-                use BitStringSegmentOption as Opt;
-
                 let mut offset = Offset::new();
                 for segment in segments {
-                    match segment.options.as_slice() {
-                        [] | [Opt::Int { .. }] => {
-                            self.push_byte_at(offset.bytes);
-                            self.traverse_pattern(subject, &segment.value)?;
-                            self.pop();
-                            offset.increment(1);
-                            Ok(())
-                        }
-
-                        [Opt::Size { value: size, .. }] => match &**size {
-                            Pattern::Int { value, .. } => {
-                                let start = offset.bytes;
-                                let increment = value
-                                    .parse::<usize>()
-                                    .expect("part of an Int node should always parse as integer");
-                                offset.increment(increment / 8);
-                                let end = offset.bytes;
-
-                                self.push_int_from_slice(start, end);
-                                self.traverse_pattern(subject, &segment.value)?;
-                                self.pop();
-                                Ok(())
-                            }
-                            _ => Err(Error::Unsupported {
-                                feature: "This bit string size option in patterns".into(),
-                                location: segment.location,
-                            }),
-                        },
-
-                        [Opt::Float { .. }] => {
-                            self.push_float_at(offset.bytes);
-                            self.traverse_pattern(subject, &segment.value)?;
-                            self.pop();
-                            offset.increment(8);
-                            Ok(())
-                        }
-
-                        [Opt::Binary { .. }] => {
-                            self.push_rest_from(offset.bytes);
-                            self.traverse_pattern(subject, &segment.value)?;
-                            self.pop();
-                            offset.set_open_ended();
-                            Ok(())
-                        }
-
-                        _ => Err(Error::Unsupported {
-                            feature: "This bit string segment option in patterns".into(),
-                            location: segment.location,
-                        }),
-                    }?;
+                    self.traverse_bit_string_segment(subject, segment, &mut offset)?;
                 }
 
-                self.push_bitstring_length_check(subject.clone(), offset.bytes, offset.open_ended);
+                self.push_bitstring_length_check(
+                    subject.clone(),
+                    offset.to_doc(),
+                    offset.open_ended,
+                );
                 Ok(())
             }
             Pattern::VarUsage { location, .. } => Err(Error::Unsupported {
@@ -498,6 +554,169 @@ impl<'module_ctx, 'expression_gen, 'a> Generator<'module_ctx, 'expression_gen, '
         }
     }
 
+    fn traverse_bit_string_segment(
+        &mut self,
+        subject: &Document<'a>,
+        segment: &'a TypedPatternBitStringSegment,
+        offset: &mut Offset<'a>,
+    ) -> Result<(), Error> {
+        let details = segment_details(&segment.options);
+
+        match (details.type_, &*segment.value) {
+            // Strings are matched byte by byte against their encoded literal
+            (
+                SegmentType::Utf8 | SegmentType::Utf16 | SegmentType::Utf32,
+                Pattern::String { value, .. },
+            ) => {
+                let bytes = encode_string(details.type_, details.is_big_endian, value);
+                for byte in bytes {
+                    self.push_byte_at(offset.to_doc());
+                    self.push_equality_check(subject.clone(), byte.to_doc());
+                    self.pop();
+                    offset.increment(1);
+                }
+                return Ok(());
+            }
+
+            (SegmentType::Utf8 | SegmentType::Utf16 | SegmentType::Utf32, _) => {
+                return Err(Error::Unsupported {
+                    feature: "This bit string segment option in patterns".into(),
+                    location: segment.location,
+                })
+            }
+
+            // Codepoints are decoded at runtime, failing to match if the bytes
+            // are not a valid encoding of one. All but utf32 take a varying
+            // number of bytes, so the offset of the segments after them is only
+            // known at runtime.
+            (
+                SegmentType::Utf8Codepoint
+                | SegmentType::Utf16Codepoint
+                | SegmentType::Utf32Codepoint,
+                _,
+            ) => {
+                let bit_string = docvec![subject.clone(), self.path_document()];
+                let start = offset.to_doc();
+                self.push_codepoint_at(details.type_, start.clone());
+                self.push_booly_check(subject.clone(), true);
+                self.traverse_pattern(subject, &segment.value)?;
+                self.pop();
+                match details.type_ {
+                    SegmentType::Utf8Codepoint => offset.increment_at_runtime(docvec![
+                        bit_string,
+                        ".utf8CodepointSize(",
+                        start,
+                        ")"
+                    ]),
+                    SegmentType::Utf16Codepoint => offset.increment_at_runtime(docvec![
+                        bit_string,
+                        ".utf16CodepointSize(",
+                        start,
+                        ")"
+                    ]),
+                    _ => offset.increment(4),
+                }
+                return Ok(());
+            }
+
+            // Unsized binaries take the rest of the bit string
+            (SegmentType::Binary | SegmentType::BitString, _) if details.size.is_none() => {
+                self.push_rest_from(offset.to_doc());
+                self.traverse_pattern(subject, &segment.value)?;
+                self.pop();
+                offset.set_open_ended();
+                return Ok(());
+            }
+
+            _ => (),
+        }
+
+        let start = offset.to_doc();
+        let bits = details.literal_size_in_bits();
+        match (bits, details.size) {
+            (Some(bits), _) if bits >= 0 && bits % 8 == 0 => offset.increment(bits as usize / 8),
+            (Some(_), _) => {
+                return Err(Error::Unsupported {
+                    feature: "Non byte aligned bit string segments in patterns".into(),
+                    location: segment.location,
+                })
+            }
+            // A size given by a variable is only known at runtime, so it can
+            // only be matched when its unit makes it a whole number of bytes.
+            (None, Some(size))
+                if i64::from(details.unit) % 8 == 0 && details.type_ != SegmentType::Float =>
+            {
+                let size = self.bit_string_segment_size(size, segment.location)?;
+                let bytes_per_unit = details.unit / 8;
+                offset.increment_at_runtime(if bytes_per_unit == 1 {
+                    size
+                } else {
+                    docvec![size, " * ", bytes_per_unit]
+                });
+            }
+            (None, _) => {
+                return Err(Error::Unsupported {
+                    feature: "This bit string size option in patterns".into(),
+                    location: segment.location,
+                })
+            }
+        }
+        let end = offset.to_doc();
+
+        match details.type_ {
+            SegmentType::Int if bits == Some(8) && !details.is_signed => self.push_byte_at(start),
+            SegmentType::Int => {
+                self.push_int_from_slice(start, end, details.is_big_endian, details.is_signed)
+            }
+            SegmentType::Float if bits == Some(64) && details.is_big_endian => {
+                self.push_float_at(start)
+            }
+            SegmentType::Float if bits == Some(32) || bits == Some(64) => {
+                self.push_float_from_slice(start, end, details.is_big_endian)
+            }
+            SegmentType::Float => {
+                return Err(Error::Unsupported {
+                    feature: "16 bit float segments in patterns".into(),
+                    location: segment.location,
+                })
+            }
+            _ => self.push_binary_from_slice(start, end),
+        }
+        self.traverse_pattern(subject, &segment.value)?;
+        self.pop();
+        Ok(())
+    }
+
+    /// The size of a segment given by a variable. The checks of a pattern are
+    /// made before any of its variables are assigned, so a variable assigned
+    /// earlier in the same pattern is read from the subject instead.
+    ///
+    fn bit_string_segment_size(
+        &mut self,
+        size: &'a TypedPattern,
+        location: SrcSpan,
+    ) -> Result<Document<'a>, Error> {
+        match size {
+            Pattern::VarUsage { name, .. } => {
+                let assigned = self
+                    .assignments
+                    .iter()
+                    .rev()
+                    .find(|assignment| assignment.name == name.as_str());
+                Ok(match assigned {
+                    Some(assignment) => {
+                        docvec![assignment.subject.clone(), assignment.path.clone()]
+                    }
+                    None => self.local_var(name),
+                })
+            }
+            _ => Err(Error::Unsupported {
+                feature: "This bit string size option in patterns".into(),
+                location,
+            }),
+        }
+    }
+
     fn push_assignment(&mut self, subject: Document<'a>, name: &'a SmolStr) {
         let var = self.next_local_var(name);
         let path = self.path_document();
@@ -566,7 +785,7 @@ impl<'module_ctx, 'expression_gen, 'a> Generator<'module_ctx, 'expression_gen, '
     fn push_bitstring_length_check(
         &mut self,
         subject: Document<'a>,
-        expected_bytes: usize,
+        expected_bytes: Document<'a>,
         has_tail_spread: bool,
     ) {
         self.checks.push(Check::BitStringLength {
@@ -634,7 +853,7 @@ pub enum Check<'a> {
     BitStringLength {
         subject: Document<'a>,
         path: Document<'a>,
-        expected_bytes: usize,
+        expected_bytes: Document<'a>,
         has_tail_spread: bool,
     },
     StringPrefix {
@@ -727,11 +946,11 @@ impl<'a> Check<'a> {
                 expected_bytes,
                 has_tail_spread,
             } => {
-                let length_check = Document::String(if has_tail_spread {
-                    format!(".length >= {expected_bytes}")
+                let length_check = if has_tail_spread {
+                    docvec![".length >= ", expected_bytes]
                 } else {
-                    format!(".length == {expected_bytes}")
-                });
+                    docvec![".length == ", expected_bytes]
+                };
                 if match_desired {
                     docvec![subject, path, length_check,]
                 } else {
@@ -780,6 +999,61 @@ pub(crate) fn assign_subjects<'a>(
     }
     out
 }
+
+fn bool(value: bool) -> Document<'static> {
+    if value {
+        "true".to_doc()
+    } else {
+        "false".to_doc()
+    }
+}
+
+/// Encodes a Gleam string literal, which still has its escape sequences in
+/// it, as the bytes of a utf8, utf16 or utf32 bit string segment.
+///
+fn encode_string(type_: SegmentType, is_big_endian: bool, value: &str) -> Vec<u8> {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some('e') => unescaped.push('\u{1b}'),
+                Some('f') => unescaped.push('\u{0c}'),
+                Some('n') => unescaped.push('\n'),
+                Some('r') => unescaped.push('\r'),
+                Some('t') => unescaped.push('\t'),
+                Some(c) => unescaped.push(c),
+                None => break,
+            },
+            c => unescaped.push(c),
+        }
+    }
+
+    match type_ {
+        SegmentType::Utf16 => unescaped
+            .encode_utf16()
+            .flat_map(|unit| {
+                if is_big_endian {
+                    unit.to_be_bytes()
+                } else {
+                    unit.to_le_bytes()
+                }
+            })
+            .collect(),
+        SegmentType::Utf32 => unescaped
+            .chars()
+            .flat_map(|c| {
+                if is_big_endian {
+                    (c as u32).to_be_bytes()
+                } else {
+                    (c as u32).to_le_bytes()
+                }
+            })
+            .collect(),
+        _ => unescaped.into_bytes(),
+    }
+}
+
 // Helper function to calculate length of str as utf16 without escape characters
 fn utf16_no_escape_len(str: &SmolStr) -> usize {
     let mut filtered_str = String::new();
@@ -181,7 +181,7 @@ fn match_sized() {
     assert_js!(
         r#"
 fn go(x) {
-  let <<a:16, b:8>> = x
+  let assert <<a:16, b:8>> = x
 }
 "#,
     );
@@ -192,7 +192,7 @@ fn discard_sized() {
     assert_js!(
         r#"
 fn go(x) {
-  let <<_:16, _:8>> = x
+  let assert <<_:16, _:8>> = x
 }
 "#,
     );
@@ -203,7 +203,7 @@ fn match_sized_value() {
     assert_js!(
         r#"
 fn go(x) {
-  let <<258:16>> = x
+  let assert <<258:16>> = x
 }
 "#,
     );
@@ -214,7 +214,7 @@ fn match_float() {
     assert_js!(
         r#"
 fn go(x) {
-  let <<a:float, b:int>> = x
+  let assert <<a:float, b:int>> = x
 }
 "#,
     );
@@ -225,7 +225,7 @@ fn match_rest() {
     assert_js!(
         r#"
 fn go(x) {
-  let <<_, b:binary>> = <<1,2,3>>
+  let assert <<_, b:binary>> = <<1,2,3>>
 }
 "#,
     );
@@ -233,3 +233,144 @@ fn go(x) {
 
 // binary rest
 // utf8 matched
+
+#[test]
+fn sized_little_endian() {
+    assert_js!(
+        r#"
+fn go() {
+  <<256:size(16)-little>>
+}
+"#,
+    );
+}
+
+#[test]
+fn sized_with_unit() {
+    assert_js!(
+        r#"
+fn go(x, y) {
+  <<1:size(2)-unit(8), x:size(y)-unit(8)>>
+}
+"#,
+    );
+}
+
+#[test]
+fn sized_float() {
+    assert_js!(
+        r#"
+fn go() {
+  <<1.1:float-size(32), 1.1:float-little>>
+}
+"#,
+    );
+}
+
+#[test]
+fn sized_bit_string() {
+    assert_js!(
+        r#"
+fn go(x) {
+  <<x:bit_string-size(16)>>
+}
+"#,
+    );
+}
+
+#[test]
+fn utf16_and_utf32() {
+    assert_js!(
+        r#"
+fn go(x) {
+  <<"Gleam":utf16, "Gleam":utf32-little, x:utf16_codepoint, x:utf32_codepoint>>
+}
+"#,
+    );
+}
+
+#[test]
+fn constant() {
+    assert_js!(
+        r#"
+const data = <<1, 256:size(16)-little, 1.5:float-size(32), "Gleam":utf8>>
+"#,
+    );
+}
+
+#[test]
+fn match_signed_and_endianness() {
+    assert_js!(
+        r#"
+fn go(x) {
+  let assert <<a:16-little-signed, b:8-signed, c:size(4)-unit(8)-big>> = x
+}
+"#,
+    );
+}
+
+#[test]
+fn match_sized_float() {
+    assert_js!(
+        r#"
+fn go(x) {
+  let assert <<a:float-size(32), b:float-little>> = x
+}
+"#,
+    );
+}
+
+#[test]
+fn match_sized_binary() {
+    assert_js!(
+        r#"
+fn go(x) {
+  let assert <<a:binary-size(2), b:bit_string-size(8), rest:binary>> = x
+}
+"#,
+    );
+}
+
+#[test]
+fn match_utf_strings() {
+    assert_js!(
+        r#"
+fn go(x) {
+  let assert <<"Gl\n":utf8, "é":utf16, "a":utf32-little>> = x
+}
+"#,
+    );
+}
+
+#[test]
+fn match_variable_sized_binary() {
+    assert_js!(
+        r#"
+fn go(x) {
+  let assert <<len:8, data:binary-size(len), rest:bit_string>> = x
+}
+"#,
+    );
+}
+
+#[test]
+fn match_variable_sized_int_with_unit() {
+    assert_js!(
+        r#"
+fn go(x, len) {
+  let assert <<a:size(len)-unit(16), b:binary-size(len)>> = x
+}
+"#,
+    );
+}
+
+#[test]
+fn match_utf_codepoints() {
+    assert_js!(
+        r#"
+fn go(x) {
+  let assert <<a:utf8_codepoint, b:utf16_codepoint, c:utf32_codepoint, d:8>> = x
+}
+"#,
+    );
+}
@@ -5,8 +5,9 @@ fn guards_cause_badmatch_to_render() {
     assert_js!(
         r#"pub fn main(x, y) {
   case x {
-    1 -> 1
-    _ if y -> 0
+    True -> 1
+    False if y -> 0
+    False -> 2
   }
 }
 "#,
@@ -1,10 +1,19 @@
+mod code_action;
 mod compiler;
+mod completion;
+mod configuration;
 mod engine;
 mod feedback;
 mod files;
+mod inlay_hints;
 mod progress;
+mod references;
+mod rename;
 mod router;
+mod semantic_tokens;
 mod server;
+mod signature_help;
+mod symbols;
 
 #[cfg(test)]
 mod tests;
@@ -15,8 +24,8 @@ use crate::{
     ast::SrcSpan, build::Target, line_numbers::LineNumbers, manifest::Manifest,
     paths::ProjectPaths, Result,
 };
-use lsp_types::{Position, Range};
-use std::any::Any;
+use lsp_types::{Position, Range, Url};
+use std::{any::Any, path::PathBuf};
 
 #[derive(Debug)]
 pub struct LockGuard(pub Box<dyn Any>);
@@ -42,3 +51,9 @@ pub fn src_span_to_lsp_range(location: SrcSpan, line_numbers: &LineNumbers) -> R
         Position::new(end.line - 1, end.column - 1),
     )
 }
+
+pub fn path_to_uri(path: PathBuf) -> Url {
+    let mut file: String = "file://".into();
+    file.push_str(&path.as_os_str().to_string_lossy());
+    Url::parse(&file).expect("path_to_uri URL parse")
+}
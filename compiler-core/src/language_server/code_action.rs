@@ -0,0 +1,365 @@
+use std::{collections::HashMap, path::PathBuf};
+
+use lsp_types::{self as lsp, CodeAction, CodeActionKind, Range, TextEdit, Url};
+use smol_str::SmolStr;
+
+use crate::{
+    ast::{Import, ModuleStatement, SrcSpan, UnqualifiedImport},
+    error::closest_match,
+    line_numbers::LineNumbers,
+    parse, type_, Error, Warning,
+};
+
+use super::src_span_to_lsp_range;
+
+/// The problems found by the most recent compilation of each module, kept so
+/// that fixes can be offered for them when the client asks.
+///
+/// As with diagnostics, the warnings for a module remain valid until that
+/// module is compiled again.
+///
+#[derive(Debug, Default)]
+pub struct ProblemsBookKeeper {
+    warnings: HashMap<PathBuf, Vec<Warning>>,
+    error: Option<Error>,
+}
+
+impl ProblemsBookKeeper {
+    /// Replace the warnings of any modules that have been compiled or that
+    /// have new warnings.
+    pub fn compiled(&mut self, compiled: &[PathBuf], warnings: &[Warning]) {
+        for path in compiled {
+            let _ = self.warnings.remove(path);
+        }
+        let mut new_warnings: HashMap<_, Vec<_>> = HashMap::new();
+        for warning in warnings {
+            let Warning::Type { path, .. } = warning;
+            new_warnings
+                .entry(path.clone())
+                .or_default()
+                .push(warning.clone());
+        }
+        self.warnings.extend(new_warnings);
+    }
+
+    /// Record the outcome of a compilation. Any previous error is no longer
+    /// valid as the compiler either succeeded or stopped at a new error.
+    pub fn set_error(&mut self, error: Option<Error>) {
+        self.error = error;
+    }
+
+    pub fn warnings(&self, path: &PathBuf) -> &[Warning] {
+        self.warnings
+            .get(path)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    pub fn error(&self) -> Option<&Error> {
+        self.error.as_ref()
+    }
+}
+
+/// Builds the quick fixes for the problems in one module that are within the
+/// range the client asked about.
+///
+pub struct QuickFixes<'a> {
+    uri: &'a Url,
+    range: Range,
+    /// The diagnostics the client sent, so each fix can say which diagnostic
+    /// it resolves.
+    diagnostics: &'a [lsp::Diagnostic],
+    /// The names of all the modules that could be imported by the module.
+    importable_modules: Vec<&'a SmolStr>,
+    actions: Vec<CodeAction>,
+}
+
+impl<'a> QuickFixes<'a> {
+    pub fn new(
+        uri: &'a Url,
+        range: Range,
+        diagnostics: &'a [lsp::Diagnostic],
+        importable_modules: Vec<&'a SmolStr>,
+    ) -> Self {
+        Self {
+            uri,
+            range,
+            diagnostics,
+            importable_modules,
+            actions: vec![],
+        }
+    }
+
+    pub fn into_actions(self) -> Vec<CodeAction> {
+        self.actions
+    }
+
+    pub fn warning(&mut self, warning: &Warning) {
+        let Warning::Type { src, warning, .. } = warning;
+        let line_numbers = LineNumbers::new(src);
+        match warning {
+            type_::Warning::UnusedImportedModule { location, name } => {
+                let Some(import) = find_import(src, |import| import.location == *location) else {
+                    return;
+                };
+                let title = format!("Remove unused import of `{name}`");
+                let span = whole_lines(src, import_span(src, &import));
+                self.push(&title, *location, &line_numbers, span, "");
+            }
+
+            type_::Warning::UnusedImportedValue { location, name }
+            | type_::Warning::UnusedType {
+                location,
+                name,
+                imported: true,
+            }
+            | type_::Warning::UnusedConstructor {
+                location,
+                name,
+                imported: true,
+            } => {
+                let Some(import) = find_import(src, |import| {
+                    import
+                        .unqualified
+                        .iter()
+                        .any(|item| item.location == *location)
+                }) else {
+                    return;
+                };
+                let Some(span) = unqualified_import_removal(src, &import, *location) else {
+                    return;
+                };
+                let title = format!("Remove unused import of `{name}`");
+                self.push(&title, *location, &line_numbers, span, "");
+            }
+
+            type_::Warning::UnnecessaryDoubleIntNegation { location }
+            | type_::Warning::UnnecessaryDoubleBoolNegation { location } => {
+                let span = SrcSpan::new(location.start, location.start + 2);
+                let title = "Remove double negation";
+                self.push(title, *location, &line_numbers, span, "");
+            }
+
+            type_::Warning::Todo { .. }
+            | type_::Warning::ImplicitlyDiscardedResult { .. }
+            | type_::Warning::UnusedLiteral { .. }
+            | type_::Warning::NoFieldsRecordUpdate { .. }
+            | type_::Warning::AllFieldsRecordUpdate { .. }
+            | type_::Warning::UnusedType { .. }
+            | type_::Warning::UnusedConstructor { .. }
+            | type_::Warning::UnusedPrivateModuleConstant { .. }
+            | type_::Warning::UnusedPrivateFunction { .. }
+            | type_::Warning::UnusedVariable { .. }
+            | type_::Warning::UnreachableCaseClause { .. }
+            | type_::Warning::DeprecatedItem { .. } => (),
+        }
+    }
+
+    pub fn error(&mut self, error: &Error) {
+        let Error::Type { src, errors, .. } = error else {
+            return;
+        };
+        let line_numbers = LineNumbers::new(src);
+        for error in errors {
+            self.type_error(src, &line_numbers, error);
+        }
+    }
+
+    fn type_error(&mut self, src: &str, line_numbers: &LineNumbers, error: &type_::Error) {
+        match error {
+            type_::Error::UnknownVariable {
+                location,
+                name,
+                variables: options,
+            }
+            | type_::Error::UnknownType {
+                location,
+                name,
+                types: options,
+            } => {
+                if let Some(suggestion) = closest_match(name, options) {
+                    let title = format!("Replace with `{suggestion}`");
+                    self.push(&title, *location, line_numbers, *location, suggestion);
+                }
+            }
+
+            type_::Error::UnknownModule { location, name, .. } => {
+                // Only offer to import a module when it is used in an
+                // expression such as `list.map`, not when the unknown module
+                // is itself being imported.
+                let used = src.get(location.start as usize..);
+                if !used.is_some_and(|used| used.starts_with(&format!("{name}."))) {
+                    return;
+                }
+                let insert_at = import_insertion_point(src);
+                let candidates = self
+                    .importable_modules
+                    .iter()
+                    .filter(|module| module.rsplit('/').next() == Some(name.as_str()))
+                    .cloned()
+                    .collect::<Vec<_>>();
+                for module in candidates {
+                    let title = format!("Import `{module}`");
+                    // Leave a blank line between a new first import and the
+                    // rest of the module.
+                    let new_text = if insert_at == 0 {
+                        format!("import {module}\n\n")
+                    } else {
+                        format!("import {module}\n")
+                    };
+                    let span = SrcSpan::new(insert_at, insert_at);
+                    self.push(&title, *location, line_numbers, span, &new_text);
+                }
+            }
+
+            _ => (),
+        }
+    }
+
+    /// Add a fix for the problem at `location`, which replaces the source at
+    /// `span` with `new_text`.
+    fn push(
+        &mut self,
+        title: &str,
+        location: SrcSpan,
+        line_numbers: &LineNumbers,
+        span: SrcSpan,
+        new_text: &str,
+    ) {
+        let problem_range = src_span_to_lsp_range(location, line_numbers);
+        if !overlaps(problem_range, self.range) {
+            return;
+        }
+
+        let diagnostics: Vec<_> = self
+            .diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.range == problem_range)
+            .cloned()
+            .collect();
+        let edit = TextEdit {
+            range: src_span_to_lsp_range(span, line_numbers),
+            new_text: new_text.into(),
+        };
+
+        self.actions.push(CodeAction {
+            title: title.into(),
+            kind: Some(CodeActionKind::QUICKFIX),
+            diagnostics: (!diagnostics.is_empty()).then_some(diagnostics),
+            edit: Some(lsp::WorkspaceEdit {
+                changes: Some(HashMap::from([(self.uri.clone(), vec![edit])])),
+                document_changes: None,
+                change_annotations: None,
+            }),
+            command: None,
+            is_preferred: None,
+            disabled: None,
+            data: None,
+        });
+    }
+}
+
+fn overlaps(a: Range, b: Range) -> bool {
+    a.start <= b.end && b.start <= a.end
+}
+
+/// Find an import in the source of a module. The module has been type checked
+/// so it is known to parse.
+fn find_import(src: &str, predicate: impl Fn(&Import<()>) -> bool) -> Option<Import<()>> {
+    let (module, _) = parse::parse_module(src).ok()?;
+    module
+        .statements
+        .into_iter()
+        .flat_map(|group| group.statements())
+        .find_map(|statement| match statement {
+            ModuleStatement::Import(import) if predicate(&import) => Some(import),
+            _ => None,
+        })
+}
+
+/// The location of an import statement, including the `import` keyword
+/// which is not part of its location.
+fn import_span(src: &str, import: &Import<()>) -> SrcSpan {
+    let start = src
+        .get(..import.location.start as usize)
+        .and_then(|before| before.rfind("import"))
+        .unwrap_or(import.location.start as usize);
+    SrcSpan::new(start as u32, import.location.end)
+}
+
+/// Extend a span to cover the lines it is on, including the final newline,
+/// if there is nothing else on those lines.
+fn whole_lines(src: &str, span: SrcSpan) -> SrcSpan {
+    let before = src.get(..span.start as usize).unwrap_or_default();
+    let after = src.get(span.end as usize..).unwrap_or_default();
+    let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+    let line_end = after.find('\n').map(|i| i + 1).unwrap_or(after.len());
+    if !before[line_start..].trim().is_empty() || !after[..line_end].trim().is_empty() {
+        return span;
+    }
+    SrcSpan::new(line_start as u32, span.end + line_end as u32)
+}
+
+/// The end of an unqualified import, including any alias given with `as`.
+fn unqualified_import_end(src: &str, item: &UnqualifiedImport) -> u32 {
+    let Some(alias) = &item.as_name else {
+        return item.location.end;
+    };
+    let after = src.get(item.location.end as usize..).unwrap_or_default();
+    after
+        .find(alias.as_str())
+        .map(|offset| item.location.end + (offset + alias.len()) as u32)
+        .unwrap_or(item.location.end)
+}
+
+/// The span to remove to take an unqualified import out of an import
+/// statement, along with the comma separating it from its neighbours. If it
+/// is the only unqualified import the whole `.{...}` is removed.
+fn unqualified_import_removal(
+    src: &str,
+    import: &Import<()>,
+    location: SrcSpan,
+) -> Option<SrcSpan> {
+    let items = &import.unqualified;
+    let index = items.iter().position(|item| item.location == location)?;
+    let item = items.get(index)?;
+
+    if items.len() == 1 {
+        let statement = src.get(..import.location.end as usize)?;
+        let open = statement.get(..location.start as usize)?.rfind(".{")?;
+        let close = statement.rfind('}')?;
+        return Some(SrcSpan::new(open as u32, close as u32 + 1));
+    }
+
+    match items.get(index + 1) {
+        Some(next) => Some(SrcSpan::new(item.location.start, next.location.start)),
+        None => {
+            let previous = items.get(index.checked_sub(1)?)?;
+            let start = unqualified_import_end(src, previous);
+            Some(SrcSpan::new(start, unqualified_import_end(src, item)))
+        }
+    }
+}
+
+/// Where to insert a new import: after the existing imports if there are
+/// any, otherwise at the start of the module.
+fn import_insertion_point(src: &str) -> u32 {
+    let Ok((module, _)) = parse::parse_module(src) else {
+        return 0;
+    };
+    module
+        .statements
+        .iter()
+        .flat_map(|group| group.statements_ref())
+        .filter_map(|statement| match statement {
+            ModuleStatement::Import(import) => Some(import.location.end),
+            _ => None,
+        })
+        .max()
+        .map(|end| {
+            let after = src.get(end as usize..).unwrap_or_default();
+            let line_end = after.find('\n').map(|i| i + 1).unwrap_or(after.len());
+            end + line_end as u32
+        })
+        .unwrap_or(0)
+}
@@ -1,17 +1,26 @@
 use debug_ignore::DebugIgnore;
+use smol_str::SmolStr;
 
 use crate::{
-    build::{self, Mode, Module, NullTelemetry, ProjectCompiler},
+    build::{self, package_compiler, Mode, Module, NullTelemetry, Origin, ProjectCompiler},
     config::PackageConfig,
     io::{CommandExecutor, FileSystemReader, FileSystemWriter, Stdio},
     language_server::Locker,
     line_numbers::LineNumbers,
     manifest::Manifest,
+    parse::error::ParseError,
     paths::ProjectPaths,
-    warning::VectorWarningEmitterIO,
+    type_,
+    warning::{TypeWarningEmitter, VectorWarningEmitterIO},
     Error, Result, Warning,
 };
-use std::{collections::HashMap, path::PathBuf, sync::Arc};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::SystemTime,
+};
+use vec1::Vec1;
 
 /// A wrapper around the project compiler which makes it possible to repeatedly
 /// recompile the top level package, reusing the information about the already
@@ -114,8 +123,15 @@ where
         // Restore the state so that later we can compile the root again
         self.project_compiler.restore(checkpoint);
 
-        // Return any error
-        let package = result?;
+        // Return any error, recovering from syntax errors so that all of them
+        // can be reported
+        let package = match result {
+            Ok(package) => package,
+            Err(Error::Parse { path, src, errors }) => {
+                return Err(self.recover_from_syntax_errors(path, src, errors))
+            }
+            Err(error) => return Err(error),
+        };
         let mut compiled_modules = Vec::with_capacity(package.modules.len());
 
         // Store the compiled module information
@@ -130,6 +146,139 @@ where
 
         Ok(compiled_modules)
     }
+
+    /// Parse a module with error recovery so that each of its syntax errors
+    /// can be reported, rather than only the first.
+    ///
+    /// The definitions that could be parsed are type checked against the
+    /// most recently compiled modules and, if that succeeds, replace any
+    /// previously compiled version of the module so that hovering and the
+    /// like keep working for the parts of the module without errors.
+    ///
+    fn recover_from_syntax_errors(
+        &mut self,
+        path: PathBuf,
+        src: SmolStr,
+        errors: Vec1<ParseError>,
+    ) -> Error {
+        let (mut ast, extra, recovered) = crate::parse::parse_module_recovering(&src);
+        let errors = Vec1::try_from_vec(recovered).unwrap_or(errors);
+        let error = Error::Parse {
+            path: path.clone(),
+            src: src.clone(),
+            errors,
+        };
+
+        let previous = self
+            .modules
+            .values()
+            .find(|module| module.input_path == path);
+        let (name, origin, mtime) = match previous {
+            Some(previous) => (previous.name.clone(), previous.origin, previous.mtime),
+            None => match self.module_name_and_origin(&path) {
+                Some((name, origin)) => {
+                    let io = self.project_compiler.io();
+                    let mtime = io
+                        .modification_time(&path)
+                        .unwrap_or_else(|_| SystemTime::now());
+                    (name, origin, mtime)
+                }
+                None => return error,
+            },
+        };
+
+        let target = self.project_compiler.target();
+        let ids = self.project_compiler.ids().clone();
+        let package = self.project_compiler.config().name.clone();
+        ast.name = name.clone();
+        let dependencies = ast.dependencies(target);
+
+        // The module is checked against the dependencies and the most
+        // recently compiled modules of the project, which are added to the
+        // importable modules for the duration.
+        let importable_modules = self.project_compiler.get_importable_modules_mut();
+        let mut added = vec![(
+            SmolStr::from("gleam"),
+            importable_modules.insert("gleam".into(), type_::build_prelude(&ids)),
+        )];
+        for module in self.modules.values() {
+            let replaced =
+                importable_modules.insert(module.name.clone(), module.ast.type_info.clone());
+            added.push((module.name.clone(), replaced));
+        }
+
+        // Warnings would be misleading as the definitions with errors are
+        // missing, so they are discarded.
+        let warnings = TypeWarningEmitter::null();
+        let result = crate::analyse::infer_module(
+            target,
+            &ids,
+            ast,
+            origin,
+            &package,
+            importable_modules,
+            &warnings,
+        );
+
+        for (name, replaced) in added {
+            let _ = match replaced {
+                Some(module) => importable_modules.insert(name, module),
+                None => importable_modules.remove(&name),
+            };
+        }
+
+        let ast = match result {
+            Ok(ast) => ast,
+            Err(_) => return error,
+        };
+
+        let line_numbers = LineNumbers::new(&src);
+        let source = ModuleSourceInformation {
+            path: path.as_os_str().to_string_lossy().to_string(),
+            line_numbers,
+        };
+        let module = Module {
+            name: name.clone(),
+            code: src,
+            mtime,
+            input_path: path,
+            origin,
+            ast,
+            extra,
+            dependencies,
+        };
+        _ = self.sources.insert(name.to_string(), source);
+        _ = self.modules.insert(name.to_string(), module);
+        error
+    }
+
+    /// The name of a module of the project and whether it is a test module,
+    /// worked out from its path.
+    fn module_name_and_origin(&self, path: &Path) -> Option<(SmolStr, Origin)> {
+        let paths = self.project_compiler.paths();
+        [
+            (paths.src_directory(), Origin::Src),
+            (paths.test_directory(), Origin::Test),
+        ]
+        .into_iter()
+        .find(|(directory, _)| path.starts_with(directory))
+        .map(|(directory, origin)| (package_compiler::module_name(&directory, path), origin))
+    }
+
+    /// The interfaces of the dependency modules, with the modules of the
+    /// project taking their most recently compiled form.
+    pub fn importable_modules(&self) -> HashMap<SmolStr, &type_::Module> {
+        let mut importable_modules: HashMap<_, _> = self
+            .project_compiler
+            .get_importable_modules()
+            .iter()
+            .map(|(name, module)| (name.clone(), module))
+            .collect();
+        for module in self.modules.values() {
+            let _ = importable_modules.insert(module.name.clone(), &module.ast.type_info);
+        }
+        importable_modules
+    }
 }
 
 impl<IO> LspProjectCompiler<IO> {
@@ -0,0 +1,673 @@
+use std::{collections::HashMap, sync::Arc};
+
+use itertools::Itertools;
+use lsp_types::{
+    CompletionItem, CompletionItemKind, CompletionItemTag, Documentation, MarkupContent, MarkupKind,
+};
+use smol_str::SmolStr;
+
+use crate::{
+    analyse::get_compatible_record_fields,
+    ast::{
+        ArgNames, AssignName, Deprecation, ModuleStatement, Pattern, Statement, TypedArg,
+        TypedExpr, TypedPattern, TypedStatement,
+    },
+    build::{Module, Origin},
+    type_::{
+        self, collapse_links, pretty::Printer, Type, ValueConstructor, ValueConstructorVariant,
+    },
+};
+
+/// What is being completed, worked out from the source code before the
+/// cursor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Context<'a> {
+    /// The path of the module being imported in an `import` statement.
+    ImportPath,
+    /// The list of unqualified values and types in an `import` statement.
+    UnqualifiedImport { module: &'a str },
+    /// The name after a `.`, which is a member of an imported module or a
+    /// field of a record held in a variable.
+    Select { qualifier: &'a str },
+    /// Any other expression.
+    Expression,
+    /// Somewhere nothing can be usefully suggested, such as after the `as`
+    /// of an import.
+    Nothing,
+}
+
+/// Suggests completions for the name being typed at a position in a module.
+///
+/// The source code being edited is often not valid Gleam as the programmer is
+/// part way through typing something, so the last successfully compiled
+/// version of the module is used to find out what is in scope, while the
+/// current source code is used to find out what is being typed.
+///
+pub struct Completer<'a> {
+    /// The current source code of the module, which may be newer than the
+    /// compiled module.
+    src: &'a str,
+    byte_index: u32,
+    module: &'a Module,
+    /// The interfaces of every module that can be imported, including the
+    /// modules of the project.
+    importable_modules: &'a HashMap<SmolStr, &'a type_::Module>,
+    /// The compiled modules of the project, used to find the documentation of
+    /// the types they define.
+    project_modules: &'a HashMap<String, Module>,
+}
+
+impl<'a> Completer<'a> {
+    pub fn new(
+        src: &'a str,
+        byte_index: u32,
+        module: &'a Module,
+        importable_modules: &'a HashMap<SmolStr, &'a type_::Module>,
+        project_modules: &'a HashMap<String, Module>,
+    ) -> Self {
+        Self {
+            src,
+            byte_index,
+            module,
+            importable_modules,
+            project_modules,
+        }
+    }
+
+    pub fn completions(&self) -> Vec<CompletionItem> {
+        match context(self.src, self.byte_index as usize) {
+            Context::ImportPath => self.import_path_completions(),
+            Context::UnqualifiedImport { module } => self
+                .importable_modules
+                .get(module)
+                .map(|module| self.module_member_completions(module))
+                .unwrap_or_default(),
+            Context::Select { qualifier } => self.select_completions(qualifier),
+            Context::Expression => self.expression_completions(),
+            Context::Nothing => vec![],
+        }
+    }
+
+    fn import_path_completions(&self) -> Vec<CompletionItem> {
+        let in_test = self.module.origin == Origin::Test;
+        self.importable_modules
+            .iter()
+            // Test modules can only be imported by other test modules
+            .filter(|(_, module)| in_test || module.origin == Origin::Src)
+            .filter(|(name, _)| *name != &self.module.name && name.as_str() != "gleam")
+            .map(|(name, module)| CompletionItem {
+                label: name.to_string(),
+                kind: Some(CompletionItemKind::MODULE),
+                detail: Some(module.package.to_string()).filter(|package| !package.is_empty()),
+                ..Default::default()
+            })
+            .sorted_by(|a, b| a.label.cmp(&b.label))
+            .collect()
+    }
+
+    fn select_completions(&self, qualifier: &str) -> Vec<CompletionItem> {
+        // A local variable shadows an imported module with the same name
+        let locals = self.locals();
+        if let Some(local) = locals.iter().find(|local| local.name == qualifier) {
+            return self.field_completions(local.type_.clone());
+        }
+
+        let imported = imported_module(self.module, qualifier);
+        match imported.and_then(|module| self.importable_modules.get(module)) {
+            Some(module) => self.module_member_completions(module),
+            None => vec![],
+        }
+    }
+
+    /// The public values and types of a module.
+    fn module_member_completions(&self, module: &type_::Module) -> Vec<CompletionItem> {
+        let values = module
+            .values
+            .iter()
+            .filter(|(_, value)| value.public)
+            .map(|(name, value)| value_completion(name, value));
+        let types = module
+            .types
+            .iter()
+            .filter(|(_, type_)| type_.public)
+            .map(|(name, type_)| {
+                let documentation = self.type_documentation(&module.name, name);
+                let item = completion(name, CompletionItemKind::CLASS, &type_.typ, documentation);
+                with_deprecation(item, &type_.deprecation)
+            });
+        values
+            .chain(types)
+            .sorted_by(|a, b| a.label.cmp(&b.label))
+            .collect()
+    }
+
+    /// The labelled fields of the record held by a variable of the given type.
+    fn field_completions(&self, type_: Arc<Type>) -> Vec<CompletionItem> {
+        let (module, name) = match collapse_links(type_).as_ref() {
+            Type::App { module, name, .. } => (module.clone(), name.clone()),
+            _ => return vec![],
+        };
+
+        // The accessors of private types are not part of the module interface
+        // so fields of types defined in this module come from its definition.
+        if module == self.module.name {
+            let custom_type =
+                self.module
+                    .ast
+                    .statements
+                    .iter()
+                    .find_map(|statement| match statement {
+                        ModuleStatement::CustomType(custom_type) if custom_type.name == name => {
+                            Some(custom_type)
+                        }
+                        _ => None,
+                    });
+            let constructors = match custom_type {
+                Some(custom_type) => &custom_type.constructors,
+                None => return vec![],
+            };
+            return get_compatible_record_fields(constructors)
+                .into_iter()
+                .filter_map(|(index, label, _)| {
+                    let argument = constructors.first()?.arguments.get(index)?;
+                    Some(completion(
+                        label,
+                        CompletionItemKind::FIELD,
+                        &argument.type_,
+                        argument.doc.as_deref(),
+                    ))
+                })
+                .collect();
+        }
+
+        let accessors = self
+            .importable_modules
+            .get(&module)
+            .and_then(|module| module.accessors.get(&name));
+        match accessors {
+            Some(accessors) => accessors
+                .accessors
+                .values()
+                .sorted_by_key(|accessor| accessor.index)
+                .map(|accessor| {
+                    completion(
+                        &accessor.label,
+                        CompletionItemKind::FIELD,
+                        &accessor.type_,
+                        None,
+                    )
+                })
+                .collect(),
+            None => vec![],
+        }
+    }
+
+    /// The local variables in scope, followed by the values defined in or
+    /// imported into the module, followed by the values of the prelude.
+    fn expression_completions(&self) -> Vec<CompletionItem> {
+        let mut completions: Vec<_> = self
+            .locals()
+            .into_iter()
+            .map(|local| completion(local.name, CompletionItemKind::VARIABLE, &local.type_, None))
+            .collect();
+
+        for statement in &self.module.ast.statements {
+            match statement {
+                ModuleStatement::Function(function) => {
+                    let arguments = function.arguments.iter().map(|arg| arg.type_.clone());
+                    let type_ = type_::fn_(arguments.collect(), function.return_type.clone());
+                    let item = completion(
+                        &function.name,
+                        CompletionItemKind::FUNCTION,
+                        &type_,
+                        function.documentation.as_deref(),
+                    );
+                    completions.push(with_deprecation(item, &function.deprecation));
+                }
+
+                ModuleStatement::ExternalFunction(function) => {
+                    let arguments = function.arguments.iter().map(|arg| arg.type_.clone());
+                    let type_ = type_::fn_(arguments.collect(), function.return_type.clone());
+                    let item = completion(
+                        &function.name,
+                        CompletionItemKind::FUNCTION,
+                        &type_,
+                        function.documentation.as_deref(),
+                    );
+                    completions.push(with_deprecation(item, &function.deprecation));
+                }
+
+                ModuleStatement::ModuleConstant(constant) => {
+                    let item = completion(
+                        &constant.name,
+                        CompletionItemKind::CONSTANT,
+                        &constant.type_,
+                        constant.documentation.as_deref(),
+                    );
+                    completions.push(with_deprecation(item, &constant.deprecation));
+                }
+
+                ModuleStatement::CustomType(custom_type) => {
+                    let record_type = Arc::new(Type::App {
+                        public: custom_type.public,
+                        module: self.module.name.clone(),
+                        name: custom_type.name.clone(),
+                        args: custom_type.typed_parameters.clone(),
+                    });
+                    for constructor in &custom_type.constructors {
+                        let type_ = if constructor.arguments.is_empty() {
+                            record_type.clone()
+                        } else {
+                            let arguments = constructor.arguments.iter().map(|a| a.type_.clone());
+                            type_::fn_(arguments.collect(), record_type.clone())
+                        };
+                        let item = completion(
+                            &constructor.name,
+                            CompletionItemKind::CONSTRUCTOR,
+                            &type_,
+                            constructor.documentation.as_deref(),
+                        );
+                        completions.push(with_deprecation(item, &custom_type.deprecation));
+                    }
+                }
+
+                ModuleStatement::Import(import) => {
+                    let module = match self.importable_modules.get(&import.module) {
+                        Some(module) => module,
+                        None => continue,
+                    };
+                    for unqualified in &import.unqualified {
+                        if let Some(value) = module.values.get(&unqualified.name) {
+                            completions.push(value_completion(unqualified.variable_name(), value));
+                        }
+                    }
+                }
+
+                ModuleStatement::TypeAlias(_) | ModuleStatement::ExternalType(_) => (),
+            }
+        }
+
+        let prelude = type_::build_prelude(&crate::uid::UniqueIdGenerator::new());
+        completions.extend(
+            prelude
+                .values
+                .iter()
+                .sorted_by(|a, b| a.0.cmp(b.0))
+                .map(|(name, value)| value_completion(name, value)),
+        );
+
+        completions
+    }
+
+    fn type_documentation(&self, module: &str, name: &str) -> Option<&'a str> {
+        let module = self.project_modules.get(module)?;
+        module
+            .ast
+            .statements
+            .iter()
+            .find_map(|statement| match statement {
+                ModuleStatement::CustomType(custom_type) if custom_type.name == name => {
+                    Some(custom_type.documentation.as_deref())
+                }
+                ModuleStatement::ExternalType(type_) if type_.name == name => {
+                    Some(type_.documentation.as_deref())
+                }
+                ModuleStatement::TypeAlias(alias) if alias.alias == name => {
+                    Some(alias.documentation.as_deref())
+                }
+                _ => None,
+            })
+            .flatten()
+    }
+
+    fn locals(&self) -> Vec<Local<'a>> {
+        locals(self.module, self.byte_index)
+    }
+}
+
+/// The name of the module imported with the given alias, if any.
+pub(super) fn imported_module<'a>(module: &'a Module, alias: &str) -> Option<&'a SmolStr> {
+    module.ast.statements.iter().find_map(|statement| {
+        let ModuleStatement::Import(import) = statement else {
+            return None;
+        };
+        (import.variable_name() == alias).then_some(&import.module)
+    })
+}
+
+/// The local variables in scope at a position in a module, with later
+/// definitions shadowing earlier ones.
+pub(super) fn locals(module: &Module, byte_index: u32) -> Vec<Local<'_>> {
+    // The compiled module may be older than the source being edited, so
+    // the cursor is taken to be in the last function that starts before
+    // it, even if that function appeared to end earlier. The statements
+    // are not in source order as they are sorted for type inference.
+    let function = module
+        .ast
+        .statements
+        .iter()
+        .filter(|statement| statement.location().start <= byte_index)
+        .max_by_key(|statement| statement.location().start);
+    let function = match function {
+        Some(ModuleStatement::Function(function)) => function,
+        _ => return vec![],
+    };
+
+    let mut collector = LocalsCollector {
+        byte_index,
+        locals: vec![],
+    };
+    for argument in &function.arguments {
+        collector.argument(argument);
+    }
+    collector.statements(&function.body);
+
+    collector
+        .locals
+        .into_iter()
+        .rev()
+        .unique_by(|local| local.name)
+        .collect()
+}
+
+fn context(src: &str, byte_index: usize) -> Context<'_> {
+    let is_name_char = |c: char| c.is_ascii_alphanumeric() || c == '_';
+    let before = src.get(..byte_index).unwrap_or(src);
+
+    // The start of the `import` statement the cursor may be in
+    let import_start = before
+        .rfind("\nimport ")
+        .map(|index| index + 1)
+        .or_else(|| before.starts_with("import ").then_some(0));
+    if let Some(start) = import_start {
+        let statement = &before[start + "import ".len()..];
+        match statement.split_once(".{") {
+            Some((module, unqualified)) if !unqualified.contains('}') => {
+                return Context::UnqualifiedImport {
+                    module: module.trim(),
+                }
+            }
+            Some(_) => (),
+            None if !statement.contains(char::is_whitespace) => return Context::ImportPath,
+            None if !statement.contains('\n') => return Context::Nothing,
+            None => (),
+        }
+    }
+
+    // Skip back over the name being typed
+    let before_name = before.trim_end_matches(is_name_char);
+    match before_name.strip_suffix('.') {
+        None => Context::Expression,
+        Some(before_dot) => {
+            let qualifier_start = before_dot.trim_end_matches(is_name_char).len();
+            let qualifier = &before_dot[qualifier_start..];
+            let is_lowercase_name = qualifier.starts_with(|c: char| c.is_ascii_lowercase());
+            let is_chained = before_dot[..qualifier_start].ends_with('.');
+            if is_lowercase_name && !is_chained {
+                Context::Select { qualifier }
+            } else {
+                Context::Nothing
+            }
+        }
+    }
+}
+
+fn value_completion(name: &str, value: &ValueConstructor) -> CompletionItem {
+    let kind = match value.variant {
+        ValueConstructorVariant::LocalVariable { .. } => CompletionItemKind::VARIABLE,
+        ValueConstructorVariant::ModuleConstant { .. } => CompletionItemKind::CONSTANT,
+        ValueConstructorVariant::ModuleFn { .. } => CompletionItemKind::FUNCTION,
+        ValueConstructorVariant::Record { .. } => CompletionItemKind::CONSTRUCTOR,
+    };
+    let documentation = match &value.variant {
+        ValueConstructorVariant::LocalVariable { .. } => None,
+        ValueConstructorVariant::ModuleConstant { documentation, .. }
+        | ValueConstructorVariant::ModuleFn { documentation, .. }
+        | ValueConstructorVariant::Record { documentation, .. } => documentation.as_deref(),
+    };
+    let item = completion(name, kind, &value.type_, documentation);
+    with_deprecation(item, &value.deprecation)
+}
+
+fn completion(
+    label: &str,
+    kind: CompletionItemKind,
+    type_: &Type,
+    documentation: Option<&str>,
+) -> CompletionItem {
+    CompletionItem {
+        label: label.into(),
+        kind: Some(kind),
+        detail: Some(Printer::new().pretty_print(type_, 0)),
+        documentation: documentation.map(|documentation| {
+            Documentation::MarkupContent(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value: documentation.into(),
+            })
+        }),
+        ..Default::default()
+    }
+}
+
+/// Deprecated items are tagged so that editors show them struck through.
+fn with_deprecation(item: CompletionItem, deprecation: &Deprecation) -> CompletionItem {
+    match deprecation {
+        Deprecation::NotDeprecated => item,
+        Deprecation::Deprecated { .. } => CompletionItem {
+            tags: Some(vec![CompletionItemTag::DEPRECATED]),
+            ..item
+        },
+    }
+}
+
+#[derive(Debug)]
+pub(super) struct Local<'a> {
+    pub name: &'a SmolStr,
+    pub type_: Arc<Type>,
+}
+
+/// Collects the variables defined before a position in a function, skipping
+/// over any nested functions and case clauses that do not contain it.
+struct LocalsCollector<'a> {
+    byte_index: u32,
+    locals: Vec<Local<'a>>,
+}
+
+impl<'a> LocalsCollector<'a> {
+    fn push(&mut self, name: &'a SmolStr, type_: Arc<Type>) {
+        // Variables generated by the compiler start with an underscore and
+        // cannot be referenced by the programmer.
+        if !name.starts_with('_') {
+            self.locals.push(Local { name, type_ });
+        }
+    }
+
+    fn contains(&self, expression: &TypedExpr) -> bool {
+        let location = expression.location();
+        location.start <= self.byte_index && self.byte_index <= location.end
+    }
+
+    fn argument(&mut self, argument: &'a TypedArg) {
+        match &argument.names {
+            ArgNames::Named { name } | ArgNames::NamedLabelled { name, .. } => {
+                self.push(name, argument.type_.clone())
+            }
+            ArgNames::Discard { .. } | ArgNames::LabelledDiscard { .. } => (),
+        }
+    }
+
+    fn statements(&mut self, statements: &'a [TypedStatement]) {
+        for statement in statements {
+            match statement {
+                Statement::Expression(expression) => {
+                    if expression.location().start > self.byte_index {
+                        return;
+                    }
+                    if self.contains(expression) {
+                        return self.expression(expression);
+                    }
+                }
+
+                // A variable is in scope once the value assigned to it has
+                // been written.
+                Statement::Assignment(assignment) => {
+                    if assignment.location.start > self.byte_index {
+                        return;
+                    }
+                    if self.byte_index <= assignment.value.location().end {
+                        return self.expression(&assignment.value);
+                    }
+                    self.pattern(&assignment.pattern);
+                }
+
+                Statement::Use(_) => (),
+            }
+        }
+    }
+
+    fn expression(&mut self, expression: &'a TypedExpr) {
+        match expression {
+            TypedExpr::Int { .. }
+            | TypedExpr::Float { .. }
+            | TypedExpr::String { .. }
+            | TypedExpr::Var { .. }
+            | TypedExpr::ModuleSelect { .. }
+            | TypedExpr::Todo { .. }
+            | TypedExpr::Panic { .. } => (),
+
+            TypedExpr::Block { statements, .. } => self.statements(statements),
+
+            TypedExpr::Fn { args, body, .. } => {
+                for argument in args {
+                    self.argument(argument);
+                }
+                self.statements(body);
+            }
+
+            TypedExpr::Case {
+                subjects, clauses, ..
+            } => {
+                self.any_of(subjects);
+                let clause = clauses.iter().find(|clause| {
+                    let location = clause.location();
+                    location.start <= self.byte_index && self.byte_index <= location.end
+                });
+                if let Some(clause) = clause {
+                    // Alternative patterns define the same variables
+                    for pattern in &clause.pattern {
+                        self.pattern(pattern);
+                    }
+                    if self.contains(&clause.then) {
+                        self.expression(&clause.then);
+                    }
+                }
+            }
+
+            TypedExpr::Pipeline {
+                assignments,
+                finally,
+                ..
+            } => {
+                for assignment in assignments {
+                    if self.contains(&assignment.value) {
+                        return self.expression(&assignment.value);
+                    }
+                }
+                if self.contains(finally) {
+                    self.expression(finally);
+                }
+            }
+
+            TypedExpr::List { elements, tail, .. } => {
+                self.any_of(elements.iter().chain(tail.as_deref()))
+            }
+
+            TypedExpr::Call { fun, args, .. } => {
+                let arguments = args.iter().map(|argument| &argument.value);
+                self.any_of(std::iter::once(fun.as_ref()).chain(arguments))
+            }
+
+            TypedExpr::BinOp { left, right, .. } => self.any_of([left.as_ref(), right.as_ref()]),
+
+            TypedExpr::RecordAccess { record, .. } => self.any_of([record.as_ref()]),
+
+            TypedExpr::Tuple { elems, .. } => self.any_of(elems),
+
+            TypedExpr::TupleIndex { tuple, .. } => self.any_of([tuple.as_ref()]),
+
+            TypedExpr::BitString { segments, .. } => {
+                self.any_of(segments.iter().map(|segment| segment.value.as_ref()))
+            }
+
+            TypedExpr::RecordUpdate { spread, args, .. } => {
+                let arguments = args.iter().map(|argument| &argument.value);
+                self.any_of(std::iter::once(spread.as_ref()).chain(arguments))
+            }
+
+            TypedExpr::NegateBool { value, .. } | TypedExpr::NegateInt { value, .. } => {
+                self.any_of([value.as_ref()])
+            }
+        }
+    }
+
+    /// Collect the variables from whichever of the expressions contains the
+    /// position, if any.
+    fn any_of(&mut self, expressions: impl IntoIterator<Item = &'a TypedExpr>) {
+        if let Some(expression) = expressions.into_iter().find(|e| self.contains(e)) {
+            self.expression(expression);
+        }
+    }
+
+    fn pattern(&mut self, pattern: &'a TypedPattern) {
+        match pattern {
+            Pattern::Int { .. }
+            | Pattern::Float { .. }
+            | Pattern::String { .. }
+            | Pattern::VarUsage { .. }
+            | Pattern::Discard { .. } => (),
+
+            Pattern::Var { name, type_, .. } => self.push(name, type_.clone()),
+
+            Pattern::Assign { name, pattern, .. } => {
+                self.push(name, pattern.type_());
+                self.pattern(pattern);
+            }
+
+            Pattern::List { elements, tail, .. } => {
+                for element in elements {
+                    self.pattern(element);
+                }
+                if let Some(tail) = tail {
+                    self.pattern(tail);
+                }
+            }
+
+            Pattern::Constructor { arguments, .. } => {
+                for argument in arguments {
+                    self.pattern(&argument.value);
+                }
+            }
+
+            Pattern::Tuple { elems, .. } => {
+                for elem in elems {
+                    self.pattern(elem);
+                }
+            }
+
+            Pattern::BitString { segments, .. } => {
+                for segment in segments {
+                    self.pattern(&segment.value);
+                }
+            }
+
+            Pattern::Concatenate {
+                right_side_assignment,
+                ..
+            } => {
+                if let AssignName::Variable(name) = right_side_assignment {
+                    self.push(name, type_::string());
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use serde::Deserialize;
+
+/// Settings for the language server given by the client, either as the
+/// initialisation options or in a `workspace/didChangeConfiguration`
+/// notification.
+///
+/// Any settings that are not given take their default value.
+///
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Configuration {
+    pub inlay_hints: InlayHintsConfig,
+}
+
+impl Configuration {
+    /// Read the configuration from the JSON sent by the client. The settings
+    /// may be nested under a `gleam` key, as editors commonly do when they
+    /// send the settings for all their extensions. Settings that cannot be
+    /// understood are ignored.
+    pub fn from_json(json: serde_json::Value) -> Self {
+        let json = match json {
+            serde_json::Value::Object(mut object) if object.contains_key("gleam") => {
+                object.remove("gleam").unwrap_or_default()
+            }
+            json => json,
+        };
+        serde_json::from_value(json).unwrap_or_default()
+    }
+}
+
+/// Which kinds of inlay hints to show. All are shown by default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct InlayHintsConfig {
+    /// The type of the pattern of a `let` without a type annotation.
+    pub let_bindings: bool,
+    /// The type of each step of a pipeline that spans multiple lines.
+    pub pipelines: bool,
+    /// The type of function arguments without a type annotation.
+    pub function_parameters: bool,
+    /// The return type of functions without a return annotation.
+    pub function_return_types: bool,
+}
+
+impl Default for InlayHintsConfig {
+    fn default() -> Self {
+        Self {
+            let_bindings: true,
+            pipelines: true,
+            function_parameters: true,
+            function_return_types: true,
+        }
+    }
+}
@@ -1,20 +1,33 @@
 use crate::{
-    ast::{TypedExpr, TypedPattern},
+    ast::{SrcSpan, TypedExpr, TypedPattern},
     build::{Located, Module},
     config::PackageConfig,
     io::{CommandExecutor, FileSystemReader, FileSystemWriter},
     language_server::{
-        compiler::LspProjectCompiler, files::FileSystemProxy, progress::ProgressReporter,
+        code_action::{ProblemsBookKeeper, QuickFixes},
+        compiler::LspProjectCompiler,
+        completion::Completer,
+        configuration::InlayHintsConfig,
+        files::FileSystemProxy,
+        inlay_hints,
+        progress::ProgressReporter,
+        references::{self, ReferenceKind},
+        rename::{self, RenameError},
+        semantic_tokens,
+        signature_help::SignatureHelper,
+        symbols,
     },
     line_numbers::LineNumbers,
     paths::ProjectPaths,
-    type_::pretty::Printer,
+    type_::{self, pretty::Printer},
     Error, Result, Warning,
 };
+use itertools::Itertools;
 use lsp_types::{self as lsp, Hover, HoverContents, MarkedString, Url};
+use smol_str::SmolStr;
 use std::path::PathBuf;
 
-use super::{src_span_to_lsp_range, DownloadDependencies, MakeLocker};
+use super::{path_to_uri, src_span_to_lsp_range, DownloadDependencies, MakeLocker};
 
 #[derive(Debug, PartialEq, Eq)]
 pub struct Response<T> {
@@ -35,6 +48,14 @@ pub struct LanguageServerEngine<IO, Reporter> {
 
     modules_compiled_since_last_feedback: Vec<PathBuf>,
 
+    /// The warnings and error from compiling the modules, used to offer
+    /// fixes for them.
+    problems: ProblemsBookKeeper,
+
+    /// Used to read the source code of dependency modules, which is not kept
+    /// by the compiler.
+    io: FileSystemProxy<IO>,
+
     // Used to publish progress notifications to the client without waiting for
     // the usual request-response loop.
     progress_reporter: Reporter,
@@ -74,6 +95,8 @@ where
 
         Ok(Self {
             modules_compiled_since_last_feedback: vec![],
+            problems: ProblemsBookKeeper::default(),
+            io,
             progress_reporter,
             compiler,
             paths,
@@ -89,6 +112,7 @@ where
         self.progress_reporter.compilation_started();
         let result = self.compiler.compile();
         self.progress_reporter.compilation_finished();
+        self.problems.set_error(result.as_ref().err().cloned());
 
         let modules = result?;
         self.modules_compiled_since_last_feedback
@@ -156,29 +180,352 @@ where
         })
     }
 
-    // TODO: function & constructor labels
-    // TODO: module types (including private)
-    // TODO: module values (including private)
-    // TODO: locally defined variables
-    // TODO: imported module values
-    // TODO: imported module types
-    // TODO: record accessors
-    // TODO: module imports
+    /// Suggest completions for the name being typed at the cursor. The
+    /// source code is passed in as the module may have changed since it was
+    /// last successfully compiled.
     pub fn completion(
         &mut self,
         params: lsp::CompletionParams,
+        src: SmolStr,
     ) -> Response<Option<Vec<lsp::CompletionItem>>> {
         self.respond(|this| {
-            let found = this
-                .node_at_position(&params.text_document_position)
-                .map(|(_, found)| found);
+            let params = params.text_document_position;
+            let module = match this.module_for_uri(&params.text_document.uri) {
+                Some(module) => module,
+                None => return Ok(None),
+            };
+            let line_numbers = LineNumbers::new(&src);
+            let byte_index =
+                line_numbers.byte_index(params.position.line, params.position.character);
+
+            let importable_modules = this.compiler.importable_modules();
+            let completer = Completer::new(
+                &src,
+                byte_index,
+                module,
+                &importable_modules,
+                &this.compiler.modules,
+            );
+            Ok(Some(completer.completions()))
+        })
+    }
 
-            Ok(match found {
-                None => None,
-                Some(Located::Pattern(_pattern)) => None,
-                Some(Located::Statement(_statement)) => None,
-                Some(Located::Expression(_expression)) => None,
-                Some(Located::ModuleStatement(_statement)) => None,
+    /// Show the signature of the function whose arguments are being typed at
+    /// the cursor. As with completions the source code is passed in as the
+    /// module may have changed since it was last successfully compiled.
+    pub fn signature_help(
+        &mut self,
+        params: lsp::SignatureHelpParams,
+        src: SmolStr,
+    ) -> Response<Option<lsp::SignatureHelp>> {
+        self.respond(|this| {
+            let params = params.text_document_position_params;
+            let module = match this.module_for_uri(&params.text_document.uri) {
+                Some(module) => module,
+                None => return Ok(None),
+            };
+            let line_numbers = LineNumbers::new(&src);
+            let byte_index =
+                line_numbers.byte_index(params.position.line, params.position.character);
+            let importable_modules = this.compiler.importable_modules();
+
+            let helper = SignatureHelper::new(
+                &src,
+                byte_index,
+                module,
+                &importable_modules,
+                &this.compiler.modules,
+            );
+            Ok(helper.signature_help())
+        })
+    }
+
+    /// The inferred types of the unannotated bindings, pipeline steps,
+    /// arguments, and return values within the visible range of a module.
+    pub fn inlay_hints(
+        &mut self,
+        params: lsp::InlayHintParams,
+        config: InlayHintsConfig,
+    ) -> Response<Option<Vec<lsp::InlayHint>>> {
+        self.respond(|this| {
+            let module = match this.module_for_uri(&params.text_document.uri) {
+                Some(module) => module,
+                None => return Ok(None),
+            };
+            let line_numbers = LineNumbers::new(&module.code);
+            let range = SrcSpan::new(
+                line_numbers.byte_index(params.range.start.line, params.range.start.character),
+                line_numbers.byte_index(params.range.end.line, params.range.end.character),
+            );
+            Ok(Some(inlay_hints::inlay_hints(module, range, config)))
+        })
+    }
+
+    /// Find every use of the value, type, or local variable under the cursor
+    /// across all the modules of the project.
+    pub fn references(
+        &mut self,
+        params: lsp::ReferenceParams,
+    ) -> Response<Option<Vec<lsp::Location>>> {
+        self.respond(|this| {
+            let include_declaration = params.context.include_declaration;
+            let params = params.text_document_position;
+            let module = match this.module_for_uri(&params.text_document.uri) {
+                Some(module) => module,
+                None => return Ok(None),
+            };
+            let line_numbers = LineNumbers::new(&module.code);
+            let byte_index =
+                line_numbers.byte_index(params.position.line, params.position.character);
+            let symbol = match references::reference_at(module, byte_index) {
+                Some(reference) => reference.symbol,
+                None => return Ok(None),
+            };
+
+            let modules = this
+                .compiler
+                .modules
+                .values()
+                .sorted_by(|a, b| a.name.cmp(&b.name));
+
+            let mut locations = vec![];
+            for module in modules {
+                let line_numbers = LineNumbers::new(&module.code);
+                let uri = path_to_uri(module.input_path.clone());
+                for reference in references::module_references(module) {
+                    if reference.symbol != symbol
+                        || (!include_declaration && reference.kind == ReferenceKind::Definition)
+                    {
+                        continue;
+                    }
+                    locations.push(lsp::Location {
+                        uri: uri.clone(),
+                        range: src_span_to_lsp_range(reference.location, &line_numbers),
+                    });
+                }
+            }
+            Ok(Some(locations))
+        })
+    }
+
+    pub fn prepare_rename(
+        &mut self,
+        params: lsp::TextDocumentPositionParams,
+    ) -> Response<Result<Option<lsp::PrepareRenameResponse>, RenameError>> {
+        self.respond(|this| {
+            let module = match this.module_for_uri(&params.text_document.uri) {
+                Some(module) => module,
+                None => return Ok(Ok(None)),
+            };
+            let line_numbers = LineNumbers::new(&module.code);
+            let byte_index =
+                line_numbers.byte_index(params.position.line, params.position.character);
+            let reference = rename::prepare(&this.compiler.modules, module, byte_index);
+            Ok(reference.map(|reference| {
+                reference.map(|reference| {
+                    let range = src_span_to_lsp_range(reference.location, &line_numbers);
+                    lsp::PrepareRenameResponse::Range(range)
+                })
+            }))
+        })
+    }
+
+    /// Rename a value, type, or local variable, updating its definition and
+    /// every reference to it across the project.
+    pub fn rename(
+        &mut self,
+        params: lsp::RenameParams,
+    ) -> Response<Result<Option<lsp::WorkspaceEdit>, RenameError>> {
+        self.respond(|this| {
+            let position = params.text_document_position;
+            let module = match this.module_for_uri(&position.text_document.uri) {
+                Some(module) => module,
+                None => return Ok(Ok(None)),
+            };
+            let line_numbers = LineNumbers::new(&module.code);
+            let byte_index =
+                line_numbers.byte_index(position.position.line, position.position.character);
+            Ok(rename::rename(
+                &this.compiler.modules,
+                module,
+                byte_index,
+                &params.new_name,
+            ))
+        })
+    }
+
+    /// The outline of a module.
+    pub fn document_symbols(
+        &mut self,
+        params: lsp::DocumentSymbolParams,
+    ) -> Response<Option<lsp::DocumentSymbolResponse>> {
+        self.respond(|this| {
+            let module = match this.module_for_uri(&params.text_document.uri) {
+                Some(module) => module,
+                None => return Ok(None),
+            };
+            Ok(Some(symbols::document_symbols(module).into()))
+        })
+    }
+
+    /// Search for definitions by name in the modules of the project and the
+    /// modules of its dependencies.
+    pub fn workspace_symbols(
+        &mut self,
+        params: lsp::WorkspaceSymbolParams,
+    ) -> Response<Option<Vec<lsp::SymbolInformation>>> {
+        self.respond(|this| {
+            let query = params.query.as_str();
+            let project_modules = this
+                .compiler
+                .modules
+                .values()
+                .sorted_by(|a, b| a.name.cmp(&b.name));
+            let mut found = symbols::project_symbols(project_modules, query);
+
+            let dependency_modules = this
+                .compiler
+                .project_compiler
+                .get_importable_modules()
+                .values()
+                .filter(|module| {
+                    // The prelude has no source code.
+                    !module.package.is_empty()
+                        && !this.compiler.modules.contains_key(module.name.as_str())
+                })
+                .sorted_by(|a, b| a.name.cmp(&b.name));
+            for module in dependency_modules {
+                let path = this
+                    .paths
+                    .build_packages_package(&module.package)
+                    .join("src")
+                    .join(module.name.as_str())
+                    .with_extension("gleam");
+                let src = match this.io.read(&path) {
+                    Ok(src) => src,
+                    Err(_) => continue,
+                };
+                let uri = path_to_uri(path);
+                found.extend(symbols::interface_symbols(module, &uri, &src, query));
+            }
+
+            Ok(Some(found))
+        })
+    }
+
+    /// The semantic tokens of a whole module, used by the client to highlight
+    /// names by what they refer to.
+    pub fn semantic_tokens(
+        &mut self,
+        params: lsp::SemanticTokensParams,
+    ) -> Response<Option<lsp::SemanticTokensResult>> {
+        self.respond(|this| {
+            let tokens = this.module_semantic_tokens(&params.text_document.uri, None);
+            Ok(tokens.map(lsp::SemanticTokensResult::Tokens))
+        })
+    }
+
+    /// The semantic tokens within a range of a module.
+    pub fn semantic_tokens_range(
+        &mut self,
+        params: lsp::SemanticTokensRangeParams,
+    ) -> Response<Option<lsp::SemanticTokensRangeResult>> {
+        self.respond(|this| {
+            let tokens = this.module_semantic_tokens(&params.text_document.uri, Some(params.range));
+            Ok(tokens.map(lsp::SemanticTokensRangeResult::Tokens))
+        })
+    }
+
+    fn module_semantic_tokens(
+        &self,
+        uri: &Url,
+        range: Option<lsp::Range>,
+    ) -> Option<lsp::SemanticTokens> {
+        let module = self.module_for_uri(uri)?;
+        let line_numbers = LineNumbers::new(&module.code);
+        let range = range.map(|range| {
+            SrcSpan::new(
+                line_numbers.byte_index(range.start.line, range.start.character),
+                line_numbers.byte_index(range.end.line, range.end.character),
+            )
+        });
+        let unused_variables: Vec<_> = self
+            .problems
+            .warnings(&module.input_path)
+            .iter()
+            .filter_map(|warning| match warning {
+                Warning::Type {
+                    warning: type_::Warning::UnusedVariable { location, .. },
+                    ..
+                } => Some(*location),
+                Warning::Type { .. } => None,
+            })
+            .collect();
+        let importable_modules = self.compiler.importable_modules();
+        let data =
+            semantic_tokens::semantic_tokens(module, &importable_modules, &unused_variables, range);
+        Some(lsp::SemanticTokens {
+            result_id: None,
+            data,
+        })
+    }
+
+    /// Offer quick fixes for the warnings and errors within the given range
+    /// of a module.
+    pub fn code_actions(
+        &mut self,
+        params: lsp::CodeActionParams,
+    ) -> Response<Option<Vec<lsp::CodeAction>>> {
+        self.respond(|this| {
+            let uri = &params.text_document.uri;
+            // The module may have failed to compile, in which case only its
+            // path is known.
+            let path = uri_to_path(uri);
+            let is_test = path.starts_with(this.paths.test_directory());
+
+            let importable_modules = this
+                .compiler
+                .project_compiler
+                .get_importable_modules()
+                .iter()
+                .filter(|(_, interface)| is_test || interface.origin.is_src())
+                .map(|(name, _)| name)
+                .chain(
+                    this.compiler
+                        .modules
+                        .values()
+                        .filter(|other| is_test || !other.is_test())
+                        .map(|other| &other.name),
+                )
+                .sorted()
+                .dedup()
+                .collect();
+
+            let mut fixes = QuickFixes::new(
+                uri,
+                params.range,
+                &params.context.diagnostics,
+                importable_modules,
+            );
+            for warning in this.problems.warnings(&path) {
+                fixes.warning(warning);
+            }
+            if let Some(
+                error @ Error::Type {
+                    path: error_path, ..
+                },
+            ) = this.problems.error()
+            {
+                if error_path == &path {
+                    fixes.error(error);
+                }
+            }
+
+            let actions = fixes.into_actions();
+            Ok(if actions.is_empty() {
+                None
+            } else {
+                Some(actions)
             })
         })
     }
@@ -187,6 +534,7 @@ where
         let result = handler(self);
         let warnings = self.take_warnings();
         let modules = std::mem::take(&mut self.modules_compiled_since_last_feedback);
+        self.problems.compiled(&modules, &warnings);
         Response {
             result,
             warnings,
@@ -194,35 +542,6 @@ where
         }
     }
 
-    // fn completion_for_import(&self) -> Vec<lsp::CompletionItem> {
-    //     // TODO: Test
-    //     let dependencies_modules = self
-    //         .compiler
-    //         .project_compiler
-    //         .get_importable_modules()
-    //         .keys()
-    //         .map(|name| name.to_string());
-    //     // TODO: Test
-    //     let project_modules = self
-    //         .compiler
-    //         .modules
-    //         .iter()
-    //         // TODO: We should autocomplete test modules if we are in the test dir
-    //         // TODO: Test
-    //         .filter(|(_name, module)| module.origin.is_src())
-    //         .map(|(name, _module)| name)
-    //         .cloned();
-    //     dependencies_modules
-    //         .chain(project_modules)
-    //         .map(|label| lsp::CompletionItem {
-    //             label,
-    //             kind: None,
-    //             documentation: None,
-    //             ..Default::default()
-    //         })
-    //         .collect()
-    // }
-
     pub fn hover(&mut self, params: lsp::HoverParams) -> Response<Option<Hover>> {
         self.respond(|this| {
             let params = params.text_document_position_params;
@@ -254,15 +573,7 @@ where
     }
 
     fn module_for_uri(&self, uri: &Url) -> Option<&Module> {
-        use itertools::Itertools;
-
-        // The to_file_path method is available on these platforms
-        #[cfg(any(unix, windows, target_os = "redox", target_os = "wasi"))]
-        let path = uri.to_file_path().expect("URL file");
-
-        #[cfg(not(any(unix, windows, target_os = "redox", target_os = "wasi")))]
-        let path: PathBuf = uri.path().into();
-
+        let path = uri_to_path(uri);
         let components = path
             .strip_prefix(self.paths.root())
             .ok()?
@@ -277,6 +588,15 @@ where
     }
 }
 
+fn uri_to_path(uri: &Url) -> PathBuf {
+    // The to_file_path method is available on these platforms
+    #[cfg(any(unix, windows, target_os = "redox", target_os = "wasi"))]
+    return uri.to_file_path().expect("URL file");
+
+    #[cfg(not(any(unix, windows, target_os = "redox", target_os = "wasi")))]
+    return uri.path().into();
+}
+
 fn hover_for_pattern(pattern: &TypedPattern, line_numbers: LineNumbers) -> Hover {
     let documentation = pattern.get_documentation().unwrap_or_default();
 
@@ -296,6 +616,7 @@ fn hover_for_pattern(pattern: &TypedPattern, line_numbers: LineNumbers) -> Hover
 
 fn hover_for_expression(expression: &TypedExpr, line_numbers: LineNumbers) -> Hover {
     let documentation = expression.get_documentation().unwrap_or_default();
+    let deprecation = deprecation_notice(expression);
 
     // Show the type of the hovered node to the user
     let type_ = Printer::new().pretty_print(expression.type_().as_ref(), 0);
@@ -303,10 +624,29 @@ fn hover_for_expression(expression: &TypedExpr, line_numbers: LineNumbers) -> Ho
         "```gleam
 {type_}
 ```
-{documentation}"
+{deprecation}{documentation}"
     );
     Hover {
         contents: HoverContents::Scalar(MarkedString::String(contents)),
         range: Some(src_span_to_lsp_range(expression.location(), &line_numbers)),
     }
 }
+
+/// A line showing the name of a deprecated value struck through, followed by
+/// the reason it was deprecated.
+fn deprecation_notice(expression: &TypedExpr) -> String {
+    let message = match expression.get_deprecation_message() {
+        Some(message) => message,
+        None => return String::new(),
+    };
+    let name = match expression {
+        TypedExpr::ModuleSelect {
+            module_alias,
+            label,
+            ..
+        } => format!("{module_alias}.{label}"),
+        TypedExpr::Var { name, .. } => name.to_string(),
+        _ => return String::new(),
+    };
+    format!("~~{name}~~ is deprecated: {message}\n\n")
+}
@@ -98,7 +98,8 @@ impl FeedbackBookKeeper {
     ///
     /// Send diagnostics for any warnings and remove any diagnostics for files
     /// that have compiled without warnings, AND ALSO send diagnostics for the
-    /// error that caused compilation to fail.
+    /// error that caused compilation to fail, one for each problem it is made
+    /// up of.
     ///
     pub fn build_with_error(
         &mut self,
@@ -106,20 +107,21 @@ impl FeedbackBookKeeper {
         compiled: impl Iterator<Item = PathBuf>,
         warnings: Vec<Warning>,
     ) -> Feedback {
-        let diagnostic = error.to_diagnostic();
         let mut feedback = self.response(compiled, warnings);
 
         // A new error means that any existing errors are no longer valid. Unset them.
         self.unset_errors(&mut feedback);
 
-        match diagnostic.location.as_ref().map(|l| l.path.clone()) {
-            Some(path) => {
-                _ = self.files_with_errors.insert(path.clone());
-                feedback.append_diagnostic(path, diagnostic);
-            }
+        for diagnostic in error.to_diagnostics() {
+            match diagnostic.location.as_ref().map(|l| l.path.clone()) {
+                Some(path) => {
+                    _ = self.files_with_errors.insert(path.clone());
+                    feedback.append_diagnostic(path, diagnostic);
+                }
 
-            None => {
-                feedback.append_message(diagnostic);
+                None => {
+                    feedback.append_message(diagnostic);
+                }
             }
         }
 
@@ -149,6 +151,7 @@ mod tests {
         parse::error::{ParseError, ParseErrorType},
         type_,
     };
+    use vec1::vec1;
 
     #[test]
     fn feedback() {
@@ -262,10 +265,10 @@ mod tests {
         let error = Error::Parse {
             path: file3.clone(),
             src: "blah".into(),
-            error: ParseError {
+            errors: vec1![ParseError {
                 error: ParseErrorType::ConcatPatternVariableLeftHandSide,
                 location: SrcSpan::new(1, 4),
-            },
+            }],
         };
 
         let feedback =
@@ -319,10 +322,10 @@ mod tests {
         let error = Error::Parse {
             path: file1.clone(),
             src: "blah".into(),
-            error: ParseError {
+            errors: vec1![ParseError {
                 error: ParseErrorType::ConcatPatternVariableLeftHandSide,
                 location: SrcSpan::new(1, 4),
-            },
+            }],
         };
 
         let feedback = book_keeper.build_with_error(error.clone(), vec![].into_iter(), vec![]);
@@ -359,10 +362,10 @@ mod tests {
         let error = |file: &Path| Error::Parse {
             path: file.to_path_buf(),
             src: "blah".into(),
-            error: ParseError {
+            errors: vec1![ParseError {
                 error: ParseErrorType::ConcatPatternVariableLeftHandSide,
                 location: SrcSpan::new(1, 4),
-            },
+            }],
         };
 
         let feedback = book_keeper.build_with_error(error(&file1), vec![].into_iter(), vec![]);
@@ -400,10 +403,10 @@ mod tests {
         let error = Error::Parse {
             path: file1.clone(),
             src: "blah".into(),
-            error: ParseError {
+            errors: vec1![ParseError {
                 error: ParseErrorType::ConcatPatternVariableLeftHandSide,
                 location: SrcSpan::new(1, 4),
-            },
+            }],
         };
 
         let feedback = book_keeper.build_with_error(error.clone(), vec![].into_iter(), vec![]);
@@ -429,4 +432,48 @@ mod tests {
             feedback
         );
     }
+
+    #[test]
+    fn error_with_many_problems() {
+        let mut book_keeper = FeedbackBookKeeper::default();
+        let file1 = PathBuf::from("src/file1.gleam");
+
+        let error = Error::Parse {
+            path: file1.clone(),
+            src: "blah blah".into(),
+            errors: vec1![
+                ParseError {
+                    error: ParseErrorType::ConcatPatternVariableLeftHandSide,
+                    location: SrcSpan::new(1, 4),
+                },
+                ParseError {
+                    error: ParseErrorType::ExpectedExpr,
+                    location: SrcSpan::new(6, 9),
+                }
+            ],
+        };
+
+        let feedback = book_keeper.build_with_error(error.clone(), vec![].into_iter(), vec![]);
+
+        // Each problem gets its own diagnostic
+        assert_eq!(
+            Feedback {
+                diagnostics: HashMap::from([(file1.clone(), error.to_diagnostics())]),
+                messages: vec![],
+            },
+            feedback
+        );
+        assert_eq!(error.to_diagnostics().len(), 2);
+
+        // They are all removed once the file compiles
+        let feedback = book_keeper.response(vec![file1.clone()].into_iter(), vec![]);
+
+        assert_eq!(
+            Feedback {
+                diagnostics: HashMap::from([(file1, vec![])]),
+                messages: vec![],
+            },
+            feedback
+        );
+    }
 }
@@ -0,0 +1,295 @@
+use lsp_types::{InlayHint, InlayHintKind, InlayHintLabel};
+
+use crate::{
+    ast::{
+        ArgNames, BitStringSegmentOption, ModuleStatement, SrcSpan, Statement, TypedArg, TypedExpr,
+        TypedStatement, USE_ASSIGNMENT_VARIABLE,
+    },
+    build::Module,
+    line_numbers::LineNumbers,
+    type_::{pretty::Printer, Type},
+};
+
+use super::{configuration::InlayHintsConfig, src_span_to_lsp_range};
+
+/// The inferred types of the bindings, pipeline steps, arguments, and return
+/// values of a module that have not been annotated, for those hints enabled in
+/// the configuration that fall within the given range of byte indexes.
+///
+pub fn inlay_hints(module: &Module, range: SrcSpan, config: InlayHintsConfig) -> Vec<InlayHint> {
+    let line_numbers = LineNumbers::new(&module.code);
+    let mut finder = HintFinder {
+        src: &module.code,
+        line_numbers: &line_numbers,
+        config,
+        printer: Printer::new(),
+        hints: vec![],
+    };
+    for statement in &module.ast.statements {
+        if let ModuleStatement::Function(function) = statement {
+            // Type variables are named consistently within each function.
+            finder.printer = Printer::new();
+            for argument in &function.arguments {
+                finder.argument(argument);
+            }
+            if function.return_annotation.is_none() {
+                finder.return_type(function.location.end, &function.return_type);
+            }
+            for statement in &function.body {
+                finder.statement(statement);
+            }
+        }
+    }
+
+    let mut hints = finder.hints;
+    hints.retain(|hint| range.start <= hint.position && hint.position <= range.end);
+    hints.sort_by_key(|hint| hint.position);
+    hints
+        .into_iter()
+        .map(|hint| {
+            let position =
+                src_span_to_lsp_range(SrcSpan::new(hint.position, hint.position), &line_numbers)
+                    .start;
+            InlayHint {
+                position,
+                label: InlayHintLabel::String(hint.label),
+                kind: Some(InlayHintKind::TYPE),
+                text_edits: None,
+                tooltip: None,
+                padding_left: Some(hint.padding_left),
+                padding_right: None,
+            }
+        })
+        .collect()
+}
+
+#[derive(Debug)]
+struct Hint {
+    /// The byte index the hint is shown at.
+    position: u32,
+    label: String,
+    padding_left: bool,
+}
+
+struct HintFinder<'a> {
+    src: &'a str,
+    line_numbers: &'a LineNumbers,
+    config: InlayHintsConfig,
+    printer: Printer,
+    hints: Vec<Hint>,
+}
+
+impl<'a> HintFinder<'a> {
+    /// A hint that follows a name, as a type annotation would.
+    fn annotation(&mut self, position: u32, type_: &Type) {
+        let type_ = self.printer.pretty_print(type_, 0);
+        self.hints.push(Hint {
+            position,
+            label: format!(": {type_}"),
+            padding_left: false,
+        });
+    }
+
+    fn return_type(&mut self, position: u32, type_: &Type) {
+        if !self.config.function_return_types {
+            return;
+        }
+        let type_ = self.printer.pretty_print(type_, 0);
+        self.hints.push(Hint {
+            position,
+            label: format!("-> {type_}"),
+            padding_left: true,
+        });
+    }
+
+    fn argument(&mut self, argument: &TypedArg) {
+        if !self.config.function_parameters || argument.annotation.is_some() {
+            return;
+        }
+        // Arguments introduced by the compiler are not written by the
+        // programmer, so there is nowhere to show their type.
+        let generated = match &argument.names {
+            ArgNames::Named { name } => name.starts_with(USE_ASSIGNMENT_VARIABLE),
+            ArgNames::Discard { .. }
+            | ArgNames::LabelledDiscard { .. }
+            | ArgNames::NamedLabelled { .. } => false,
+        };
+        if !generated {
+            self.annotation(argument.location.end, &argument.type_);
+        }
+    }
+
+    fn statement(&mut self, statement: &TypedStatement) {
+        match statement {
+            Statement::Expression(expression) => self.expression(expression),
+            Statement::Assignment(assignment) => {
+                if self.config.let_bindings && assignment.annotation.is_none() {
+                    let position = assignment.pattern.location().end;
+                    self.annotation(position, &assignment.value.type_());
+                }
+                self.expression(&assignment.value);
+            }
+            Statement::Use(_) => (),
+        }
+    }
+
+    fn expression(&mut self, expression: &TypedExpr) {
+        match expression {
+            TypedExpr::Int { .. }
+            | TypedExpr::Float { .. }
+            | TypedExpr::String { .. }
+            | TypedExpr::Var { .. }
+            | TypedExpr::ModuleSelect { .. }
+            | TypedExpr::Todo { .. }
+            | TypedExpr::Panic { .. } => (),
+
+            TypedExpr::Block { statements, .. } => {
+                for statement in statements {
+                    self.statement(statement);
+                }
+            }
+
+            TypedExpr::Pipeline {
+                location,
+                assignments,
+                finally,
+            } => {
+                // Hints for a pipeline on a single line would crowd it, so
+                // they are only shown when each step is on its own line.
+                let multiline = self.line_numbers.line_number(location.start)
+                    != self.line_numbers.line_number(location.end);
+                let steps = assignments
+                    .iter()
+                    .map(|assignment| assignment.value.as_ref())
+                    .chain(std::iter::once(finally.as_ref()));
+                for step in steps {
+                    if self.config.pipelines && multiline {
+                        let type_ = self.printer.pretty_print(&step.type_(), 0);
+                        self.hints.push(Hint {
+                            position: step.location().end,
+                            label: type_,
+                            padding_left: true,
+                        });
+                    }
+                    self.expression(step);
+                }
+            }
+
+            TypedExpr::Fn {
+                location,
+                typ,
+                is_capture,
+                args,
+                body,
+                return_annotation,
+            } => {
+                // The arguments of a function capture are never written.
+                if !is_capture {
+                    for argument in args {
+                        self.argument(argument);
+                    }
+                    if let (None, Some(return_type)) = (return_annotation, typ.return_type()) {
+                        if let Some(position) = self.closing_paren(*location, args) {
+                            self.return_type(position, &return_type);
+                        }
+                    }
+                }
+                for statement in body {
+                    self.statement(statement);
+                }
+            }
+
+            TypedExpr::List { elements, tail, .. } => {
+                for element in elements {
+                    self.expression(element);
+                }
+                if let Some(tail) = tail {
+                    self.expression(tail);
+                }
+            }
+
+            TypedExpr::Call { fun, args, .. } => {
+                self.expression(fun);
+                for argument in args {
+                    match &argument.value {
+                        // The callback given implicitly by `use` is written as
+                        // the body of the function that follows it, so only
+                        // its arguments can have hints.
+                        TypedExpr::Fn { args, body, .. } if argument.implicit => {
+                            for argument in args {
+                                self.argument(argument);
+                            }
+                            for statement in body {
+                                self.statement(statement);
+                            }
+                        }
+                        value => self.expression(value),
+                    }
+                }
+            }
+
+            TypedExpr::BinOp { left, right, .. } => {
+                self.expression(left);
+                self.expression(right);
+            }
+
+            TypedExpr::Case {
+                subjects, clauses, ..
+            } => {
+                for subject in subjects {
+                    self.expression(subject);
+                }
+                for clause in clauses {
+                    self.expression(&clause.then);
+                }
+            }
+
+            TypedExpr::RecordAccess { record, .. } => self.expression(record),
+
+            TypedExpr::Tuple { elems, .. } => {
+                for elem in elems {
+                    self.expression(elem);
+                }
+            }
+
+            TypedExpr::TupleIndex { tuple, .. } => self.expression(tuple),
+
+            TypedExpr::BitString { segments, .. } => {
+                for segment in segments {
+                    self.expression(&segment.value);
+                    for option in &segment.options {
+                        if let BitStringSegmentOption::Size { value, .. } = option {
+                            self.expression(value);
+                        }
+                    }
+                }
+            }
+
+            TypedExpr::RecordUpdate { spread, args, .. } => {
+                self.expression(spread);
+                for argument in args {
+                    self.expression(&argument.value);
+                }
+            }
+
+            TypedExpr::NegateBool { value, .. } | TypedExpr::NegateInt { value, .. } => {
+                self.expression(value)
+            }
+        }
+    }
+
+    /// The position just after the closing parenthesis of the arguments of an
+    /// anonymous function. Its location covers its body, so the parenthesis
+    /// is found by looking for it after the last argument.
+    fn closing_paren(&self, location: SrcSpan, args: &[TypedArg]) -> Option<u32> {
+        let from = args
+            .last()
+            .map(|argument| argument.location.end)
+            .unwrap_or(location.start);
+        let offset = self
+            .src
+            .get(from as usize..location.end as usize)?
+            .find(')')?;
+        Some(from + offset as u32 + 1)
+    }
+}
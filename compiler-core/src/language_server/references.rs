@@ -0,0 +1,893 @@
+use std::collections::{HashMap, HashSet};
+
+use smol_str::SmolStr;
+
+use crate::{
+    ast::{
+        Arg, ArgNames, AssignName, BitStringSegmentOption, ClauseGuard, Constant, CustomType,
+        ExternalFunction, ExternalType, Function, Import, ModuleConstant, ModuleStatement, Pattern,
+        SrcSpan, TypeAlias, TypeAst, TypedArg, TypedClause, TypedClauseGuard, TypedConstant,
+        TypedExpr, TypedModuleStatement, TypedPattern, TypedStatement,
+    },
+    build::Module,
+    type_::{PatternConstructor, ValueConstructorVariant},
+};
+
+/// Something defined in Gleam code that can be referred to by name.
+///
+/// Module level items are identified by the module that defines them and
+/// their name, while local variables are identified by the location at which
+/// they were defined.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Symbol {
+    /// A function, constant, or record constructor.
+    Value { module: SmolStr, name: SmolStr },
+    /// A custom type, external type, or type alias.
+    Type { module: SmolStr, name: SmolStr },
+    /// A variable or function argument defined within a function.
+    Local {
+        module: SmolStr,
+        definition: SrcSpan,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReferenceKind {
+    /// The place where the symbol is defined.
+    Definition,
+    /// The symbol being named in an unqualified import.
+    Import,
+    /// Any other use of the symbol.
+    Usage,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reference {
+    pub symbol: Symbol,
+    /// The location of the name as written in the source code, not including
+    /// any module qualifier or other surrounding syntax.
+    pub location: SrcSpan,
+    pub kind: ReferenceKind,
+}
+
+impl Reference {
+    /// Whether the cursor at the given byte index is on this reference. The
+    /// end of the name is included as that is where the cursor will be after
+    /// the name has been typed.
+    pub fn is_at(&self, byte_index: u32) -> bool {
+        self.location.start <= byte_index && byte_index <= self.location.end
+    }
+}
+
+/// Find every reference to a value, type, or local variable in a module,
+/// including their definitions.
+///
+pub fn module_references(module: &Module) -> Vec<Reference> {
+    let mut collector = ReferenceCollector::new(module);
+    for statement in &module.ast.statements {
+        collector.module_statement(statement);
+    }
+    collector.references
+}
+
+/// The span of the source code each local variable of a module is in scope
+/// for, by the location of its definition. This starts at the definition and
+/// ends with the block, function, or case clause it is defined in.
+///
+pub fn local_scopes(module: &Module) -> HashMap<SrcSpan, SrcSpan> {
+    let mut collector = ReferenceCollector::new(module);
+    for statement in &module.ast.statements {
+        collector.module_statement(statement);
+    }
+    collector.scopes
+}
+
+/// Find the reference at the given byte index of a module, if there is one.
+///
+pub fn reference_at(module: &Module, byte_index: u32) -> Option<Reference> {
+    module_references(module)
+        .into_iter()
+        .find(|reference| reference.is_at(byte_index))
+}
+
+/// Find the label of an argument or record field at the given byte index of
+/// a module, if there is one. Labels are not references as they belong to
+/// the function or record they are given to rather than being defined by
+/// themselves.
+///
+pub fn label_at(module: &Module, byte_index: u32) -> Option<SrcSpan> {
+    let mut collector = ReferenceCollector::new(module);
+    for statement in &module.ast.statements {
+        collector.module_statement(statement);
+    }
+    collector
+        .labels
+        .into_iter()
+        .find(|label| label.start <= byte_index && byte_index <= label.end)
+}
+
+struct ReferenceCollector<'a> {
+    module_name: &'a SmolStr,
+    code: &'a str,
+    /// Imported modules by the name they are referred to with in this module.
+    imported_modules: HashMap<&'a str, &'a SmolStr>,
+    /// Unqualified imports by the name they are referred to with in this
+    /// module, giving the module they come from and their original name.
+    unqualified: HashMap<&'a str, (&'a SmolStr, &'a SmolStr)>,
+    /// The names of the types defined in this module. Unqualified type names
+    /// that are neither defined here nor imported come from the prelude.
+    defined_types: HashSet<&'a str>,
+    /// The variables defined by the patterns of the clause currently being
+    /// collected, used to resolve the variables in clause guards.
+    clause_variables: HashMap<SmolStr, SrcSpan>,
+    /// Where the innermost block, function, or case clause being collected
+    /// ends, which is where the variables defined in it go out of scope.
+    scope_end: u32,
+    /// Where the variables defined by the pattern being collected come into
+    /// scope, if that is not where they are defined, as with `let`.
+    scope_start: Option<u32>,
+    scopes: HashMap<SrcSpan, SrcSpan>,
+    /// The locations of the labels of arguments and record fields.
+    labels: Vec<SrcSpan>,
+    references: Vec<Reference>,
+}
+
+impl<'a> ReferenceCollector<'a> {
+    fn new(module: &'a Module) -> Self {
+        let mut imported_modules = HashMap::new();
+        let mut unqualified = HashMap::new();
+        let mut defined_types = HashSet::new();
+        for statement in &module.ast.statements {
+            match statement {
+                ModuleStatement::CustomType(CustomType { name, .. })
+                | ModuleStatement::ExternalType(ExternalType { name, .. }) => {
+                    let _ = defined_types.insert(name.as_str());
+                }
+                ModuleStatement::TypeAlias(TypeAlias { alias, .. }) => {
+                    let _ = defined_types.insert(alias.as_str());
+                }
+                _ => (),
+            }
+            if let ModuleStatement::Import(import) = statement {
+                let Import {
+                    module: imported, ..
+                } = import;
+                let alias = import
+                    .as_name
+                    .as_deref()
+                    .unwrap_or_else(|| imported.rsplit('/').next().unwrap_or(imported.as_str()));
+                let _ = imported_modules.insert(alias, imported);
+                for item in &import.unqualified {
+                    let _ = unqualified.insert(item.variable_name(), (imported, &item.name));
+                }
+            }
+        }
+
+        Self {
+            module_name: &module.name,
+            code: &module.code,
+            imported_modules,
+            unqualified,
+            defined_types,
+            clause_variables: HashMap::new(),
+            scope_end: module.code.len() as u32,
+            scope_start: None,
+            scopes: HashMap::new(),
+            labels: vec![],
+            references: vec![],
+        }
+    }
+
+    fn push(&mut self, symbol: Symbol, location: Option<SrcSpan>, kind: ReferenceKind) {
+        if let Some(location) = location {
+            if let (Symbol::Local { definition, .. }, ReferenceKind::Definition) = (&symbol, kind) {
+                let start = self.scope_start.unwrap_or(location.start);
+                let scope = SrcSpan::new(start, self.scope_end);
+                let _ = self.scopes.insert(*definition, scope);
+            }
+            self.references.push(Reference {
+                symbol,
+                location,
+                kind,
+            });
+        }
+    }
+
+    /// Collect the references within a block, function, or case clause, with
+    /// any variables defined in it going out of scope at the given position.
+    fn in_scope_until(&mut self, end: u32, collect: impl FnOnce(&mut Self)) {
+        let outer_end = std::mem::replace(&mut self.scope_end, end);
+        collect(self);
+        self.scope_end = outer_end;
+    }
+
+    /// Find the location of a name within a span of the source code. The
+    /// name must be a complete identifier, not part of a longer one.
+    fn find_name(&self, within: SrcSpan, name: &str) -> Option<SrcSpan> {
+        let is_name_char = |c: char| c.is_ascii_alphanumeric() || c == '_';
+        let source = self.code.get(within.start as usize..within.end as usize)?;
+        source
+            .match_indices(name)
+            .find(|(index, _)| {
+                let before = source.get(..*index).and_then(|s| s.chars().next_back());
+                let after = source
+                    .get(index + name.len()..)
+                    .and_then(|s| s.chars().next());
+                !before.map(is_name_char).unwrap_or(false)
+                    && !after.map(is_name_char).unwrap_or(false)
+            })
+            .map(|(index, _)| {
+                let start = within.start + index as u32;
+                SrcSpan::new(start, start + name.len() as u32)
+            })
+    }
+
+    /// Record the label an argument or record field starts with, if it has
+    /// one.
+    fn label(&mut self, location: SrcSpan, label: Option<&SmolStr>) {
+        let label = label.and_then(|label| {
+            let end = location.start + label.len() as u32;
+            let source = self.code.get(location.start as usize..end as usize)?;
+            (source == label).then_some(SrcSpan::new(location.start, end))
+        });
+        if let Some(label) = label {
+            self.labels.push(label);
+        }
+    }
+
+    /// The location of a name that is at the very end of a span, such as the
+    /// `name` in `module.name`.
+    fn trailing_name(&self, within: SrcSpan, name: &str) -> Option<SrcSpan> {
+        let start = within.end.checked_sub(name.len() as u32)?;
+        let location = SrcSpan::new(start, within.end);
+        let source = self.code.get(start as usize..within.end as usize)?;
+        (source == name).then_some(location)
+    }
+
+    fn value(&self, module: &SmolStr, name: &SmolStr) -> Symbol {
+        Symbol::Value {
+            module: module.clone(),
+            name: name.clone(),
+        }
+    }
+
+    fn local(&self, definition: SrcSpan) -> Symbol {
+        Symbol::Local {
+            module: self.module_name.clone(),
+            definition,
+        }
+    }
+
+    /// Resolve a value referred to by the given name, optionally qualified by
+    /// a module alias.
+    fn resolve_value(&self, module: Option<&str>, name: &SmolStr) -> Option<Symbol> {
+        match module {
+            Some(alias) => {
+                let module = self.imported_modules.get(alias)?;
+                Some(self.value(module, name))
+            }
+            None => match self.unqualified.get(name.as_str()) {
+                Some((module, name)) => Some(self.value(module, name)),
+                None => Some(self.value(self.module_name, name)),
+            },
+        }
+    }
+
+    /// Resolve a type referred to by the given name, optionally qualified by
+    /// a module alias.
+    fn resolve_type(&self, module: Option<&str>, name: &SmolStr) -> Option<Symbol> {
+        let (module, name) = match module {
+            Some(alias) => ((*self.imported_modules.get(alias)?).clone(), name),
+            None => match self.unqualified.get(name.as_str()) {
+                Some((module, name)) => ((*module).clone(), *name),
+                None if self.defined_types.contains(name.as_str()) => {
+                    (self.module_name.clone(), name)
+                }
+                None => ("".into(), name),
+            },
+        };
+        Some(Symbol::Type {
+            module,
+            name: name.clone(),
+        })
+    }
+
+    fn module_statement(&mut self, statement: &'a TypedModuleStatement) {
+        match statement {
+            ModuleStatement::Function(Function {
+                location,
+                end_position,
+                name,
+                arguments,
+                body,
+                return_annotation,
+                ..
+            }) => {
+                let symbol = self.value(self.module_name, name);
+                let definition = self.find_name(*location, name);
+                self.push(symbol, definition, ReferenceKind::Definition);
+                self.in_scope_until(*end_position, |collector| {
+                    for argument in arguments {
+                        collector.argument(argument);
+                    }
+                    if let Some(annotation) = return_annotation {
+                        collector.type_ast(annotation);
+                    }
+                    for statement in body {
+                        collector.statement(statement);
+                    }
+                });
+            }
+
+            ModuleStatement::ExternalFunction(ExternalFunction {
+                location,
+                name,
+                arguments,
+                return_,
+                ..
+            }) => {
+                let symbol = self.value(self.module_name, name);
+                let definition = self.find_name(*location, name);
+                self.push(symbol, definition, ReferenceKind::Definition);
+                for argument in arguments {
+                    self.label(argument.location, argument.label.as_ref());
+                    self.type_ast(&argument.annotation);
+                }
+                self.type_ast(return_);
+            }
+
+            ModuleStatement::TypeAlias(TypeAlias {
+                location,
+                alias,
+                type_ast,
+                ..
+            }) => {
+                self.type_definition(*location, alias);
+                self.type_ast(type_ast);
+            }
+
+            ModuleStatement::CustomType(CustomType {
+                location,
+                name,
+                constructors,
+                ..
+            }) => {
+                self.type_definition(*location, name);
+                for constructor in constructors {
+                    let symbol = self.value(self.module_name, &constructor.name);
+                    let definition = self.find_name(constructor.location, &constructor.name);
+                    self.push(symbol, definition, ReferenceKind::Definition);
+                    for argument in &constructor.arguments {
+                        self.label(argument.location, argument.label.as_ref());
+                        self.type_ast(&argument.ast);
+                    }
+                }
+            }
+
+            ModuleStatement::ExternalType(ExternalType { location, name, .. }) => {
+                self.type_definition(*location, name);
+            }
+
+            ModuleStatement::Import(Import {
+                module,
+                unqualified,
+                ..
+            }) => {
+                for item in unqualified {
+                    let location = Some(item.location);
+                    if item.is_value() {
+                        let symbol = self.value(module, &item.name);
+                        self.push(symbol, location, ReferenceKind::Import);
+                    }
+                    // Capitalised names may refer to a type as well as a
+                    // record constructor.
+                    if item.name.starts_with(|c: char| c.is_uppercase()) {
+                        let symbol = Symbol::Type {
+                            module: module.clone(),
+                            name: item.name.clone(),
+                        };
+                        self.push(symbol, location, ReferenceKind::Import);
+                    }
+                }
+            }
+
+            ModuleStatement::ModuleConstant(ModuleConstant {
+                location,
+                name,
+                annotation,
+                value,
+                ..
+            }) => {
+                let symbol = self.value(self.module_name, name);
+                self.push(symbol, Some(*location), ReferenceKind::Definition);
+                if let Some(annotation) = annotation {
+                    self.type_ast(annotation);
+                }
+                self.constant(value);
+            }
+        }
+    }
+
+    fn type_definition(&mut self, location: SrcSpan, name: &SmolStr) {
+        let symbol = Symbol::Type {
+            module: self.module_name.clone(),
+            name: name.clone(),
+        };
+        let definition = self.find_name(location, name);
+        self.push(symbol, definition, ReferenceKind::Definition);
+    }
+
+    fn argument(&mut self, argument: &TypedArg) {
+        let Arg {
+            names,
+            location,
+            annotation,
+            ..
+        } = argument;
+        let name_location = match names {
+            ArgNames::Discard { .. } => None,
+            ArgNames::LabelledDiscard { label, .. } => {
+                self.label(*location, Some(label));
+                None
+            }
+            ArgNames::Named { name } => self.find_name(*location, name),
+            // Skip over the label, which may be the same as the name
+            ArgNames::NamedLabelled { name, label } => {
+                self.label(*location, Some(label));
+                let after_label = SrcSpan::new(location.start + label.len() as u32, location.end);
+                self.find_name(after_label, name)
+            }
+        };
+        self.push(
+            self.local(*location),
+            name_location,
+            ReferenceKind::Definition,
+        );
+        if let Some(annotation) = annotation {
+            self.type_ast(annotation);
+        }
+    }
+
+    fn type_ast(&mut self, type_ast: &TypeAst) {
+        match type_ast {
+            TypeAst::Constructor {
+                location,
+                module,
+                name,
+                arguments,
+            } => {
+                if let Some(symbol) = self.resolve_type(module.as_deref(), name) {
+                    // Skip over the module qualifier, if there is one
+                    let offset = module.as_ref().map(|m| m.len() as u32 + 1).unwrap_or(0);
+                    let within = SrcSpan::new(location.start + offset, location.end);
+                    let location = self.find_name(within, name);
+                    self.push(symbol, location, ReferenceKind::Usage);
+                }
+                for argument in arguments {
+                    self.type_ast(argument);
+                }
+            }
+
+            TypeAst::Fn {
+                arguments, return_, ..
+            } => {
+                for argument in arguments {
+                    self.type_ast(argument);
+                }
+                self.type_ast(return_);
+            }
+
+            TypeAst::Tuple { elems, .. } => {
+                for elem in elems {
+                    self.type_ast(elem);
+                }
+            }
+
+            TypeAst::Var { .. } | TypeAst::Hole { .. } => (),
+        }
+    }
+
+    fn statement(&mut self, statement: &TypedStatement) {
+        match statement {
+            crate::ast::Statement::Expression(expression) => self.expression(expression),
+            crate::ast::Statement::Assignment(assignment) => {
+                self.expression(&assignment.value);
+                // The variables can only be used after the value is assigned
+                self.scope_start = Some(assignment.value.location().end);
+                self.pattern(&assignment.pattern);
+                self.scope_start = None;
+                if let Some(annotation) = &assignment.annotation {
+                    self.type_ast(annotation);
+                }
+            }
+            crate::ast::Statement::Use(_) => (),
+        }
+    }
+
+    fn expression(&mut self, expression: &TypedExpr) {
+        match expression {
+            TypedExpr::Int { .. }
+            | TypedExpr::Float { .. }
+            | TypedExpr::String { .. }
+            | TypedExpr::Todo { .. }
+            | TypedExpr::Panic { .. } => (),
+
+            TypedExpr::Var {
+                location,
+                constructor,
+                name,
+            } => {
+                // Variables generated by the compiler start with an underscore
+                // and cannot be referenced by the programmer.
+                if name.starts_with('_') {
+                    return;
+                }
+                let symbol = match &constructor.variant {
+                    ValueConstructorVariant::LocalVariable { location } => {
+                        Some(self.local(*location))
+                    }
+                    ValueConstructorVariant::Record {
+                        name: original,
+                        module,
+                        ..
+                    } => Some(self.value(module, original)),
+                    ValueConstructorVariant::ModuleConstant { .. }
+                    | ValueConstructorVariant::ModuleFn { .. } => self.resolve_value(None, name),
+                };
+                if let Some(symbol) = symbol {
+                    self.push(symbol, Some(*location), ReferenceKind::Usage);
+                }
+            }
+
+            TypedExpr::ModuleSelect {
+                location,
+                label,
+                module_name,
+                ..
+            } => {
+                let symbol = self.value(module_name, label);
+                let location = self.trailing_name(*location, label);
+                self.push(symbol, location, ReferenceKind::Usage);
+            }
+
+            TypedExpr::Block {
+                location,
+                statements,
+            } => self.in_scope_until(location.end, |collector| {
+                for statement in statements {
+                    collector.statement(statement);
+                }
+            }),
+
+            TypedExpr::Pipeline {
+                assignments,
+                finally,
+                ..
+            } => {
+                for assignment in assignments {
+                    self.expression(&assignment.value);
+                }
+                self.expression(finally);
+            }
+
+            TypedExpr::Fn {
+                location,
+                args,
+                body,
+                return_annotation,
+                ..
+            } => self.in_scope_until(location.end, |collector| {
+                for argument in args {
+                    collector.argument(argument);
+                }
+                if let Some(annotation) = return_annotation {
+                    collector.type_ast(annotation);
+                }
+                for statement in body {
+                    collector.statement(statement);
+                }
+            }),
+
+            TypedExpr::List { elements, tail, .. } => {
+                for element in elements {
+                    self.expression(element);
+                }
+                if let Some(tail) = tail {
+                    self.expression(tail);
+                }
+            }
+
+            TypedExpr::Call { fun, args, .. } => {
+                self.expression(fun);
+                for argument in args {
+                    self.label(argument.location, argument.label.as_ref());
+                    self.expression(&argument.value);
+                }
+            }
+
+            TypedExpr::BinOp { left, right, .. } => {
+                self.expression(left);
+                self.expression(right);
+            }
+
+            TypedExpr::Case {
+                subjects, clauses, ..
+            } => {
+                for subject in subjects {
+                    self.expression(subject);
+                }
+                for clause in clauses {
+                    self.clause(clause);
+                }
+            }
+
+            TypedExpr::RecordAccess {
+                location,
+                label,
+                record,
+                ..
+            } => {
+                self.expression(record);
+                if let Some(label) = self.trailing_name(*location, label) {
+                    self.labels.push(label);
+                }
+            }
+
+            TypedExpr::Tuple { elems, .. } => {
+                for elem in elems {
+                    self.expression(elem);
+                }
+            }
+
+            TypedExpr::TupleIndex { tuple, .. } => self.expression(tuple),
+
+            TypedExpr::BitString { segments, .. } => {
+                for segment in segments {
+                    self.expression(&segment.value);
+                    for option in &segment.options {
+                        if let BitStringSegmentOption::Size { value, .. } = option {
+                            self.expression(value);
+                        }
+                    }
+                }
+            }
+
+            TypedExpr::RecordUpdate {
+                constructor,
+                spread,
+                args,
+                ..
+            } => {
+                self.expression(constructor);
+                self.expression(spread);
+                for argument in args {
+                    self.label(argument.location, Some(&argument.label));
+                    self.expression(&argument.value);
+                }
+            }
+
+            TypedExpr::NegateBool { value, .. } | TypedExpr::NegateInt { value, .. } => {
+                self.expression(value)
+            }
+        }
+    }
+
+    fn clause(&mut self, clause: &TypedClause) {
+        self.in_scope_until(clause.location().end, |collector| {
+            collector.clause_in_scope(clause)
+        });
+    }
+
+    fn clause_in_scope(&mut self, clause: &TypedClause) {
+        let first = self.references.len();
+        for pattern in &clause.pattern {
+            self.pattern(pattern);
+        }
+        for patterns in &clause.alternative_patterns {
+            for pattern in patterns {
+                self.pattern(pattern);
+            }
+        }
+
+        // Record the variables the patterns define so that they can be found
+        // when used in the guard.
+        let code = self.code;
+        self.clause_variables = self
+            .references
+            .iter()
+            .skip(first)
+            .filter(|reference| reference.kind == ReferenceKind::Definition)
+            .filter_map(|reference| match &reference.symbol {
+                Symbol::Local { definition, .. } => {
+                    let location = reference.location;
+                    let name = code.get(location.start as usize..location.end as usize)?;
+                    Some((name.into(), *definition))
+                }
+                Symbol::Value { .. } | Symbol::Type { .. } => None,
+            })
+            .collect();
+
+        if let Some(guard) = &clause.guard {
+            self.clause_guard(guard);
+        }
+        self.clause_variables.clear();
+        self.expression(&clause.then);
+    }
+
+    fn clause_guard(&mut self, guard: &TypedClauseGuard) {
+        match guard {
+            ClauseGuard::Equals { left, right, .. }
+            | ClauseGuard::NotEquals { left, right, .. }
+            | ClauseGuard::GtInt { left, right, .. }
+            | ClauseGuard::GtEqInt { left, right, .. }
+            | ClauseGuard::LtInt { left, right, .. }
+            | ClauseGuard::LtEqInt { left, right, .. }
+            | ClauseGuard::GtFloat { left, right, .. }
+            | ClauseGuard::GtEqFloat { left, right, .. }
+            | ClauseGuard::LtFloat { left, right, .. }
+            | ClauseGuard::LtEqFloat { left, right, .. }
+            | ClauseGuard::Or { left, right, .. }
+            | ClauseGuard::And { left, right, .. } => {
+                self.clause_guard(left);
+                self.clause_guard(right);
+            }
+
+            ClauseGuard::Var { location, name, .. } => {
+                if let Some(definition) = self.clause_variables.get(name) {
+                    let symbol = self.local(*definition);
+                    self.push(symbol, Some(*location), ReferenceKind::Usage);
+                }
+            }
+
+            ClauseGuard::TupleIndex { tuple, .. } => self.clause_guard(tuple),
+
+            ClauseGuard::Constant(constant) => self.constant(constant),
+        }
+    }
+
+    fn pattern(&mut self, pattern: &TypedPattern) {
+        match pattern {
+            Pattern::Int { .. }
+            | Pattern::Float { .. }
+            | Pattern::String { .. }
+            | Pattern::Discard { .. } => (),
+
+            // A variable used as the size of a bit string segment
+            Pattern::VarUsage {
+                location,
+                constructor,
+                ..
+            } => {
+                if let Some(ValueConstructorVariant::LocalVariable {
+                    location: definition,
+                }) = constructor.as_ref().map(|constructor| &constructor.variant)
+                {
+                    let symbol = self.local(*definition);
+                    self.push(symbol, Some(*location), ReferenceKind::Usage);
+                }
+            }
+
+            Pattern::Var { location, .. } => {
+                let symbol = self.local(*location);
+                self.push(symbol, Some(*location), ReferenceKind::Definition);
+            }
+
+            Pattern::Assign {
+                location, pattern, ..
+            } => {
+                self.pattern(pattern);
+                let symbol = self.local(*location);
+                self.push(symbol, Some(*location), ReferenceKind::Definition);
+            }
+
+            Pattern::Concatenate {
+                right_location,
+                right_side_assignment,
+                ..
+            } => {
+                if let AssignName::Variable(_) = right_side_assignment {
+                    let symbol = self.local(*right_location);
+                    self.push(symbol, Some(*right_location), ReferenceKind::Definition);
+                }
+            }
+
+            Pattern::List { elements, tail, .. } => {
+                for element in elements {
+                    self.pattern(element);
+                }
+                if let Some(tail) = tail {
+                    self.pattern(tail);
+                }
+            }
+
+            Pattern::Tuple { elems, .. } => {
+                for elem in elems {
+                    self.pattern(elem);
+                }
+            }
+
+            Pattern::Constructor {
+                location,
+                name,
+                module,
+                arguments,
+                constructor,
+                ..
+            } => {
+                if let crate::analyse::Inferred::Known(PatternConstructor::Record {
+                    name: original,
+                    module: Some(defining_module),
+                    ..
+                }) = constructor
+                {
+                    let symbol = self.value(defining_module, original);
+                    let offset = module.as_ref().map(|m| m.len() as u32 + 1).unwrap_or(0);
+                    let within = SrcSpan::new(location.start + offset, location.end);
+                    let location = self.find_name(within, name);
+                    self.push(symbol, location, ReferenceKind::Usage);
+                }
+                for argument in arguments {
+                    self.label(argument.location, argument.label.as_ref());
+                    self.pattern(&argument.value);
+                }
+            }
+
+            Pattern::BitString { segments, .. } => {
+                for segment in segments {
+                    self.pattern(&segment.value);
+                    for option in &segment.options {
+                        if let BitStringSegmentOption::Size { value, .. } = option {
+                            self.pattern(value);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn constant(&mut self, constant: &TypedConstant) {
+        match constant {
+            Constant::Int { .. }
+            | Constant::Float { .. }
+            | Constant::String { .. }
+            | Constant::BitString { .. } => (),
+
+            Constant::Tuple { elements, .. } | Constant::List { elements, .. } => {
+                for element in elements {
+                    self.constant(element);
+                }
+            }
+
+            Constant::Record {
+                location,
+                module,
+                name,
+                args,
+                ..
+            } => {
+                if let Some(symbol) = self.resolve_value(module.as_deref(), name) {
+                    let offset = module.as_ref().map(|m| m.len() as u32 + 1).unwrap_or(0);
+                    let within = SrcSpan::new(location.start + offset, location.end);
+                    let location = self.find_name(within, name);
+                    self.push(symbol, location, ReferenceKind::Usage);
+                }
+                for argument in args {
+                    self.label(argument.location, argument.label.as_ref());
+                    self.constant(&argument.value);
+                }
+            }
+
+            Constant::Var {
+                location,
+                module,
+                name,
+                ..
+            } => {
+                if let Some(symbol) = self.resolve_value(module.as_deref(), name) {
+                    let location = self.trailing_name(*location, name);
+                    self.push(symbol, location, ReferenceKind::Usage);
+                }
+            }
+        }
+    }
+}
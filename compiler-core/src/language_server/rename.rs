@@ -0,0 +1,395 @@
+use std::collections::{HashMap, HashSet};
+
+use itertools::Itertools;
+use lsp_types::{self as lsp, TextEdit};
+use smol_str::SmolStr;
+
+use crate::{
+    ast::{ModuleStatement, SrcSpan},
+    build::Module,
+    line_numbers::LineNumbers,
+    parse::{lexer, token::Token},
+};
+
+use super::{
+    path_to_uri,
+    references::{self, Reference, ReferenceKind, Symbol},
+    src_span_to_lsp_range,
+};
+
+/// The reasons a rename can be refused.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RenameError {
+    /// The new name is not a valid name for the symbol being renamed.
+    InvalidName { name: String },
+    /// The symbol is defined in a dependency or in the prelude, so the
+    /// definition cannot be edited.
+    NotInProject { module: SmolStr },
+    /// The symbol is referred to using an alias given to it in an
+    /// unqualified import, so the text under the cursor is not its name.
+    Aliased { alias: String, name: SmolStr },
+    /// A value with the new name already exists in a module that would see
+    /// the renamed value.
+    DuplicateName { name: String, module: SmolStr },
+    /// A type with the new name already exists in a module that would see
+    /// the renamed type.
+    DuplicateTypeName { name: String, module: SmolStr },
+    /// A local variable with the new name is in scope where the renamed
+    /// symbol is used, or the renamed variable is in scope where something
+    /// else with the new name is used, so a name would change its meaning.
+    ShadowedName { name: String, module: SmolStr },
+    /// The label of an argument or record field, which would have to be
+    /// renamed everywhere the function or record is called, matched on, or
+    /// accessed, so it is not renamed.
+    Label { label: String },
+}
+
+impl RenameError {
+    pub fn message(&self) -> String {
+        match self {
+            RenameError::InvalidName { name } => format!("`{name}` is not a valid name here."),
+            RenameError::NotInProject { module } if module.is_empty() => {
+                "Items defined in the Gleam prelude cannot be renamed.".into()
+            }
+            RenameError::NotInProject { module } => format!(
+                "`{module}` is not a module of this project so its definitions cannot be renamed."
+            ),
+            RenameError::Aliased { alias, name } => format!(
+                "`{alias}` is an alias for `{name}`. Rename the alias in the import, or rename \
+`{name}` where it is defined."
+            ),
+            RenameError::DuplicateName { name, module } => {
+                format!("The module `{module}` already has a value named `{name}`.")
+            }
+            RenameError::DuplicateTypeName { name, module } => {
+                format!("The module `{module}` already has a type named `{name}`.")
+            }
+            RenameError::ShadowedName { name, module } => format!(
+                "A variable named `{name}` in the module `{module}` would shadow or be \
+shadowed by the renamed item."
+            ),
+            RenameError::Label { label } => {
+                format!("`{label}` is a label, and labels cannot be renamed yet.")
+            }
+        }
+    }
+}
+
+/// Find the symbol at the given position and check it can be renamed.
+///
+pub fn prepare(
+    modules: &HashMap<String, Module>,
+    module: &Module,
+    byte_index: u32,
+) -> Result<Option<Reference>, RenameError> {
+    let reference = match references::reference_at(module, byte_index) {
+        Some(reference) => reference,
+        None => {
+            return match references::label_at(module, byte_index) {
+                Some(label) => Err(RenameError::Label {
+                    label: source_text(module, label).into(),
+                }),
+                None => Ok(None),
+            }
+        }
+    };
+
+    if let Symbol::Value {
+        module: defining_module,
+        name,
+    }
+    | Symbol::Type {
+        module: defining_module,
+        name,
+    } = &reference.symbol
+    {
+        if !modules.contains_key(defining_module.as_str()) {
+            return Err(RenameError::NotInProject {
+                module: defining_module.clone(),
+            });
+        }
+
+        let written = source_text(module, reference.location);
+        if written != name {
+            return Err(RenameError::Aliased {
+                alias: written.into(),
+                name: name.clone(),
+            });
+        }
+    }
+
+    Ok(Some(reference))
+}
+
+/// Rename the symbol at the given position, returning the edits to make to
+/// each module of the project.
+///
+pub fn rename(
+    modules: &HashMap<String, Module>,
+    module: &Module,
+    byte_index: u32,
+    new_name: &str,
+) -> Result<Option<lsp::WorkspaceEdit>, RenameError> {
+    let reference = match prepare(modules, module, byte_index)? {
+        Some(reference) => reference,
+        None => return Ok(None),
+    };
+    let symbol = reference.symbol;
+    let old_name = source_text(module, reference.location);
+
+    let is_upname = old_name.starts_with(|c: char| c.is_ascii_uppercase());
+    if !is_valid_name(new_name, is_upname) {
+        return Err(RenameError::InvalidName {
+            name: new_name.into(),
+        });
+    }
+    if new_name == old_name {
+        return Ok(None);
+    }
+
+    let mut changes = HashMap::new();
+    for module in modules.values() {
+        let module_references = references::module_references(module);
+        let mut edits = vec![];
+
+        for reference in &module_references {
+            // Uses of aliases given in unqualified imports are left alone,
+            // only the name being imported changes.
+            if reference.symbol != symbol || source_text(module, reference.location) != old_name {
+                continue;
+            }
+
+            match &symbol {
+                Symbol::Value { .. } => check_value_is_unique(module, &reference.kind, new_name)?,
+                Symbol::Type { .. } => check_type_is_unique(module, &reference.kind, new_name)?,
+                Symbol::Local { .. } => (),
+            }
+
+            // An unqualified import of a capitalised name can import both a
+            // type and a record constructor. If only one of them is being
+            // renamed the import of the other one has to be kept.
+            let imports_other = reference.kind == ReferenceKind::Import
+                && module_references.iter().any(|other| {
+                    other.location == reference.location
+                        && other.symbol != symbol
+                        && is_defined(modules, &other.symbol)
+                });
+            let new_text = if imports_other {
+                format!("{old_name}, {new_name}")
+            } else {
+                new_name.into()
+            };
+
+            edits.push(TextEdit {
+                range: src_span_to_lsp_range(reference.location, &LineNumbers::new(&module.code)),
+                new_text,
+            });
+        }
+
+        if !edits.is_empty() {
+            check_not_shadowed(module, &module_references, &symbol, old_name, new_name)?;
+            let _ = changes.insert(path_to_uri(module.input_path.clone()), edits);
+        }
+    }
+
+    Ok(Some(lsp::WorkspaceEdit {
+        changes: Some(changes),
+        document_changes: None,
+        change_annotations: None,
+    }))
+}
+
+fn source_text(module: &Module, location: SrcSpan) -> &str {
+    module
+        .code
+        .get(location.start as usize..location.end as usize)
+        .unwrap_or_default()
+}
+
+fn is_valid_name(name: &str, is_upname: bool) -> bool {
+    let tokens: Vec<_> = lexer::make_tokenizer(name).collect();
+    match tokens.as_slice() {
+        [Ok((_, Token::Name { .. }, end))] => !is_upname && *end as usize == name.len(),
+        [Ok((_, Token::UpName { .. }, end))] => is_upname && *end as usize == name.len(),
+        _ => false,
+    }
+}
+
+/// Whether a symbol has a definition in one of the modules of the project.
+fn is_defined(modules: &HashMap<String, Module>, symbol: &Symbol) -> bool {
+    let module = match symbol {
+        Symbol::Value { module, .. } | Symbol::Type { module, .. } => module,
+        Symbol::Local { .. } => return true,
+    };
+    modules.get(module.as_str()).is_some_and(|module| {
+        references::module_references(module)
+            .iter()
+            .any(|reference| {
+                reference.kind == ReferenceKind::Definition && &reference.symbol == symbol
+            })
+    })
+}
+
+/// A module sees the renamed item by its unqualified name if it defines it
+/// or imports it unqualified, in which case the new name must not clash with
+/// the other names it uses, as the analyser would reject it with a duplicate
+/// name error.
+fn sees_unqualified(kind: &ReferenceKind) -> bool {
+    match kind {
+        ReferenceKind::Definition | ReferenceKind::Import => true,
+        ReferenceKind::Usage => false,
+    }
+}
+
+fn check_value_is_unique(
+    module: &Module,
+    kind: &ReferenceKind,
+    new_name: &str,
+) -> Result<(), RenameError> {
+    if sees_unqualified(kind) && module_names(module).values.contains(new_name) {
+        return Err(RenameError::DuplicateName {
+            name: new_name.into(),
+            module: module.name.clone(),
+        });
+    }
+    Ok(())
+}
+
+fn check_type_is_unique(
+    module: &Module,
+    kind: &ReferenceKind,
+    new_name: &str,
+) -> Result<(), RenameError> {
+    if sees_unqualified(kind) && module_names(module).types.contains(new_name) {
+        return Err(RenameError::DuplicateTypeName {
+            name: new_name.into(),
+            module: module.name.clone(),
+        });
+    }
+    Ok(())
+}
+
+/// Local variables shadow the other values with the same name, so renaming
+/// must not move a use of the renamed symbol into the scope of a later
+/// variable with the new name, or move the use of something else with the
+/// new name into the scope of a renamed variable defined after it.
+fn check_not_shadowed(
+    module: &Module,
+    module_references: &[Reference],
+    symbol: &Symbol,
+    old_name: &str,
+    new_name: &str,
+) -> Result<(), RenameError> {
+    let scopes = references::local_scopes(module);
+    let scope_of = |symbol: &Symbol| match symbol {
+        Symbol::Local { definition, .. } => scopes.get(definition).copied(),
+        Symbol::Value { .. } | Symbol::Type { .. } => None,
+    };
+    let renamed_scope = scope_of(symbol);
+    // Of two variables in scope at the same place the one defined last is
+    // used, and module level values are shadowed by any variable.
+    let is_defined_after_renamed = |scope: SrcSpan| match renamed_scope {
+        Some(renamed) => scope.start > renamed.start,
+        None => true,
+    };
+    let uses = |name: &str| {
+        module_references
+            .iter()
+            .filter(|reference| {
+                reference.kind == ReferenceKind::Usage
+                    && source_text(module, reference.location) == name
+                    && !is_qualified(module, reference.location)
+            })
+            .collect_vec()
+    };
+    let renamed_uses = uses(old_name);
+
+    let is_shadowed = module_references
+        .iter()
+        .filter(|reference| {
+            reference.kind == ReferenceKind::Definition
+                && &reference.symbol != symbol
+                && source_text(module, reference.location) == new_name
+        })
+        .filter_map(|variable| scope_of(&variable.symbol))
+        .filter(|scope| is_defined_after_renamed(*scope))
+        .any(|scope| {
+            renamed_uses.iter().any(|reference| {
+                &reference.symbol == symbol && scope.contains(reference.location.start)
+            })
+        });
+
+    let shadows = renamed_scope.is_some_and(|renamed| {
+        uses(new_name).iter().any(|reference| {
+            let is_innermost = scope_of(&reference.symbol).is_some_and(is_defined_after_renamed);
+            &reference.symbol != symbol
+                && !is_innermost
+                && renamed.contains(reference.location.start)
+        })
+    });
+
+    if is_shadowed || shadows {
+        return Err(RenameError::ShadowedName {
+            name: new_name.into(),
+            module: module.name.clone(),
+        });
+    }
+    Ok(())
+}
+
+/// Whether a name is qualified with a module, as in `module.name`.
+fn is_qualified(module: &Module, location: SrcSpan) -> bool {
+    module
+        .code
+        .get(..location.start as usize)
+        .is_some_and(|before| before.ends_with('.'))
+}
+
+#[derive(Debug, Default)]
+struct ModuleNames<'a> {
+    values: HashSet<&'a str>,
+    types: HashSet<&'a str>,
+}
+
+/// The names of the values and types that are in scope at the top level of a
+/// module without being qualified.
+fn module_names(module: &Module) -> ModuleNames<'_> {
+    let mut names = ModuleNames::default();
+    for statement in &module.ast.statements {
+        match statement {
+            ModuleStatement::Function(function) => {
+                let _ = names.values.insert(&function.name);
+            }
+            ModuleStatement::ExternalFunction(function) => {
+                let _ = names.values.insert(&function.name);
+            }
+            ModuleStatement::ModuleConstant(constant) => {
+                let _ = names.values.insert(&constant.name);
+            }
+            ModuleStatement::TypeAlias(alias) => {
+                let _ = names.types.insert(&alias.alias);
+            }
+            ModuleStatement::ExternalType(type_) => {
+                let _ = names.types.insert(&type_.name);
+            }
+            ModuleStatement::CustomType(type_) => {
+                let _ = names.types.insert(&type_.name);
+                for constructor in &type_.constructors {
+                    let _ = names.values.insert(&constructor.name);
+                }
+            }
+            ModuleStatement::Import(import) => {
+                for item in &import.unqualified {
+                    let name = item.variable_name();
+                    if item.is_value() {
+                        let _ = names.values.insert(name);
+                    }
+                    if name.starts_with(|c: char| c.is_ascii_uppercase()) {
+                        let _ = names.types.insert(name);
+                    }
+                }
+            }
+        }
+    }
+    names
+}
@@ -0,0 +1,727 @@
+use std::collections::{HashMap, HashSet};
+
+use lsp_types::{SemanticToken, SemanticTokenModifier, SemanticTokenType, SemanticTokensLegend};
+use smol_str::SmolStr;
+
+use crate::{
+    ast::{
+        Arg, ArgNames, CallArg, ClauseGuard, Constant, ModuleStatement, Pattern, SrcSpan,
+        Statement, TypeAst, TypedArg, TypedClause, TypedClauseGuard, TypedConstant, TypedExpr,
+        TypedModuleStatement, TypedPattern, TypedStatement,
+    },
+    build::Module,
+    line_numbers::LineNumbers,
+    parse::{lexer, token::Token},
+    type_::{self, ValueConstructorVariant},
+};
+
+use super::references::{self, ReferenceKind, Symbol};
+
+/// The kinds of token, in the order they are given in the legend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Namespace,
+    Type,
+    TypeParameter,
+    Parameter,
+    Variable,
+    Label,
+    Constructor,
+    Function,
+}
+
+const TOKEN_TYPES: [SemanticTokenType; 8] = [
+    SemanticTokenType::NAMESPACE,
+    SemanticTokenType::TYPE,
+    SemanticTokenType::TYPE_PARAMETER,
+    SemanticTokenType::PARAMETER,
+    SemanticTokenType::VARIABLE,
+    SemanticTokenType::PROPERTY,
+    SemanticTokenType::ENUM_MEMBER,
+    SemanticTokenType::FUNCTION,
+];
+
+// The modifiers of a token are a bitset, each bit being the index of the
+// modifier in the legend.
+const DECLARATION: u32 = 1 << 0;
+const READONLY: u32 = 1 << 1;
+const UNUSED: u32 = 1 << 2;
+
+/// The types and modifiers of the semantic tokens, which the client is given
+/// when the server is initialised so that it can decode the tokens.
+///
+pub fn legend() -> SemanticTokensLegend {
+    SemanticTokensLegend {
+        token_types: TOKEN_TYPES.to_vec(),
+        token_modifiers: vec![
+            SemanticTokenModifier::DECLARATION,
+            SemanticTokenModifier::READONLY,
+            // Bindings that are never used, so that they can be dimmed.
+            SemanticTokenModifier::new("unused"),
+        ],
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Highlight {
+    location: SrcSpan,
+    kind: Kind,
+    modifiers: u32,
+}
+
+/// The semantic tokens of a module, optionally only those that start within
+/// the given range of byte indexes. The values of other modules are looked
+/// up in the importable modules to find whether they are functions,
+/// constants, or record constructors. The given unused variables are marked
+/// as unused.
+///
+pub fn semantic_tokens(
+    module: &Module,
+    importable_modules: &HashMap<SmolStr, &type_::Module>,
+    unused_variables: &[SrcSpan],
+    range: Option<SrcSpan>,
+) -> Vec<SemanticToken> {
+    let mut collector = HighlightCollector::new(&module.code);
+    for statement in &module.ast.statements {
+        collector.module_statement(statement);
+    }
+    let HighlightCollector {
+        highlights: syntactic,
+        parameters,
+        ..
+    } = collector;
+
+    let values = module_values(module);
+    let mut highlights: Vec<_> = references::module_references(module)
+        .into_iter()
+        .filter(|reference| is_name(&module.code, reference.location))
+        .map(|reference| {
+            let (kind, mut modifiers) = match &reference.symbol {
+                Symbol::Local { definition, .. } if parameters.contains(&definition.start) => {
+                    (Kind::Parameter, 0)
+                }
+                Symbol::Local { .. } => (Kind::Variable, 0),
+                Symbol::Type { .. } => (Kind::Type, 0),
+                Symbol::Value {
+                    module: value_module,
+                    name,
+                } if value_module == &module.name => values
+                    .get(name.as_str())
+                    .copied()
+                    .unwrap_or_else(|| value_kind_from_name(name)),
+                Symbol::Value {
+                    module: value_module,
+                    name,
+                } => importable_modules
+                    .get(value_module)
+                    .and_then(|module| module.values.get(name))
+                    .map(|value| match value.variant {
+                        ValueConstructorVariant::Record { .. } => (Kind::Constructor, 0),
+                        ValueConstructorVariant::ModuleConstant { .. } => {
+                            (Kind::Variable, READONLY)
+                        }
+                        ValueConstructorVariant::ModuleFn { .. }
+                        | ValueConstructorVariant::LocalVariable { .. } => (Kind::Function, 0),
+                    })
+                    .unwrap_or_else(|| value_kind_from_name(name)),
+            };
+            if reference.kind == ReferenceKind::Definition {
+                modifiers |= DECLARATION;
+                if let Symbol::Local { definition, .. } = &reference.symbol {
+                    if unused_variables.contains(definition) {
+                        modifiers |= UNUSED;
+                    }
+                }
+            }
+            Highlight {
+                location: reference.location,
+                kind,
+                modifiers,
+            }
+        })
+        .collect();
+    highlights.extend(syntactic);
+
+    // A name may be found more than once, such as a capitalised unqualified
+    // import which can be both a type and a record constructor. Only the
+    // first is kept.
+    highlights.sort_by_key(|highlight| highlight.location.start);
+    highlights.dedup_by_key(|highlight| highlight.location.start);
+    if let Some(range) = range {
+        highlights.retain(|highlight| {
+            range.start <= highlight.location.start && highlight.location.start < range.end
+        });
+    }
+    encode(&highlights, &LineNumbers::new(&module.code))
+}
+
+/// Encode the tokens as the client expects, each relative to the one before
+/// it.
+fn encode(highlights: &[Highlight], line_numbers: &LineNumbers) -> Vec<SemanticToken> {
+    let mut previous_line = 0;
+    let mut previous_column = 0;
+    highlights
+        .iter()
+        .map(|highlight| {
+            let position = line_numbers.line_and_column_number(highlight.location.start);
+            let line = position.line - 1;
+            let column = position.column - 1;
+            let delta_start = if line == previous_line {
+                column - previous_column
+            } else {
+                column
+            };
+            let token = SemanticToken {
+                delta_line: line - previous_line,
+                delta_start,
+                length: highlight.location.end - highlight.location.start,
+                token_type: highlight.kind as u32,
+                token_modifiers_bitset: highlight.modifiers,
+            };
+            previous_line = line;
+            previous_column = column;
+            token
+        })
+        .collect()
+}
+
+/// The kinds of the values defined in a module. The interface of the module
+/// only includes its public values, so they are taken from its definitions.
+fn module_values(module: &Module) -> HashMap<&str, (Kind, u32)> {
+    let mut values = HashMap::new();
+    for statement in &module.ast.statements {
+        match statement {
+            ModuleStatement::Function(function) => {
+                let _ = values.insert(function.name.as_str(), (Kind::Function, 0));
+            }
+            ModuleStatement::ExternalFunction(function) => {
+                let _ = values.insert(function.name.as_str(), (Kind::Function, 0));
+            }
+            ModuleStatement::ModuleConstant(constant) => {
+                let _ = values.insert(constant.name.as_str(), (Kind::Variable, READONLY));
+            }
+            ModuleStatement::CustomType(type_) => {
+                for constructor in &type_.constructors {
+                    let _ = values.insert(constructor.name.as_str(), (Kind::Constructor, 0));
+                }
+            }
+            ModuleStatement::TypeAlias(_)
+            | ModuleStatement::ExternalType(_)
+            | ModuleStatement::Import(_) => (),
+        }
+    }
+    values
+}
+
+fn value_kind_from_name(name: &str) -> (Kind, u32) {
+    if name.starts_with(|c: char| c.is_uppercase()) {
+        (Kind::Constructor, 0)
+    } else {
+        (Kind::Function, 0)
+    }
+}
+
+/// Whether a span of the source code is a single name. Some references cover
+/// more than the name, such as an aliased unqualified import, and these are
+/// not highlighted.
+fn is_name(code: &str, location: SrcSpan) -> bool {
+    code.get(location.start as usize..location.end as usize)
+        .map(|name| !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_'))
+        .unwrap_or(false)
+}
+
+/// Collects the names that are not references to values, types, or local
+/// variables: module names and aliases, labels, and type parameters. The
+/// locations of the arguments of functions are also collected so that the
+/// local variables they define can be told apart from others.
+struct HighlightCollector<'a> {
+    code: &'a str,
+    highlights: Vec<Highlight>,
+    /// The start of each function argument.
+    parameters: HashSet<u32>,
+}
+
+impl<'a> HighlightCollector<'a> {
+    fn new(code: &'a str) -> Self {
+        Self {
+            code,
+            highlights: vec![],
+            parameters: HashSet::new(),
+        }
+    }
+
+    /// Highlight a name that starts at the given byte index, if it is there.
+    fn push_at(&mut self, start: u32, name: &str, kind: Kind) {
+        let location = SrcSpan::new(start, start + name.len() as u32);
+        if self
+            .code
+            .get(location.start as usize..location.end as usize)
+            == Some(name)
+        {
+            self.highlights.push(Highlight {
+                location,
+                kind,
+                modifiers: 0,
+            });
+        }
+    }
+
+    /// Highlight a name that ends at the given byte index, such as the label
+    /// in `record.label`.
+    fn push_before(&mut self, end: u32, name: &str, kind: Kind) {
+        if let Some(start) = end.checked_sub(name.len() as u32) {
+            self.push_at(start, name, kind);
+        }
+    }
+
+    fn module_qualifier(&mut self, location: SrcSpan, module: &Option<SmolStr>) {
+        if let Some(module) = module {
+            self.push_at(location.start, module, Kind::Namespace);
+        }
+    }
+
+    fn label(&mut self, location: SrcSpan, label: &Option<SmolStr>) {
+        if let Some(label) = label {
+            self.push_at(location.start, label, Kind::Label);
+        }
+    }
+
+    fn module_statement(&mut self, statement: &TypedModuleStatement) {
+        match statement {
+            ModuleStatement::Function(function) => {
+                for argument in &function.arguments {
+                    self.argument(argument, true);
+                }
+                if let Some(annotation) = &function.return_annotation {
+                    self.type_ast(annotation);
+                }
+                for statement in &function.body {
+                    self.statement(statement);
+                }
+            }
+
+            ModuleStatement::ExternalFunction(function) => {
+                for argument in &function.arguments {
+                    self.label(argument.location, &argument.label);
+                    self.type_ast(&argument.annotation);
+                }
+                self.type_ast(&function.return_);
+            }
+
+            ModuleStatement::TypeAlias(alias) => {
+                self.type_parameters(alias.location, &alias.alias);
+                self.type_ast(&alias.type_ast);
+            }
+
+            ModuleStatement::CustomType(type_) => {
+                self.type_parameters(type_.location, &type_.name);
+                for constructor in &type_.constructors {
+                    for argument in &constructor.arguments {
+                        self.label(argument.location, &argument.label);
+                        self.type_ast(&argument.ast);
+                    }
+                }
+            }
+
+            ModuleStatement::ExternalType(type_) => {
+                self.type_parameters(type_.location, &type_.name);
+            }
+
+            ModuleStatement::Import(import) => self.import(import.location),
+
+            ModuleStatement::ModuleConstant(constant) => {
+                if let Some(annotation) = &constant.annotation {
+                    self.type_ast(annotation);
+                }
+                self.constant(&constant.value);
+            }
+        }
+    }
+
+    /// The module path and alias of an import. The unqualified values and
+    /// types it imports are references so they are highlighted elsewhere.
+    fn import(&mut self, location: SrcSpan) {
+        let code = self.code;
+        let src = code
+            .get(location.start as usize..location.end as usize)
+            .unwrap_or_default();
+        let mut in_unqualified = false;
+        for (start, token, end) in lexer::make_tokenizer(src).filter_map(Result::ok) {
+            match token {
+                Token::LeftBrace => in_unqualified = true,
+                Token::RightBrace => in_unqualified = false,
+                Token::Name { .. } if !in_unqualified => {
+                    self.highlights.push(Highlight {
+                        location: SrcSpan::new(location.start + start, location.start + end),
+                        kind: Kind::Namespace,
+                        modifiers: 0,
+                    });
+                }
+                _ => (),
+            }
+        }
+    }
+
+    /// The type parameters of a type definition, which are written in
+    /// parentheses after its name. Their locations are not recorded in the
+    /// AST so they are found with the lexer.
+    fn type_parameters(&mut self, location: SrcSpan, name: &str) {
+        let code = self.code;
+        let src = code
+            .get(location.start as usize..location.end as usize)
+            .unwrap_or_default();
+        let mut tokens = lexer::make_tokenizer(src)
+            .filter_map(Result::ok)
+            .skip_while(
+                |(_, token, _)| !matches!(token, Token::UpName { name: found } if found == name),
+            )
+            .skip(1);
+        if !matches!(tokens.next(), Some((_, Token::LeftParen, _))) {
+            return;
+        }
+        for (start, token, end) in tokens {
+            match token {
+                Token::Name { .. } => self.highlights.push(Highlight {
+                    location: SrcSpan::new(location.start + start, location.start + end),
+                    kind: Kind::TypeParameter,
+                    modifiers: 0,
+                }),
+                Token::RightParen => break,
+                _ => (),
+            }
+        }
+    }
+
+    fn argument(&mut self, argument: &TypedArg, is_parameter: bool) {
+        let Arg {
+            names,
+            location,
+            annotation,
+            ..
+        } = argument;
+        match names {
+            ArgNames::LabelledDiscard { label, .. } | ArgNames::NamedLabelled { label, .. } => {
+                self.push_at(location.start, label, Kind::Label)
+            }
+            ArgNames::Discard { .. } | ArgNames::Named { .. } => (),
+        }
+        if is_parameter {
+            let _ = self.parameters.insert(location.start);
+        }
+        if let Some(annotation) = annotation {
+            self.type_ast(annotation);
+        }
+    }
+
+    fn type_ast(&mut self, type_ast: &TypeAst) {
+        match type_ast {
+            TypeAst::Constructor {
+                location,
+                module,
+                arguments,
+                ..
+            } => {
+                self.module_qualifier(*location, module);
+                for argument in arguments {
+                    self.type_ast(argument);
+                }
+            }
+
+            TypeAst::Fn {
+                arguments, return_, ..
+            } => {
+                for argument in arguments {
+                    self.type_ast(argument);
+                }
+                self.type_ast(return_);
+            }
+
+            TypeAst::Tuple { elems, .. } => {
+                for elem in elems {
+                    self.type_ast(elem);
+                }
+            }
+
+            TypeAst::Var { location, name } => {
+                self.push_at(location.start, name, Kind::TypeParameter)
+            }
+
+            TypeAst::Hole { .. } => (),
+        }
+    }
+
+    fn statement(&mut self, statement: &TypedStatement) {
+        match statement {
+            Statement::Expression(expression) => self.expression(expression),
+            Statement::Assignment(assignment) => {
+                self.pattern(&assignment.pattern);
+                if let Some(annotation) = &assignment.annotation {
+                    self.type_ast(annotation);
+                }
+                self.expression(&assignment.value);
+            }
+            Statement::Use(_) => (),
+        }
+    }
+
+    fn expression(&mut self, expression: &TypedExpr) {
+        match expression {
+            TypedExpr::Int { .. }
+            | TypedExpr::Float { .. }
+            | TypedExpr::String { .. }
+            | TypedExpr::Var { .. }
+            | TypedExpr::Todo { .. }
+            | TypedExpr::Panic { .. } => (),
+
+            TypedExpr::ModuleSelect {
+                location,
+                module_alias,
+                ..
+            } => self.push_at(location.start, module_alias, Kind::Namespace),
+
+            TypedExpr::Block { statements, .. } => {
+                for statement in statements {
+                    self.statement(statement);
+                }
+            }
+
+            TypedExpr::Pipeline {
+                assignments,
+                finally,
+                ..
+            } => {
+                for assignment in assignments {
+                    self.expression(&assignment.value);
+                }
+                self.expression(finally);
+            }
+
+            TypedExpr::Fn {
+                args,
+                body,
+                return_annotation,
+                ..
+            } => {
+                for argument in args {
+                    self.argument(argument, true);
+                }
+                if let Some(annotation) = return_annotation {
+                    self.type_ast(annotation);
+                }
+                for statement in body {
+                    self.statement(statement);
+                }
+            }
+
+            TypedExpr::List { elements, tail, .. } => {
+                for element in elements {
+                    self.expression(element);
+                }
+                if let Some(tail) = tail {
+                    self.expression(tail);
+                }
+            }
+
+            TypedExpr::Call { fun, args, .. } => {
+                self.expression(fun);
+                for argument in args {
+                    match &argument.value {
+                        // The arguments of the callback given implicitly by
+                        // `use` are written as variables being assigned.
+                        TypedExpr::Fn { args, body, .. } if argument.implicit => {
+                            for argument in args {
+                                self.argument(argument, false);
+                            }
+                            for statement in body {
+                                self.statement(statement);
+                            }
+                        }
+                        value => {
+                            self.label(argument.location, &argument.label);
+                            self.expression(value);
+                        }
+                    }
+                }
+            }
+
+            TypedExpr::BinOp { left, right, .. } => {
+                self.expression(left);
+                self.expression(right);
+            }
+
+            TypedExpr::Case {
+                subjects, clauses, ..
+            } => {
+                for subject in subjects {
+                    self.expression(subject);
+                }
+                for clause in clauses {
+                    self.clause(clause);
+                }
+            }
+
+            TypedExpr::RecordAccess {
+                location,
+                label,
+                record,
+                ..
+            } => {
+                self.expression(record);
+                self.push_before(location.end, label, Kind::Label);
+            }
+
+            TypedExpr::Tuple { elems, .. } => {
+                for elem in elems {
+                    self.expression(elem);
+                }
+            }
+
+            TypedExpr::TupleIndex { tuple, .. } => self.expression(tuple),
+
+            TypedExpr::BitString { segments, .. } => {
+                for segment in segments {
+                    self.expression(&segment.value);
+                }
+            }
+
+            TypedExpr::RecordUpdate { spread, args, .. } => {
+                self.expression(spread);
+                for argument in args {
+                    self.push_at(argument.location.start, &argument.label, Kind::Label);
+                    self.expression(&argument.value);
+                }
+            }
+
+            TypedExpr::NegateBool { value, .. } | TypedExpr::NegateInt { value, .. } => {
+                self.expression(value)
+            }
+        }
+    }
+
+    fn clause(&mut self, clause: &TypedClause) {
+        for pattern in &clause.pattern {
+            self.pattern(pattern);
+        }
+        for patterns in &clause.alternative_patterns {
+            for pattern in patterns {
+                self.pattern(pattern);
+            }
+        }
+        if let Some(guard) = &clause.guard {
+            self.clause_guard(guard);
+        }
+        self.expression(&clause.then);
+    }
+
+    fn clause_guard(&mut self, guard: &TypedClauseGuard) {
+        match guard {
+            ClauseGuard::Equals { left, right, .. }
+            | ClauseGuard::NotEquals { left, right, .. }
+            | ClauseGuard::GtInt { left, right, .. }
+            | ClauseGuard::GtEqInt { left, right, .. }
+            | ClauseGuard::LtInt { left, right, .. }
+            | ClauseGuard::LtEqInt { left, right, .. }
+            | ClauseGuard::GtFloat { left, right, .. }
+            | ClauseGuard::GtEqFloat { left, right, .. }
+            | ClauseGuard::LtFloat { left, right, .. }
+            | ClauseGuard::LtEqFloat { left, right, .. }
+            | ClauseGuard::Or { left, right, .. }
+            | ClauseGuard::And { left, right, .. } => {
+                self.clause_guard(left);
+                self.clause_guard(right);
+            }
+
+            ClauseGuard::TupleIndex { tuple, .. } => self.clause_guard(tuple),
+
+            ClauseGuard::Constant(constant) => self.constant(constant),
+
+            ClauseGuard::Var { .. } => (),
+        }
+    }
+
+    fn pattern(&mut self, pattern: &TypedPattern) {
+        match pattern {
+            Pattern::Int { .. }
+            | Pattern::Float { .. }
+            | Pattern::String { .. }
+            | Pattern::Var { .. }
+            | Pattern::VarUsage { .. }
+            | Pattern::Discard { .. }
+            | Pattern::Concatenate { .. } => (),
+
+            Pattern::Assign { pattern, .. } => self.pattern(pattern),
+
+            Pattern::List { elements, tail, .. } => {
+                for element in elements {
+                    self.pattern(element);
+                }
+                if let Some(tail) = tail {
+                    self.pattern(tail);
+                }
+            }
+
+            Pattern::Tuple { elems, .. } => {
+                for elem in elems {
+                    self.pattern(elem);
+                }
+            }
+
+            Pattern::Constructor {
+                location,
+                module,
+                arguments,
+                ..
+            } => {
+                self.module_qualifier(*location, module);
+                for CallArg {
+                    label,
+                    location,
+                    value,
+                    ..
+                } in arguments
+                {
+                    self.label(*location, label);
+                    self.pattern(value);
+                }
+            }
+
+            Pattern::BitString { segments, .. } => {
+                for segment in segments {
+                    self.pattern(&segment.value);
+                }
+            }
+        }
+    }
+
+    fn constant(&mut self, constant: &TypedConstant) {
+        match constant {
+            Constant::Int { .. }
+            | Constant::Float { .. }
+            | Constant::String { .. }
+            | Constant::BitString { .. } => (),
+
+            Constant::Tuple { elements, .. } | Constant::List { elements, .. } => {
+                for element in elements {
+                    self.constant(element);
+                }
+            }
+
+            Constant::Record {
+                location,
+                module,
+                args,
+                ..
+            } => {
+                self.module_qualifier(*location, module);
+                for argument in args {
+                    self.label(argument.location, &argument.label);
+                    self.constant(&argument.value);
+                }
+            }
+
+            Constant::Var {
+                location, module, ..
+            } => self.module_qualifier(*location, module),
+        }
+    }
+}
@@ -2,19 +2,26 @@ use crate::{
     diagnostic::{Diagnostic, Level},
     io::{CommandExecutor, FileSystemReader, FileSystemWriter},
     language_server::{
+        configuration::Configuration,
         engine::{self, LanguageServerEngine},
         feedback::{Feedback, FeedbackBookKeeper},
         files::FileSystemProxy,
+        path_to_uri,
+        rename::RenameError,
         router::Router,
-        src_span_to_lsp_range, DownloadDependencies, MakeLocker,
+        semantic_tokens, src_span_to_lsp_range, DownloadDependencies, MakeLocker,
     },
     line_numbers::LineNumbers,
     Result,
 };
 use debug_ignore::DebugIgnore;
 use lsp::{
-    notification::{DidChangeWatchedFiles, DidOpenTextDocument},
-    request::GotoDefinition,
+    notification::{DidChangeConfiguration, DidChangeWatchedFiles, DidOpenTextDocument},
+    request::{
+        CodeActionRequest, DocumentSymbolRequest, GotoDefinition, InlayHintRequest,
+        PrepareRenameRequest, References, Rename, SemanticTokensFullRequest,
+        SemanticTokensRangeRequest, SignatureHelpRequest, WorkspaceSymbol,
+    },
     HoverProviderCapability, Position, Range, TextEdit, Url,
 };
 use lsp_types::{
@@ -24,10 +31,18 @@ use lsp_types::{
     InitializeParams, PublishDiagnosticsParams,
 };
 use serde_json::Value as Json;
-use std::{collections::HashMap, path::PathBuf};
+use smol_str::SmolStr;
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
 
 use super::progress::ConnectionProgressReporter;
 
+/// The LSP error code for a request that was valid but could not be carried
+/// out. It is not yet defined by the `lsp-server` crate.
+const REQUEST_FAILED: i32 = -32803;
+
 /// This class is responsible for handling the language server protocol and
 /// delegating the work to the engine.
 ///
@@ -37,10 +52,12 @@ use super::progress::ConnectionProgressReporter;
 /// - Sending diagnostics and messages to the client.
 /// - Tracking the state of diagnostics and messages.
 /// - Performing the initialisation handshake.
+/// - Tracking the configuration given by the client.
 ///
 #[derive(Debug)]
 pub struct LanguageServer<'a, IO> {
     initialise_params: InitializeParams,
+    configuration: Configuration,
     connection: DebugIgnore<&'a lsp_server::Connection>,
     outside_of_project_feedback: FeedbackBookKeeper,
     router: Router<IO, ConnectionProgressReporter<'a>>,
@@ -58,12 +75,18 @@ where
 {
     pub fn new(connection: &'a lsp_server::Connection, io: IO) -> Result<Self> {
         let initialise_params = initialisation_handshake(connection);
+        let configuration = initialise_params
+            .initialization_options
+            .clone()
+            .map(Configuration::from_json)
+            .unwrap_or_default();
         let reporter = ConnectionProgressReporter::new(connection, &initialise_params);
         let io = FileSystemProxy::new(io);
         let router = Router::new(reporter, io.clone());
         Ok(Self {
             connection: connection.into(),
             initialise_params,
+            configuration,
             outside_of_project_feedback: FeedbackBookKeeper::default(),
             router,
             io,
@@ -126,21 +149,82 @@ where
                 self.goto_definition(params)
             }
 
+            "textDocument/codeAction" => {
+                let params = cast_request::<CodeActionRequest>(request);
+                self.code_action(params)
+            }
+
             "textDocument/completion" => {
                 let params = cast_request::<Completion>(request);
                 self.completion(params)
             }
 
+            "textDocument/references" => {
+                let params = cast_request::<References>(request);
+                self.references(params)
+            }
+
+            "textDocument/prepareRename" => {
+                let params = cast_request::<PrepareRenameRequest>(request);
+                let (result, feedback) = self.prepare_rename(params);
+                self.publish_feedback(feedback);
+                return self.send_response(id, result);
+            }
+
+            "textDocument/rename" => {
+                let params = cast_request::<Rename>(request);
+                let (result, feedback) = self.rename(params);
+                self.publish_feedback(feedback);
+                return self.send_response(id, result);
+            }
+
+            "textDocument/signatureHelp" => {
+                let params = cast_request::<SignatureHelpRequest>(request);
+                self.signature_help(params)
+            }
+
+            "textDocument/documentSymbol" => {
+                let params = cast_request::<DocumentSymbolRequest>(request);
+                self.document_symbol(params)
+            }
+
+            "textDocument/inlayHint" => {
+                let params = cast_request::<InlayHintRequest>(request);
+                self.inlay_hint(params)
+            }
+
+            "textDocument/semanticTokens/full" => {
+                let params = cast_request::<SemanticTokensFullRequest>(request);
+                self.semantic_tokens(params)
+            }
+
+            "textDocument/semanticTokens/range" => {
+                let params = cast_request::<SemanticTokensRangeRequest>(request);
+                self.semantic_tokens_range(params)
+            }
+
+            "workspace/symbol" => {
+                let params = cast_request::<WorkspaceSymbol>(request);
+                self.workspace_symbol(params)
+            }
+
             _ => panic!("Unsupported LSP request"),
         };
 
         self.publish_feedback(feedback);
+        self.send_response(id, Ok(payload))
+    }
 
-        let response = lsp_server::Response {
-            id,
-            error: None,
-            result: Some(payload),
+    fn send_response(
+        &self,
+        id: lsp_server::RequestId,
+        result: Result<Json, lsp_server::ResponseError>,
+    ) {
+        let (result, error) = match result {
+            Ok(payload) => (Some(payload), None),
+            Err(error) => (None, Some(error)),
         };
+        let response = lsp_server::Response { id, error, result };
         self.connection
             .sender
             .send(lsp_server::Message::Response(response))
@@ -174,6 +258,12 @@ where
                 self.watched_files_changed(params)
             }
 
+            "workspace/didChangeConfiguration" => {
+                let params = cast_notification::<DidChangeConfiguration>(notification);
+                self.configuration = Configuration::from_json(params.settings);
+                return;
+            }
+
             _ => return,
         };
 
@@ -366,7 +456,138 @@ where
 
     fn completion(&mut self, params: lsp::CompletionParams) -> (Json, Feedback) {
         let path = path(&params.text_document_position.text_document.uri);
-        self.respond_with_engine(path, |engine| engine.completion(params))
+        let src = match self.read_src(&path) {
+            Ok(src) => src,
+            Err(feedback) => return (Json::Null, feedback),
+        };
+        self.respond_with_engine(path, |engine| engine.completion(params, src))
+    }
+
+    fn signature_help(&mut self, params: lsp::SignatureHelpParams) -> (Json, Feedback) {
+        let path = path(&params.text_document_position_params.text_document.uri);
+        let src = match self.read_src(&path) {
+            Ok(src) => src,
+            Err(feedback) => return (Json::Null, feedback),
+        };
+        self.respond_with_engine(path, |engine| engine.signature_help(params, src))
+    }
+
+    /// Read the current source code of a module. This is used rather than
+    /// the source of the compiled module as it will usually have been edited
+    /// since it last compiled.
+    fn read_src(&mut self, path: &Path) -> Result<SmolStr, Feedback> {
+        self.io.read(path).map(SmolStr::from).map_err(|error| {
+            match self.router.project_for_path(path) {
+                Ok(Some(project)) => project.feedback.error(error),
+                Ok(None) | Err(_) => self.outside_of_project_feedback.error(error),
+            }
+        })
+    }
+
+    fn code_action(&mut self, params: lsp::CodeActionParams) -> (Json, Feedback) {
+        let path = path(&params.text_document.uri);
+        self.respond_with_engine(path, |engine| engine.code_actions(params))
+    }
+
+    fn references(&mut self, params: lsp::ReferenceParams) -> (Json, Feedback) {
+        let path = path(&params.text_document_position.text_document.uri);
+        self.respond_with_engine(path, |engine| engine.references(params))
+    }
+
+    fn prepare_rename(
+        &mut self,
+        params: lsp::TextDocumentPositionParams,
+    ) -> (Result<Json, lsp_server::ResponseError>, Feedback) {
+        let path = path(&params.text_document.uri);
+        self.respond_with_rename_refusal(path, |engine| engine.prepare_rename(params))
+    }
+
+    fn rename(
+        &mut self,
+        params: lsp::RenameParams,
+    ) -> (Result<Json, lsp_server::ResponseError>, Feedback) {
+        let path = path(&params.text_document_position.text_document.uri);
+        self.respond_with_rename_refusal(path, |engine| engine.rename(params))
+    }
+
+    fn document_symbol(&mut self, params: lsp::DocumentSymbolParams) -> (Json, Feedback) {
+        let path = path(&params.text_document.uri);
+        self.respond_with_engine(path, |engine| engine.document_symbols(params))
+    }
+
+    fn inlay_hint(&mut self, params: lsp::InlayHintParams) -> (Json, Feedback) {
+        let path = path(&params.text_document.uri);
+        let config = self.configuration.inlay_hints;
+        self.respond_with_engine(path, |engine| engine.inlay_hints(params, config))
+    }
+
+    fn semantic_tokens(&mut self, params: lsp::SemanticTokensParams) -> (Json, Feedback) {
+        let path = path(&params.text_document.uri);
+        self.respond_with_engine(path, |engine| engine.semantic_tokens(params))
+    }
+
+    fn semantic_tokens_range(
+        &mut self,
+        params: lsp::SemanticTokensRangeParams,
+    ) -> (Json, Feedback) {
+        let path = path(&params.text_document.uri);
+        self.respond_with_engine(path, |engine| engine.semantic_tokens_range(params))
+    }
+
+    /// Workspace symbol requests are not made for any particular file, so the
+    /// project at the root of the workspace is searched.
+    fn workspace_symbol(&mut self, params: lsp::WorkspaceSymbolParams) -> (Json, Feedback) {
+        let root = match &self.initialise_params.root_uri {
+            Some(uri) => path(uri),
+            None => return (Json::Null, Feedback::default()),
+        };
+        let path = root.join("gleam.toml");
+        self.respond_with_engine(path, |engine| engine.workspace_symbols(params))
+    }
+
+    /// A rename can be refused by the engine, for example if the new name
+    /// would clash with an existing one. In that case the client gets an
+    /// error response explaining why, which it shows to the user.
+    fn respond_with_rename_refusal<T, Handler>(
+        &mut self,
+        path: PathBuf,
+        handler: Handler,
+    ) -> (Result<Json, lsp_server::ResponseError>, Feedback)
+    where
+        T: serde::Serialize,
+        Handler: FnOnce(
+            &mut LanguageServerEngine<IO, ConnectionProgressReporter<'a>>,
+        ) -> engine::Response<Result<Option<T>, RenameError>>,
+    {
+        let mut refusal = None;
+        let (json, feedback) = self.respond_with_engine(path, |engine| {
+            let engine::Response {
+                result,
+                warnings,
+                compiled_modules,
+            } = handler(engine);
+            let result = result.map(|outcome| {
+                outcome.unwrap_or_else(|error| {
+                    refusal = Some(error);
+                    None
+                })
+            });
+            engine::Response {
+                result,
+                warnings,
+                compiled_modules,
+            }
+        });
+
+        let result = match refusal {
+            Some(error) => Err(lsp_server::ResponseError {
+                code: REQUEST_FAILED,
+                message: error.message(),
+                data: None,
+            }),
+            None => Ok(json),
+        };
+        (result, feedback)
     }
 
     /// A file opened in the editor may be unsaved, so store a copy of the
@@ -454,24 +675,36 @@ fn initialisation_handshake(connection: &lsp_server::Connection) -> InitializePa
             resolve_provider: None,
             trigger_characters: Some(vec![".".into(), " ".into()]),
             all_commit_characters: None,
+            completion_item: None,
+            work_done_progress_options: lsp::WorkDoneProgressOptions {
+                work_done_progress: None,
+            },
+        }),
+        signature_help_provider: Some(lsp::SignatureHelpOptions {
+            trigger_characters: Some(vec!["(".into(), ",".into()]),
+            retrigger_characters: None,
             work_done_progress_options: lsp::WorkDoneProgressOptions {
                 work_done_progress: None,
             },
         }),
-        signature_help_provider: None,
         definition_provider: Some(lsp::OneOf::Left(true)),
         type_definition_provider: None,
         implementation_provider: None,
-        references_provider: None,
+        references_provider: Some(lsp::OneOf::Left(true)),
         document_highlight_provider: None,
-        document_symbol_provider: None,
-        workspace_symbol_provider: None,
-        code_action_provider: None,
+        document_symbol_provider: Some(lsp::OneOf::Left(true)),
+        workspace_symbol_provider: Some(lsp::OneOf::Left(true)),
+        code_action_provider: Some(lsp::CodeActionProviderCapability::Simple(true)),
         code_lens_provider: None,
         document_formatting_provider: Some(lsp::OneOf::Left(true)),
         document_range_formatting_provider: None,
         document_on_type_formatting_provider: None,
-        rename_provider: None,
+        rename_provider: Some(lsp::OneOf::Right(lsp::RenameOptions {
+            prepare_provider: Some(true),
+            work_done_progress_options: lsp::WorkDoneProgressOptions {
+                work_done_progress: None,
+            },
+        })),
         document_link_provider: None,
         color_provider: None,
         folding_range_provider: None,
@@ -479,9 +712,20 @@ fn initialisation_handshake(connection: &lsp_server::Connection) -> InitializePa
         execute_command_provider: None,
         workspace: None,
         call_hierarchy_provider: None,
-        semantic_tokens_provider: None,
+        semantic_tokens_provider: Some(
+            lsp::SemanticTokensOptions {
+                work_done_progress_options: lsp::WorkDoneProgressOptions {
+                    work_done_progress: None,
+                },
+                legend: semantic_tokens::legend(),
+                range: Some(true),
+                full: Some(lsp::SemanticTokensFullOptions::Bool(true)),
+            }
+            .into(),
+        ),
         moniker_provider: None,
         linked_editing_range_provider: None,
+        inlay_hint_provider: Some(lsp::OneOf::Left(true)),
         experimental: None,
     };
     let server_capabilities_json =
@@ -554,7 +798,9 @@ fn diagnostic_to_lsp(diagnostic: Diagnostic) -> Vec<lsp::Diagnostic> {
     let main = lsp::Diagnostic {
         range: src_span_to_lsp_range(location.label.span, &line_numbers),
         severity: Some(severity),
-        code: None,
+        code: diagnostic
+            .code
+            .map(|code| lsp::NumberOrString::String(code.into())),
         code_description: None,
         source: None,
         message: text,
@@ -576,12 +822,6 @@ fn diagnostic_to_lsp(diagnostic: Diagnostic) -> Vec<lsp::Diagnostic> {
     }
 }
 
-fn path_to_uri(path: PathBuf) -> Url {
-    let mut file: String = "file://".into();
-    file.push_str(&path.as_os_str().to_string_lossy());
-    Url::parse(&file).expect("path_to_uri URL parse")
-}
-
 fn path(uri: &Url) -> PathBuf {
     // The to_file_path method is available on these platforms
     #[cfg(any(unix, windows, target_os = "redox", target_os = "wasi"))]
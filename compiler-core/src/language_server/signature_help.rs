@@ -0,0 +1,446 @@
+use std::{collections::HashMap, sync::Arc};
+
+use lsp_types::{
+    Documentation, MarkupContent, MarkupKind, ParameterInformation, ParameterLabel, SignatureHelp,
+    SignatureInformation,
+};
+use smol_str::SmolStr;
+
+use crate::{
+    ast::{ArgNames, ModuleStatement},
+    build::Module,
+    parse::{lexer, token::Token},
+    type_::{
+        self, collapse_links, pretty::Printer, Type, ValueConstructor, ValueConstructorVariant,
+    },
+};
+
+use super::completion::{imported_module, locals};
+
+/// A call being typed, worked out from the source code before the cursor.
+#[derive(Debug, PartialEq, Eq)]
+struct Call {
+    qualifier: Option<SmolStr>,
+    name: SmolStr,
+    /// The call is the right hand side of a pipe, so the first argument is
+    /// the value being piped into it.
+    piped: bool,
+    /// The call is the right hand side of a `use`, so the last argument is
+    /// the callback made of the rest of the block.
+    used: bool,
+    /// The labels of the arguments before the one being typed.
+    labels: Vec<SmolStr>,
+    /// The number of unlabelled arguments before the one being typed.
+    positional: usize,
+    /// The label of the argument being typed, if it has one.
+    label: Option<SmolStr>,
+}
+
+#[derive(Debug)]
+struct Parameter {
+    label: Option<SmolStr>,
+    name: Option<SmolStr>,
+    type_: Arc<Type>,
+}
+
+#[derive(Debug)]
+struct Signature {
+    parameters: Vec<Parameter>,
+    return_type: Arc<Type>,
+    documentation: Option<SmolStr>,
+}
+
+/// Shows the signature of the function being called at a position in a
+/// module, highlighting the argument being typed.
+///
+/// As with completions the source code is often not valid Gleam while the
+/// arguments are being typed, so the call is found from the current source
+/// code while the function is looked up in the last compiled version of the
+/// module.
+///
+pub struct SignatureHelper<'a> {
+    src: &'a str,
+    byte_index: u32,
+    module: &'a Module,
+    /// The interfaces of every module that can be imported, including the
+    /// modules of the project.
+    importable_modules: &'a HashMap<SmolStr, &'a type_::Module>,
+    /// The compiled modules of the project, used to find the names of the
+    /// arguments of the functions they define.
+    project_modules: &'a HashMap<String, Module>,
+}
+
+impl<'a> SignatureHelper<'a> {
+    pub fn new(
+        src: &'a str,
+        byte_index: u32,
+        module: &'a Module,
+        importable_modules: &'a HashMap<SmolStr, &'a type_::Module>,
+        project_modules: &'a HashMap<String, Module>,
+    ) -> Self {
+        Self {
+            src,
+            byte_index,
+            module,
+            importable_modules,
+            project_modules,
+        }
+    }
+
+    pub fn signature_help(&self) -> Option<SignatureHelp> {
+        let call = call(self.src.get(..self.byte_index as usize)?)?;
+        let signature = match &call.qualifier {
+            Some(qualifier) => {
+                let module = imported_module(self.module, qualifier)?;
+                self.imported_signature(module, &call.name)?
+            }
+            None => self.unqualified_signature(&call.name)?,
+        };
+        let active_parameter = active_parameter(&call, &signature.parameters);
+
+        let mut printer = Printer::new();
+        let name = match &call.qualifier {
+            Some(qualifier) => format!("{qualifier}.{}", call.name),
+            None => call.name.to_string(),
+        };
+        let parameters: Vec<_> = signature
+            .parameters
+            .iter()
+            .map(|parameter| {
+                let type_ = printer.pretty_print(&parameter.type_, 0);
+                match (&parameter.label, &parameter.name) {
+                    (Some(label), Some(name)) if label != name => {
+                        format!("{label} {name}: {type_}")
+                    }
+                    (Some(name), _) | (None, Some(name)) => format!("{name}: {type_}"),
+                    (None, None) => type_,
+                }
+            })
+            .collect();
+        let label = format!(
+            "{name}({}) -> {}",
+            parameters.join(", "),
+            printer.pretty_print(&signature.return_type, 0)
+        );
+
+        let information = SignatureInformation {
+            label,
+            documentation: signature.documentation.map(|documentation| {
+                Documentation::MarkupContent(MarkupContent {
+                    kind: MarkupKind::Markdown,
+                    value: documentation.to_string(),
+                })
+            }),
+            parameters: Some(
+                parameters
+                    .into_iter()
+                    .map(|parameter| ParameterInformation {
+                        label: ParameterLabel::Simple(parameter),
+                        documentation: None,
+                    })
+                    .collect(),
+            ),
+            active_parameter,
+        };
+        Some(SignatureHelp {
+            signatures: vec![information],
+            active_signature: Some(0),
+            active_parameter,
+        })
+    }
+
+    /// The signature of a function or record constructor referred to without
+    /// a module qualifier. Local variables shadow the values of the module,
+    /// which shadow the values of the prelude.
+    fn unqualified_signature(&self, name: &str) -> Option<Signature> {
+        if let Some(local) = locals(self.module, self.byte_index)
+            .into_iter()
+            .find(|local| local.name == name)
+        {
+            return type_signature(local.type_, None, None);
+        }
+
+        if let Some(signature) = definition_signature(self.module, name) {
+            return Some(signature);
+        }
+
+        let imported = self.module.ast.statements.iter().find_map(|statement| {
+            let ModuleStatement::Import(import) = statement else {
+                return None;
+            };
+            import
+                .unqualified
+                .iter()
+                .find(|item| item.is_value() && item.variable_name() == name)
+                .map(|item| (&import.module, &item.name))
+        });
+        if let Some((module, name)) = imported {
+            return self.imported_signature(module, name);
+        }
+
+        let prelude = type_::build_prelude(&crate::uid::UniqueIdGenerator::new());
+        let value = prelude.values.get(name)?;
+        value_signature(value)
+    }
+
+    /// The signature of a function or record constructor defined in another
+    /// module. The names of the arguments are only known if the module is
+    /// part of the project.
+    fn imported_signature(&self, module: &str, name: &str) -> Option<Signature> {
+        if let Some(module) = self.project_modules.get(module) {
+            if let Some(signature) = definition_signature(module, name) {
+                return Some(signature);
+            }
+        }
+        let value = self.importable_modules.get(module)?.values.get(name)?;
+        value_signature(value)
+    }
+}
+
+/// The signature of a function or record constructor defined in a module.
+fn definition_signature(module: &Module, name: &str) -> Option<Signature> {
+    module
+        .ast
+        .statements
+        .iter()
+        .find_map(|statement| match statement {
+            ModuleStatement::Function(function) if function.name == name => Some(Signature {
+                parameters: function
+                    .arguments
+                    .iter()
+                    .map(|argument| {
+                        let (label, name) = match &argument.names {
+                            ArgNames::Named { name } => (None, Some(name)),
+                            ArgNames::NamedLabelled { label, name } => (Some(label), Some(name)),
+                            ArgNames::LabelledDiscard { label, .. } => (Some(label), None),
+                            ArgNames::Discard { .. } => (None, None),
+                        };
+                        Parameter {
+                            label: label.cloned(),
+                            name: name.cloned(),
+                            type_: argument.type_.clone(),
+                        }
+                    })
+                    .collect(),
+                return_type: function.return_type.clone(),
+                documentation: function.documentation.clone(),
+            }),
+
+            ModuleStatement::ExternalFunction(function) if function.name == name => {
+                Some(Signature {
+                    parameters: function
+                        .arguments
+                        .iter()
+                        .map(|argument| Parameter {
+                            label: argument.label.clone(),
+                            name: None,
+                            type_: argument.type_.clone(),
+                        })
+                        .collect(),
+                    return_type: function.return_type.clone(),
+                    documentation: function.documentation.clone(),
+                })
+            }
+
+            ModuleStatement::CustomType(custom_type) => {
+                let constructor = custom_type
+                    .constructors
+                    .iter()
+                    .find(|constructor| constructor.name == name)?;
+                Some(Signature {
+                    parameters: constructor
+                        .arguments
+                        .iter()
+                        .map(|argument| Parameter {
+                            label: argument.label.clone(),
+                            name: None,
+                            type_: argument.type_.clone(),
+                        })
+                        .collect(),
+                    return_type: Arc::new(Type::App {
+                        public: custom_type.public,
+                        module: module.name.clone(),
+                        name: custom_type.name.clone(),
+                        args: custom_type.typed_parameters.clone(),
+                    }),
+                    documentation: constructor.documentation.clone(),
+                })
+            }
+
+            _ => None,
+        })
+}
+
+/// The signature of a value from a module interface, which has the labels of
+/// its arguments but not their names.
+fn value_signature(value: &ValueConstructor) -> Option<Signature> {
+    let (field_map, documentation) = match &value.variant {
+        ValueConstructorVariant::ModuleFn {
+            field_map,
+            documentation,
+            ..
+        }
+        | ValueConstructorVariant::Record {
+            field_map,
+            documentation,
+            ..
+        } => (field_map.as_ref(), documentation.clone()),
+        ValueConstructorVariant::LocalVariable { .. }
+        | ValueConstructorVariant::ModuleConstant { .. } => (None, None),
+    };
+    let labels = field_map.map(|field_map| {
+        field_map
+            .fields
+            .iter()
+            .map(|(label, index)| (*index, label))
+            .collect::<HashMap<_, _>>()
+    });
+    type_signature(value.type_.clone(), labels, documentation)
+}
+
+fn type_signature(
+    type_: Arc<Type>,
+    labels: Option<HashMap<u32, &SmolStr>>,
+    documentation: Option<SmolStr>,
+) -> Option<Signature> {
+    let type_ = collapse_links(type_);
+    let Type::Fn { args, retrn } = type_.as_ref() else {
+        return None;
+    };
+    let parameters = args
+        .iter()
+        .enumerate()
+        .map(|(index, type_)| Parameter {
+            label: labels
+                .as_ref()
+                .and_then(|labels| labels.get(&(index as u32)))
+                .map(|label| (*label).clone()),
+            name: None,
+            type_: type_.clone(),
+        })
+        .collect();
+    Some(Signature {
+        parameters,
+        return_type: retrn.clone(),
+        documentation,
+    })
+}
+
+/// The index of the parameter the argument being typed is for. Labelled
+/// arguments go to the parameter with that label, and the unlabelled
+/// arguments fill the remaining parameters in order.
+fn active_parameter(call: &Call, parameters: &[Parameter]) -> Option<u32> {
+    let index_of_label = |label: &SmolStr| {
+        parameters
+            .iter()
+            .position(|parameter| parameter.label.as_ref() == Some(label))
+    };
+
+    let index = match &call.label {
+        Some(label) => index_of_label(label)?,
+        None => {
+            let labelled: Vec<_> = call.labels.iter().filter_map(index_of_label).collect();
+            let implicit = usize::from(call.piped);
+            (0..parameters.len())
+                .filter(|index| !labelled.contains(index))
+                .nth(implicit + call.positional)?
+        }
+    };
+
+    // The callback given by `use` is never typed in the arguments
+    if call.used && index + 1 == parameters.len() {
+        return None;
+    }
+    Some(index as u32)
+}
+
+/// Find the innermost call whose arguments are being typed at the end of the
+/// source code.
+fn call(before: &str) -> Option<Call> {
+    let tokens: Vec<_> = lexer::make_tokenizer(before)
+        .filter_map(Result::ok)
+        .map(|(_, token, _)| token)
+        .filter(|token| {
+            !matches!(
+                token,
+                Token::CommentNormal
+                    | Token::CommentDoc { .. }
+                    | Token::CommentModule
+                    | Token::EmptyLine
+                    | Token::EndOfFile
+            )
+        })
+        .collect();
+
+    // The brackets that have not been closed, and for parentheses the commas
+    // within them.
+    let mut open: Vec<(Token, usize, Vec<usize>)> = vec![];
+    for (index, token) in tokens.iter().enumerate() {
+        match token {
+            Token::LeftParen | Token::LeftSquare | Token::LeftBrace => {
+                open.push((token.clone(), index, vec![]))
+            }
+            Token::RightParen | Token::RightSquare | Token::RightBrace => {
+                let _ = open.pop();
+            }
+            Token::Comma => {
+                if let Some((_, _, commas)) = open.last_mut() {
+                    commas.push(index);
+                }
+            }
+            _ => (),
+        }
+    }
+    let (bracket, paren, commas) = open.pop()?;
+    if bracket != Token::LeftParen {
+        return None;
+    }
+
+    let token = |index: usize| tokens.get(index);
+    let name = match token(paren.checked_sub(1)?)? {
+        Token::Name { name } | Token::UpName { name } => name.clone(),
+        _ => return None,
+    };
+    let (qualifier, start) = match (
+        paren.checked_sub(2).and_then(token),
+        paren.checked_sub(3).and_then(token),
+    ) {
+        (Some(Token::Dot), Some(Token::Name { name })) => (Some(name.clone()), paren - 3),
+        _ => (None, paren - 1),
+    };
+    let preceding = start.checked_sub(1).and_then(token);
+    // The parameters of a function definition rather than a call
+    if preceding == Some(&Token::Fn) {
+        return None;
+    }
+
+    let mut labels = vec![];
+    let mut positional = 0;
+    let mut label = None;
+    let argument_starts = std::iter::once(paren).chain(commas.iter().copied());
+    let argument_count = commas.len() + 1;
+    for (argument, start) in argument_starts.enumerate() {
+        let argument_label = match (token(start + 1), token(start + 2)) {
+            (Some(Token::Name { name }), Some(Token::Colon)) => Some(name.clone()),
+            _ => None,
+        };
+        if argument + 1 == argument_count {
+            label = argument_label;
+        } else {
+            match argument_label {
+                Some(argument_label) => labels.push(argument_label),
+                None => positional += 1,
+            }
+        }
+    }
+
+    Some(Call {
+        qualifier,
+        name,
+        piped: preceding == Some(&Token::Pipe),
+        used: preceding == Some(&Token::LArrow),
+        labels,
+        positional,
+        label,
+    })
+}
@@ -0,0 +1,264 @@
+use lsp_types::{DocumentSymbol, Location, SymbolInformation, SymbolKind, Url};
+
+use crate::{
+    ast::{ModuleStatement, SrcSpan, TypedModuleStatement},
+    build::Module,
+    line_numbers::LineNumbers,
+    parse::{lexer, token::Token},
+    type_::{self, ValueConstructorVariant},
+};
+
+use super::{path_to_uri, src_span_to_lsp_range};
+
+/// A definition in a module, along with the definitions nested within it.
+#[derive(Debug)]
+struct Symbol<'a> {
+    name: &'a str,
+    kind: SymbolKind,
+    /// The whole of the definition.
+    location: SrcSpan,
+    /// The name of the definition.
+    name_location: SrcSpan,
+    children: Vec<Symbol<'a>>,
+}
+
+/// The outline of a module: its functions, constants, and types, in the order
+/// they are defined, with the constructors of each custom type nested within
+/// it.
+///
+pub fn document_symbols(module: &Module) -> Vec<DocumentSymbol> {
+    let line_numbers = LineNumbers::new(&module.code);
+    module_symbols(module)
+        .into_iter()
+        .map(|symbol| document_symbol(symbol, &line_numbers))
+        .collect()
+}
+
+/// The definitions of the project modules whose names match the query.
+///
+pub fn project_symbols<'a>(
+    modules: impl IntoIterator<Item = &'a Module>,
+    query: &str,
+) -> Vec<SymbolInformation> {
+    let mut found = vec![];
+    for module in modules {
+        let line_numbers = LineNumbers::new(&module.code);
+        let uri = path_to_uri(module.input_path.clone());
+        for symbol in module_symbols(module) {
+            if matches(symbol.name, query) {
+                found.push(symbol_information(
+                    symbol.name,
+                    symbol.kind,
+                    location(&uri, symbol.location, &line_numbers),
+                    &module.name,
+                ));
+            }
+            for child in symbol.children {
+                if matches(child.name, query) {
+                    found.push(symbol_information(
+                        child.name,
+                        child.kind,
+                        location(&uri, child.location, &line_numbers),
+                        &format!("{}.{}", module.name, symbol.name),
+                    ));
+                }
+            }
+        }
+    }
+    found
+}
+
+/// The public definitions in the interface of a dependency module whose names
+/// match the query. The interface does not contain the source code of the
+/// module so it must be given to be able to find the lines of the
+/// definitions.
+///
+pub fn interface_symbols(
+    module: &type_::Module,
+    uri: &Url,
+    src: &str,
+    query: &str,
+) -> Vec<SymbolInformation> {
+    let line_numbers = LineNumbers::new(src);
+    let values = module
+        .values
+        .iter()
+        .filter(|(_, value)| value.public)
+        .filter_map(|(name, value)| match &value.variant {
+            ValueConstructorVariant::ModuleFn { location, .. } => {
+                Some((name, SymbolKind::FUNCTION, *location))
+            }
+            ValueConstructorVariant::ModuleConstant { location, .. } => {
+                Some((name, SymbolKind::CONSTANT, *location))
+            }
+            ValueConstructorVariant::Record { location, .. } => {
+                Some((name, SymbolKind::CONSTRUCTOR, *location))
+            }
+            ValueConstructorVariant::LocalVariable { .. } => None,
+        });
+    let types = module
+        .types
+        .iter()
+        .filter(|(_, type_)| type_.public)
+        .map(|(name, type_)| (name, SymbolKind::CLASS, type_.origin));
+
+    values
+        .chain(types)
+        .filter(|(name, _, _)| matches(name, query))
+        .map(|(name, kind, span)| {
+            symbol_information(name, kind, location(uri, span, &line_numbers), &module.name)
+        })
+        .collect()
+}
+
+/// A name matches a query if it contains the query, ignoring case. Every
+/// name matches an empty query.
+fn matches(name: &str, query: &str) -> bool {
+    name.to_lowercase().contains(&query.to_lowercase())
+}
+
+fn location(uri: &Url, span: SrcSpan, line_numbers: &LineNumbers) -> Location {
+    Location {
+        uri: uri.clone(),
+        range: src_span_to_lsp_range(span, line_numbers),
+    }
+}
+
+#[allow(deprecated)]
+fn symbol_information(
+    name: &str,
+    kind: SymbolKind,
+    location: Location,
+    container: &str,
+) -> SymbolInformation {
+    SymbolInformation {
+        name: name.into(),
+        kind,
+        tags: None,
+        deprecated: None,
+        location,
+        container_name: Some(container.into()),
+    }
+}
+
+#[allow(deprecated)]
+fn document_symbol(symbol: Symbol<'_>, line_numbers: &LineNumbers) -> DocumentSymbol {
+    let children: Vec<_> = symbol
+        .children
+        .into_iter()
+        .map(|child| document_symbol(child, line_numbers))
+        .collect();
+    DocumentSymbol {
+        name: symbol.name.into(),
+        detail: None,
+        kind: symbol.kind,
+        tags: None,
+        deprecated: None,
+        range: src_span_to_lsp_range(symbol.location, line_numbers),
+        selection_range: src_span_to_lsp_range(symbol.name_location, line_numbers),
+        children: (!children.is_empty()).then_some(children),
+    }
+}
+
+/// The symbols of a module in source order. The statements of a typed module
+/// are ordered for type inference rather than as they were written, so they
+/// have to be sorted.
+fn module_symbols(module: &Module) -> Vec<Symbol<'_>> {
+    let src = &module.code;
+    let mut symbols: Vec<_> = module
+        .ast
+        .statements
+        .iter()
+        .filter_map(|statement| statement_symbol(src, statement))
+        .collect();
+    symbols.sort_by_key(|symbol| symbol.location.start);
+    symbols
+}
+
+fn statement_symbol<'a>(src: &str, statement: &'a TypedModuleStatement) -> Option<Symbol<'a>> {
+    let symbol = |name: &'a str, kind, location: SrcSpan| Symbol {
+        name,
+        kind,
+        location,
+        name_location: name_location(src, location, name),
+        children: vec![],
+    };
+
+    match statement {
+        ModuleStatement::Function(function) => {
+            let location = SrcSpan::new(function.location.start, function.end_position);
+            Some(symbol(&function.name, SymbolKind::FUNCTION, location))
+        }
+
+        ModuleStatement::ExternalFunction(function) => Some(symbol(
+            &function.name,
+            SymbolKind::FUNCTION,
+            function.location,
+        )),
+
+        ModuleStatement::ModuleConstant(constant) => Some(symbol(
+            &constant.name,
+            SymbolKind::CONSTANT,
+            constant.location,
+        )),
+
+        ModuleStatement::TypeAlias(alias) => {
+            Some(symbol(&alias.alias, SymbolKind::CLASS, alias.location))
+        }
+
+        ModuleStatement::ExternalType(type_) => {
+            Some(symbol(&type_.name, SymbolKind::CLASS, type_.location))
+        }
+
+        ModuleStatement::CustomType(type_) => {
+            let children: Vec<_> = type_
+                .constructors
+                .iter()
+                .map(|constructor| {
+                    symbol(
+                        &constructor.name,
+                        SymbolKind::CONSTRUCTOR,
+                        constructor.location,
+                    )
+                })
+                .collect();
+            // The location of a custom type only covers its head, so extend
+            // it to the closing brace after the last constructor.
+            let constructors_end = children
+                .iter()
+                .map(|child| child.location.end)
+                .max()
+                .unwrap_or(type_.location.end);
+            let end = src
+                .get(constructors_end as usize..)
+                .and_then(|after| after.find('}'))
+                .map(|offset| constructors_end + offset as u32 + 1)
+                .unwrap_or(constructors_end);
+            let location = SrcSpan::new(type_.location.start, end);
+            Some(Symbol {
+                children,
+                ..symbol(&type_.name, SymbolKind::CLASS, location)
+            })
+        }
+
+        ModuleStatement::Import(_) => None,
+    }
+}
+
+/// The location of the name of a definition, found by looking for the first
+/// token of the definition with that name. If it cannot be found the whole
+/// definition is used.
+fn name_location(src: &str, location: SrcSpan, name: &str) -> SrcSpan {
+    let definition = src
+        .get(location.start as usize..location.end as usize)
+        .unwrap_or_default();
+    lexer::make_tokenizer(definition)
+        .filter_map(Result::ok)
+        .find_map(|(start, token, end)| match token {
+            Token::Name { name: found } | Token::UpName { name: found } if found == name => {
+                Some(SrcSpan::new(location.start + start, location.start + end))
+            }
+            _ => None,
+        })
+        .unwrap_or(location)
+}
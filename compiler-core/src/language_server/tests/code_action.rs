@@ -0,0 +1,346 @@
+use lsp_types::{
+    CodeActionContext, CodeActionParams, PartialResultParams, Position, Range,
+    TextDocumentIdentifier, Url, WorkDoneProgressParams,
+};
+
+use super::*;
+
+/// Replace a module that has already been compiled, updating its
+/// modification time so that it is compiled again.
+fn edit_src_module(io: &LanguageServerTestIO, name: &str, code: &str) {
+    io.src_module(name, code);
+    let path = io.paths.src_directory().join(name).with_extension("gleam");
+    io.io.set_modification_time(&path, SystemTime::now());
+}
+
+/// The titles of the code actions for a range of a module, along with the
+/// source of the module after each has been applied.
+fn code_actions(
+    engine: &mut Engine,
+    io: &LanguageServerTestIO,
+    path: &str,
+    range: Range,
+) -> Vec<(String, String)> {
+    let params = CodeActionParams {
+        text_document: TextDocumentIdentifier {
+            uri: Url::from_file_path(path).unwrap(),
+        },
+        range,
+        context: CodeActionContext {
+            diagnostics: vec![],
+            only: None,
+        },
+        work_done_progress_params: WorkDoneProgressParams::default(),
+        partial_result_params: PartialResultParams::default(),
+    };
+    let actions = engine
+        .code_actions(params)
+        .result
+        .unwrap()
+        .unwrap_or_default();
+    let src = io.read(Path::new(path)).unwrap();
+    let line_numbers = crate::line_numbers::LineNumbers::new(&src);
+
+    actions
+        .into_iter()
+        .map(|action| {
+            let mut changes = action.edit.unwrap().changes.unwrap();
+            let edits = changes.remove(&Url::from_file_path(path).unwrap()).unwrap();
+            let mut new_src = src.clone();
+            for edit in edits.into_iter().rev() {
+                let start = line_numbers
+                    .byte_index(edit.range.start.line, edit.range.start.character)
+                    as usize;
+                let end =
+                    line_numbers.byte_index(edit.range.end.line, edit.range.end.character) as usize;
+                new_src.replace_range(start..end, &edit.new_text);
+            }
+            (action.title, new_src)
+        })
+        .collect()
+}
+
+fn whole_module() -> Range {
+    Range::new(Position::new(0, 0), Position::new(100, 0))
+}
+
+#[test]
+fn remove_unused_imported_module() {
+    let io = LanguageServerTestIO::new();
+    io.src_module("app/maths", "pub fn double(x) { x * 2 }\n");
+    io.src_module(
+        "app",
+        "import app/maths
+
+pub fn main() {
+  1
+}
+",
+    );
+    let mut engine = compiled_engine(&io);
+
+    assert_eq!(
+        code_actions(&mut engine, &io, "/src/app.gleam", whole_module()),
+        vec![(
+            "Remove unused import of `maths`".into(),
+            "
+pub fn main() {
+  1
+}
+"
+            .into()
+        )]
+    );
+}
+
+#[test]
+fn remove_unused_unqualified_imports() {
+    let io = LanguageServerTestIO::new();
+    io.src_module(
+        "app/maths",
+        "pub fn one() { 1 }
+pub fn two() { 2 }
+pub fn three() { 3 }
+",
+    );
+    io.src_module(
+        "app",
+        "import app/maths.{one, two, three as drei}
+
+pub fn main() {
+  two()
+}
+",
+    );
+    let mut engine = compiled_engine(&io);
+
+    let mut actions = code_actions(&mut engine, &io, "/src/app.gleam", whole_module());
+    actions.sort();
+    assert_eq!(
+        actions,
+        vec![
+            (
+                "Remove unused import of `drei`".into(),
+                "import app/maths.{one, two}
+
+pub fn main() {
+  two()
+}
+"
+                .into()
+            ),
+            (
+                "Remove unused import of `one`".into(),
+                "import app/maths.{two, three as drei}
+
+pub fn main() {
+  two()
+}
+"
+                .into()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn remove_only_unused_unqualified_import() {
+    let io = LanguageServerTestIO::new();
+    io.src_module(
+        "app/maths",
+        "pub type Number { Number(Int) }
+pub fn double(x) { x * 2 }
+",
+    );
+    io.src_module(
+        "app",
+        "import app/maths.{Number}
+
+pub fn main() {
+  maths.double(1)
+}
+",
+    );
+    let mut engine = compiled_engine(&io);
+
+    assert_eq!(
+        code_actions(&mut engine, &io, "/src/app.gleam", whole_module()),
+        vec![(
+            "Remove unused import of `Number`".into(),
+            "import app/maths
+
+pub fn main() {
+  maths.double(1)
+}
+"
+            .into()
+        )]
+    );
+}
+
+#[test]
+fn remove_double_negation() {
+    let io = LanguageServerTestIO::new();
+    io.src_module(
+        "app",
+        "pub fn main(x, y) {
+  #(--x, !!y)
+}
+",
+    );
+    let mut engine = compiled_engine(&io);
+
+    let mut actions = code_actions(&mut engine, &io, "/src/app.gleam", whole_module());
+    actions.sort();
+    assert_eq!(
+        actions,
+        vec![
+            (
+                "Remove double negation".into(),
+                "pub fn main(x, y) {
+  #(--x, y)
+}
+"
+                .into()
+            ),
+            (
+                "Remove double negation".into(),
+                "pub fn main(x, y) {
+  #(x, !!y)
+}
+"
+                .into()
+            ),
+        ]
+    );
+
+    // Only the fixes for problems in the requested range are given
+    let range = Range::new(Position::new(1, 3), Position::new(1, 4));
+    assert_eq!(
+        code_actions(&mut engine, &io, "/src/app.gleam", range),
+        vec![(
+            "Remove double negation".into(),
+            "pub fn main(x, y) {
+  #(x, !!y)
+}
+"
+            .into()
+        )]
+    );
+}
+
+#[test]
+fn replace_unknown_variable() {
+    let io = LanguageServerTestIO::new();
+    io.src_module(
+        "app",
+        "pub fn main(value) {
+  valeu + 1
+}
+",
+    );
+    let mut engine = setup_engine(&io);
+    assert!(engine.compile_please().result.is_err());
+
+    assert_eq!(
+        code_actions(&mut engine, &io, "/src/app.gleam", whole_module()),
+        vec![(
+            "Replace with `value`".into(),
+            "pub fn main(value) {
+  value + 1
+}
+"
+            .into()
+        )]
+    );
+}
+
+#[test]
+fn import_unknown_module() {
+    let io = LanguageServerTestIO::new();
+    io.src_module("app/maths", "pub fn double(x) { x * 2 }\n");
+    io.src_module("app/other", "pub fn double(x) { x * 2 }\n");
+    io.src_module(
+        "app",
+        "import app/other
+
+pub fn main() {
+  other.double(1)
+}
+",
+    );
+    let mut engine = compiled_engine(&io);
+
+    // Once compiled the project modules are known, so they can be suggested
+    // when the module is edited to use one without importing it.
+    edit_src_module(
+        &io,
+        "app",
+        "import app/other
+
+pub fn main() {
+  other.double(maths.double(1))
+}
+",
+    );
+    let _ = engine.compile_please();
+
+    assert_eq!(
+        code_actions(&mut engine, &io, "/src/app.gleam", whole_module()),
+        vec![(
+            "Import `app/maths`".into(),
+            "import app/other
+import app/maths
+
+pub fn main() {
+  other.double(maths.double(1))
+}
+"
+            .into()
+        )]
+    );
+}
+
+#[test]
+fn import_unknown_module_without_imports() {
+    let io = LanguageServerTestIO::new();
+    io.src_module("app/maths", "pub fn double(x) { x * 2 }\n");
+    io.src_module("app", "pub fn main() { 1 }\n");
+    let mut engine = compiled_engine(&io);
+
+    edit_src_module(
+        &io,
+        "app",
+        "pub fn main() {
+  maths.double(1)
+}
+",
+    );
+    let _ = engine.compile_please();
+
+    assert_eq!(
+        code_actions(&mut engine, &io, "/src/app.gleam", whole_module()),
+        vec![(
+            "Import `app/maths`".into(),
+            "import app/maths
+
+pub fn main() {
+  maths.double(1)
+}
+"
+            .into()
+        )]
+    );
+}
+
+#[test]
+fn no_code_actions() {
+    let io = LanguageServerTestIO::new();
+    io.src_module("app", "pub fn main() { 1 }\n");
+    let mut engine = compiled_engine(&io);
+
+    assert_eq!(
+        code_actions(&mut engine, &io, "/src/app.gleam", whole_module()),
+        vec![]
+    );
+}
@@ -1,4 +1,12 @@
+use std::time::SystemTime;
+
+use lsp_types::{
+    HoverParams, Position, TextDocumentIdentifier, TextDocumentPositionParams, Url,
+    WorkDoneProgressParams,
+};
+
 use super::*;
+use crate::ast::SrcSpan;
 
 #[test]
 fn compile_please() {
@@ -93,3 +101,82 @@ fn compile_error_in_test() {
         ]
     )
 }
+
+#[test]
+fn compile_recovers_from_syntax_errors() {
+    let io = LanguageServerTestIO::new();
+    let mut engine = setup_engine(&io);
+
+    io.src_module("app", "pub fn two() {\n  let two = 2\n  two\n}\n");
+    assert!(engine.compile_please().result.is_ok());
+
+    let src = "pub fn one() { 1 + }
+
+pub fn two() {
+  let two = 2
+  two
+}
+
+fn three( {
+  3
+}
+";
+    io.src_module("app", src);
+    let path = io.paths.src_directory().join("app.gleam");
+    io.io.set_modification_time(&path, SystemTime::now());
+
+    // Each syntax error is reported
+    let response = engine.compile_please();
+    let error = response.result.unwrap_err();
+    let labels: Vec<_> = error
+        .to_diagnostics()
+        .into_iter()
+        .filter_map(|diagnostic| diagnostic.location)
+        .map(|location| location.label.span)
+        .collect();
+    assert_eq!(labels, vec![SrcSpan::new(17, 18), SrcSpan::new(70, 71)]);
+
+    // The functions that could be parsed have been analysed, so the language
+    // server works for them at their new positions
+    let params = HoverParams {
+        text_document_position_params: TextDocumentPositionParams {
+            text_document: TextDocumentIdentifier {
+                uri: Url::from_file_path(path).unwrap(),
+            },
+            position: Position::new(3, 7),
+        },
+        work_done_progress_params: WorkDoneProgressParams::default(),
+    };
+    assert!(engine.hover(params).result.unwrap().is_some());
+}
+
+#[test]
+fn compile_recovers_from_syntax_errors_in_a_module_never_compiled() {
+    let io = LanguageServerTestIO::new();
+    let mut engine = setup_engine(&io);
+
+    io.src_module(
+        "app",
+        "pub fn one() { 1 + }
+
+pub fn two() {
+  let two = 2
+  two
+}
+",
+    );
+    assert!(engine.compile_please().result.is_err());
+
+    // The function that could be parsed has been analysed even though the
+    // module has not been compiled before
+    let params = HoverParams {
+        text_document_position_params: TextDocumentPositionParams {
+            text_document: TextDocumentIdentifier {
+                uri: Url::from_file_path(io.paths.src_directory().join("app.gleam")).unwrap(),
+            },
+            position: Position::new(3, 7),
+        },
+        work_done_progress_params: WorkDoneProgressParams::default(),
+    };
+    assert!(engine.hover(params).result.unwrap().is_some());
+}
@@ -0,0 +1,341 @@
+use lsp_types::{
+    CompletionItem, CompletionItemKind, CompletionParams, Documentation, MarkupContent, MarkupKind,
+    PartialResultParams, Position, TextDocumentIdentifier, TextDocumentPositionParams, Url,
+    WorkDoneProgressParams,
+};
+
+use super::*;
+
+/// Get the completions at a position in a module, where the module has been
+/// edited to have the given source code since it was compiled.
+fn completions(
+    engine: &mut Engine,
+    path: &str,
+    src: &str,
+    position: Position,
+) -> Vec<CompletionItem> {
+    let params = CompletionParams {
+        text_document_position: TextDocumentPositionParams {
+            text_document: TextDocumentIdentifier {
+                uri: Url::from_file_path(path).unwrap(),
+            },
+            position,
+        },
+        work_done_progress_params: WorkDoneProgressParams::default(),
+        partial_result_params: PartialResultParams::default(),
+        context: None,
+    };
+    let response = engine.completion(params, src.into());
+    response.result.unwrap().unwrap_or_default()
+}
+
+fn labels(completions: &[CompletionItem]) -> Vec<&str> {
+    completions.iter().map(|item| item.label.as_str()).collect()
+}
+
+fn find<'a>(completions: &'a [CompletionItem], label: &str) -> &'a CompletionItem {
+    completions
+        .iter()
+        .find(|item| item.label == label)
+        .unwrap_or_else(|| panic!("No completion for {label}"))
+}
+
+#[test]
+fn local_variables_in_scope() {
+    let io = LanguageServerTestIO::new();
+    let src = "pub fn main(first) {
+  let second = first + 1
+  let result = case second {
+    1 -> 0
+    other -> other
+  }
+  let third = 3
+  result
+}
+";
+    io.src_module("app", src);
+    let mut engine = compiled_engine(&io);
+
+    let found = completions(&mut engine, "/src/app.gleam", src, Position::new(6, 14));
+    let found_labels = labels(&found);
+    assert!(found_labels.contains(&"first"));
+    assert!(found_labels.contains(&"second"));
+    assert!(found_labels.contains(&"result"));
+    // Defined in a different clause or after the cursor
+    assert!(!found_labels.contains(&"other"));
+    assert!(!found_labels.contains(&"third"));
+
+    let found = completions(&mut engine, "/src/app.gleam", src, Position::new(4, 15));
+    let found_labels = labels(&found);
+    assert!(found_labels.contains(&"other"));
+    // Not yet defined
+    assert!(!found_labels.contains(&"result"));
+
+    assert_eq!(
+        find(&found, "other").kind,
+        Some(CompletionItemKind::VARIABLE)
+    );
+    assert_eq!(find(&found, "other").detail.as_deref(), Some("Int"));
+}
+
+#[test]
+fn local_variable_shadowing() {
+    let io = LanguageServerTestIO::new();
+    let src = "pub fn main(value) {
+  let value = value + 1
+  let value = int_to_string(value)
+  value
+}
+
+fn int_to_string(_x: Int) -> String {
+  \"\"
+}
+";
+    io.src_module("app", src);
+    let mut engine = compiled_engine(&io);
+
+    let found = completions(&mut engine, "/src/app.gleam", src, Position::new(3, 2));
+    let values: Vec<_> = found
+        .iter()
+        .filter(|item| item.label == "value")
+        .map(|item| item.detail.as_deref())
+        .collect();
+    assert_eq!(values, vec![Some("String")]);
+}
+
+#[test]
+fn module_values_functions_and_constructors() {
+    let io = LanguageServerTestIO::new();
+    let src = "/// The answer
+const answer = 42
+
+pub type Wibble {
+  Wibble(Int)
+  Wobble
+}
+
+fn private(x: Int) -> Wibble {
+  Wibble(x)
+}
+
+pub fn main() {
+  1
+}
+";
+    io.src_module("app", src);
+    let mut engine = compiled_engine(&io);
+
+    let found = completions(&mut engine, "/src/app.gleam", src, Position::new(13, 2));
+
+    let answer = find(&found, "answer");
+    assert_eq!(answer.kind, Some(CompletionItemKind::CONSTANT));
+    assert_eq!(answer.detail.as_deref(), Some("Int"));
+    assert_eq!(
+        answer.documentation,
+        Some(Documentation::MarkupContent(MarkupContent {
+            kind: MarkupKind::Markdown,
+            value: " The answer\n".into()
+        }))
+    );
+
+    let private = find(&found, "private");
+    assert_eq!(private.kind, Some(CompletionItemKind::FUNCTION));
+    assert_eq!(private.detail.as_deref(), Some("fn(Int) -> Wibble"));
+
+    assert_eq!(
+        find(&found, "Wibble").detail.as_deref(),
+        Some("fn(Int) -> Wibble")
+    );
+    assert_eq!(find(&found, "Wobble").detail.as_deref(), Some("Wibble"));
+
+    // Prelude values
+    assert_eq!(
+        find(&found, "Ok").kind,
+        Some(CompletionItemKind::CONSTRUCTOR)
+    );
+}
+
+#[test]
+fn imported_module_members() {
+    let io = LanguageServerTestIO::new();
+    io.src_module(
+        "app/maths",
+        "/// Doubles a number
+pub fn double(x: Int) -> Int {
+  x * 2
+}
+
+fn private() {
+  1
+}
+
+pub type Number {
+  Number(Int)
+}
+",
+    );
+    let src = "import app/maths
+
+pub fn main() {
+  1
+}
+";
+    io.src_module("app", src);
+    let mut engine = compiled_engine(&io);
+
+    // The module has been edited and no longer compiles
+    let edited = "import app/maths
+
+pub fn main() {
+  maths.
+}
+";
+    let found = completions(&mut engine, "/src/app.gleam", edited, Position::new(3, 8));
+    assert_eq!(labels(&found), vec!["Number", "Number", "double"]);
+
+    let double = find(&found, "double");
+    assert_eq!(double.kind, Some(CompletionItemKind::FUNCTION));
+    assert_eq!(double.detail.as_deref(), Some("fn(Int) -> Int"));
+    assert_eq!(
+        double.documentation,
+        Some(Documentation::MarkupContent(MarkupContent {
+            kind: MarkupKind::Markdown,
+            value: " Doubles a number\n".into()
+        }))
+    );
+}
+
+#[test]
+fn aliased_imported_module_members() {
+    let io = LanguageServerTestIO::new();
+    io.src_module("app/maths", "pub fn double(x: Int) -> Int { x * 2 }\n");
+    let src = "import app/maths as m
+
+pub fn main() {
+  m.do
+}
+";
+    io.src_module("app", "import app/maths as m\n");
+    let mut engine = compiled_engine(&io);
+
+    let found = completions(&mut engine, "/src/app.gleam", src, Position::new(3, 6));
+    assert_eq!(labels(&found), vec!["double"]);
+}
+
+#[test]
+fn record_fields() {
+    let io = LanguageServerTestIO::new();
+    io.src_module(
+        "app/person",
+        "pub type Person {
+  Person(name: String, age: Int)
+}
+",
+    );
+    let src = "import app/person.{Person}
+
+type Box {
+  Box(inner: Int, Int)
+}
+
+pub fn main(person: Person) {
+  let box = Box(1, 2)
+  person.name
+}
+";
+    io.src_module("app", src);
+    let mut engine = compiled_engine(&io);
+
+    let edited = src.replace("person.name", "box.");
+    let found = completions(&mut engine, "/src/app.gleam", &edited, Position::new(8, 6));
+    assert_eq!(labels(&found), vec!["inner"]);
+    let inner = find(&found, "inner");
+    assert_eq!(inner.kind, Some(CompletionItemKind::FIELD));
+    assert_eq!(inner.detail.as_deref(), Some("Int"));
+
+    let edited = src.replace("person.name", "person.");
+    let found = completions(&mut engine, "/src/app.gleam", &edited, Position::new(8, 9));
+    assert_eq!(labels(&found), vec!["name", "age"]);
+    assert_eq!(find(&found, "age").detail.as_deref(), Some("Int"));
+}
+
+#[test]
+fn import_paths() {
+    let io = LanguageServerTestIO::new();
+    io.src_module("app", "pub fn main() { 1 }\n");
+    io.src_module("app/maths", "pub fn double(x) { x * 2 }\n");
+    io.test_module("app_test", "pub fn main() { 1 }\n");
+    io.test_module("helpers", "pub fn setup() { 1 }\n");
+    let mut engine = compiled_engine(&io);
+
+    let src = "import app/\n\npub fn main() { 1 }\n";
+    let found = completions(&mut engine, "/src/app.gleam", src, Position::new(0, 11));
+    assert_eq!(labels(&found), vec!["app/maths"]);
+    assert_eq!(
+        find(&found, "app/maths").kind,
+        Some(CompletionItemKind::MODULE)
+    );
+
+    // Test modules can import other test modules
+    let src = "import \n\npub fn main() { 1 }\n";
+    let found = completions(
+        &mut engine,
+        "/test/app_test.gleam",
+        src,
+        Position::new(0, 7),
+    );
+    assert_eq!(labels(&found), vec!["app", "app/maths", "helpers"]);
+
+    // Nothing is suggested for the alias
+    let src = "import app/maths as \n\npub fn main() { 1 }\n";
+    let found = completions(&mut engine, "/src/app.gleam", src, Position::new(0, 20));
+    assert!(found.is_empty());
+}
+
+#[test]
+fn unqualified_imports() {
+    let io = LanguageServerTestIO::new();
+    io.src_module(
+        "app/maths",
+        "pub fn double(x: Int) -> Int { x * 2 }
+pub const zero = 0
+pub type Number { Number(Int) }
+",
+    );
+    io.src_module("app", "pub fn main() { 1 }\n");
+    let mut engine = compiled_engine(&io);
+
+    let src = "import app/maths.{double, \n\npub fn main() { 1 }\n";
+    let found = completions(&mut engine, "/src/app.gleam", src, Position::new(0, 26));
+    assert_eq!(labels(&found), vec!["Number", "Number", "double", "zero"]);
+    assert_eq!(
+        find(&found, "zero").kind,
+        Some(CompletionItemKind::CONSTANT)
+    );
+
+    // Over multiple lines
+    let src = "import app/maths.{\n  double,\n  \n}\n\npub fn main() { 1 }\n";
+    let found = completions(&mut engine, "/src/app.gleam", src, Position::new(2, 2));
+    assert_eq!(labels(&found), vec!["Number", "Number", "double", "zero"]);
+}
+
+#[test]
+fn unqualified_imported_values() {
+    let io = LanguageServerTestIO::new();
+    io.src_module("app/maths", "pub fn double(x: Int) -> Int { x * 2 }\n");
+    let src = "import app/maths.{double as twice}
+
+pub fn main() {
+  twice(1)
+}
+";
+    io.src_module("app", src);
+    let mut engine = compiled_engine(&io);
+
+    let found = completions(&mut engine, "/src/app.gleam", src, Position::new(3, 2));
+    assert_eq!(
+        find(&found, "twice").detail.as_deref(),
+        Some("fn(Int) -> Int")
+    );
+    assert!(!labels(&found).contains(&"double"));
+}
@@ -0,0 +1,242 @@
+use lsp_types::{
+    InlayHintLabel, InlayHintParams, Position, Range, TextDocumentIdentifier, Url,
+    WorkDoneProgressParams,
+};
+
+use super::*;
+use crate::language_server::configuration::{Configuration, InlayHintsConfig};
+
+/// The position and label of each hint within the range of `app`.
+fn inlay_hints(
+    engine: &mut Engine,
+    range: Range,
+    config: InlayHintsConfig,
+) -> Vec<((u32, u32), String)> {
+    let params = InlayHintParams {
+        work_done_progress_params: WorkDoneProgressParams::default(),
+        text_document: TextDocumentIdentifier {
+            uri: Url::from_file_path("/src/app.gleam").unwrap(),
+        },
+        range,
+    };
+    engine
+        .inlay_hints(params, config)
+        .result
+        .unwrap()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|hint| {
+            let label = match hint.label {
+                InlayHintLabel::String(label) => label,
+                InlayHintLabel::LabelParts(_) => panic!("Unexpected label parts"),
+            };
+            ((hint.position.line, hint.position.character), label)
+        })
+        .collect()
+}
+
+fn whole_module() -> Range {
+    Range::new(Position::new(0, 0), Position::new(100, 0))
+}
+
+#[test]
+fn let_bindings() {
+    let io = LanguageServerTestIO::new();
+    io.src_module(
+        "app",
+        "pub fn main() -> Int {
+  let x = 1
+  let #(y, z): #(Int, Float) = #(x, 2.0)
+  let list = [y]
+  x
+}
+",
+    );
+    let mut engine = compiled_engine(&io);
+
+    assert_eq!(
+        inlay_hints(&mut engine, whole_module(), InlayHintsConfig::default()),
+        vec![((1, 7), ": Int".into()), ((3, 10), ": List(Int)".into())]
+    );
+}
+
+#[test]
+fn pipelines() {
+    let io = LanguageServerTestIO::new();
+    io.src_module(
+        "app",
+        "fn to_float(x: Int) -> Float {
+  todo
+}
+
+fn pair(x: a, y: b) -> #(a, b) {
+  #(x, y)
+}
+
+pub fn main() -> #(Float, Int) {
+  let one: Int = 1 |> pair(2) |> fn(x: #(Int, Int)) -> Int { x.0 }
+  one
+  |> to_float
+  |> pair(1)
+}
+",
+    );
+    let mut engine = compiled_engine(&io);
+
+    // Only the pipeline that spans multiple lines has hints
+    assert_eq!(
+        inlay_hints(&mut engine, whole_module(), InlayHintsConfig::default()),
+        vec![
+            ((10, 5), "Int".into()),
+            ((11, 13), "Float".into()),
+            ((12, 12), "#(Float, Int)".into()),
+        ]
+    );
+}
+
+#[test]
+fn function_arguments_and_return_types() {
+    let io = LanguageServerTestIO::new();
+    io.src_module(
+        "app",
+        "fn add(to x, amount y: Int) {
+  x + y
+}
+
+fn apply(f, x: a) -> b {
+  f(x)
+}
+
+pub fn main() -> Int {
+  let double: fn(Int) -> Int = fn(n) { n * 2 }
+  apply(add(1, _), double(2))
+}
+",
+    );
+    let mut engine = compiled_engine(&io);
+
+    assert_eq!(
+        inlay_hints(&mut engine, whole_module(), InlayHintsConfig::default()),
+        vec![
+            ((0, 11), ": Int".into()),
+            ((0, 27), "-> Int".into()),
+            ((4, 10), ": fn(a) -> b".into()),
+            ((9, 35), ": Int".into()),
+            ((9, 36), "-> Int".into()),
+        ]
+    );
+}
+
+#[test]
+fn use_callback_arguments() {
+    let io = LanguageServerTestIO::new();
+    io.src_module(
+        "app",
+        "fn with(f: fn(Int, String) -> a) -> a {
+  f(1, \"\")
+}
+
+pub fn main() -> Int {
+  use x, _ <- with
+  x
+}
+",
+    );
+    let mut engine = compiled_engine(&io);
+
+    assert_eq!(
+        inlay_hints(&mut engine, whole_module(), InlayHintsConfig::default()),
+        vec![((5, 7), ": Int".into()), ((5, 10), ": String".into())]
+    );
+}
+
+#[test]
+fn hints_within_range() {
+    let io = LanguageServerTestIO::new();
+    io.src_module(
+        "app",
+        "pub fn main() -> Int {
+  let x = 1
+  let y = 2
+  x + y
+}
+",
+    );
+    let mut engine = compiled_engine(&io);
+
+    let range = Range::new(Position::new(2, 0), Position::new(3, 0));
+    assert_eq!(
+        inlay_hints(&mut engine, range, InlayHintsConfig::default()),
+        vec![((2, 7), ": Int".into())]
+    );
+}
+
+#[test]
+fn hints_can_be_disabled() {
+    let io = LanguageServerTestIO::new();
+    io.src_module(
+        "app",
+        "pub fn main(x) {
+  let y = x
+  y
+  |> fn(z: Int) { z }
+}
+",
+    );
+    let mut engine = compiled_engine(&io);
+
+    let all = vec![
+        ((0, 13), ": Int".into()),
+        ((0, 14), "-> Int".into()),
+        ((1, 7), ": Int".into()),
+        ((2, 3), "Int".into()),
+        ((3, 15), "-> Int".into()),
+        ((3, 21), "Int".into()),
+    ];
+    assert_eq!(
+        inlay_hints(&mut engine, whole_module(), InlayHintsConfig::default()),
+        all
+    );
+
+    let none = InlayHintsConfig {
+        let_bindings: false,
+        pipelines: false,
+        function_parameters: false,
+        function_return_types: false,
+    };
+    assert_eq!(inlay_hints(&mut engine, whole_module(), none), vec![]);
+
+    let only_lets = InlayHintsConfig {
+        let_bindings: true,
+        ..none
+    };
+    assert_eq!(
+        inlay_hints(&mut engine, whole_module(), only_lets),
+        vec![((1, 7), ": Int".into())]
+    );
+}
+
+#[test]
+fn configuration_from_client_settings() {
+    let config = Configuration::from_json(serde_json::json!({
+        "gleam": { "inlayHints": { "pipelines": false } }
+    }));
+    assert_eq!(
+        config.inlay_hints,
+        InlayHintsConfig {
+            pipelines: false,
+            ..InlayHintsConfig::default()
+        }
+    );
+
+    let config = Configuration::from_json(serde_json::json!({
+        "inlayHints": { "letBindings": false }
+    }));
+    assert!(!config.inlay_hints.let_bindings);
+    assert!(config.inlay_hints.pipelines);
+
+    assert_eq!(
+        Configuration::from_json(serde_json::json!("nonsense")),
+        Configuration::default()
+    );
+}
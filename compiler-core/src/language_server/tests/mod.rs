@@ -1,4 +1,12 @@
+mod code_action;
 mod compilation;
+mod completion;
+mod inlay_hints;
+mod references;
+mod rename;
+mod semantic_tokens;
+mod signature_help;
+mod symbols;
 
 use std::{
     collections::HashMap,
@@ -56,13 +64,13 @@ impl LanguageServerTestIO {
 
     pub fn src_module(&self, name: &str, code: &str) {
         let src_dir = self.paths.src_directory();
-        let path = src_dir.join(name).with_extension(".gleam");
+        let path = src_dir.join(name).with_extension("gleam");
         self.io.write(&path, code).unwrap()
     }
 
     pub fn test_module(&self, name: &str, code: &str) {
         let test_dir = self.paths.test_directory();
-        let path = test_dir.join(name).with_extension(".gleam");
+        let path = test_dir.join(name).with_extension("gleam");
         self.io.write(&path, code).unwrap()
     }
 
@@ -153,6 +161,7 @@ impl DownloadDependencies for LanguageServerTestIO {
         Ok(Manifest {
             requirements: HashMap::new(),
             packages: vec![],
+            local_requirements: HashMap::new(),
         })
     }
 }
@@ -229,9 +238,9 @@ impl ProgressReporter for LanguageServerTestIO {
     }
 }
 
-fn setup_engine(
-    io: &LanguageServerTestIO,
-) -> LanguageServerEngine<LanguageServerTestIO, LanguageServerTestIO> {
+type Engine = LanguageServerEngine<LanguageServerTestIO, LanguageServerTestIO>;
+
+fn setup_engine(io: &LanguageServerTestIO) -> Engine {
     LanguageServerEngine::new(
         PackageConfig::default(),
         io.clone(),
@@ -240,3 +249,10 @@ fn setup_engine(
     )
     .unwrap()
 }
+
+fn compiled_engine(io: &LanguageServerTestIO) -> Engine {
+    let mut engine = setup_engine(io);
+    let response = engine.compile_please();
+    assert!(response.result.is_ok());
+    engine
+}
@@ -0,0 +1,444 @@
+use lsp_types::{
+    PartialResultParams, Position, ReferenceContext, ReferenceParams, TextDocumentIdentifier,
+    TextDocumentPositionParams, Url, WorkDoneProgressParams,
+};
+
+use super::*;
+
+/// A path and the start and end line and column of a reference
+type Found = (String, (u32, u32), (u32, u32));
+
+fn find_references(
+    engine: &mut Engine,
+    path: &str,
+    position: Position,
+    include_declaration: bool,
+) -> Option<Vec<Found>> {
+    let params = ReferenceParams {
+        text_document_position: TextDocumentPositionParams {
+            text_document: TextDocumentIdentifier {
+                uri: Url::from_file_path(path).unwrap(),
+            },
+            position,
+        },
+        work_done_progress_params: WorkDoneProgressParams::default(),
+        partial_result_params: PartialResultParams::default(),
+        context: ReferenceContext {
+            include_declaration,
+        },
+    };
+    let response = engine.references(params);
+    let locations = response.result.unwrap()?;
+    Some(
+        locations
+            .into_iter()
+            .map(|location| {
+                let range = location.range;
+                (
+                    location.uri.path().to_string(),
+                    (range.start.line, range.start.character),
+                    (range.end.line, range.end.character),
+                )
+            })
+            .collect(),
+    )
+}
+
+#[test]
+fn local_variable() {
+    let io = LanguageServerTestIO::new();
+    io.src_module(
+        "app",
+        "pub fn main(x) {
+  let y = x + 1
+  y * y + x
+}
+",
+    );
+
+    let mut engine = compiled_engine(&io);
+
+    assert_eq!(
+        find_references(&mut engine, "/src/app.gleam", Position::new(1, 6), true),
+        Some(vec![
+            ("/src/app.gleam".into(), (1, 6), (1, 7)),
+            ("/src/app.gleam".into(), (2, 2), (2, 3)),
+            ("/src/app.gleam".into(), (2, 6), (2, 7)),
+        ])
+    );
+}
+
+#[test]
+fn function_argument() {
+    let io = LanguageServerTestIO::new();
+    io.src_module(
+        "app",
+        "pub fn main(x x: Int) {
+  let y = x + 1
+  y * y + x
+}
+",
+    );
+
+    let mut engine = compiled_engine(&io);
+
+    assert_eq!(
+        find_references(&mut engine, "/src/app.gleam", Position::new(2, 10), true),
+        Some(vec![
+            ("/src/app.gleam".into(), (0, 14), (0, 15)),
+            ("/src/app.gleam".into(), (1, 10), (1, 11)),
+            ("/src/app.gleam".into(), (2, 10), (2, 11)),
+        ])
+    );
+}
+
+#[test]
+fn shadowed_local_variable() {
+    let io = LanguageServerTestIO::new();
+    io.src_module(
+        "app",
+        "pub fn main(x) {
+  let x = x + 1
+  x
+}
+",
+    );
+
+    let mut engine = compiled_engine(&io);
+
+    assert_eq!(
+        find_references(&mut engine, "/src/app.gleam", Position::new(0, 12), true),
+        Some(vec![
+            ("/src/app.gleam".into(), (0, 12), (0, 13)),
+            ("/src/app.gleam".into(), (1, 10), (1, 11)),
+        ])
+    );
+}
+
+#[test]
+fn variable_in_case_guard() {
+    let io = LanguageServerTestIO::new();
+    io.src_module(
+        "app",
+        "pub fn main(x) {
+  case x {
+    #(a, b) if a > b -> a
+    _ -> 0
+  }
+}
+",
+    );
+
+    let mut engine = compiled_engine(&io);
+
+    assert_eq!(
+        find_references(&mut engine, "/src/app.gleam", Position::new(2, 6), true),
+        Some(vec![
+            ("/src/app.gleam".into(), (2, 6), (2, 7)),
+            ("/src/app.gleam".into(), (2, 15), (2, 16)),
+            ("/src/app.gleam".into(), (2, 24), (2, 25)),
+        ])
+    );
+}
+
+#[test]
+fn variable_in_bit_string_size() {
+    let io = LanguageServerTestIO::new();
+    io.src_module(
+        "app",
+        "pub fn main(x) {
+  case x {
+    <<size, data:binary-size(size)>> -> data
+    _ -> x
+  }
+}
+",
+    );
+
+    let mut engine = compiled_engine(&io);
+
+    assert_eq!(
+        find_references(&mut engine, "/src/app.gleam", Position::new(2, 6), true),
+        Some(vec![
+            ("/src/app.gleam".into(), (2, 6), (2, 10)),
+            ("/src/app.gleam".into(), (2, 29), (2, 33)),
+        ])
+    );
+}
+
+#[test]
+fn module_function_across_modules() {
+    let io = LanguageServerTestIO::new();
+    io.src_module(
+        "app/maths",
+        "pub fn double(x) {
+  x * 2
+}
+
+pub fn quadruple(x) {
+  double(double(x))
+}
+",
+    );
+    io.src_module(
+        "app",
+        "import app/maths
+import app/maths.{double as twice} as m
+
+pub fn main() {
+  maths.double(1) + m.double(2) + twice(3)
+}
+",
+    );
+    io.test_module(
+        "app_test",
+        "import app/maths.{double}
+
+pub fn double_test() {
+  double(1)
+}
+",
+    );
+
+    let mut engine = compiled_engine(&io);
+
+    let expected = vec![
+        ("/src/app.gleam".into(), (1, 18), (1, 24)),
+        ("/src/app.gleam".into(), (4, 8), (4, 14)),
+        ("/src/app.gleam".into(), (4, 22), (4, 28)),
+        ("/src/app.gleam".into(), (4, 34), (4, 39)),
+        ("/src/app/maths.gleam".into(), (0, 7), (0, 13)),
+        ("/src/app/maths.gleam".into(), (5, 2), (5, 8)),
+        ("/src/app/maths.gleam".into(), (5, 9), (5, 15)),
+        ("/test/app_test.gleam".into(), (0, 18), (0, 24)),
+        ("/test/app_test.gleam".into(), (3, 2), (3, 8)),
+    ];
+
+    // From the definition
+    assert_eq!(
+        find_references(
+            &mut engine,
+            "/src/app/maths.gleam",
+            Position::new(0, 9),
+            true
+        ),
+        Some(expected.clone())
+    );
+
+    // From a qualified use in another module
+    assert_eq!(
+        find_references(&mut engine, "/src/app.gleam", Position::new(4, 10), true),
+        Some(expected.clone())
+    );
+
+    // From an aliased unqualified use
+    assert_eq!(
+        find_references(&mut engine, "/src/app.gleam", Position::new(4, 36), true),
+        Some(expected)
+    );
+}
+
+#[test]
+fn excluding_the_declaration() {
+    let io = LanguageServerTestIO::new();
+    io.src_module(
+        "app",
+        "pub fn main() {
+  helper() + helper()
+}
+
+fn helper() {
+  1
+}
+",
+    );
+
+    let mut engine = compiled_engine(&io);
+
+    assert_eq!(
+        find_references(&mut engine, "/src/app.gleam", Position::new(4, 4), false),
+        Some(vec![
+            ("/src/app.gleam".into(), (1, 2), (1, 8)),
+            ("/src/app.gleam".into(), (1, 13), (1, 19)),
+        ])
+    );
+}
+
+#[test]
+fn module_constant() {
+    let io = LanguageServerTestIO::new();
+    io.src_module(
+        "app/config",
+        "pub const size = 10
+
+pub const sizes = [size, size]
+",
+    );
+    io.src_module(
+        "app",
+        "import app/config
+
+pub fn main() {
+  config.size
+}
+",
+    );
+
+    let mut engine = compiled_engine(&io);
+
+    assert_eq!(
+        find_references(&mut engine, "/src/app.gleam", Position::new(3, 10), true),
+        Some(vec![
+            ("/src/app.gleam".into(), (3, 9), (3, 13)),
+            ("/src/app/config.gleam".into(), (0, 10), (0, 14)),
+            ("/src/app/config.gleam".into(), (2, 19), (2, 23)),
+            ("/src/app/config.gleam".into(), (2, 25), (2, 29)),
+        ])
+    );
+}
+
+#[test]
+fn custom_type() {
+    let io = LanguageServerTestIO::new();
+    io.src_module(
+        "app/shape",
+        "pub type Shape {
+  Circle(radius: Float)
+  Square(side: Float)
+}
+
+pub type Shapes =
+  List(Shape)
+",
+    );
+    io.src_module(
+        "app",
+        "import app/shape.{Shape, Circle}
+
+pub fn area(shape: Shape) -> Float {
+  case shape {
+    Circle(radius) -> radius *. radius *. 3.14
+    shape.Square(side) -> side *. side
+  }
+}
+
+pub fn unit() -> shape.Shape {
+  shape.Square(1.0)
+}
+",
+    );
+
+    let mut engine = compiled_engine(&io);
+
+    assert_eq!(
+        find_references(
+            &mut engine,
+            "/src/app/shape.gleam",
+            Position::new(0, 11),
+            true
+        ),
+        Some(vec![
+            ("/src/app.gleam".into(), (0, 18), (0, 23)),
+            ("/src/app.gleam".into(), (2, 19), (2, 24)),
+            ("/src/app.gleam".into(), (9, 23), (9, 28)),
+            ("/src/app/shape.gleam".into(), (0, 9), (0, 14)),
+            ("/src/app/shape.gleam".into(), (6, 7), (6, 12)),
+        ])
+    );
+}
+
+#[test]
+fn record_constructor() {
+    let io = LanguageServerTestIO::new();
+    io.src_module(
+        "app/shape",
+        "pub type Shape {
+  Circle(radius: Float)
+  Square(side: Float)
+}
+
+pub const unit = Square(1.0)
+",
+    );
+    io.src_module(
+        "app",
+        "import app/shape.{Shape, Circle}
+
+pub fn area(shape: Shape) -> Float {
+  case shape {
+    Circle(radius) -> radius *. radius *. 3.14
+    shape.Square(side) -> side *. side
+  }
+}
+
+pub fn unit() -> shape.Shape {
+  shape.Square(1.0)
+}
+",
+    );
+
+    let mut engine = compiled_engine(&io);
+
+    assert_eq!(
+        find_references(&mut engine, "/src/app.gleam", Position::new(10, 9), true),
+        Some(vec![
+            ("/src/app.gleam".into(), (5, 10), (5, 16)),
+            ("/src/app.gleam".into(), (10, 8), (10, 14)),
+            ("/src/app/shape.gleam".into(), (2, 2), (2, 8)),
+            ("/src/app/shape.gleam".into(), (5, 17), (5, 23)),
+        ])
+    );
+}
+
+#[test]
+fn record_constructor_in_record_update() {
+    let io = LanguageServerTestIO::new();
+    io.src_module(
+        "app/point",
+        "pub type Point {
+  Point(x: Int, y: Int)
+}
+",
+    );
+    io.src_module(
+        "app",
+        "import app/point.{Point}
+
+pub fn move(p: Point) {
+  let p = Point(..p, x: 1)
+  point.Point(..p, y: 1)
+}
+",
+    );
+
+    let mut engine = compiled_engine(&io);
+
+    assert_eq!(
+        find_references(&mut engine, "/src/app.gleam", Position::new(3, 10), true),
+        Some(vec![
+            ("/src/app.gleam".into(), (0, 18), (0, 23)),
+            ("/src/app.gleam".into(), (3, 10), (3, 15)),
+            ("/src/app.gleam".into(), (4, 8), (4, 13)),
+            ("/src/app/point.gleam".into(), (1, 2), (1, 7)),
+        ])
+    );
+}
+
+#[test]
+fn nothing_at_position() {
+    let io = LanguageServerTestIO::new();
+    io.src_module(
+        "app",
+        "pub fn main() {
+  1 + 2
+}
+",
+    );
+
+    let mut engine = compiled_engine(&io);
+
+    assert_eq!(
+        find_references(&mut engine, "/src/app.gleam", Position::new(1, 3), true),
+        None
+    );
+}
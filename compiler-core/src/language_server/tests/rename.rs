@@ -0,0 +1,666 @@
+use lsp_types::{
+    Position, RenameParams, TextDocumentIdentifier, TextDocumentPositionParams, Url,
+    WorkDoneProgressParams,
+};
+
+use super::*;
+use crate::language_server::rename::RenameError;
+
+fn position_params(path: &str, position: Position) -> TextDocumentPositionParams {
+    TextDocumentPositionParams {
+        text_document: TextDocumentIdentifier {
+            uri: Url::from_file_path(path).unwrap(),
+        },
+        position,
+    }
+}
+
+/// Rename the symbol at the given position and apply the resulting edits to
+/// the source of the modules, returning the new source of each changed
+/// module.
+fn rename(
+    engine: &mut Engine,
+    io: &LanguageServerTestIO,
+    path: &str,
+    position: Position,
+    new_name: &str,
+) -> Result<Vec<(String, String)>, RenameError> {
+    let params = RenameParams {
+        text_document_position: position_params(path, position),
+        new_name: new_name.into(),
+        work_done_progress_params: WorkDoneProgressParams::default(),
+    };
+    let edit = match engine.rename(params).result.unwrap()? {
+        Some(edit) => edit,
+        None => return Ok(vec![]),
+    };
+
+    let mut changed: Vec<_> = edit
+        .changes
+        .unwrap()
+        .into_iter()
+        .map(|(uri, mut edits)| {
+            let path = uri.path().to_string();
+            let mut src = io.read(Path::new(&path)).unwrap();
+            let line_numbers = crate::line_numbers::LineNumbers::new(&src);
+            // Apply the edits from last to first so the earlier positions
+            // remain valid.
+            edits.sort_by_key(|edit| (edit.range.start.line, edit.range.start.character));
+            for edit in edits.into_iter().rev() {
+                let start = line_numbers
+                    .byte_index(edit.range.start.line, edit.range.start.character)
+                    as usize;
+                let end =
+                    line_numbers.byte_index(edit.range.end.line, edit.range.end.character) as usize;
+                src.replace_range(start..end, &edit.new_text);
+            }
+            (path, src)
+        })
+        .collect();
+    changed.sort();
+    Ok(changed)
+}
+
+#[test]
+fn rename_local_variable() {
+    let io = LanguageServerTestIO::new();
+    io.src_module(
+        "app",
+        "pub fn main(x) {
+  let y = x + 1
+  y * y
+}
+",
+    );
+    let mut engine = compiled_engine(&io);
+
+    assert_eq!(
+        rename(&mut engine, &io, "/src/app.gleam", Position::new(2, 2), "z"),
+        Ok(vec![(
+            "/src/app.gleam".into(),
+            "pub fn main(x) {
+  let z = x + 1
+  z * z
+}
+"
+            .into()
+        )])
+    );
+}
+
+#[test]
+fn rename_function_across_modules() {
+    let io = LanguageServerTestIO::new();
+    io.src_module(
+        "app/maths",
+        "pub fn double(x) {
+  x * 2
+}
+",
+    );
+    io.src_module(
+        "app",
+        "import app/maths.{double}
+import app/other.{double as twice}
+
+pub fn main() {
+  maths.double(1) + double(2) + twice(3)
+}
+",
+    );
+    io.src_module(
+        "app/other",
+        "import app/maths
+
+pub fn double(x) {
+  maths.double(x)
+}
+",
+    );
+    let mut engine = compiled_engine(&io);
+
+    assert_eq!(
+        rename(
+            &mut engine,
+            &io,
+            "/src/app/maths.gleam",
+            Position::new(0, 8),
+            "times_two"
+        ),
+        Ok(vec![
+            (
+                "/src/app.gleam".into(),
+                "import app/maths.{times_two}
+import app/other.{double as twice}
+
+pub fn main() {
+  maths.times_two(1) + times_two(2) + twice(3)
+}
+"
+                .into()
+            ),
+            (
+                "/src/app/maths.gleam".into(),
+                "pub fn times_two(x) {
+  x * 2
+}
+"
+                .into()
+            ),
+            (
+                "/src/app/other.gleam".into(),
+                "import app/maths
+
+pub fn double(x) {
+  maths.times_two(x)
+}
+"
+                .into()
+            ),
+        ])
+    );
+}
+
+#[test]
+fn rename_aliased_import_keeps_alias() {
+    let io = LanguageServerTestIO::new();
+    io.src_module("app/maths", "pub fn double(x) { x * 2 }\n");
+    io.src_module(
+        "app",
+        "import app/maths.{double as twice}
+
+pub fn main() {
+  twice(3)
+}
+",
+    );
+    let mut engine = compiled_engine(&io);
+
+    assert_eq!(
+        rename(
+            &mut engine,
+            &io,
+            "/src/app.gleam",
+            Position::new(0, 19),
+            "dbl"
+        ),
+        Ok(vec![
+            (
+                "/src/app.gleam".into(),
+                "import app/maths.{dbl as twice}
+
+pub fn main() {
+  twice(3)
+}
+"
+                .into()
+            ),
+            (
+                "/src/app/maths.gleam".into(),
+                "pub fn dbl(x) { x * 2 }\n".into()
+            ),
+        ])
+    );
+
+    // The alias itself cannot be renamed this way
+    assert_eq!(
+        rename(
+            &mut engine,
+            &io,
+            "/src/app.gleam",
+            Position::new(3, 3),
+            "dbl"
+        ),
+        Err(RenameError::Aliased {
+            alias: "twice".into(),
+            name: "double".into()
+        })
+    );
+}
+
+#[test]
+fn rename_type_imported_with_its_constructor() {
+    let io = LanguageServerTestIO::new();
+    io.src_module(
+        "app/box",
+        "pub type Box {
+  Box(inner: Int)
+}
+",
+    );
+    io.src_module(
+        "app",
+        "import app/box.{Box}
+
+pub fn main() -> Box {
+  Box(1)
+}
+",
+    );
+    let mut engine = compiled_engine(&io);
+
+    assert_eq!(
+        rename(
+            &mut engine,
+            &io,
+            "/src/app.gleam",
+            Position::new(2, 18),
+            "Crate"
+        ),
+        Ok(vec![
+            (
+                "/src/app.gleam".into(),
+                "import app/box.{Box, Crate}
+
+pub fn main() -> Crate {
+  Box(1)
+}
+"
+                .into()
+            ),
+            (
+                "/src/app/box.gleam".into(),
+                "pub type Crate {
+  Box(inner: Int)
+}
+"
+                .into()
+            ),
+        ])
+    );
+}
+
+#[test]
+fn rename_clashing_with_existing_value() {
+    let io = LanguageServerTestIO::new();
+    io.src_module(
+        "app",
+        "pub fn main() {
+  helper()
+}
+
+fn helper() {
+  1
+}
+",
+    );
+    let mut engine = compiled_engine(&io);
+
+    assert_eq!(
+        rename(
+            &mut engine,
+            &io,
+            "/src/app.gleam",
+            Position::new(1, 3),
+            "main"
+        ),
+        Err(RenameError::DuplicateName {
+            name: "main".into(),
+            module: "app".into()
+        })
+    );
+}
+
+#[test]
+fn rename_clashing_with_value_in_importing_module() {
+    let io = LanguageServerTestIO::new();
+    io.src_module("app/maths", "pub fn double(x) { x * 2 }\n");
+    io.src_module(
+        "app",
+        "import app/maths.{double}
+
+pub fn main() {
+  double(triple(1))
+}
+
+fn triple(x) {
+  x * 3
+}
+",
+    );
+    let mut engine = compiled_engine(&io);
+
+    assert_eq!(
+        rename(
+            &mut engine,
+            &io,
+            "/src/app/maths.gleam",
+            Position::new(0, 8),
+            "triple"
+        ),
+        Err(RenameError::DuplicateName {
+            name: "triple".into(),
+            module: "app".into()
+        })
+    );
+}
+
+#[test]
+fn rename_function_shadowed_by_local_variable() {
+    let io = LanguageServerTestIO::new();
+    io.src_module(
+        "app",
+        "pub fn main() {
+  let value = 1
+  helper() + value
+}
+
+fn helper() {
+  1
+}
+",
+    );
+    let mut engine = compiled_engine(&io);
+
+    assert_eq!(
+        rename(
+            &mut engine,
+            &io,
+            "/src/app.gleam",
+            Position::new(2, 3),
+            "value"
+        ),
+        Err(RenameError::ShadowedName {
+            name: "value".into(),
+            module: "app".into()
+        })
+    );
+}
+
+#[test]
+fn rename_local_variable_shadowing_another() {
+    let io = LanguageServerTestIO::new();
+    io.src_module(
+        "app",
+        "pub fn main(x) {
+  let y = x + 1
+  y + x
+}
+",
+    );
+    let mut engine = compiled_engine(&io);
+
+    assert_eq!(
+        rename(&mut engine, &io, "/src/app.gleam", Position::new(1, 6), "x"),
+        Err(RenameError::ShadowedName {
+            name: "x".into(),
+            module: "app".into()
+        })
+    );
+}
+
+#[test]
+fn rename_local_variable_to_name_no_longer_used() {
+    let io = LanguageServerTestIO::new();
+    io.src_module(
+        "app",
+        "pub fn main(x) {
+  let y = x + 1
+  y
+}
+",
+    );
+    let mut engine = compiled_engine(&io);
+
+    assert_eq!(
+        rename(&mut engine, &io, "/src/app.gleam", Position::new(2, 2), "x"),
+        Ok(vec![(
+            "/src/app.gleam".into(),
+            "pub fn main(x) {
+  let x = x + 1
+  x
+}
+"
+            .into()
+        )])
+    );
+}
+
+#[test]
+fn rename_clashing_with_existing_type() {
+    let io = LanguageServerTestIO::new();
+    io.src_module(
+        "app",
+        "pub type One {
+  One
+}
+
+pub type Two {
+  Two
+}
+",
+    );
+    let mut engine = compiled_engine(&io);
+
+    assert_eq!(
+        rename(
+            &mut engine,
+            &io,
+            "/src/app.gleam",
+            Position::new(0, 10),
+            "Two"
+        ),
+        Err(RenameError::DuplicateTypeName {
+            name: "Two".into(),
+            module: "app".into()
+        })
+    );
+}
+
+#[test]
+fn rename_to_invalid_name() {
+    let io = LanguageServerTestIO::new();
+    io.src_module("app", "pub fn main() { 1 }\n");
+    let mut engine = compiled_engine(&io);
+
+    for name in ["Main", "case", "two words", "_main", ""] {
+        assert_eq!(
+            rename(
+                &mut engine,
+                &io,
+                "/src/app.gleam",
+                Position::new(0, 8),
+                name
+            ),
+            Err(RenameError::InvalidName { name: name.into() })
+        );
+    }
+}
+
+#[test]
+fn prepare_rename_prelude_value() {
+    let io = LanguageServerTestIO::new();
+    io.src_module("app", "pub fn main() { Ok(1) }\n");
+    let mut engine = compiled_engine(&io);
+
+    let response = engine.prepare_rename(position_params("/src/app.gleam", Position::new(0, 17)));
+    assert_eq!(
+        response.result.unwrap(),
+        Err(RenameError::NotInProject { module: "".into() })
+    );
+}
+
+#[test]
+fn prepare_rename_prelude_type() {
+    let io = LanguageServerTestIO::new();
+    io.src_module("app", "pub fn main() -> Int { 1 }\n");
+    let mut engine = compiled_engine(&io);
+
+    let response = engine.prepare_rename(position_params("/src/app.gleam", Position::new(0, 18)));
+    assert_eq!(
+        response.result.unwrap(),
+        Err(RenameError::NotInProject { module: "".into() })
+    );
+}
+
+#[test]
+fn prepare_rename_function() {
+    let io = LanguageServerTestIO::new();
+    io.src_module("app", "pub fn main() { 1 }\n");
+    let mut engine = compiled_engine(&io);
+
+    let response = engine.prepare_rename(position_params("/src/app.gleam", Position::new(0, 9)));
+    assert_eq!(
+        response.result.unwrap(),
+        Ok(Some(lsp_types::PrepareRenameResponse::Range(
+            lsp_types::Range::new(Position::new(0, 7), Position::new(0, 11))
+        )))
+    );
+}
+
+#[test]
+fn rename_record_constructor_called_with_labels() {
+    let io = LanguageServerTestIO::new();
+    io.src_module(
+        "app",
+        "pub type Box {
+  Box(inner: Int)
+}
+
+pub fn main() {
+  let Box(inner: x) = Box(inner: 1)
+  x
+}
+",
+    );
+    let mut engine = compiled_engine(&io);
+
+    assert_eq!(
+        rename(
+            &mut engine,
+            &io,
+            "/src/app.gleam",
+            Position::new(5, 23),
+            "Crate"
+        ),
+        Ok(vec![(
+            "/src/app.gleam".into(),
+            "pub type Box {
+  Crate(inner: Int)
+}
+
+pub fn main() {
+  let Crate(inner: x) = Crate(inner: 1)
+  x
+}
+"
+            .into()
+        )])
+    );
+}
+
+#[test]
+fn rename_record_constructor_in_record_update() {
+    let io = LanguageServerTestIO::new();
+    io.src_module(
+        "app",
+        "pub type Point {
+  Point(x: Int, y: Int)
+}
+
+pub fn move(p: Point) {
+  Point(..p, x: 1)
+}
+",
+    );
+    let mut engine = compiled_engine(&io);
+
+    assert_eq!(
+        rename(
+            &mut engine,
+            &io,
+            "/src/app.gleam",
+            Position::new(5, 3),
+            "Pair"
+        ),
+        Ok(vec![(
+            "/src/app.gleam".into(),
+            "pub type Point {
+  Pair(x: Int, y: Int)
+}
+
+pub fn move(p: Point) {
+  Pair(..p, x: 1)
+}
+"
+            .into()
+        )])
+    );
+}
+
+#[test]
+fn rename_label() {
+    let io = LanguageServerTestIO::new();
+    io.src_module(
+        "app",
+        "pub type Box {
+  Box(inner: Int)
+}
+
+pub fn wrap(contents value: Int) {
+  Box(inner: value)
+}
+
+pub fn main() {
+  let Box(inner: x) = wrap(contents: 1)
+  let y = Box(..wrap(contents: 2), inner: x)
+  y.inner
+}
+",
+    );
+    let mut engine = compiled_engine(&io);
+
+    let labels = [
+        ((1, 6), "inner"),
+        ((4, 12), "contents"),
+        ((5, 6), "inner"),
+        ((9, 10), "inner"),
+        ((9, 27), "contents"),
+        ((10, 21), "contents"),
+        ((10, 35), "inner"),
+        ((11, 4), "inner"),
+    ];
+    for ((line, character), label) in labels {
+        assert_eq!(
+            rename(
+                &mut engine,
+                &io,
+                "/src/app.gleam",
+                Position::new(line, character),
+                "renamed"
+            ),
+            Err(RenameError::Label {
+                label: label.into()
+            })
+        );
+    }
+
+    // The name of a labelled argument can still be renamed
+    assert_eq!(
+        rename(
+            &mut engine,
+            &io,
+            "/src/app.gleam",
+            Position::new(4, 21),
+            "number"
+        ),
+        Ok(vec![(
+            "/src/app.gleam".into(),
+            "pub type Box {
+  Box(inner: Int)
+}
+
+pub fn wrap(contents number: Int) {
+  Box(inner: number)
+}
+
+pub fn main() {
+  let Box(inner: x) = wrap(contents: 1)
+  let y = Box(..wrap(contents: 2), inner: x)
+  y.inner
+}
+"
+            .into()
+        )])
+    );
+}
@@ -0,0 +1,213 @@
+use lsp_types::{
+    PartialResultParams, Position, Range, SemanticTokens, SemanticTokensParams,
+    SemanticTokensRangeParams, SemanticTokensRangeResult, SemanticTokensResult,
+    TextDocumentIdentifier, Url, WorkDoneProgressParams,
+};
+
+use super::*;
+use crate::language_server::semantic_tokens;
+
+fn document() -> TextDocumentIdentifier {
+    TextDocumentIdentifier {
+        uri: Url::from_file_path("/src/app.gleam").unwrap(),
+    }
+}
+
+/// Decode the tokens into the text they cover, their type, and their
+/// modifiers, using the legend the client is given.
+fn decode(src: &str, tokens: SemanticTokens) -> Vec<(String, String, Vec<String>)> {
+    let legend = semantic_tokens::legend();
+    let lines: Vec<_> = src.lines().collect();
+    let mut line = 0;
+    let mut column = 0;
+    tokens
+        .data
+        .into_iter()
+        .map(|token| {
+            if token.delta_line > 0 {
+                column = 0;
+            }
+            line += token.delta_line as usize;
+            column += token.delta_start as usize;
+            let text = lines
+                .get(line)
+                .and_then(|text| text.get(column..column + token.length as usize))
+                .unwrap_or_default();
+            let type_ = legend
+                .token_types
+                .get(token.token_type as usize)
+                .map(|type_| type_.as_str())
+                .unwrap_or_default();
+            let modifiers = legend
+                .token_modifiers
+                .iter()
+                .enumerate()
+                .filter(|(index, _)| token.token_modifiers_bitset & (1 << index) != 0)
+                .map(|(_, modifier)| modifier.as_str().to_string())
+                .collect();
+            (text.to_string(), type_.to_string(), modifiers)
+        })
+        .collect()
+}
+
+fn semantic_tokens(engine: &mut Engine, src: &str) -> Vec<(String, String, Vec<String>)> {
+    let params = SemanticTokensParams {
+        work_done_progress_params: WorkDoneProgressParams::default(),
+        partial_result_params: PartialResultParams::default(),
+        text_document: document(),
+    };
+    match engine.semantic_tokens(params).result.unwrap() {
+        Some(SemanticTokensResult::Tokens(tokens)) => decode(src, tokens),
+        other => panic!("Unexpected semantic tokens {other:?}"),
+    }
+}
+
+fn token(text: &str, type_: &str, modifiers: &[&str]) -> (String, String, Vec<String>) {
+    (
+        text.into(),
+        type_.into(),
+        modifiers
+            .iter()
+            .map(|modifier| modifier.to_string())
+            .collect(),
+    )
+}
+
+#[test]
+fn modules_values_and_labels() {
+    let src = "import app/maths.{Number} as m
+
+pub type Pair(a) {
+  Pair(first: a, second: Number)
+}
+
+const one = 1
+
+pub fn main() {
+  let pair = Pair(first: one, second: m.Number(2))
+  m.double(pair.first)
+}
+";
+    let io = LanguageServerTestIO::new();
+    io.src_module(
+        "app/maths",
+        "pub type Number { Number(Int) }
+pub fn double(x) { x * 2 }
+",
+    );
+    io.src_module("app", src);
+    let mut engine = compiled_engine(&io);
+
+    assert_eq!(
+        semantic_tokens(&mut engine, src),
+        vec![
+            token("app", "namespace", &[]),
+            token("maths", "namespace", &[]),
+            // Both a type and a record constructor
+            token("Number", "enumMember", &[]),
+            token("m", "namespace", &[]),
+            token("Pair", "type", &["declaration"]),
+            token("a", "typeParameter", &[]),
+            token("Pair", "enumMember", &["declaration"]),
+            token("first", "property", &[]),
+            token("a", "typeParameter", &[]),
+            token("second", "property", &[]),
+            token("Number", "type", &[]),
+            token("one", "variable", &["declaration", "readonly"]),
+            token("main", "function", &["declaration"]),
+            token("pair", "variable", &["declaration"]),
+            token("Pair", "enumMember", &[]),
+            token("first", "property", &[]),
+            token("one", "variable", &["readonly"]),
+            token("second", "property", &[]),
+            token("m", "namespace", &[]),
+            token("Number", "enumMember", &[]),
+            token("m", "namespace", &[]),
+            token("double", "function", &[]),
+            token("pair", "variable", &[]),
+            token("first", "property", &[]),
+        ]
+    );
+}
+
+#[test]
+fn parameters_and_unused_variables() {
+    let src = "fn apply(with f: fn(a) -> b, to x) -> b {
+  let unused = x
+  let y = x
+  f(y)
+}
+
+pub fn main() {
+  use z <- apply(to: 1)
+  z
+}
+";
+    let io = LanguageServerTestIO::new();
+    io.src_module("app", src);
+    let mut engine = compiled_engine(&io);
+
+    assert_eq!(
+        semantic_tokens(&mut engine, src),
+        vec![
+            token("apply", "function", &["declaration"]),
+            token("with", "property", &[]),
+            token("f", "parameter", &["declaration"]),
+            token("a", "typeParameter", &[]),
+            token("b", "typeParameter", &[]),
+            token("to", "property", &[]),
+            token("x", "parameter", &["declaration"]),
+            token("b", "typeParameter", &[]),
+            token("unused", "variable", &["declaration", "unused"]),
+            token("x", "parameter", &[]),
+            token("y", "variable", &["declaration"]),
+            token("x", "parameter", &[]),
+            token("f", "parameter", &[]),
+            token("y", "variable", &[]),
+            token("main", "function", &["declaration"]),
+            token("z", "variable", &["declaration"]),
+            token("apply", "function", &[]),
+            token("to", "property", &[]),
+            token("z", "variable", &[]),
+        ]
+    );
+}
+
+#[test]
+fn tokens_within_range() {
+    let src = "pub fn main() {
+  let x = 1
+  let y = x
+  y
+}
+";
+    let io = LanguageServerTestIO::new();
+    io.src_module("app", src);
+    let mut engine = compiled_engine(&io);
+
+    let params = SemanticTokensRangeParams {
+        work_done_progress_params: WorkDoneProgressParams::default(),
+        partial_result_params: PartialResultParams::default(),
+        text_document: document(),
+        range: Range::new(Position::new(2, 0), Position::new(3, 0)),
+    };
+    let tokens = match engine.semantic_tokens_range(params).result.unwrap() {
+        Some(SemanticTokensRangeResult::Tokens(tokens)) => tokens,
+        other => panic!("Unexpected semantic tokens {other:?}"),
+    };
+    // The first token is relative to the start of the document.
+    assert_eq!(
+        tokens
+            .data
+            .first()
+            .map(|token| (token.delta_line, token.delta_start)),
+        Some((2, 6))
+    );
+    assert_eq!(
+        decode(src, tokens),
+        vec![
+            token("y", "variable", &["declaration"]),
+            token("x", "variable", &[]),
+        ]
+    );
+}
@@ -0,0 +1,179 @@
+use lsp_types::{
+    Documentation, MarkupContent, MarkupKind, ParameterLabel, Position, SignatureHelp,
+    SignatureHelpParams, TextDocumentIdentifier, TextDocumentPositionParams, Url,
+    WorkDoneProgressParams,
+};
+
+use super::*;
+
+/// Get the signature help at the end of the given source code, which is
+/// inserted into the body of `main`.
+fn signature_help(engine: &mut Engine, header: &str, typed: &str) -> Option<SignatureHelp> {
+    let src = format!("{header}\npub fn main() {{\n  {typed}\n}}\n");
+    let line = header.lines().count() as u32 + 2;
+    let params = SignatureHelpParams {
+        context: None,
+        text_document_position_params: TextDocumentPositionParams {
+            text_document: TextDocumentIdentifier {
+                uri: Url::from_file_path("/src/app.gleam").unwrap(),
+            },
+            position: Position::new(line, typed.len() as u32 + 2),
+        },
+        work_done_progress_params: WorkDoneProgressParams::default(),
+    };
+    engine.signature_help(params, src.into()).result.unwrap()
+}
+
+/// The signature label and the label of the active parameter.
+fn active(help: Option<SignatureHelp>) -> Option<(String, Option<String>)> {
+    let help = help?;
+    let signature = help.signatures.into_iter().next()?;
+    let parameter = help.active_parameter.and_then(|index| {
+        match &signature.parameters.as_ref()?.get(index as usize)?.label {
+            ParameterLabel::Simple(label) => Some(label.clone()),
+            ParameterLabel::LabelOffsets(_) => None,
+        }
+    });
+    Some((signature.label, parameter))
+}
+
+const HEADER: &str = "import app/maths.{double as twice}
+
+fn add(to x: Int, amount y: Int) -> Int {
+  x + y
+}
+
+fn wrap(a: Int, b: String, f: fn(Int) -> c) -> c {
+  f(a)
+}
+";
+
+fn engine_with_header(io: &LanguageServerTestIO) -> Engine {
+    io.src_module(
+        "app/maths",
+        "/// Doubles a number
+pub fn double(x: Int) -> Int { x * 2 }
+",
+    );
+    io.src_module("app", &format!("{HEADER}\npub fn main() {{ twice(1) }}\n"));
+    compiled_engine(io)
+}
+
+#[test]
+fn positional_arguments() {
+    let io = LanguageServerTestIO::new();
+    let mut engine = engine_with_header(&io);
+
+    let label = "add(to x: Int, amount y: Int) -> Int".to_string();
+    assert_eq!(
+        active(signature_help(&mut engine, HEADER, "add(")),
+        Some((label.clone(), Some("to x: Int".into())))
+    );
+    assert_eq!(
+        active(signature_help(&mut engine, HEADER, "add(1, ")),
+        Some((label.clone(), Some("amount y: Int".into())))
+    );
+    // Arguments that are themselves calls
+    assert_eq!(
+        active(signature_help(&mut engine, HEADER, "add(add(1, 2), ")),
+        Some((label.clone(), Some("amount y: Int".into())))
+    );
+    assert_eq!(
+        active(signature_help(&mut engine, HEADER, "add(1, add(")),
+        Some((label.clone(), Some("to x: Int".into())))
+    );
+    // Too many arguments
+    assert_eq!(
+        active(signature_help(&mut engine, HEADER, "add(1, 2, ")),
+        Some((label, None))
+    );
+}
+
+#[test]
+fn labelled_arguments() {
+    let io = LanguageServerTestIO::new();
+    let mut engine = engine_with_header(&io);
+
+    let label = "add(to x: Int, amount y: Int) -> Int".to_string();
+    assert_eq!(
+        active(signature_help(&mut engine, HEADER, "add(amount: ")),
+        Some((label.clone(), Some("amount y: Int".into())))
+    );
+    // Unlabelled arguments fill the parameters that have not been given
+    assert_eq!(
+        active(signature_help(&mut engine, HEADER, "add(amount: 1, ")),
+        Some((label, Some("to x: Int".into())))
+    );
+}
+
+#[test]
+fn piped_and_used_calls() {
+    let io = LanguageServerTestIO::new();
+    let mut engine = engine_with_header(&io);
+
+    assert_eq!(
+        active(signature_help(&mut engine, HEADER, "1 |> add(")),
+        Some((
+            "add(to x: Int, amount y: Int) -> Int".into(),
+            Some("amount y: Int".into())
+        ))
+    );
+
+    let label = "wrap(a: Int, b: String, f: fn(Int) -> a) -> a".to_string();
+    assert_eq!(
+        active(signature_help(&mut engine, HEADER, "use x <- wrap(1, ")),
+        Some((label.clone(), Some("b: String".into())))
+    );
+    // The callback is never given in the arguments
+    assert_eq!(
+        active(signature_help(
+            &mut engine,
+            HEADER,
+            "use x <- wrap(1, \"\", "
+        )),
+        Some((label, None))
+    );
+}
+
+#[test]
+fn imported_and_prelude_values() {
+    let io = LanguageServerTestIO::new();
+    let mut engine = engine_with_header(&io);
+
+    let help = signature_help(&mut engine, HEADER, "twice(");
+    assert_eq!(
+        help.as_ref()
+            .and_then(|help| help.signatures.first())
+            .and_then(|signature| signature.documentation.clone()),
+        Some(Documentation::MarkupContent(MarkupContent {
+            kind: MarkupKind::Markdown,
+            value: " Doubles a number\n".into()
+        }))
+    );
+    assert_eq!(
+        active(help),
+        Some(("twice(x: Int) -> Int".into(), Some("x: Int".into())))
+    );
+
+    let header = HEADER.replace(".{double as twice}", "");
+    assert_eq!(
+        active(signature_help(&mut engine, &header, "maths.double(")),
+        Some(("maths.double(x: Int) -> Int".into(), Some("x: Int".into())))
+    );
+
+    assert_eq!(
+        active(signature_help(&mut engine, HEADER, "Ok(")),
+        Some(("Ok(a) -> Result(a, b)".into(), Some("a".into())))
+    );
+}
+
+#[test]
+fn not_in_a_call() {
+    let io = LanguageServerTestIO::new();
+    let mut engine = engine_with_header(&io);
+
+    assert_eq!(signature_help(&mut engine, HEADER, "[add(1, 2), "), None);
+    assert_eq!(signature_help(&mut engine, HEADER, "#(1, "), None);
+    assert_eq!(signature_help(&mut engine, HEADER, "unknown(1, "), None);
+    assert_eq!(signature_help(&mut engine, HEADER, "fn(x, "), None);
+}
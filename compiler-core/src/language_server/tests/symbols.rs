@@ -0,0 +1,150 @@
+use lsp_types::{
+    DocumentSymbol, DocumentSymbolParams, DocumentSymbolResponse, PartialResultParams, Position,
+    Range, SymbolKind, TextDocumentIdentifier, Url, WorkDoneProgressParams, WorkspaceSymbolParams,
+};
+
+use super::*;
+
+fn document_symbols(engine: &mut Engine, path: &str) -> Vec<DocumentSymbol> {
+    let params = DocumentSymbolParams {
+        text_document: TextDocumentIdentifier {
+            uri: Url::from_file_path(path).unwrap(),
+        },
+        work_done_progress_params: WorkDoneProgressParams::default(),
+        partial_result_params: PartialResultParams::default(),
+    };
+    match engine.document_symbols(params).result.unwrap() {
+        Some(DocumentSymbolResponse::Nested(symbols)) => symbols,
+        other => panic!("Unexpected document symbols {other:?}"),
+    }
+}
+
+/// The name, kind, and container of each symbol matching the query.
+fn workspace_symbols(engine: &mut Engine, query: &str) -> Vec<(String, SymbolKind, String)> {
+    let params = WorkspaceSymbolParams {
+        partial_result_params: PartialResultParams::default(),
+        work_done_progress_params: WorkDoneProgressParams::default(),
+        query: query.into(),
+    };
+    engine
+        .workspace_symbols(params)
+        .result
+        .unwrap()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|symbol| (symbol.name, symbol.kind, symbol.container_name.unwrap()))
+        .collect()
+}
+
+fn range(start: (u32, u32), end: (u32, u32)) -> Range {
+    Range::new(Position::new(start.0, start.1), Position::new(end.0, end.1))
+}
+
+#[test]
+fn document_outline() {
+    let io = LanguageServerTestIO::new();
+    io.src_module(
+        "app",
+        "import gleam
+
+pub type Wibble {
+  Wibble(Int)
+  Wobble
+}
+
+const answer = 42
+
+pub type Alias =
+  Wibble
+
+pub fn main() {
+  wobble(answer)
+}
+
+external fn wobble(Int) -> Wibble =
+  \"app_ffi\" \"wobble\"
+",
+    );
+    let mut engine = compiled_engine(&io);
+
+    let symbols = document_symbols(&mut engine, "/src/app.gleam");
+    let outline: Vec<_> = symbols
+        .iter()
+        .map(|symbol| (symbol.name.as_str(), symbol.kind))
+        .collect();
+    assert_eq!(
+        outline,
+        vec![
+            ("Wibble", SymbolKind::CLASS),
+            ("answer", SymbolKind::CONSTANT),
+            ("Alias", SymbolKind::CLASS),
+            ("main", SymbolKind::FUNCTION),
+            ("wobble", SymbolKind::FUNCTION),
+        ]
+    );
+
+    let wibble = symbols.first().unwrap();
+    assert_eq!(wibble.range, range((2, 0), (5, 1)));
+    assert_eq!(wibble.selection_range, range((2, 9), (2, 15)));
+    let constructors: Vec<_> = wibble
+        .children
+        .iter()
+        .flatten()
+        .map(|child| (child.name.as_str(), child.kind, child.selection_range))
+        .collect();
+    assert_eq!(
+        constructors,
+        vec![
+            ("Wibble", SymbolKind::CONSTRUCTOR, range((3, 2), (3, 8))),
+            ("Wobble", SymbolKind::CONSTRUCTOR, range((4, 2), (4, 8))),
+        ]
+    );
+
+    let main = symbols.iter().find(|symbol| symbol.name == "main").unwrap();
+    assert_eq!(main.range, range((12, 0), (14, 1)));
+    assert_eq!(main.selection_range, range((12, 7), (12, 11)));
+    assert_eq!(main.children, None);
+}
+
+#[test]
+fn workspace_search() {
+    let io = LanguageServerTestIO::new();
+    io.src_module(
+        "app/maths",
+        "pub type Number {
+  Number(Int)
+}
+
+pub fn double(x) { x * 2 }
+
+fn halve(x) { x / 2 }
+",
+    );
+    io.src_module(
+        "app",
+        "pub fn main() { 1 }
+pub const numbers = [1, 2]
+",
+    );
+    let mut engine = compiled_engine(&io);
+
+    assert_eq!(
+        workspace_symbols(&mut engine, "NUMBER"),
+        vec![
+            ("numbers".into(), SymbolKind::CONSTANT, "app".into()),
+            ("Number".into(), SymbolKind::CLASS, "app/maths".into()),
+            (
+                "Number".into(),
+                SymbolKind::CONSTRUCTOR,
+                "app/maths.Number".into()
+            ),
+        ]
+    );
+
+    assert_eq!(
+        workspace_symbols(&mut engine, "al"),
+        vec![("halve".into(), SymbolKind::FUNCTION, "app/maths".into())]
+    );
+
+    assert_eq!(workspace_symbols(&mut engine, "").len(), 6);
+}
@@ -52,6 +52,7 @@
 extern crate pretty_assertions;
 
 pub mod analyse;
+pub mod api_diff;
 pub mod ast;
 pub mod bit_string;
 pub mod build;
@@ -61,6 +62,7 @@ pub mod diagnostic;
 pub mod docs;
 pub mod erlang;
 pub mod error;
+pub mod explanation;
 pub mod format;
 pub mod hex;
 pub mod io;
@@ -72,6 +74,8 @@ pub mod metadata;
 pub mod parse;
 pub mod paths;
 pub mod pretty;
+pub mod requirement;
+pub mod test_runner;
 pub mod type_;
 pub mod uid;
 pub mod version;
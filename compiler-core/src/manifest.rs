@@ -1,15 +1,22 @@
 use std::collections::HashMap;
+use std::path::PathBuf;
 
+use crate::requirement::{toml_string, Requirement};
 use crate::Result;
-use hexpm::version::{Range, Version};
+use hexpm::version::Version;
 use itertools::Itertools;
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
 pub struct Manifest {
     #[serde(serialize_with = "ordered_map")]
-    pub requirements: HashMap<String, Range>,
+    pub requirements: HashMap<String, Requirement>,
     #[serde(serialize_with = "sorted_vec")]
     pub packages: Vec<ManifestPackage>,
+    /// The requirements of each path dependency at the time the versions
+    /// were resolved. Path dependencies can be edited at any time, so these
+    /// are used to tell when their versions need to be resolved again.
+    #[serde(default, serialize_with = "ordered_map")]
+    pub local_requirements: HashMap<String, HashMap<String, Requirement>>,
 }
 
 impl Manifest {
@@ -22,6 +29,7 @@ impl Manifest {
         let Self {
             requirements,
             packages,
+            local_requirements,
         } = self;
 
         buffer.push_str(
@@ -75,10 +83,24 @@ impl Manifest {
             }
 
             match source {
-                ManifestPackageSource::Hex { outer_checksum } => {
+                ManifestPackageSource::Hex {
+                    outer_checksum,
+                    repository,
+                } => {
                     buffer.push_str(r#", source = "hex", outer_checksum = ""#);
                     buffer.push_str(&outer_checksum.to_string());
-                    buffer.push('"');
+                    buffer.push_str(r#"", repository = "#);
+                    buffer.push_str(&toml_string(repository));
+                }
+                ManifestPackageSource::Git { repo, commit } => {
+                    buffer.push_str(r#", source = "git", repo = "#);
+                    buffer.push_str(&toml_string(repo));
+                    buffer.push_str(", commit = ");
+                    buffer.push_str(&toml_string(commit));
+                }
+                ManifestPackageSource::Local { path } => {
+                    buffer.push_str(r#", source = "local", path = "#);
+                    buffer.push_str(&toml_string(&path.to_string_lossy()));
                 }
             };
 
@@ -88,11 +110,24 @@ impl Manifest {
 
         // Requirements
         buffer.push_str("[requirements]\n");
-        for (name, range) in requirements.iter().sorted_by(|a, b| a.0.cmp(b.0)) {
+        for (name, requirement) in requirements.iter().sorted_by(|a, b| a.0.cmp(b.0)) {
             buffer.push_str(name);
-            buffer.push_str(" = \"");
-            buffer.push_str(&range.to_string());
-            buffer.push_str("\"\n");
+            buffer.push_str(" = ");
+            buffer.push_str(&requirement.to_toml());
+            buffer.push('\n');
+        }
+
+        // Requirements of path dependencies
+        for (package, requirements) in local_requirements.iter().sorted_by(|a, b| a.0.cmp(b.0)) {
+            buffer.push_str("\n[local_requirements.");
+            buffer.push_str(package);
+            buffer.push_str("]\n");
+            for (name, requirement) in requirements.iter().sorted_by(|a, b| a.0.cmp(b.0)) {
+                buffer.push_str(name);
+                buffer.push_str(" = ");
+                buffer.push_str(&requirement.to_toml());
+                buffer.push('\n');
+            }
         }
 
         buffer
@@ -103,10 +138,16 @@ impl Manifest {
 fn manifest_toml_format() {
     let mut manifest = Manifest {
         requirements: [
-            ("zzz".into(), Range::new("> 0.0.0".into())),
-            ("aaa".into(), Range::new("> 0.0.0".into())),
-            ("gleam_stdlib".into(), Range::new("~> 0.17".into())),
-            ("gleeunit".into(), Range::new("~> 0.1".into())),
+            (
+                "zzz".into(),
+                Requirement::Hex {
+                    version: hexpm::version::Range::new("> 0.0.0".into()),
+                    repository: Some("company".into()),
+                },
+            ),
+            ("aaa".into(), Requirement::hex("> 0.0.0")),
+            ("gleam_stdlib".into(), Requirement::hex("~> 0.17")),
+            ("gleeunit".into(), Requirement::hex("~> 0.1")),
         ]
         .into(),
         packages: vec![
@@ -118,6 +159,7 @@ fn manifest_toml_format() {
                 requirements: vec![],
                 source: ManifestPackageSource::Hex {
                     outer_checksum: Base16Checksum(vec![1, 22]),
+                    repository: "hexpm".into(),
                 },
             },
             ManifestPackage {
@@ -128,6 +170,7 @@ fn manifest_toml_format() {
                 requirements: vec!["zzz".into(), "gleam_stdlib".into()],
                 source: ManifestPackageSource::Hex {
                     outer_checksum: Base16Checksum(vec![3, 22]),
+                    repository: "hexpm".into(),
                 },
             },
             ManifestPackage {
@@ -138,6 +181,7 @@ fn manifest_toml_format() {
                 requirements: vec![],
                 source: ManifestPackageSource::Hex {
                     outer_checksum: Base16Checksum(vec![3, 22]),
+                    repository: "company".into(),
                 },
             },
             ManifestPackage {
@@ -148,9 +192,11 @@ fn manifest_toml_format() {
                 requirements: vec!["gleam_stdlib".into()],
                 source: ManifestPackageSource::Hex {
                     outer_checksum: Base16Checksum(vec![3, 46]),
+                    repository: "hexpm".into(),
                 },
             },
         ],
+        local_requirements: HashMap::new(),
     };
     let buffer = manifest.to_toml();
     assert_eq!(
@@ -159,17 +205,108 @@ fn manifest_toml_format() {
 # You typically do not need to edit this file
 
 packages = [
-  { name = "aaa", version = "0.4.0", build_tools = ["rebar3", "make"], requirements = ["zzz", "gleam_stdlib"], otp_app = "aaa_app", source = "hex", outer_checksum = "0316" },
-  { name = "gleam_stdlib", version = "0.17.1", build_tools = ["gleam"], requirements = [], source = "hex", outer_checksum = "0116" },
-  { name = "gleeunit", version = "0.4.0", build_tools = ["gleam"], requirements = ["gleam_stdlib"], source = "hex", outer_checksum = "032E" },
-  { name = "zzz", version = "0.4.0", build_tools = ["mix"], requirements = [], source = "hex", outer_checksum = "0316" },
+  { name = "aaa", version = "0.4.0", build_tools = ["rebar3", "make"], requirements = ["zzz", "gleam_stdlib"], otp_app = "aaa_app", source = "hex", outer_checksum = "0316", repository = "hexpm" },
+  { name = "gleam_stdlib", version = "0.17.1", build_tools = ["gleam"], requirements = [], source = "hex", outer_checksum = "0116", repository = "hexpm" },
+  { name = "gleeunit", version = "0.4.0", build_tools = ["gleam"], requirements = ["gleam_stdlib"], source = "hex", outer_checksum = "032E", repository = "hexpm" },
+  { name = "zzz", version = "0.4.0", build_tools = ["mix"], requirements = [], source = "hex", outer_checksum = "0316", repository = "company" },
 ]
 
 [requirements]
 aaa = "> 0.0.0"
 gleam_stdlib = "~> 0.17"
 gleeunit = "~> 0.1"
-zzz = "> 0.0.0"
+zzz = { version = "> 0.0.0", repository = "company" }
+"#
+    );
+    let deserialised: Manifest = toml::from_str(&buffer).unwrap();
+    manifest.packages.sort_by(|a, b| a.name.cmp(&b.name));
+    assert_eq!(deserialised, manifest);
+}
+
+#[test]
+fn manifest_toml_format_with_path_and_git_packages() {
+    let mut manifest = Manifest {
+        requirements: [
+            ("gleam_stdlib".into(), Requirement::hex("~> 0.17")),
+            (
+                "shared".into(),
+                Requirement::Path {
+                    path: "../shared".into(),
+                },
+            ),
+            (
+                "remote".into(),
+                Requirement::Git {
+                    git: "https://example.com/remote.git".into(),
+                    reference: "main".into(),
+                },
+            ),
+        ]
+        .into(),
+        packages: vec![
+            ManifestPackage {
+                name: "gleam_stdlib".into(),
+                version: Version::new(0, 17, 1),
+                build_tools: ["gleam".into()].into(),
+                otp_app: None,
+                requirements: vec![],
+                source: ManifestPackageSource::Hex {
+                    outer_checksum: Base16Checksum(vec![1, 22]),
+                    repository: "hexpm".into(),
+                },
+            },
+            ManifestPackage {
+                name: "shared".into(),
+                version: Version::new(1, 0, 0),
+                build_tools: ["gleam".into()].into(),
+                otp_app: None,
+                requirements: vec!["gleam_stdlib".into()],
+                source: ManifestPackageSource::Local {
+                    path: "../shared".into(),
+                },
+            },
+            ManifestPackage {
+                name: "remote".into(),
+                version: Version::new(0, 2, 0),
+                build_tools: ["gleam".into()].into(),
+                otp_app: None,
+                requirements: vec![],
+                source: ManifestPackageSource::Git {
+                    repo: "https://example.com/remote.git".into(),
+                    commit: "6f2a8b5e0d6c1f0c4bde7d1a0b2c3d4e5f6a7b8c".into(),
+                },
+            },
+        ],
+        local_requirements: [(
+            "shared".into(),
+            [
+                ("gleam_stdlib".into(), Requirement::hex("~> 0.17")),
+                ("gleeunit".into(), Requirement::hex("~> 0.1")),
+            ]
+            .into(),
+        )]
+        .into(),
+    };
+    let buffer = manifest.to_toml();
+    assert_eq!(
+        buffer,
+        r#"# This file was generated by Gleam
+# You typically do not need to edit this file
+
+packages = [
+  { name = "gleam_stdlib", version = "0.17.1", build_tools = ["gleam"], requirements = [], source = "hex", outer_checksum = "0116", repository = "hexpm" },
+  { name = "remote", version = "0.2.0", build_tools = ["gleam"], requirements = [], source = "git", repo = "https://example.com/remote.git", commit = "6f2a8b5e0d6c1f0c4bde7d1a0b2c3d4e5f6a7b8c" },
+  { name = "shared", version = "1.0.0", build_tools = ["gleam"], requirements = ["gleam_stdlib"], source = "local", path = "../shared" },
+]
+
+[requirements]
+gleam_stdlib = "~> 0.17"
+remote = { git = "https://example.com/remote.git", ref = "main" }
+shared = { path = "../shared" }
+
+[local_requirements.shared]
+gleam_stdlib = "~> 0.17"
+gleeunit = "~> 0.1"
 "#
     );
     let deserialised: Manifest = toml::from_str(&buffer).unwrap();
@@ -177,6 +314,91 @@ zzz = "> 0.0.0"
     assert_eq!(deserialised, manifest);
 }
 
+#[test]
+fn manifest_toml_escapes_paths_and_urls() {
+    let manifest = Manifest {
+        requirements: [
+            (
+                "shared".into(),
+                Requirement::Path {
+                    path: r"..\shared".into(),
+                },
+            ),
+            (
+                "remote".into(),
+                Requirement::Git {
+                    git: r#"https://example.com/"remote".git"#.into(),
+                    reference: r"v1\0".into(),
+                },
+            ),
+        ]
+        .into(),
+        packages: vec![
+            ManifestPackage {
+                name: "remote".into(),
+                version: Version::new(0, 2, 0),
+                build_tools: ["gleam".into()].into(),
+                otp_app: None,
+                requirements: vec![],
+                source: ManifestPackageSource::Git {
+                    repo: r#"https://example.com/"remote".git"#.into(),
+                    commit: r#"a"b\c"#.into(),
+                },
+            },
+            ManifestPackage {
+                name: "shared".into(),
+                version: Version::new(1, 0, 0),
+                build_tools: ["gleam".into()].into(),
+                otp_app: None,
+                requirements: vec![],
+                source: ManifestPackageSource::Local {
+                    path: r#"..\"shared""#.into(),
+                },
+            },
+        ],
+        local_requirements: HashMap::new(),
+    };
+    let buffer = manifest.to_toml();
+    assert_eq!(
+        buffer,
+        r#"# This file was generated by Gleam
+# You typically do not need to edit this file
+
+packages = [
+  { name = "remote", version = "0.2.0", build_tools = ["gleam"], requirements = [], source = "git", repo = "https://example.com/\"remote\".git", commit = "a\"b\\c" },
+  { name = "shared", version = "1.0.0", build_tools = ["gleam"], requirements = [], source = "local", path = "..\\\"shared\"" },
+]
+
+[requirements]
+remote = { git = "https://example.com/\"remote\".git", ref = "v1\\0" }
+shared = { path = "..\\shared" }
+"#
+    );
+    let deserialised: Manifest = toml::from_str(&buffer).unwrap();
+    assert_eq!(deserialised, manifest);
+}
+
+#[test]
+fn hex_packages_without_repository_are_from_hexpm() {
+    let manifest: Manifest = toml::from_str(
+        r#"packages = [
+  { name = "gleam_stdlib", version = "0.17.1", build_tools = ["gleam"], requirements = [], source = "hex", outer_checksum = "0116" },
+]
+
+[requirements]
+gleam_stdlib = "~> 0.17"
+"#,
+    )
+    .unwrap();
+    assert_eq!(
+        manifest.packages.first().map(|package| &package.source),
+        Some(&ManifestPackageSource::Hex {
+            outer_checksum: Base16Checksum(vec![1, 22]),
+            repository: "hexpm".into(),
+        })
+    );
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Base16Checksum(pub Vec<u8>);
 
@@ -237,6 +459,7 @@ impl Default for ManifestPackage {
             version: Version::new(1, 0, 0),
             source: ManifestPackageSource::Hex {
                 outer_checksum: Base16Checksum(vec![]),
+                repository: "hexpm".into(),
             },
         }
     }
@@ -246,7 +469,21 @@ impl Default for ManifestPackage {
 #[serde(tag = "source")]
 pub enum ManifestPackageSource {
     #[serde(rename = "hex")]
-    Hex { outer_checksum: Base16Checksum },
+    Hex {
+        outer_checksum: Base16Checksum,
+        /// Manifests written before repositories could be configured only
+        /// have packages from the public Hex repository.
+        #[serde(default = "default_repository")]
+        repository: String,
+    },
+    #[serde(rename = "git")]
+    Git { repo: String, commit: String },
+    #[serde(rename = "local")]
+    Local { path: PathBuf },
+}
+
+fn default_repository() -> String {
+    crate::hex::HEXPM_REPOSITORY.into()
 }
 
 fn ordered_map<S, K, V>(value: &HashMap<K, V>, serializer: S) -> Result<S::Ok, S::Error>
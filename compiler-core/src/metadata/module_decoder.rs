@@ -5,8 +5,8 @@ use smol_str::SmolStr;
 
 use crate::{
     ast::{
-        BitStringSegment, BitStringSegmentOption, CallArg, Constant, SrcSpan, TypedConstant,
-        TypedConstantBitStringSegment, TypedConstantBitStringSegmentOption,
+        BitStringSegment, BitStringSegmentOption, CallArg, Constant, Deprecation, SrcSpan,
+        TypedConstant, TypedConstantBitStringSegment, TypedConstantBitStringSegmentOption,
     },
     build::Origin,
     schema_capnp::{self as schema, *},
@@ -89,6 +89,7 @@ impl ModuleDecoder {
             module: reader.get_module()?.into(),
             parameters: read_vec!(reader.get_parameters()?, self, type_),
             typ: type_,
+            deprecation: self.deprecation(reader.has_deprecated(), reader.get_deprecated()?),
         })
     }
 
@@ -150,6 +151,7 @@ impl ModuleDecoder {
         let variant = self.value_constructor_variant(&reader.get_variant()?)?;
         Ok(ValueConstructor {
             public: true,
+            deprecation: self.deprecation(reader.has_deprecated(), reader.get_deprecated()?),
             type_,
             variant,
         })
@@ -370,6 +372,16 @@ impl ModuleDecoder {
         }
     }
 
+    fn deprecation(&self, is_deprecated: bool, message: &str) -> Deprecation {
+        if is_deprecated {
+            Deprecation::Deprecated {
+                message: message.into(),
+            }
+        } else {
+            Deprecation::NotDeprecated
+        }
+    }
+
     fn src_span(&self, reader: &src_span::Reader<'_>) -> Result<SrcSpan> {
         Ok(SrcSpan {
             start: reader.get_start(),
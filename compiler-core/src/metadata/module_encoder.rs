@@ -2,7 +2,7 @@ use smol_str::SmolStr;
 
 use crate::{
     ast::{
-        Constant, SrcSpan, TypedConstant, TypedConstantBitStringSegment,
+        Constant, Deprecation, SrcSpan, TypedConstant, TypedConstantBitStringSegment,
         TypedConstantBitStringSegmentOption,
     },
     schema_capnp::{self as schema, *},
@@ -133,6 +133,9 @@ impl<'a> ModuleEncoder<'a> {
                 .init_parameters(constructor.parameters.len() as u32),
             &constructor.parameters,
         );
+        if let Deprecation::Deprecated { message } = &constructor.deprecation {
+            builder.set_deprecated(message);
+        }
     }
 
     fn build_types_constructors_mapping(
@@ -151,7 +154,13 @@ impl<'a> ModuleEncoder<'a> {
         constructor: &ValueConstructor,
     ) {
         self.build_type(builder.reborrow().init_type(), &constructor.type_);
-        self.build_value_constructor_variant(builder.init_variant(), &constructor.variant);
+        self.build_value_constructor_variant(
+            builder.reborrow().init_variant(),
+            &constructor.variant,
+        );
+        if let Deprecation::Deprecated { message } = &constructor.deprecation {
+            builder.set_deprecated(message);
+        }
     }
 
     fn build_src_span(&mut self, mut builder: src_span::Builder<'_>, span: SrcSpan) {
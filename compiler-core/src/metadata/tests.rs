@@ -4,8 +4,8 @@ use type_::{AccessorsMap, FieldMap, RecordAccessor};
 use super::*;
 use crate::{
     ast::{
-        BitStringSegment, BitStringSegmentOption, CallArg, Constant, SrcSpan, TypedConstant,
-        TypedConstantBitStringSegmentOption,
+        BitStringSegment, BitStringSegmentOption, CallArg, Constant, Deprecation, SrcSpan,
+        TypedConstant, TypedConstantBitStringSegmentOption,
     },
     build::Origin,
     type_::{self, Module, Type, TypeConstructor, ValueConstructor, ValueConstructorVariant},
@@ -42,6 +42,7 @@ fn constant_module(constant: TypedConstant) -> Module {
                     location: SrcSpan::default(),
                     module: "one/two".into(),
                 },
+                deprecation: Deprecation::NotDeprecated,
             },
         )]
         .into(),
@@ -91,6 +92,7 @@ fn module_with_app_type() {
                 origin: Default::default(),
                 module: "the/module".into(),
                 parameters: vec![],
+                deprecation: Deprecation::NotDeprecated,
             },
         )]
         .into(),
@@ -101,6 +103,47 @@ fn module_with_app_type() {
     assert_eq!(roundtrip(&module), module);
 }
 
+#[test]
+fn deprecated_type() {
+    let module = Module {
+        package: "some_package".into(),
+        origin: Origin::Src,
+        name: "a/b".into(),
+        types: [(
+            "ListIntType".into(),
+            TypeConstructor {
+                typ: type_::list(type_::int()),
+                public: true,
+                origin: Default::default(),
+                module: "the/module".into(),
+                parameters: vec![],
+                deprecation: Deprecation::Deprecated {
+                    message: "Use NewType instead".into(),
+                },
+            },
+        )]
+        .into(),
+        types_constructors: HashMap::new(),
+        values: HashMap::new(),
+        accessors: HashMap::new(),
+    };
+    assert_eq!(roundtrip(&module), module);
+}
+
+#[test]
+fn deprecated_constant() {
+    let mut module = constant_module(Constant::Int {
+        location: Default::default(),
+        value: "1".into(),
+    });
+    for value in module.values.values_mut() {
+        value.deprecation = Deprecation::Deprecated {
+            message: "Use two instead".into(),
+        };
+    }
+    assert_eq!(roundtrip(&module), module);
+}
+
 #[test]
 fn module_with_fn_type() {
     let module = Module {
@@ -115,6 +158,7 @@ fn module_with_fn_type() {
                 origin: Default::default(),
                 module: "the/module".into(),
                 parameters: vec![],
+                deprecation: Deprecation::NotDeprecated,
             },
         )]
         .into(),
@@ -139,6 +183,7 @@ fn module_with_tuple_type() {
                 origin: Default::default(),
                 module: "the/module".into(),
                 parameters: vec![],
+                deprecation: Deprecation::NotDeprecated,
             },
         )]
         .into(),
@@ -169,6 +214,7 @@ fn module_with_generic_type() {
                     origin: Default::default(),
                     module: "the/module".into(),
                     parameters: vec![t1, t2],
+                    deprecation: Deprecation::NotDeprecated,
                 },
             )]
             .into(),
@@ -199,6 +245,7 @@ fn module_with_type_links() {
                     origin: Default::default(),
                     module: "a".into(),
                     parameters: vec![],
+                    deprecation: Deprecation::NotDeprecated,
                 },
             )]
             .into(),
@@ -251,6 +298,7 @@ fn module_fn_value() {
                         end: 1100,
                     },
                 },
+                deprecation: Deprecation::NotDeprecated,
             },
         )]
         .into(),
@@ -285,6 +333,7 @@ fn module_fn_value_regression() {
                         end: 1100,
                     },
                 },
+                deprecation: Deprecation::NotDeprecated,
             },
         )]
         .into(),
@@ -318,6 +367,7 @@ fn module_fn_value_with_field_map() {
                     arity: 5,
                     location: SrcSpan { start: 2, end: 11 },
                 },
+                deprecation: Deprecation::NotDeprecated,
             },
         )]
         .into(),
@@ -354,6 +404,7 @@ fn record_value() {
                         end: random.gen(),
                     },
                 },
+                deprecation: Deprecation::NotDeprecated,
             },
         )]
         .into(),
@@ -393,6 +444,7 @@ fn record_value_with_field_map() {
                         end: random.gen(),
                     },
                 },
+                deprecation: Deprecation::NotDeprecated,
             },
         )]
         .into(),
@@ -601,6 +653,7 @@ fn constant_var() {
                 location: SrcSpan::default(),
                 module: "one/two".into(),
             },
+            deprecation: Deprecation::NotDeprecated,
         })),
     };
 
@@ -623,6 +676,7 @@ fn constant_var() {
                         location: SrcSpan::default(),
                         module: "one/two".into(),
                     },
+                    deprecation: Deprecation::NotDeprecated,
                 },
             ),
             (
@@ -636,6 +690,7 @@ fn constant_var() {
                         location: SrcSpan::default(),
                         module: "one/two".into(),
                     },
+                    deprecation: Deprecation::NotDeprecated,
                 },
             ),
         ]
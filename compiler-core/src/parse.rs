@@ -52,18 +52,18 @@
 pub mod error;
 pub mod extra;
 pub mod lexer;
-mod token;
+pub mod token;
 
 use crate::analyse::Inferred;
 use crate::ast::{
     Arg, ArgNames, AssignName, Assignment, AssignmentKind, BinOp, BitStringSegment,
-    BitStringSegmentOption, CallArg, Clause, ClauseGuard, Constant, CustomType, ExternalFnArg,
-    ExternalFunction, ExternalType, Function, HasLocation, Import, Module, ModuleConstant,
-    ModuleStatement, Pattern, RecordConstructor, RecordConstructorArg, RecordUpdateSpread, SrcSpan,
-    Statement, TargetGroup, TodoKind, TypeAlias, TypeAst, UnqualifiedImport, UntypedArg,
-    UntypedClause, UntypedClauseGuard, UntypedConstant, UntypedExpr, UntypedExternalFnArg,
-    UntypedModule, UntypedModuleStatement, UntypedPattern, UntypedRecordUpdateArg,
-    UntypedStatement, Use, UseAssignment, CAPTURE_VARIABLE,
+    BitStringSegmentOption, CallArg, Clause, ClauseGuard, Constant, CustomType, Deprecation,
+    ExternalFnArg, ExternalFunction, ExternalType, Function, HasLocation, Import, Module,
+    ModuleConstant, ModuleStatement, Pattern, RecordConstructor, RecordConstructorArg,
+    RecordUpdateSpread, SrcSpan, Statement, TargetGroup, TodoKind, TypeAlias, TypeAst,
+    UnqualifiedImport, UntypedArg, UntypedClause, UntypedClauseGuard, UntypedConstant, UntypedExpr,
+    UntypedExternalFnArg, UntypedModule, UntypedModuleStatement, UntypedPattern,
+    UntypedRecordUpdateArg, UntypedStatement, Use, UseAssignment, CAPTURE_VARIABLE,
 };
 use crate::build::Target;
 use crate::parse::extra::ModuleExtra;
@@ -89,6 +89,22 @@ pub fn parse_module(src: &str) -> Result<(UntypedModule, ModuleExtra), ParseErro
     Ok((module, parser.extra))
 }
 
+/// Parse a module, recovering from syntax errors rather than stopping at the
+/// first one.
+///
+/// When a top level statement cannot be parsed the tokens are skipped until
+/// the start of the next statement, or a closing brace in the first column of
+/// a line, and parsing resumes from there. The module returned contains each of
+/// the statements that could be parsed, and every error found is returned in
+/// the order they occur in the source.
+///
+pub fn parse_module_recovering(src: &str) -> (UntypedModule, ModuleExtra, Vec<ParseError>) {
+    let lex = lexer::make_tokenizer(src);
+    let mut parser = Parser::new(lex);
+    let (module, errors) = parser.parse_module_recovering(src);
+    (module, parser.extra, errors)
+}
+
 //
 // Test Interface
 //
@@ -146,6 +162,147 @@ where
         })
     }
 
+    fn parse_module_recovering(&mut self, src: &str) -> (UntypedModule, Vec<ParseError>) {
+        let mut statements: Vec<TargetGroup> = vec![];
+        let mut errors = vec![];
+        let mut lex_errors = vec![];
+        // A lex error leaves a gap in the tokens, so parsing continues until
+        // both are exhausted.
+        while self.tok0.is_some() || self.tok1.is_some() {
+            let start = match &self.tok0 {
+                Some((start, _, _)) => *start,
+                None => {
+                    let _ = self.next_tok();
+                    continue;
+                }
+            };
+            // Lex errors are set aside as they are found so that each statement
+            // is only checked for its own.
+            lex_errors.append(&mut self.lex_errors);
+            let result = match &self.tok0 {
+                Some((_, Token::If, _)) => self.parse_target_group(),
+                _ => self
+                    .parse_module_statement()
+                    .map(|statement| statement.map(|s| TargetGroup::Any(vec![s]))),
+            };
+            // A statement containing a lex error is incomplete, so it is
+            // discarded along with any error caused by the missing tokens.
+            // The lex error itself is reported below.
+            if !self.lex_errors.is_empty() {
+                if result.is_err() {
+                    self.synchronise(src, start);
+                }
+                continue;
+            }
+            match result {
+                Ok(Some(TargetGroup::Any(new))) => match statements.last_mut() {
+                    Some(TargetGroup::Any(previous)) => previous.extend(new),
+                    _ => statements.push(TargetGroup::Any(new)),
+                },
+                Ok(Some(group)) => statements.push(group),
+                Ok(None) => {
+                    let (start, end) = match &self.tok0 {
+                        Some((start, _, end)) => (*start, *end),
+                        None => (start, start),
+                    };
+                    errors.push(ParseError {
+                        error: ParseErrorType::UnexpectedToken {
+                            expected: vec!["An import, const, type, if block, or function.".into()],
+                            hint: None,
+                        },
+                        location: SrcSpan { start, end },
+                    });
+                    self.synchronise(src, start);
+                }
+                Err(error) => {
+                    errors.push(error);
+                    self.synchronise(src, start);
+                }
+            }
+        }
+
+        lex_errors.append(&mut self.lex_errors);
+        errors.extend(lex_errors.into_iter().map(|error| ParseError {
+            location: error.location,
+            error: ParseErrorType::LexError { error },
+        }));
+        errors.sort_by_key(|error| error.location.start);
+
+        let module = Module {
+            name: "".into(),
+            documentation: vec![],
+            type_info: (),
+            statements,
+        };
+        (module, errors)
+    }
+
+    // Skip tokens after a syntax error until reaching one that can start a
+    // top level statement, or just after the closing brace that closes every
+    // brace opened since the start of the statement with the error, as that
+    // is most likely the end of its definition.
+    // At least the token at `from` is always skipped so parsing cannot get
+    // stuck on the same token.
+    fn synchronise(&mut self, src: &str, from: u32) {
+        let consumed_until = match (&self.tok0, &self.tok1) {
+            (Some((start, _, _)), _) | (None, Some((start, _, _))) => *start,
+            (None, None) => return,
+        };
+        let consumed = src
+            .get(from as usize..consumed_until as usize)
+            .unwrap_or_default();
+        let mut depth = unclosed_braces(consumed);
+        loop {
+            match (&self.tok0, &self.tok1) {
+                (None, None) => return,
+                (Some((start, _, _)), _) if *start > from && self.at_statement_start() => return,
+                _ => (),
+            }
+            let ends_definition = match &self.tok0 {
+                Some((_, Token::LeftBrace, _)) => {
+                    depth += 1;
+                    false
+                }
+                Some((_, Token::RightBrace, _)) => {
+                    depth -= 1;
+                    depth == 0
+                }
+                _ => false,
+            };
+            let _ = self.next_tok();
+            if ends_definition {
+                return;
+            }
+        }
+    }
+
+    // Whether the next tokens can only be the start of a top level statement.
+    // `fn` and `if` are also used within expressions, so they are only taken
+    // to be the start of a function definition or a target group when followed
+    // by a name or a target.
+    fn at_statement_start(&self) -> bool {
+        match (&self.tok0, &self.tok1) {
+            (
+                Some((
+                    _,
+                    Token::Import
+                    | Token::Const
+                    | Token::Type
+                    | Token::Pub
+                    | Token::External
+                    | Token::At,
+                    _,
+                )),
+                _,
+            ) => true,
+            (Some((_, Token::Fn, _)), Some((_, Token::Name { .. }, _))) => true,
+            (Some((_, Token::If, _)), Some((_, Token::Name { name }, _))) => {
+                Target::from_str(name).is_ok()
+            }
+            _ => false,
+        }
+    }
+
     // The way the parser is currenly implemented, it cannot exit immediately while advancing
     // the token stream upon seing a LexError. That is to avoid having to put `?` all over the
     // place and instead we collect LexErrors in `self.lex_errors` and attempt to continue parsing.
@@ -220,6 +377,57 @@ where
     }
 
     fn parse_module_statement(&mut self) -> Result<Option<UntypedModuleStatement>, ParseError> {
+        let (location, deprecation) = match self.parse_attributes()? {
+            None => return self.parse_definition(),
+            Some(attribute) => attribute,
+        };
+        let mut statement = self.parse_definition()?;
+        match &mut statement {
+            Some(
+                ModuleStatement::Function(Function { deprecation: d, .. })
+                | ModuleStatement::ExternalFunction(ExternalFunction { deprecation: d, .. })
+                | ModuleStatement::CustomType(CustomType { deprecation: d, .. })
+                | ModuleStatement::TypeAlias(TypeAlias { deprecation: d, .. })
+                | ModuleStatement::ModuleConstant(ModuleConstant { deprecation: d, .. }),
+            ) => {
+                *d = deprecation;
+                Ok(statement)
+            }
+            _ => parse_error(ParseErrorType::UnexpectedAttribute, location),
+        }
+    }
+
+    // Parse the attributes given before a definition, returning the location
+    // of the attributes and the deprecation they specify.
+    //
+    // examples:
+    //   @deprecated("Use new_function instead")
+    fn parse_attributes(&mut self) -> Result<Option<(SrcSpan, Deprecation)>, ParseError> {
+        let mut attributes = None;
+        while let Some((start, _)) = self.maybe_one(&Token::At) {
+            let (_, name, name_end) = self.expect_name()?;
+            if name != "deprecated" {
+                return parse_error(
+                    ParseErrorType::UnknownAttribute,
+                    SrcSpan {
+                        start,
+                        end: name_end,
+                    },
+                );
+            }
+            let _ = self.expect_one(&Token::LeftParen)?;
+            let (_, message, _) = self.expect_string()?;
+            let (_, end) = self.expect_one(&Token::RightParen)?;
+            let location = SrcSpan { start, end };
+            if attributes.is_some() {
+                return parse_error(ParseErrorType::DuplicateAttribute, location);
+            }
+            attributes = Some((location, Deprecation::Deprecated { message }));
+        }
+        Ok(attributes)
+    }
+
+    fn parse_definition(&mut self) -> Result<Option<UntypedModuleStatement>, ParseError> {
         match (self.tok0.take(), self.tok1.as_ref()) {
             // Imports
             (Some((_, Token::Import, _)), _) => {
@@ -1410,6 +1618,7 @@ where
             body,
             return_type: (),
             return_annotation,
+            deprecation: Deprecation::NotDeprecated,
         })))
     }
 
@@ -1445,6 +1654,7 @@ where
                 fun,
                 return_: retrn,
                 return_type: (),
+                deprecation: Deprecation::NotDeprecated,
             })))
         } else {
             parse_error(
@@ -1714,6 +1924,7 @@ where
                     parameters,
                     constructors,
                     typed_parameters: vec![],
+                    deprecation: Deprecation::NotDeprecated,
                 })))
             }
         } else if let Some((eq_s, eq_e)) = self.maybe_one(&Token::Equal) {
@@ -1732,6 +1943,7 @@ where
                         parameters,
                         type_ast: t,
                         type_: (),
+                        deprecation: Deprecation::NotDeprecated,
                     })))
                 } else {
                     parse_error(
@@ -2117,6 +2329,7 @@ where
                 annotation,
                 value: Box::new(value),
                 type_: (),
+                deprecation: Deprecation::NotDeprecated,
             })))
         } else {
             parse_error(
@@ -2483,6 +2696,7 @@ where
             Some((start, Token::Name { name }, end)) => Ok(Pattern::VarUsage {
                 location: SrcSpan { start, end },
                 name,
+                constructor: None,
                 type_: (),
             }),
             Some((start, Token::Int { value }, end)) => Ok(Pattern::Int {
@@ -3049,6 +3263,18 @@ fn parse_error<T>(error: ParseErrorType, location: SrcSpan) -> Result<T, ParseEr
 // Misc Helpers
 //
 
+// The number of braces opened and not closed again in some source code.
+fn unclosed_braces(src: &str) -> i32 {
+    lexer::make_tokenizer(src)
+        .filter_map(Result::ok)
+        .map(|(_, token, _)| match token {
+            Token::LeftBrace => 1,
+            Token::RightBrace => -1,
+            _ => 0,
+        })
+        .sum()
+}
+
 // useful for checking if a user tried to enter a reserved word as a name
 fn is_reserved_word(tok: Token) -> bool {
     matches![
@@ -41,57 +41,27 @@ impl ParseError {
             ),
             ParseErrorType::ExpectedUpName => ("I was expecting a type name here.", vec![]),
             ParseErrorType::ExpectedValue => ("I was expecting a value after this.", vec![]),
-            ParseErrorType::ExtraSeparator => (
-                "This is an extra delimiter.",
-                vec!["Hint: Try removing it?".into()],
-            ),
-            ParseErrorType::ExprLparStart => (
-                "This parenthesis cannot be understood here.",
-                vec!["Hint: To group expressions in gleam use \"{\" and \"}\"".into()],
-            ),
-            ParseErrorType::IncorrectName => (
-                "I'm expecting a lowercase name here.",
-                vec![wrap(
-                    "Hint: Variable and module names start with a lowercase letter, \
-and can contain a-z, 0-9, or _.",
-                )],
-            ),
-            ParseErrorType::IncorrectUpName => (
-                "I'm expecting a type name here.",
-                vec![wrap(
-                    "Hint: Type names start with a uppercase letter, and can \
-contain a-z, A-Z, or 0-9.",
-                )],
-            ),
+            ParseErrorType::ExtraSeparator => ("This is an extra delimiter.", vec![]),
+            ParseErrorType::ExprLparStart => {
+                ("This parenthesis cannot be understood here.", vec![])
+            }
+            ParseErrorType::IncorrectName => ("I'm expecting a lowercase name here.", vec![]),
+            ParseErrorType::IncorrectUpName => ("I'm expecting a type name here.", vec![]),
             ParseErrorType::InvalidBitStringSegment => (
                 "This is not a valid BitString segment option.",
-                vec![
-                    "Hint: Valid BitString segment options are:".into(),
-                    wrap(
-                        "binary, int, float, bit_string, utf8, utf16, utf32, utf8_codepoint, \
-utf16_codepoint, utf32_codepoint, signed, unsigned, big, little, native, size, unit",
-                    ),
-                    "See: https://gleam.run/book/tour/bit-strings".into(),
-                ],
+                vec!["See: https://gleam.run/book/tour/bit-strings".into()],
             ),
             ParseErrorType::InvalidBitStringUnit => (
                 "This is not a valid BitString unit value.",
-                vec![
-                    "Hint: unit must be an integer literal >= 1 and <= 256".into(),
-                    "See: https://gleam.run/book/tour/bit-strings".into(),
-                ],
+                vec!["See: https://gleam.run/book/tour/bit-strings".into()],
             ),
             ParseErrorType::InvalidTailPattern => (
                 "This part of a list pattern can only be a name or a discard.",
                 vec![],
             ),
-            ParseErrorType::InvalidTupleAccess => (
-                "This integer is not valid for tuple access.",
-                vec![
-                    "Hint: Only non negative integer literals like 0, or 1_000 can be used."
-                        .to_string(),
-                ],
-            ),
+            ParseErrorType::InvalidTupleAccess => {
+                ("This integer is not valid for tuple access.", vec![])
+            }
             ParseErrorType::LexError { error: lex_err } => lex_err.to_parse_error_info(),
             ParseErrorType::NestedBitStringPattern => {
                 ("BitString patterns cannot be nested.", vec![])
@@ -108,16 +78,10 @@ utf16_codepoint, utf32_codepoint, signed, unsigned, big, little, native, size, u
                 "This type is not allowed in module constants.",
                 vec!["See: https://gleam.run/book/tour/constants".into()],
             ),
-            ParseErrorType::NoExpression => (
-                "There must be an expression in here.",
-                vec!["Hint: Put an expression in there or remove the brackets.".into()],
-            ),
+            ParseErrorType::NoExpression => ("There must be an expression in here.", vec![]),
             ParseErrorType::NoLetBinding => (
                 "There must be a 'let' to bind variable to value",
-                vec![
-                    "Hint: Use let for binding".into(),
-                    "See: https://gleam.run/book/tour/let-bindings".into(),
-                ],
+                vec!["See: https://gleam.run/book/tour/let-bindings".into()],
             ),
             ParseErrorType::NoValueAfterEqual => (
                 "I was expecting to see a value after this equals sign.",
@@ -127,54 +91,34 @@ utf16_codepoint, utf32_codepoint, signed, unsigned, big, little, native, size, u
                 "Type Aliases cannot be opaque",
                 vec!["See: https://gleam.run/book/tour/type-aliases".into()],
             ),
-            ParseErrorType::OpNakedRight => (
-                "This operator has no value on its right side.",
-                vec!["Hint: Remove it or put a value after it.".into()],
-            ),
+            ParseErrorType::OpNakedRight => {
+                ("This operator has no value on its right side.", vec![])
+            }
             ParseErrorType::TooManyArgHoles => (
                 "There is more than 1 argument hole in this function call.",
-                vec![
-                    "Hint: Function calls can have at most one argument hole.".into(),
-                    "See: https://gleam.run/book/tour/functions".into(),
-                ],
+                vec!["See: https://gleam.run/book/tour/functions".into()],
             ),
             ParseErrorType::UnexpectedEof => ("The module ended unexpectedly.", vec![]),
             ParseErrorType::ListSpreadWithoutElements => (
                 "This spread does nothing",
-                vec![
-                    "Hint: Try prepending some elements [1, 2, ..list].".into(),
-                    "See: https://gleam.run/book/tour/lists.html".into(),
-                ],
+                vec!["See: https://gleam.run/book/tour/lists.html".into()],
             ),
             ParseErrorType::UnexpectedReservedWord => (
                 "This is a reserved word.",
-                vec![
-                    "Hint: I was expecting to see a name here.".into(),
-                    "See: https://gleam.run/book/tour/reserved-words".into(),
-                ],
+                vec!["See: https://gleam.run/book/tour/reserved-words".into()],
             ),
             ParseErrorType::LowcaseBooleanPattern => (
                 "Did you want a Bool instead of a variable?",
-                vec![
-                    "Hint: In Gleam boolean literals are True and False".into(),
-                    "See: https://gleam.run/book/tour/bools.html".into(),
-                ],
+                vec!["See: https://gleam.run/book/tour/bools.html".into()],
             ),
             ParseErrorType::UnexpectedLabel => (
                 "Argument labels are not allowed for anonymous functions.",
                 vec!["Please remove the argument label.".into()],
             ),
-            ParseErrorType::UnexpectedToken { expected, hint } => {
+            ParseErrorType::UnexpectedToken { expected, .. } => {
                 let messages = std::iter::once("Expected one of: ".to_string())
-                    .chain(expected.iter().map(|s| s.to_string()));
-
-                let messages = match hint {
-                    Some(hint_text) => messages
-                        .chain(std::iter::once(format!("Hint: {hint_text}")))
-                        .collect(),
-                    _ => messages.collect(),
-                };
-
+                    .chain(expected.iter().map(|s| s.to_string()))
+                    .collect();
                 ("I was not expecting this.", messages)
             }
             ParseErrorType::ExpectedBoolean => (
@@ -200,8 +144,90 @@ utf16_codepoint, utf32_codepoint, signed, unsigned, big, little, native, size, u
                 "I was expecting a value here.",
                 vec!["If a list expression has a spread then a tail must also be given.".into()],
             ),
+            ParseErrorType::UnknownAttribute => ("I don't recognise this attribute.", vec![]),
+            ParseErrorType::DuplicateAttribute => {
+                ("This attribute has already been given.", vec![])
+            }
+            ParseErrorType::UnexpectedAttribute => ("This attribute cannot be used here.", vec![]),
         }
     }
+
+    /// A suggestion of how to fix the error, shown after its details.
+    pub fn hint(&self) -> Option<String> {
+        let hint = match &self.error {
+            ParseErrorType::ExtraSeparator => "Try removing it?",
+            ParseErrorType::ExprLparStart => "To group expressions in gleam use \"{\" and \"}\"",
+            ParseErrorType::IncorrectName => {
+                return Some(wrap(
+                    "Variable and module names start with a lowercase letter, \
+and can contain a-z, 0-9, or _.",
+                ))
+            }
+            ParseErrorType::IncorrectUpName => {
+                return Some(wrap(
+                    "Type names start with a uppercase letter, and can \
+contain a-z, A-Z, or 0-9.",
+                ))
+            }
+            ParseErrorType::InvalidBitStringSegment => {
+                return Some(format!(
+                    "Valid BitString segment options are:\n{}",
+                    wrap(
+                        "binary, int, float, bit_string, utf8, utf16, utf32, utf8_codepoint, \
+utf16_codepoint, utf32_codepoint, signed, unsigned, big, little, native, size, unit",
+                    )
+                ))
+            }
+            ParseErrorType::InvalidBitStringUnit => {
+                "unit must be an integer literal >= 1 and <= 256"
+            }
+            ParseErrorType::InvalidTupleAccess => {
+                "Only non negative integer literals like 0, or 1_000 can be used."
+            }
+            ParseErrorType::LexError { error } => return error.hint(),
+            ParseErrorType::NoExpression => "Put an expression in there or remove the brackets.",
+            ParseErrorType::NoLetBinding => "Use let for binding",
+            ParseErrorType::OpNakedRight => "Remove it or put a value after it.",
+            ParseErrorType::TooManyArgHoles => "Function calls can have at most one argument hole.",
+            ParseErrorType::ListSpreadWithoutElements => {
+                "Try prepending some elements [1, 2, ..list]."
+            }
+            ParseErrorType::UnexpectedReservedWord => "I was expecting to see a name here.",
+            ParseErrorType::LowcaseBooleanPattern => "In Gleam boolean literals are True and False",
+            ParseErrorType::UnexpectedToken { hint, .. } => {
+                return hint.as_ref().map(|hint| hint.to_string())
+            }
+            ParseErrorType::UnknownAttribute => {
+                "The only supported attribute is @deprecated(\"message\")."
+            }
+            ParseErrorType::DuplicateAttribute => "Remove the duplicate attribute.",
+            ParseErrorType::UnexpectedAttribute => {
+                "Only functions, external functions, types, type aliases
+and constants can be deprecated."
+            }
+            ParseErrorType::ExpectedEqual
+            | ParseErrorType::ExpectedExpr
+            | ParseErrorType::ExpectedName
+            | ParseErrorType::ExpectedPattern
+            | ParseErrorType::ExpectedType
+            | ParseErrorType::ExpectedUpName
+            | ParseErrorType::ExpectedValue
+            | ParseErrorType::InvalidTailPattern
+            | ParseErrorType::NestedBitStringPattern
+            | ParseErrorType::NoCaseClause
+            | ParseErrorType::NoConstructors
+            | ParseErrorType::NotConstType
+            | ParseErrorType::NoValueAfterEqual
+            | ParseErrorType::OpaqueTypeAlias
+            | ParseErrorType::UnexpectedEof
+            | ParseErrorType::UnexpectedLabel
+            | ParseErrorType::ExpectedBoolean
+            | ParseErrorType::UnexpectedFunction
+            | ParseErrorType::ConcatPatternVariableLeftHandSide
+            | ParseErrorType::ListSpreadWithoutTail => return None,
+        };
+        Some(hint.into())
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -248,6 +274,75 @@ pub enum ParseErrorType {
     // A variable was assigned or discarded on the left hand side of a <> pattern
     ConcatPatternVariableLeftHandSide,
     ListSpreadWithoutTail,
+    UnknownAttribute,    // an attribute other than @deprecated
+    DuplicateAttribute,  // the same attribute given twice for one definition
+    UnexpectedAttribute, // an attribute on an import or an external type
+}
+
+impl LexicalErrorType {
+    /// The stable code identifying this kind of error. See
+    /// `ParseErrorType::code`.
+    pub fn code(&self) -> &'static str {
+        match self {
+            LexicalErrorType::BadStringEscape => "E0050",
+            LexicalErrorType::DigitOutOfRadix => "E0051",
+            LexicalErrorType::NumTrailingUnderscore => "E0052",
+            LexicalErrorType::RadixIntNoValue => "E0053",
+            LexicalErrorType::UnexpectedStringEnd => "E0054",
+            LexicalErrorType::UnrecognizedToken { .. } => "E0055",
+            LexicalErrorType::BadName { .. } => "E0056",
+            LexicalErrorType::BadDiscardName { .. } => "E0057",
+            LexicalErrorType::BadUpname { .. } => "E0058",
+        }
+    }
+}
+
+impl ParseErrorType {
+    /// The code identifying this kind of error. See [`crate::explanation`]
+    /// for how codes are assigned.
+    pub fn code(&self) -> &'static str {
+        match self {
+            ParseErrorType::ExpectedEqual => "E0001",
+            ParseErrorType::ExpectedExpr => "E0002",
+            ParseErrorType::ExpectedName => "E0003",
+            ParseErrorType::ExpectedPattern => "E0004",
+            ParseErrorType::ExpectedType => "E0005",
+            ParseErrorType::ExpectedUpName => "E0006",
+            ParseErrorType::ExpectedValue => "E0007",
+            ParseErrorType::ExprLparStart => "E0008",
+            ParseErrorType::ExtraSeparator => "E0009",
+            ParseErrorType::IncorrectName => "E0010",
+            ParseErrorType::IncorrectUpName => "E0011",
+            ParseErrorType::InvalidBitStringSegment => "E0012",
+            ParseErrorType::InvalidBitStringUnit => "E0013",
+            ParseErrorType::InvalidTailPattern => "E0014",
+            ParseErrorType::InvalidTupleAccess => "E0015",
+            ParseErrorType::LexError { error } => error.error.code(),
+            ParseErrorType::NestedBitStringPattern => "E0017",
+            ParseErrorType::NoConstructors => "E0018",
+            ParseErrorType::NoCaseClause => "E0019",
+            ParseErrorType::NoExpression => "E0020",
+            ParseErrorType::NoLetBinding => "E0021",
+            ParseErrorType::NoValueAfterEqual => "E0022",
+            ParseErrorType::NotConstType => "E0023",
+            ParseErrorType::OpNakedRight => "E0024",
+            ParseErrorType::OpaqueTypeAlias => "E0025",
+            ParseErrorType::TooManyArgHoles => "E0026",
+            ParseErrorType::ListSpreadWithoutElements => "E0027",
+            ParseErrorType::LowcaseBooleanPattern => "E0028",
+            ParseErrorType::UnexpectedLabel => "E0029",
+            ParseErrorType::UnexpectedEof => "E0030",
+            ParseErrorType::UnexpectedReservedWord => "E0031",
+            ParseErrorType::UnexpectedToken { .. } => "E0032",
+            ParseErrorType::ExpectedBoolean => "E0033",
+            ParseErrorType::UnexpectedFunction => "E0034",
+            ParseErrorType::ConcatPatternVariableLeftHandSide => "E0035",
+            ParseErrorType::ListSpreadWithoutTail => "E0036",
+            ParseErrorType::UnknownAttribute => "E0037",
+            ParseErrorType::DuplicateAttribute => "E0038",
+            ParseErrorType::UnexpectedAttribute => "E0039",
+        }
+    }
 }
 
 impl LexicalError {
@@ -255,56 +350,63 @@ impl LexicalError {
         match &self.error {
             LexicalErrorType::BadStringEscape => (
                 "I don't understand this escape code",
-                vec![
-                    "Hint: Add another backslash before it.".into(),
-                    "See: https://gleam.run/book/tour/strings.html#escape-sequences".into(),
-                ],
+                vec!["See: https://gleam.run/book/tour/strings.html#escape-sequences".into()],
             ),
             LexicalErrorType::DigitOutOfRadix => {
                 ("This digit is too big for the specified radix.", vec![])
             }
-            LexicalErrorType::NumTrailingUnderscore => (
-                "Numbers cannot have a trailing underscore.",
-                vec!["Hint: remove it.".into()],
-            ),
+            LexicalErrorType::NumTrailingUnderscore => {
+                ("Numbers cannot have a trailing underscore.", vec![])
+            }
             LexicalErrorType::RadixIntNoValue => ("This integer has no value.", vec![]),
             LexicalErrorType::UnexpectedStringEnd => {
                 ("The string starting here was left open.", vec![])
             }
-            LexicalErrorType::UnrecognizedToken { tok } if *tok == ';' => (
-                "Remove this semicolon",
-                vec![
-                    "Hint: Semicolons used to be whitespace and did nothing.".into(),
-                    "You can safely remove them without your program changing.".into(),
-                ],
-            ),
-            LexicalErrorType::UnrecognizedToken { .. } => (
-                "I can't figure out what to do with this character.",
-                vec!["Hint: Is it a typo?".into()],
-            ),
+            LexicalErrorType::UnrecognizedToken { tok } if *tok == ';' => {
+                ("Remove this semicolon", vec![])
+            }
+            LexicalErrorType::UnrecognizedToken { .. } => {
+                ("I can't figure out what to do with this character.", vec![])
+            }
             LexicalErrorType::BadName { name } => (
                 "This is not a valid name.",
-                vec![
-                    "Hint: Names start with a lowercase letter and contain a-z, 0-9, or _."
-                        .to_string(),
-                    format!("Try: {}", name.to_snake_case()),
-                ],
+                vec![format!("Try: {}", name.to_snake_case())],
             ),
             LexicalErrorType::BadDiscardName { name } => (
                 "This is not a valid discard name.",
-                vec![
-                    "Hint: Discard names start with _ and contain a-z, 0-9, or _.".into(),
-                    format!("Try: _{}", name.to_snake_case()),
-                ],
+                vec![format!("Try: _{}", name.to_snake_case())],
             ),
             LexicalErrorType::BadUpname { name } => (
                 "This is not a valid upname.",
-                vec![
-                    "Hint: Upnames start with an uppercase letter and contain".into(),
-                    "only lowercase letters, numbers, and uppercase letters.".into(),
-                    format!("Try: {}", name.to_upper_camel_case()),
-                ],
+                vec![format!("Try: {}", name.to_upper_camel_case())],
             ),
         }
     }
+
+    /// A suggestion of how to fix the error, shown after its details.
+    pub fn hint(&self) -> Option<String> {
+        let hint = match &self.error {
+            LexicalErrorType::BadStringEscape => "Add another backslash before it.",
+            LexicalErrorType::NumTrailingUnderscore => "remove it.",
+            LexicalErrorType::UnrecognizedToken { tok } if *tok == ';' => {
+                "Semicolons used to be whitespace and did nothing.
+You can safely remove them without your program changing."
+            }
+            LexicalErrorType::UnrecognizedToken { .. } => "Is it a typo?",
+            LexicalErrorType::BadName { .. } => {
+                "Names start with a lowercase letter and contain a-z, 0-9, or _."
+            }
+            LexicalErrorType::BadDiscardName { .. } => {
+                "Discard names start with _ and contain a-z, 0-9, or _."
+            }
+            LexicalErrorType::BadUpname { .. } => {
+                "Upnames start with an uppercase letter and contain
+only lowercase letters, numbers, and uppercase letters."
+            }
+            LexicalErrorType::DigitOutOfRadix
+            | LexicalErrorType::RadixIntNoValue
+            | LexicalErrorType::UnexpectedStringEnd => return None,
+        };
+        Some(hint.into())
+    }
 }
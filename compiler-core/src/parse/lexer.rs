@@ -416,6 +416,9 @@ where
                     self.emit((tok_start, Token::Dot, tok_end));
                 }
             }
+            '@' => {
+                self.eat_single_char(Token::At);
+            }
             '#' => {
                 self.eat_single_char(Token::Hash);
             }
@@ -1,6 +1,13 @@
-use crate::ast::SrcSpan;
+use crate::ast::{
+    CustomType, Deprecation, ExternalFunction, ExternalType, Function, Import, ModuleConstant,
+    ModuleStatement, SrcSpan, TypeAlias,
+};
+use crate::build::Target;
 use crate::parse::error::{LexicalError, LexicalErrorType, ParseError, ParseErrorType};
+use itertools::Itertools;
+use smol_str::SmolStr;
 use std::path::PathBuf;
+use vec1::vec1;
 
 use pretty_assertions::assert_eq;
 
@@ -27,7 +34,7 @@ pub fn expect_error(src: &str) -> String {
     let error = crate::error::Error::Parse {
         src: src.into(),
         path: PathBuf::from("/src/parse/error.gleam"),
-        error: result,
+        errors: vec1![result],
     };
     error.pretty_string()
 }
@@ -408,3 +415,187 @@ a
 "
     );
 }
+
+/// The names of the statements and the locations of the errors of a module
+/// parsed with error recovery.
+fn parse_recovering(src: &str) -> (Vec<SmolStr>, Vec<SrcSpan>) {
+    let (module, _, errors) = crate::parse::parse_module_recovering(src);
+    let names = module
+        .iter_statements(Target::Erlang)
+        .map(|statement| match statement {
+            ModuleStatement::Function(Function { name, .. })
+            | ModuleStatement::ModuleConstant(ModuleConstant { name, .. })
+            | ModuleStatement::CustomType(CustomType { name, .. })
+            | ModuleStatement::TypeAlias(TypeAlias { alias: name, .. }) => name.clone(),
+            ModuleStatement::Import(Import { module, .. }) => module.clone(),
+            ModuleStatement::ExternalFunction(ExternalFunction { name, .. })
+            | ModuleStatement::ExternalType(ExternalType { name, .. }) => name.clone(),
+        })
+        .collect();
+    let errors = errors.into_iter().map(|error| error.location).collect();
+    (names, errors)
+}
+
+#[test]
+fn recovering_keeps_statements_around_errors() {
+    let src = "import gleam/io
+
+pub fn one() {
+  1 +
+}
+
+pub fn two() { 2 }
+
+fn three( {
+  3
+}
+
+const four = 4
+";
+    assert_eq!(
+        parse_recovering(src),
+        (
+            vec!["gleam/io".into(), "two".into(), "four".into()],
+            vec![SrcSpan::new(36, 37), SrcSpan::new(71, 72)]
+        )
+    );
+}
+
+#[test]
+fn recovering_resumes_at_the_next_statement() {
+    // Parsing resumes at the next keyword that starts a statement, even when
+    // it is on the same line as the closing brace of the definition with the
+    // error.
+    let src = "pub fn one() { 1 + } pub type Two { Two }
+fn three() { 3 } }
+const four = 4";
+    assert_eq!(
+        parse_recovering(src),
+        (
+            vec!["Two".into(), "three".into(), "four".into()],
+            vec![SrcSpan::new(17, 18), SrcSpan::new(59, 60)]
+        )
+    );
+}
+
+#[test]
+fn recovering_resumes_after_an_indented_closing_brace() {
+    let src = "  pub fn one() {
+    1 +
+  } 2
+
+  pub fn two() { 2 }
+";
+    assert_eq!(
+        parse_recovering(src),
+        (
+            vec!["two".into()],
+            vec![SrcSpan::new(23, 24), SrcSpan::new(29, 30)]
+        )
+    );
+}
+
+#[test]
+fn recovering_skips_nested_blocks() {
+    // The closing brace of the inner block is not the end of the function,
+    // even though it is in the first column.
+    let src = "pub fn one() {
+  let x = {
+  1 +
+}
+  x
+}
+
+pub fn two() { 2 }
+";
+    assert_eq!(
+        parse_recovering(src),
+        (vec!["two".into()], vec![SrcSpan::new(31, 32)])
+    );
+}
+
+#[test]
+fn recovering_reports_lex_errors() {
+    let src = "pub fn one() { 1 }
+
+pub fn two() { 0b012 }
+
+if erlang {
+  pub fn three() { 3 }
+}
+";
+    let (names, errors) = parse_recovering(src);
+    assert_eq!(names, vec![SmolStr::from("one"), "three".into()]);
+    assert_eq!(errors, vec![SrcSpan::new(39, 39)]);
+}
+
+#[test]
+fn recovering_without_errors_matches_parsing() {
+    let src = "import gleam/io
+
+pub type Pair(a) {
+  Pair(a, a)
+}
+
+pub fn main() {
+  io.println(\"Hello\")
+}
+";
+    let (module, _, errors) = crate::parse::parse_module_recovering(src);
+    let (expected, _) = crate::parse::parse_module(src).expect("should parse");
+    assert_eq!(errors, vec![]);
+    assert_eq!(format!("{module:?}"), format!("{expected:?}"));
+}
+
+#[test]
+fn deprecated_attribute() {
+    let src = "@deprecated(\"Use new_function instead\")
+pub fn old_function() { 1 }
+";
+    let (module, _) = crate::parse::parse_module(src).expect("should parse");
+    let statements = module.iter_statements(Target::Erlang).collect_vec();
+    match statements.as_slice() {
+        [ModuleStatement::Function(Function { deprecation, .. })] => assert_eq!(
+            deprecation,
+            &Deprecation::Deprecated {
+                message: "Use new_function instead".into()
+            }
+        ),
+        statements => panic!("unexpected statements {statements:?}"),
+    }
+}
+
+#[test]
+fn unknown_attribute() {
+    assert_eq!(
+        crate::parse::parse_module("@target(erlang)\npub fn main() { 1 }")
+            .expect_err("should not parse"),
+        ParseError {
+            error: ParseErrorType::UnknownAttribute,
+            location: SrcSpan { start: 0, end: 7 },
+        }
+    );
+}
+
+#[test]
+fn duplicate_deprecated_attribute() {
+    assert_eq!(
+        crate::parse::parse_module("@deprecated(\"a\")\n@deprecated(\"b\")\npub fn main() { 1 }")
+            .expect_err("should not parse"),
+        ParseError {
+            error: ParseErrorType::DuplicateAttribute,
+            location: SrcSpan { start: 17, end: 33 },
+        }
+    );
+}
+
+#[test]
+fn deprecated_attribute_on_import() {
+    assert_eq!(
+        crate::parse::parse_module("@deprecated(\"a\")\nimport one").expect_err("should not parse"),
+        ParseError {
+            error: ParseErrorType::UnexpectedAttribute,
+            location: SrcSpan { start: 0, end: 16 },
+        }
+    );
+}
@@ -43,6 +43,7 @@ pub enum Token {
     Colon,
     Comma,
     Hash, // '#'
+    At,   // '@'
     Bang, // '!'
     Equal,
     EqualEqual, // '=='
@@ -138,6 +139,7 @@ impl fmt::Display for Token {
             Token::Colon => ":",
             Token::Comma => ",",
             Token::Hash => "#",
+            Token::At => "@",
             Token::Bang => "!",
             Token::Equal => "=",
             Token::EqualEqual => "==",
@@ -46,6 +46,23 @@ impl ProjectPaths {
         self.root.join("build")
     }
 
+    /// Where a previous release of the package is compiled so that its API
+    /// can be compared with the current one.
+    pub fn build_api_diff_package(&self, package_name: &str, version: &str) -> PathBuf {
+        self.build_directory()
+            .join("api-diff")
+            .join(format!("{package_name}-{version}"))
+    }
+
+    pub fn vendor_directory(&self) -> PathBuf {
+        self.root.join("vendor")
+    }
+
+    pub fn vendor_package_tarball(&self, package_name: &str, version: &str) -> PathBuf {
+        self.vendor_directory()
+            .join(format!("{package_name}-{version}.tar"))
+    }
+
     pub fn build_packages_directory(&self) -> PathBuf {
         self.build_directory().join("packages")
     }
@@ -109,14 +126,18 @@ impl ProjectPaths {
     }
 }
 
-pub fn global_package_cache_package_tarball(package_name: &str, version: &str) -> PathBuf {
-    global_packages_cache().join(format!("{package_name}-{version}.tar"))
+pub fn global_package_cache_package_tarball(
+    repository: &str,
+    package_name: &str,
+    version: &str,
+) -> PathBuf {
+    global_packages_cache(repository).join(format!("{package_name}-{version}.tar"))
 }
 
-fn global_packages_cache() -> PathBuf {
+fn global_packages_cache(repository: &str) -> PathBuf {
     default_global_gleam_cache()
         .join("hex")
-        .join("hexpm")
+        .join(repository)
         .join("packages")
 }
 
@@ -126,6 +147,14 @@ pub fn default_global_gleam_cache() -> PathBuf {
         .join("gleam")
 }
 
+/// The user's global config file, which applies to every project.
+pub fn global_config() -> PathBuf {
+    dirs_next::config_dir()
+        .expect("Failed to determine user config directory")
+        .join("gleam")
+        .join("config.toml")
+}
+
 pub fn unnest(within: &Path) -> PathBuf {
     let mut path = PathBuf::new();
     for _ in within {
@@ -138,13 +167,32 @@ pub fn unnest(within: &Path) -> PathBuf {
 fn paths() {
     assert!(default_global_gleam_cache().ends_with("gleam"));
 
-    assert!(global_packages_cache().ends_with("hex/hexpm/packages"));
+    assert!(global_packages_cache("hexpm").ends_with("hex/hexpm/packages"));
 
     assert!(
-        global_package_cache_package_tarball("gleam_stdlib", "0.17.1")
+        global_package_cache_package_tarball("hexpm", "gleam_stdlib", "0.17.1")
             .ends_with("hex/hexpm/packages/gleam_stdlib-0.17.1.tar")
     );
 
-    assert!(global_package_cache_package_tarball("elli", "1.0.0")
-        .ends_with("hex/hexpm/packages/elli-1.0.0.tar"));
+    assert!(
+        global_package_cache_package_tarball("hexpm", "elli", "1.0.0")
+            .ends_with("hex/hexpm/packages/elli-1.0.0.tar")
+    );
+
+    assert!(
+        global_package_cache_package_tarball("company", "internal", "1.0.0")
+            .ends_with("hex/company/packages/internal-1.0.0.tar")
+    );
+
+    assert!(global_config().ends_with("gleam/config.toml"));
+
+    assert_eq!(
+        ProjectPaths::new("/app".into()).vendor_package_tarball("elli", "1.0.0"),
+        PathBuf::from("/app/vendor/elli-1.0.0.tar")
+    );
+
+    assert_eq!(
+        ProjectPaths::new("/app".into()).build_api_diff_package("app", "1.0.0"),
+        PathBuf::from("/app/build/api-diff/app-1.0.0")
+    );
 }
@@ -0,0 +1,296 @@
+use std::fmt;
+use std::path::PathBuf;
+
+use hexpm::version::Range;
+use serde::de::Error as _;
+use serde::ser::SerializeMap;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// A dependency as specified in the `dependencies` or `dev-dependencies`
+/// section of a `gleam.toml` file.
+///
+/// Hex dependencies are written as a version requirement string, while path
+/// and git dependencies are written as tables:
+///
+/// ```toml
+/// gleam_stdlib = "~> 0.28"
+/// internal = { version = "~> 1.0", repository = "company" }
+/// shared = { path = "../shared" }
+/// other = { git = "https://example.com/other.git", ref = "v1.0.0" }
+/// ```
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Requirement {
+    Hex {
+        version: Range,
+        /// The name of the Hex repository the package is downloaded from,
+        /// if not the one of the package that requires it.
+        repository: Option<String>,
+    },
+    Path {
+        path: PathBuf,
+    },
+    Git {
+        git: String,
+        reference: String,
+    },
+}
+
+impl Requirement {
+    pub fn hex(range: &str) -> Requirement {
+        Requirement::Hex {
+            version: Range::new(range.into()),
+            repository: None,
+        }
+    }
+
+    /// Renders the requirement as an inline TOML value, in the same format it
+    /// is read from.
+    ///
+    pub fn to_toml(&self) -> String {
+        match self {
+            Requirement::Hex {
+                version,
+                repository: None,
+            } => format!("\"{version}\""),
+            Requirement::Hex {
+                version,
+                repository: Some(repository),
+            } => format!(
+                "{{ version = \"{version}\", repository = {} }}",
+                toml_string(repository)
+            ),
+            Requirement::Path { path } => {
+                format!("{{ path = {} }}", toml_string(&path.to_string_lossy()))
+            }
+            Requirement::Git { git, reference } => format!(
+                "{{ git = {}, ref = {} }}",
+                toml_string(git),
+                toml_string(reference)
+            ),
+        }
+    }
+}
+
+/// Renders a string as a TOML string, escaping any characters such as
+/// backslashes in Windows paths that cannot appear in it unescaped.
+///
+pub(crate) fn toml_string(value: &str) -> String {
+    toml::Value::String(value.into()).to_string()
+}
+
+impl fmt::Display for Requirement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Requirement::Hex {
+                version,
+                repository: None,
+            } => write!(f, "{version}"),
+            Requirement::Hex {
+                version,
+                repository: Some(repository),
+            } => write!(f, "{version} from {repository}"),
+            Requirement::Path { path } => write!(f, "path {}", path.to_string_lossy()),
+            Requirement::Git { git, reference } => write!(f, "git {git} at {reference}"),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RequirementSpec {
+    Version(String),
+    Table(RequirementTable),
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RequirementTable {
+    version: Option<String>,
+    repository: Option<String>,
+    path: Option<PathBuf>,
+    git: Option<String>,
+    #[serde(rename = "ref")]
+    reference: Option<String>,
+}
+
+impl<'de> Deserialize<'de> for Requirement {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let table = match RequirementSpec::deserialize(deserializer)? {
+            RequirementSpec::Version(version) => {
+                return Ok(Requirement::Hex {
+                    version: Range::new(version),
+                    repository: None,
+                })
+            }
+            RequirementSpec::Table(table) => table,
+        };
+
+        match table {
+            RequirementTable {
+                version: Some(version),
+                repository,
+                path: None,
+                git: None,
+                reference: None,
+            } => Ok(Requirement::Hex {
+                version: Range::new(version),
+                repository,
+            }),
+
+            RequirementTable {
+                version: None,
+                repository: None,
+                path: Some(path),
+                git: None,
+                reference: None,
+            } => Ok(Requirement::Path { path }),
+
+            RequirementTable {
+                version: None,
+                repository: None,
+                path: None,
+                git: Some(git),
+                reference: Some(reference),
+            } => Ok(Requirement::Git { git, reference }),
+
+            RequirementTable {
+                version: None,
+                repository: None,
+                path: None,
+                git: Some(_),
+                reference: None,
+            } => Err(D::Error::custom(
+                "git dependencies must specify the `ref` to use",
+            )),
+
+            _ => Err(D::Error::custom(
+                "a dependency must be a version requirement, a `path`, or a `git` repository",
+            )),
+        }
+    }
+}
+
+impl Serialize for Requirement {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Requirement::Hex {
+                version,
+                repository: None,
+            } => serializer.serialize_str(version.as_str()),
+            Requirement::Hex {
+                version,
+                repository: Some(repository),
+            } => {
+                let mut map = serializer.serialize_map(Some(2))?;
+                map.serialize_entry("version", version.as_str())?;
+                map.serialize_entry("repository", repository)?;
+                map.end()
+            }
+            Requirement::Path { path } => {
+                let mut map = serializer.serialize_map(Some(1))?;
+                map.serialize_entry("path", path)?;
+                map.end()
+            }
+            Requirement::Git { git, reference } => {
+                let mut map = serializer.serialize_map(Some(2))?;
+                map.serialize_entry("git", git)?;
+                map.serialize_entry("ref", reference)?;
+                map.end()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+fn parse(toml: &str) -> Result<std::collections::HashMap<String, Requirement>, toml::de::Error> {
+    toml::from_str(toml)
+}
+
+#[test]
+fn read_requirements() {
+    let requirements = parse(
+        r#"
+hex = "~> 1.0"
+hex_table = { version = ">= 2.0.0" }
+private = { version = "~> 0.3", repository = "company" }
+local = { path = "../local" }
+remote = { git = "https://example.com/remote.git", ref = "main" }
+"#,
+    )
+    .unwrap();
+    assert_eq!(
+        requirements,
+        [
+            ("hex".into(), Requirement::hex("~> 1.0")),
+            ("hex_table".into(), Requirement::hex(">= 2.0.0")),
+            (
+                "private".into(),
+                Requirement::Hex {
+                    version: Range::new("~> 0.3".into()),
+                    repository: Some("company".into())
+                }
+            ),
+            (
+                "local".into(),
+                Requirement::Path {
+                    path: "../local".into()
+                }
+            ),
+            (
+                "remote".into(),
+                Requirement::Git {
+                    git: "https://example.com/remote.git".into(),
+                    reference: "main".into()
+                }
+            ),
+        ]
+        .into()
+    );
+}
+
+#[test]
+fn git_requirement_without_ref() {
+    assert!(parse(r#"remote = { git = "https://example.com/remote.git" }"#).is_err());
+}
+
+#[test]
+fn requirement_with_path_and_git() {
+    assert!(parse(r#"both = { path = "../both", git = "https://example.com" }"#).is_err());
+}
+
+#[test]
+fn repository_without_version() {
+    assert!(parse(r#"private = { repository = "company" }"#).is_err());
+}
+
+#[test]
+fn requirement_to_toml_round_trip() {
+    let requirements = [
+        Requirement::hex("~> 1.0"),
+        Requirement::Hex {
+            version: Range::new("~> 0.3".into()),
+            repository: Some("company".into()),
+        },
+        Requirement::Path {
+            path: "../local".into(),
+        },
+        Requirement::Git {
+            git: "https://example.com/remote.git".into(),
+            reference: "v1.0.0".into(),
+        },
+    ];
+    for requirement in requirements {
+        let toml = format!("package = {}", requirement.to_toml());
+        assert_eq!(
+            parse(&toml).unwrap(),
+            [("package".into(), requirement)].into()
+        );
+    }
+}
@@ -0,0 +1,260 @@
+//! Discovering the tests of a project, generating the code that runs them,
+//! and reading back the results that code reports.
+//!
+//! A test is any public function with no arguments whose name ends in
+//! `_test`, defined in a module in the `test` directory. The generated runner
+//! calls each test in turn and prints one result line per test to standard
+//! output, prefixed with `RESULT_MARKER` so that they can be told apart from
+//! anything the tests print themselves.
+
+#[cfg(test)]
+mod tests;
+
+use std::{fmt::Write, time::Duration};
+
+use askama::Template;
+use itertools::Itertools;
+use smol_str::SmolStr;
+
+use crate::type_::{self, ValueConstructor, ValueConstructorVariant};
+
+/// Printed by the generated runners before each test result.
+pub const RESULT_MARKER: char = '\u{1e}';
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct TestFunction {
+    pub module: SmolStr,
+    pub name: SmolStr,
+}
+
+impl TestFunction {
+    /// The name used to refer to the test in reports and filters, for
+    /// example `my_app/parser_test.empty_input_test`.
+    pub fn full_name(&self) -> String {
+        format!("{}.{}", self.module, self.name)
+    }
+}
+
+/// The test functions defined in the given module, sorted by name.
+pub fn module_test_functions(module: &type_::Module) -> Vec<TestFunction> {
+    module
+        .values
+        .iter()
+        .filter(|(name, value)| name.ends_with("_test") && is_test_function(value))
+        .map(|(name, _)| TestFunction {
+            module: module.name.clone(),
+            name: name.clone(),
+        })
+        .sorted()
+        .collect()
+}
+
+fn is_test_function(value: &ValueConstructor) -> bool {
+    value.public
+        && matches!(
+            value.variant,
+            ValueConstructorVariant::ModuleFn { arity: 0, .. }
+        )
+}
+
+/// Keep only the tests whose full name contains the given pattern.
+pub fn filter(tests: Vec<TestFunction>, pattern: Option<&str>) -> Vec<TestFunction> {
+    match pattern {
+        None => tests,
+        Some(pattern) => tests
+            .into_iter()
+            .filter(|test| test.full_name().contains(pattern))
+            .collect(),
+    }
+}
+
+/// The Erlang expression to evaluate to run the given tests, using the
+/// `{package}@@test` module compiled alongside the package.
+pub fn erlang_runner_expression(package: &str, tests: &[TestFunction]) -> String {
+    let tests = tests
+        .iter()
+        .map(|test| format!("{{'{}', '{}'}}", test.module.replace('/', "@"), test.name))
+        .join(", ");
+    format!("'{package}@@test':run([{tests}])")
+}
+
+/// The source of a JavaScript module that runs the given tests. It is to be
+/// written to the root of the package's JavaScript build directory.
+pub fn javascript_runner_module(tests: &[TestFunction]) -> String {
+    JavaScriptTestRunner { tests }
+        .render()
+        .expect("JavaScript test runner rendering")
+}
+
+#[derive(Template)]
+#[template(path = "gleam@@test.mjs", escape = "none")]
+struct JavaScriptTestRunner<'a> {
+    tests: &'a [TestFunction],
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TestOutcome {
+    Passed,
+    /// The test made an assertion that did not hold.
+    Failed {
+        message: String,
+    },
+    /// The test raised some other exception.
+    Crashed {
+        message: String,
+    },
+}
+
+impl TestOutcome {
+    pub fn is_passed(&self) -> bool {
+        matches!(self, Self::Passed)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TestResult {
+    pub test: TestFunction,
+    pub outcome: TestOutcome,
+    pub duration: Duration,
+}
+
+/// Split a line printed by a test runner into any output printed by the test
+/// before the result, and the result itself if the line contains one.
+pub fn parse_output_line(line: &str) -> (&str, Option<TestResult>) {
+    let (output, result) = match line.split_once(RESULT_MARKER) {
+        Some(split) => split,
+        None => return (line, None),
+    };
+    let mut fields = result.splitn(5, '\t');
+    let mut next = || fields.next().unwrap_or_default();
+    let (status, module, name, duration, message) = (next(), next(), next(), next(), next());
+
+    let message = unescape(message);
+    let outcome = match status {
+        "passed" => TestOutcome::Passed,
+        "failed" => TestOutcome::Failed { message },
+        "crashed" => TestOutcome::Crashed { message },
+        _ => return (line, None),
+    };
+    let duration = match duration.parse() {
+        Ok(microseconds) => Duration::from_micros(microseconds),
+        Err(_) => return (line, None),
+    };
+    let test = TestFunction {
+        // The Erlang runner reports the name of the Erlang module
+        module: module.replace('@', "/").into(),
+        name: name.into(),
+    };
+    let result = TestResult {
+        test,
+        outcome,
+        duration,
+    };
+    (output, Some(result))
+}
+
+fn unescape(message: &str) -> String {
+    let mut unescaped = String::with_capacity(message.len());
+    let mut chars = message.chars();
+    while let Some(char) = chars.next() {
+        if char != '\\' {
+            unescaped.push(char);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => unescaped.push('\n'),
+            Some('t') => unescaped.push('\t'),
+            Some(other) => unescaped.push(other),
+            None => unescaped.push('\\'),
+        }
+    }
+    unescaped
+}
+
+/// Render the results of a test run as a `JUnit` XML report, with one test
+/// suite per module. Failed tests are reported as failures and crashed tests
+/// as errors.
+pub fn junit_xml(package: &str, results: &[TestResult]) -> String {
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let summary = summary_attributes(results);
+    writeln!(
+        xml,
+        r#"<testsuites name="{}" {summary}>"#,
+        escape_xml(package)
+    )
+    .expect("JUnit XML writing");
+
+    let suites = results.iter().group_by(|result| &result.test.module);
+    for (module, results) in &suites {
+        let results = results.collect_vec();
+        let summary = summary_attributes(results.iter().copied());
+        writeln!(
+            xml,
+            r#"  <testsuite name="{}" {summary}>"#,
+            escape_xml(module)
+        )
+        .expect("JUnit XML writing");
+
+        for result in results {
+            let attributes = format!(
+                r#"name="{}" classname="{}" time="{}""#,
+                escape_xml(&result.test.name),
+                escape_xml(module),
+                junit_seconds(result.duration)
+            );
+            let (tag, message) = match &result.outcome {
+                TestOutcome::Passed => {
+                    writeln!(xml, "    <testcase {attributes}/>").expect("JUnit XML writing");
+                    continue;
+                }
+                TestOutcome::Failed { message } => ("failure", message),
+                TestOutcome::Crashed { message } => ("error", message),
+            };
+            let summary = message.lines().next().unwrap_or_default().trim();
+            writeln!(
+                xml,
+                r#"    <testcase {attributes}>
+      <{tag} message="{}">{}</{tag}>
+    </testcase>"#,
+                escape_xml(summary),
+                escape_xml(message)
+            )
+            .expect("JUnit XML writing");
+        }
+
+        xml.push_str("  </testsuite>\n");
+    }
+
+    xml.push_str("</testsuites>\n");
+    xml
+}
+
+fn summary_attributes<'a>(results: impl IntoIterator<Item = &'a TestResult>) -> String {
+    let (mut tests, mut failures, mut errors) = (0, 0, 0);
+    let mut time = Duration::ZERO;
+    for result in results {
+        tests += 1;
+        time += result.duration;
+        match result.outcome {
+            TestOutcome::Passed => (),
+            TestOutcome::Failed { .. } => failures += 1,
+            TestOutcome::Crashed { .. } => errors += 1,
+        }
+    }
+    format!(
+        r#"tests="{tests}" failures="{failures}" errors="{errors}" time="{}""#,
+        junit_seconds(time)
+    )
+}
+
+fn junit_seconds(duration: Duration) -> String {
+    format!("{:.6}", duration.as_secs_f64())
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
@@ -0,0 +1,173 @@
+use super::*;
+
+fn test(module: &str, name: &str) -> TestFunction {
+    TestFunction {
+        module: module.into(),
+        name: name.into(),
+    }
+}
+
+fn test_functions(src: &str) -> Vec<TestFunction> {
+    let mut module = type_::tests::compile_module(src, None, vec![])
+        .expect("should compile")
+        .type_info;
+    module.name = "my_app/parser_test".into();
+    module_test_functions(&module)
+}
+
+#[test]
+fn public_zero_arity_functions_ending_in_test_are_tests() {
+    let tests = test_functions(
+        r#"
+pub fn second_test() { Nil }
+pub fn first_test() { Nil }
+pub fn helper() { Nil }
+fn private_test() { Nil }
+pub fn with_argument_test(x) { x }
+pub const constant_test = 1
+"#,
+    );
+    assert_eq!(
+        tests,
+        vec![
+            test("my_app/parser_test", "first_test"),
+            test("my_app/parser_test", "second_test"),
+        ]
+    );
+}
+
+#[test]
+fn external_functions_can_be_tests() {
+    let tests = test_functions(r#"pub external fn ffi_test() -> Nil = "my_ffi" "run""#);
+    assert_eq!(tests, vec![test("my_app/parser_test", "ffi_test")]);
+}
+
+#[test]
+fn filter_by_full_name() {
+    let tests = vec![
+        test("my_app/parser_test", "empty_test"),
+        test("my_app/lexer_test", "empty_test"),
+        test("my_app/lexer_test", "string_test"),
+    ];
+    assert_eq!(filter(tests.clone(), None), tests);
+    assert_eq!(
+        filter(tests.clone(), Some("lexer")),
+        vec![
+            test("my_app/lexer_test", "empty_test"),
+            test("my_app/lexer_test", "string_test"),
+        ]
+    );
+    assert_eq!(
+        filter(tests.clone(), Some("parser_test.empty")),
+        vec![test("my_app/parser_test", "empty_test")]
+    );
+    assert_eq!(filter(tests, Some("nothing")), vec![]);
+}
+
+#[test]
+fn erlang_runner_expression_test() {
+    assert_eq!(
+        erlang_runner_expression(
+            "my_app",
+            &[
+                test("my_app_test", "one_test"),
+                test("my_app/parser_test", "two_test")
+            ]
+        ),
+        "'my_app@@test':run([{'my_app_test', 'one_test'}, {'my_app@parser_test', 'two_test'}])"
+    );
+}
+
+#[test]
+fn javascript_runner_module_test() {
+    insta::assert_snapshot!(javascript_runner_module(&[
+        test("my_app_test", "one_test"),
+        test("my_app/parser_test", "two_test")
+    ]));
+}
+
+#[test]
+fn parse_passed_result() {
+    assert_eq!(
+        parse_output_line("\u{1e}passed\tmy_app@parser_test\tone_test\t1500\t"),
+        (
+            "",
+            Some(TestResult {
+                test: test("my_app/parser_test", "one_test"),
+                outcome: TestOutcome::Passed,
+                duration: Duration::from_micros(1500),
+            })
+        )
+    );
+}
+
+#[test]
+fn parse_failed_result_with_output_before_it() {
+    assert_eq!(
+        parse_output_line(
+            "Hello!\u{1e}failed\tmy_app_test\tone_test\t20\terror: oh no\\n  at\\tline 1 \\\\o/"
+        ),
+        (
+            "Hello!",
+            Some(TestResult {
+                test: test("my_app_test", "one_test"),
+                outcome: TestOutcome::Failed {
+                    message: "error: oh no\n  at\tline 1 \\o/".into()
+                },
+                duration: Duration::from_micros(20),
+            })
+        )
+    );
+}
+
+#[test]
+fn parse_crashed_result() {
+    assert_eq!(
+        parse_output_line("\u{1e}crashed\tmy_app_test\tone_test\t0\tbadarith").1,
+        Some(TestResult {
+            test: test("my_app_test", "one_test"),
+            outcome: TestOutcome::Crashed {
+                message: "badarith".into()
+            },
+            duration: Duration::ZERO,
+        })
+    );
+}
+
+#[test]
+fn parse_line_without_result() {
+    assert_eq!(
+        parse_output_line("Just some output"),
+        ("Just some output", None)
+    );
+    assert_eq!(
+        parse_output_line("\u{1e}unknown\tmy_app_test\tone_test\t0\t"),
+        ("\u{1e}unknown\tmy_app_test\tone_test\t0\t", None)
+    );
+}
+
+#[test]
+fn junit_xml_report() {
+    let results = vec![
+        TestResult {
+            test: test("my_app/parser_test", "one_test"),
+            outcome: TestOutcome::Passed,
+            duration: Duration::from_micros(1500),
+        },
+        TestResult {
+            test: test("my_app/parser_test", "two_test"),
+            outcome: TestOutcome::Failed {
+                message: "error: #{gleam_error => assert}\n  in <two_test>".into(),
+            },
+            duration: Duration::from_micros(20),
+        },
+        TestResult {
+            test: test("my_app_test", "three_test"),
+            outcome: TestOutcome::Crashed {
+                message: "error: badarith".into(),
+            },
+            duration: Duration::from_millis(2),
+        },
+    ];
+    insta::assert_snapshot!(junit_xml("my_app", &results));
+}
@@ -1,5 +1,6 @@
 pub(crate) mod environment;
 pub(crate) mod error;
+pub(crate) mod exhaustiveness;
 pub(crate) mod expression;
 pub(crate) mod fields;
 pub(crate) mod hydrator;
@@ -8,7 +9,7 @@ pub(crate) mod pipe;
 pub(crate) mod prelude;
 pub mod pretty;
 #[cfg(test)]
-mod tests;
+pub(crate) mod tests;
 
 pub use environment::*;
 pub use error::{Error, UnifyErrorSituation, Warning};
@@ -19,9 +20,9 @@ use smol_str::SmolStr;
 
 use crate::{
     ast::{
-        ArgNames, BitStringSegment, CallArg, Constant, DefinitionLocation, Pattern, SrcSpan,
-        TypedConstant, TypedExpr, TypedPattern, TypedPatternBitStringSegment, TypedRecordUpdateArg,
-        UntypedMultiPattern, UntypedPattern, UntypedRecordUpdateArg,
+        ArgNames, BitStringSegment, CallArg, Constant, DefinitionLocation, Deprecation, Pattern,
+        SrcSpan, TypedConstant, TypedExpr, TypedPattern, TypedPatternBitStringSegment,
+        TypedRecordUpdateArg, UntypedMultiPattern, UntypedPattern, UntypedRecordUpdateArg,
     },
     bit_string,
     build::Origin,
@@ -302,6 +303,7 @@ impl ValueConstructorVariant {
         type_: Arc<Type>,
         module_name: &SmolStr,
         function_name: &SmolStr,
+        deprecation: &Deprecation,
     ) -> ModuleValueConstructor {
         match self {
             Self::Record {
@@ -318,6 +320,7 @@ impl ValueConstructorVariant {
                 type_,
                 location: *location,
                 documentation: documentation.clone(),
+                deprecation: deprecation.clone(),
             },
 
             // TODO: remove this clone with an rc clone
@@ -330,12 +333,14 @@ impl ValueConstructorVariant {
                 literal: literal.clone(),
                 location: *location,
                 documentation: documentation.clone(),
+                deprecation: deprecation.clone(),
             },
 
             Self::LocalVariable { location, .. } => ModuleValueConstructor::Fn {
                 name: function_name.clone(),
                 module: module_name.clone(),
                 documentation: None,
+                deprecation: deprecation.clone(),
                 location: *location,
             },
 
@@ -349,6 +354,7 @@ impl ValueConstructorVariant {
                 name: name.clone(),
                 module: module.clone(),
                 documentation: documentation.clone(),
+                deprecation: deprecation.clone(),
                 location: *location,
             },
         }
@@ -386,6 +392,7 @@ pub enum ModuleValueConstructor {
         field_map: Option<FieldMap>,
         location: SrcSpan,
         documentation: Option<SmolStr>,
+        deprecation: Deprecation,
     },
 
     Fn {
@@ -405,12 +412,14 @@ pub enum ModuleValueConstructor {
         module: SmolStr,
         name: SmolStr,
         documentation: Option<SmolStr>,
+        deprecation: Deprecation,
     },
 
     Constant {
         literal: TypedConstant,
         location: SrcSpan,
         documentation: Option<SmolStr>,
+        deprecation: Deprecation,
     },
 }
 
@@ -430,6 +439,14 @@ impl ModuleValueConstructor {
             | ModuleValueConstructor::Constant { documentation, .. } => documentation.as_deref(),
         }
     }
+
+    pub fn get_deprecation_message(&self) -> Option<&str> {
+        match self {
+            ModuleValueConstructor::Record { deprecation, .. }
+            | ModuleValueConstructor::Fn { deprecation, .. }
+            | ModuleValueConstructor::Constant { deprecation, .. } => deprecation.message(),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -579,11 +596,13 @@ pub struct TypeConstructor {
     pub module: SmolStr,
     pub parameters: Vec<Arc<Type>>,
     pub typ: Arc<Type>,
+    pub deprecation: Deprecation,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ValueConstructor {
     pub public: bool,
+    pub deprecation: Deprecation,
     pub variant: ValueConstructorVariant,
     pub type_: Arc<Type>,
 }
@@ -624,6 +643,10 @@ impl ValueConstructor {
             }
         }
     }
+
+    pub fn get_deprecation_message(&self) -> Option<&str> {
+        self.deprecation.message()
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -1,5 +1,7 @@
 use crate::{
-    analyse::Inferred, ast::PIPE_VARIABLE, uid::UniqueIdGenerator, warning::TypeWarningEmitter,
+    ast::{Layer, PIPE_VARIABLE},
+    uid::UniqueIdGenerator,
+    warning::TypeWarningEmitter,
 };
 
 use super::*;
@@ -161,6 +163,7 @@ impl<'a> Environment<'a> {
             name,
             ValueConstructor {
                 public: false,
+                deprecation: Deprecation::NotDeprecated,
                 variant: ValueConstructorVariant::LocalVariable { location },
                 type_: typ,
             },
@@ -180,12 +183,21 @@ impl<'a> Environment<'a> {
             name,
             ValueConstructor {
                 public,
+                deprecation: Deprecation::NotDeprecated,
                 variant,
                 type_: typ,
             },
         );
     }
 
+    /// Insert a value imported from another module in the current scope.
+    /// Unlike the module's own definitions it keeps its deprecation, so that
+    /// each use of a deprecated value can be warned about.
+    ///
+    pub fn insert_imported_variable(&mut self, name: SmolStr, value: ValueConstructor) {
+        let _ = self.scope.insert(name, value);
+    }
+
     /// Insert a value into the current module.
     /// Errors if the module already has a value with that name.
     ///
@@ -517,6 +529,19 @@ impl<'a> Environment<'a> {
         }
     }
 
+    /// Emit a warning if a value or type that is used at the given location
+    /// has been deprecated.
+    ///
+    pub fn warn_if_deprecated(&self, deprecation: &Deprecation, layer: Layer, location: SrcSpan) {
+        if let Deprecation::Deprecated { message } = deprecation {
+            self.warnings.emit(Warning::DeprecatedItem {
+                location,
+                message: message.clone(),
+                layer,
+            });
+        }
+    }
+
     pub fn local_value_names(&self) -> Vec<SmolStr> {
         self.scope
             .keys()
@@ -524,69 +549,6 @@ impl<'a> Environment<'a> {
             .cloned()
             .collect()
     }
-
-    /// Checks that the given patterns are exhaustive for given type.
-    /// Currently only performs exhaustiveness checking for custom types,
-    /// only at the top level (without recursing into constructor arguments).
-    pub fn check_exhaustiveness(
-        &mut self,
-        patterns: Vec<Pattern<Arc<Type>>>,
-        value_typ: Arc<Type>,
-    ) -> Result<(), Vec<SmolStr>> {
-        match &*value_typ {
-            Type::App {
-                name: type_name,
-                module: module_name,
-                ..
-            } => {
-                let m = if module_name.is_empty() || module_name == self.current_module {
-                    None
-                } else {
-                    Some(module_name.as_str())
-                };
-
-                if let Ok(constructors) = self.get_constructors_for_type(m, type_name) {
-                    let mut unmatched_constructors: HashSet<SmolStr> =
-                        constructors.iter().cloned().collect();
-
-                    for p in &patterns {
-                        // ignore Assign patterns
-                        let mut pattern = p;
-                        while let Pattern::Assign {
-                            pattern: assign_pattern,
-                            ..
-                        } = pattern
-                        {
-                            pattern = assign_pattern;
-                        }
-
-                        match pattern {
-                            // If the pattern is a Discard or Var, all constructors are covered by it
-                            Pattern::Discard { .. } => return Ok(()),
-                            Pattern::Var { .. } => return Ok(()),
-
-                            // If the pattern is a constructor, remove it from unmatched patterns
-                            Pattern::Constructor {
-                                constructor:
-                                    Inferred::Known(PatternConstructor::Record { name, .. }),
-                                ..
-                            } => {
-                                let _ = unmatched_constructors.remove(name);
-                            }
-
-                            _ => return Ok(()),
-                        }
-                    }
-
-                    if !unmatched_constructors.is_empty() {
-                        return Err(unmatched_constructors.into_iter().sorted().collect());
-                    }
-                }
-                Ok(())
-            }
-            _ => Ok(()),
-        }
-    }
 }
 
 /// Unify two types that should be the same.
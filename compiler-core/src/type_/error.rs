@@ -1,5 +1,5 @@
 use crate::{
-    ast::{BinOp, SrcSpan, TodoKind},
+    ast::{BinOp, Layer, SrcSpan, TodoKind},
     type_::Type,
 };
 
@@ -324,6 +324,21 @@ pub enum Warning {
     UnnecessaryDoubleBoolNegation {
         location: SrcSpan,
     },
+
+    /// A case clause pattern that can never match as the clauses before it
+    /// match all the values it would.
+    UnreachableCaseClause {
+        location: SrcSpan,
+        shadowed_by: SrcSpan,
+    },
+
+    /// A value or type marked with `@deprecated` that is used from another
+    /// module.
+    DeprecatedItem {
+        location: SrcSpan,
+        message: SmolStr,
+        layer: Layer,
+    },
 }
 
 impl Error {
@@ -351,9 +366,138 @@ impl Error {
             _ => self,
         }
     }
+
+    /// Where in the module the error is reported, if anywhere.
+    pub fn location(&self) -> Option<SrcSpan> {
+        match self {
+            Error::SrcImportingTest { location, .. }
+            | Error::BitStringSegmentError { location, .. }
+            | Error::UnknownVariable { location, .. }
+            | Error::UnknownType { location, .. }
+            | Error::UnknownModule { location, .. }
+            | Error::UnknownModuleType { location, .. }
+            | Error::UnknownModuleValue { location, .. }
+            | Error::UnknownModuleField { location, .. }
+            | Error::NotFn { location, .. }
+            | Error::UnknownRecordField { location, .. }
+            | Error::IncorrectArity { location, .. }
+            | Error::UpdateMultiConstructorType { location, .. }
+            | Error::UnnecessarySpreadOperator { location, .. }
+            | Error::IncorrectTypeArity { location, .. }
+            | Error::CouldNotUnify { location, .. }
+            | Error::RecursiveType { location, .. }
+            | Error::DuplicateImport { location, .. }
+            | Error::DuplicateTypeName { location, .. }
+            | Error::DuplicateArgument { location, .. }
+            | Error::DuplicateField { location, .. }
+            | Error::PrivateTypeLeak { location, .. }
+            | Error::UnexpectedLabelledArg { location, .. }
+            | Error::PositionalArgumentAfterLabelled { location, .. }
+            | Error::IncorrectNumClausePatterns { location, .. }
+            | Error::NonLocalClauseGuardVariable { location, .. }
+            | Error::ExtraVarInAlternativePattern { location, .. }
+            | Error::MissingVarInAlternativePattern { location, .. }
+            | Error::DuplicateVarInPattern { location, .. }
+            | Error::OutOfBoundsTupleIndex { location, .. }
+            | Error::NotATuple { location, .. }
+            | Error::NotATupleUnbound { location, .. }
+            | Error::RecordAccessUnknownType { location, .. }
+            | Error::RecordUpdateInvalidConstructor { location, .. }
+            | Error::UnexpectedTypeHole { location, .. }
+            | Error::NotExhaustivePatternMatch { location, .. }
+            | Error::ArgumentNameAlreadyUsed { location, .. }
+            | Error::UnlabelledAfterlabelled { location, .. } => Some(*location),
+
+            // The later definition is the one reported as the duplicate.
+            Error::DuplicateName {
+                location_a,
+                location_b,
+                ..
+            } => Some(if location_a.start < location_b.start {
+                *location_b
+            } else {
+                *location_a
+            }),
+
+            Error::UnknownLabels { unknown, .. } => unknown.first().map(|(_, location)| *location),
+
+            Error::ReservedModuleName { .. } | Error::KeywordInModuleName { .. } => None,
+        }
+    }
+
+    /// The code identifying this kind of error. See [`crate::explanation`]
+    /// for how codes are assigned.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Error::SrcImportingTest { .. } => "E0100",
+            Error::BitStringSegmentError { .. } => "E0101",
+            Error::UnknownLabels { .. } => "E0102",
+            Error::UnknownVariable { .. } => "E0103",
+            Error::UnknownType { .. } => "E0104",
+            Error::UnknownModule { .. } => "E0105",
+            Error::UnknownModuleType { .. } => "E0106",
+            Error::UnknownModuleValue { .. } => "E0107",
+            Error::UnknownModuleField { .. } => "E0108",
+            Error::NotFn { .. } => "E0109",
+            Error::UnknownRecordField { .. } => "E0110",
+            Error::IncorrectArity { .. } => "E0111",
+            Error::UpdateMultiConstructorType { .. } => "E0112",
+            Error::UnnecessarySpreadOperator { .. } => "E0113",
+            Error::IncorrectTypeArity { .. } => "E0114",
+            Error::CouldNotUnify { .. } => "E0115",
+            Error::RecursiveType { .. } => "E0116",
+            Error::DuplicateName { .. } => "E0117",
+            Error::DuplicateImport { .. } => "E0118",
+            Error::DuplicateTypeName { .. } => "E0119",
+            Error::DuplicateArgument { .. } => "E0120",
+            Error::DuplicateField { .. } => "E0121",
+            Error::PrivateTypeLeak { .. } => "E0122",
+            Error::UnexpectedLabelledArg { .. } => "E0123",
+            Error::PositionalArgumentAfterLabelled { .. } => "E0124",
+            Error::IncorrectNumClausePatterns { .. } => "E0125",
+            Error::NonLocalClauseGuardVariable { .. } => "E0126",
+            Error::ExtraVarInAlternativePattern { .. } => "E0127",
+            Error::MissingVarInAlternativePattern { .. } => "E0128",
+            Error::DuplicateVarInPattern { .. } => "E0129",
+            Error::OutOfBoundsTupleIndex { .. } => "E0130",
+            Error::NotATuple { .. } => "E0131",
+            Error::NotATupleUnbound { .. } => "E0132",
+            Error::RecordAccessUnknownType { .. } => "E0133",
+            Error::RecordUpdateInvalidConstructor { .. } => "E0134",
+            Error::UnexpectedTypeHole { .. } => "E0135",
+            Error::ReservedModuleName { .. } => "E0136",
+            Error::KeywordInModuleName { .. } => "E0137",
+            Error::NotExhaustivePatternMatch { .. } => "E0138",
+            Error::ArgumentNameAlreadyUsed { .. } => "E0139",
+            Error::UnlabelledAfterlabelled { .. } => "E0140",
+        }
+    }
 }
 
 impl Warning {
+    /// The code identifying this kind of warning. See [`crate::explanation`]
+    /// for how codes are assigned.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Warning::Todo { .. } => "E0200",
+            Warning::ImplicitlyDiscardedResult { .. } => "E0201",
+            Warning::UnusedLiteral { .. } => "E0202",
+            Warning::NoFieldsRecordUpdate { .. } => "E0203",
+            Warning::AllFieldsRecordUpdate { .. } => "E0204",
+            Warning::UnusedType { .. } => "E0205",
+            Warning::UnusedConstructor { .. } => "E0206",
+            Warning::UnusedImportedValue { .. } => "E0207",
+            Warning::UnusedImportedModule { .. } => "E0208",
+            Warning::UnusedPrivateModuleConstant { .. } => "E0209",
+            Warning::UnusedPrivateFunction { .. } => "E0210",
+            Warning::UnusedVariable { .. } => "E0211",
+            Warning::UnnecessaryDoubleIntNegation { .. } => "E0212",
+            Warning::UnnecessaryDoubleBoolNegation { .. } => "E0213",
+            Warning::UnreachableCaseClause { .. } => "E0214",
+            Warning::DeprecatedItem { .. } => "E0215",
+        }
+    }
+
     pub fn into_warning(self, path: PathBuf, src: SmolStr) -> crate::Warning {
         crate::Warning::Type {
             path,
@@ -0,0 +1,860 @@
+//! Exhaustiveness checking for `case` expressions and `let` assignments.
+//!
+//! This is an implementation of the decision tree algorithm described in
+//! [How to compile pattern matching](https://julesjacobs.com/notes/patternmatching/patternmatching.pdf)
+//! by Jules Jacobs, adapted from the reference implementation found in the
+//! `jacobs2021` directory of this repository.
+//!
+//! The patterns of a `case` expression are treated as a table where each
+//! clause is a row and each subject is a column. The table is compiled into a
+//! decision tree by repeatedly picking a variable to branch on and splitting
+//! the rows by the constructors that variable could have. Any branch of the
+//! tree that no row reaches is a value that is not matched, and the path taken
+//! to get there is used to build the missing pattern shown to the programmer.
+//! Any row that is never the first to match on a branch of the tree can never
+//! be reached, as the rows before it match all the values it would.
+//!
+
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+
+use itertools::Itertools;
+use smol_str::SmolStr;
+
+use crate::{
+    analyse::Inferred,
+    ast::{SrcSpan, TypedPattern},
+    type_::{
+        collapse_links, list, Environment, PatternConstructor, Type, TypeVar,
+        ValueConstructorVariant,
+    },
+};
+
+/// A variable used in the decision tree, standing in for either one of the
+/// values being matched on or for a value found inside of one of them.
+///
+#[derive(Debug, Clone)]
+pub struct Variable {
+    id: usize,
+    type_: Arc<Type>,
+}
+
+/// A simplified version of a typed pattern, containing only the information
+/// needed to check for exhaustiveness.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Pattern {
+    /// A variable or a discard, both of which match any value.
+    Discard,
+    Literal(Literal),
+    Constructor {
+        name: SmolStr,
+        arguments: Vec<Pattern>,
+    },
+    Tuple {
+        elements: Vec<Pattern>,
+    },
+    EmptyList,
+    NonEmptyList {
+        first: Box<Pattern>,
+        rest: Box<Pattern>,
+    },
+}
+
+impl Pattern {
+    fn from_typed(pattern: &TypedPattern) -> Self {
+        match pattern {
+            TypedPattern::Var { .. } | TypedPattern::Discard { .. } => Self::Discard,
+
+            // The variable usages are only permitted within bit strings, which
+            // are handled as a whole below.
+            TypedPattern::VarUsage { .. } => Self::Discard,
+
+            TypedPattern::Assign { pattern, .. } => Self::from_typed(pattern),
+
+            TypedPattern::Int { value, .. } => Self::Literal(Literal::Int {
+                value: value.clone(),
+                number: parse_int(value),
+            }),
+            TypedPattern::Float { value, .. } => Self::Literal(Literal::Float {
+                value: value.clone(),
+                number: parse_float(value),
+            }),
+            TypedPattern::String { value, .. } => Self::Literal(Literal::String(value.clone())),
+            TypedPattern::Concatenate {
+                left_side_string, ..
+            } => Self::Literal(Literal::StringPrefix(left_side_string.clone())),
+            TypedPattern::BitString { location, .. } => {
+                Self::Literal(Literal::BitString(*location))
+            }
+
+            TypedPattern::Constructor {
+                name,
+                arguments,
+                constructor,
+                ..
+            } => {
+                let name = match constructor {
+                    Inferred::Known(PatternConstructor::Record { name, .. }) => name.clone(),
+                    Inferred::Unknown => name.clone(),
+                };
+                Self::Constructor {
+                    name,
+                    arguments: arguments
+                        .iter()
+                        .map(|argument| Self::from_typed(&argument.value))
+                        .collect(),
+                }
+            }
+
+            TypedPattern::Tuple { elems, .. } => Self::Tuple {
+                elements: elems.iter().map(Self::from_typed).collect(),
+            },
+
+            TypedPattern::List { elements, tail, .. } => {
+                let mut rest = match tail {
+                    Some(tail) => Self::from_typed(tail),
+                    None => Self::EmptyList,
+                };
+                for element in elements.iter().rev() {
+                    rest = Self::NonEmptyList {
+                        first: Box::new(Self::from_typed(element)),
+                        rest: Box::new(rest),
+                    };
+                }
+                rest
+            }
+        }
+    }
+}
+
+/// A value of a type with an infinite (or at least impractically large)
+/// number of possible values. These can only be matched exhaustively by
+/// also having a pattern that matches any value.
+///
+/// Numbers are compared by their value rather than how they are written, so
+/// `1_000` and `1000` are the same literal. The source text is kept to show
+/// in missing patterns.
+///
+#[derive(Debug, Clone)]
+enum Literal {
+    Int {
+        value: SmolStr,
+        number: Option<i128>,
+    },
+    Float {
+        value: SmolStr,
+        number: Option<f64>,
+    },
+    String(SmolStr),
+    StringPrefix(SmolStr),
+    /// Bit string patterns are not compared with each other, so each one is
+    /// identified by its location.
+    BitString(SrcSpan),
+}
+
+impl PartialEq for Literal {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (
+                Literal::Int {
+                    number: Some(one), ..
+                },
+                Literal::Int {
+                    number: Some(other),
+                    ..
+                },
+            ) => one == other,
+            (
+                Literal::Float {
+                    number: Some(one), ..
+                },
+                Literal::Float {
+                    number: Some(other),
+                    ..
+                },
+            ) => one == other,
+            (Literal::Int { value: one, .. }, Literal::Int { value: other, .. })
+            | (Literal::Float { value: one, .. }, Literal::Float { value: other, .. })
+            | (Literal::String(one), Literal::String(other))
+            | (Literal::StringPrefix(one), Literal::StringPrefix(other)) => one == other,
+            (Literal::BitString(one), Literal::BitString(other)) => one == other,
+            _ => false,
+        }
+    }
+}
+
+// Float literals are never NaN, so every literal is equal to itself.
+impl Eq for Literal {}
+
+impl Literal {
+    fn pattern_name(&self) -> SmolStr {
+        match self {
+            Literal::Int { value, .. } | Literal::Float { value, .. } => value.clone(),
+            Literal::String(value) => format!("\"{value}\"").into(),
+            Literal::StringPrefix(prefix) => format!("\"{prefix}\" <> _").into(),
+            Literal::BitString(_) => "_".into(),
+        }
+    }
+}
+
+/// The value of an int literal such as `-1_000` or `0xFF`, or `None` if it
+/// is too large to compare by value.
+///
+fn parse_int(value: &str) -> Option<i128> {
+    let value = value.replace('_', "");
+    let (is_negative, value) = match value.strip_prefix('-') {
+        Some(value) => (true, value),
+        None => (false, value.as_str()),
+    };
+    let number = match value.get(..2) {
+        Some("0x") => i128::from_str_radix(value.get(2..)?, 16),
+        Some("0o") => i128::from_str_radix(value.get(2..)?, 8),
+        Some("0b") => i128::from_str_radix(value.get(2..)?, 2),
+        _ => value.parse(),
+    }
+    .ok()?;
+    Some(if is_negative { -number } else { number })
+}
+
+/// The value of a float literal such as `1_000.0` or `1.0e3`.
+///
+fn parse_float(value: &str) -> Option<f64> {
+    value.replace('_', "").parse().ok()
+}
+
+/// A column in the pattern matching table, testing a single variable against
+/// a single pattern.
+///
+#[derive(Debug, Clone)]
+struct Column {
+    variable: Variable,
+    pattern: Pattern,
+}
+
+/// A single row in the pattern matching table. Each alternative pattern of a
+/// clause is a row of its own.
+///
+#[derive(Debug, Clone)]
+pub struct Row {
+    columns: Vec<Column>,
+    has_guard: bool,
+    /// The position of the row in the table, set when it is compiled.
+    index: usize,
+}
+
+impl Row {
+    /// Create a row matching the given subject variables against the patterns
+    /// of a clause.
+    ///
+    pub fn new(subjects: &[Variable], patterns: &[TypedPattern], has_guard: bool) -> Self {
+        let columns = subjects
+            .iter()
+            .zip(patterns)
+            .map(|(variable, pattern)| Column {
+                variable: variable.clone(),
+                pattern: Pattern::from_typed(pattern),
+            })
+            .collect();
+        Self {
+            columns,
+            has_guard,
+            index: 0,
+        }
+    }
+
+    fn remove_column(&mut self, variable: &Variable) -> Option<Column> {
+        self.columns
+            .iter()
+            .position(|column| column.variable.id == variable.id)
+            .map(|index| self.columns.remove(index))
+    }
+}
+
+/// The constructor a variable is tested against in a decision tree case.
+///
+#[derive(Debug, Clone)]
+enum Constructor {
+    Tuple,
+    Variant { name: SmolStr },
+    EmptyList,
+    NonEmptyList,
+    Literal(Literal),
+}
+
+#[derive(Debug)]
+struct Case {
+    constructor: Constructor,
+    arguments: Vec<Variable>,
+    body: Decision,
+}
+
+/// A decision tree compiled from the rows of a pattern matching table.
+///
+#[derive(Debug)]
+enum Decision {
+    /// A clause matched.
+    Success,
+
+    /// No clause matches the values tested on the way to this point.
+    Failure,
+
+    /// A clause matched but it has a guard, so if the guard fails the
+    /// fallback decision is taken.
+    Guard(Box<Decision>),
+
+    /// The variable is tested against each of the cases, taking the fallback
+    /// decision if none of them match.
+    Switch(Variable, Vec<Case>, Option<Box<Decision>>),
+}
+
+/// The ways a variable can be branched on, based on its type.
+///
+enum Branch {
+    Tuple(Vec<Arc<Type>>),
+    List(Arc<Type>),
+    Variants(Vec<(SmolStr, Vec<Arc<Type>>)>),
+    Infinite,
+}
+
+/// The result of compiling a `case` expression or `let` assignment.
+///
+#[derive(Debug)]
+pub struct Match {
+    subjects: Vec<Variable>,
+    tree: Decision,
+    missing: bool,
+    reachable: HashSet<usize>,
+    shadowed_by: HashMap<usize, usize>,
+}
+
+impl Match {
+    /// Returns whether the row at the given position in the table matches any
+    /// values that the rows before it do not.
+    ///
+    pub fn is_reachable(&self, row: usize) -> bool {
+        self.reachable.contains(&row)
+    }
+
+    /// Returns the position of the last row matching values that the row at
+    /// the given position would also match, after which that row can no
+    /// longer be reached if it is unreachable.
+    ///
+    pub fn shadowed_by(&self, row: usize) -> Option<usize> {
+        self.shadowed_by.get(&row).copied()
+    }
+
+    /// Returns the patterns that would need to be added for the match to be
+    /// exhaustive, such as `Error(_)` or `[_, _, ..]`.
+    ///
+    pub fn missing_patterns(&self) -> Vec<SmolStr> {
+        if !self.missing {
+            return vec![];
+        }
+        let mut missing = Vec::new();
+        let mut terms = Vec::new();
+        self.add_missing_patterns(&self.tree, &mut terms, &mut missing);
+        missing.into_iter().sorted().dedup().collect()
+    }
+
+    fn add_missing_patterns<'a>(
+        &'a self,
+        decision: &'a Decision,
+        terms: &mut Vec<Term<'a>>,
+        missing: &mut Vec<SmolStr>,
+    ) {
+        match decision {
+            Decision::Success => {}
+
+            Decision::Failure => {
+                let mapping: HashMap<usize, &Term<'_>> =
+                    terms.iter().map(|term| (term.variable.id, term)).collect();
+                let name = self
+                    .subjects
+                    .iter()
+                    .map(|subject| pattern_name(subject, &mapping))
+                    .join(", ");
+                missing.push(name.into());
+            }
+
+            Decision::Guard(fallback) => self.add_missing_patterns(fallback, terms, missing),
+
+            Decision::Switch(variable, cases, fallback) => {
+                for case in cases {
+                    terms.push(Term {
+                        variable,
+                        constructor: &case.constructor,
+                        arguments: &case.arguments,
+                    });
+                    self.add_missing_patterns(&case.body, terms, missing);
+                    let _ = terms.pop();
+                }
+
+                if let Some(fallback) = fallback {
+                    self.add_missing_patterns(fallback, terms, missing);
+                }
+            }
+        }
+    }
+}
+
+/// A constructor that a variable was tested against on the way to a point in
+/// the decision tree.
+///
+struct Term<'a> {
+    variable: &'a Variable,
+    constructor: &'a Constructor,
+    arguments: &'a [Variable],
+}
+
+fn pattern_name(variable: &Variable, mapping: &HashMap<usize, &Term<'_>>) -> String {
+    let term = match mapping.get(&variable.id) {
+        Some(term) => term,
+        None => return "_".into(),
+    };
+    let arguments = || {
+        term.arguments
+            .iter()
+            .map(|argument| pattern_name(argument, mapping))
+            .join(", ")
+    };
+    match term.constructor {
+        Constructor::Tuple => format!("#({})", arguments()),
+        Constructor::Variant { name } if term.arguments.is_empty() => name.to_string(),
+        Constructor::Variant { name } => format!("{name}({})", arguments()),
+        Constructor::Literal(literal) => literal.pattern_name().to_string(),
+        Constructor::EmptyList | Constructor::NonEmptyList => {
+            let mut elements = Vec::new();
+            let mut term = *term;
+            while let (Constructor::NonEmptyList, [first, rest]) =
+                (term.constructor, term.arguments)
+            {
+                elements.push(pattern_name(first, mapping));
+                match mapping.get(&rest.id) {
+                    Some(rest) => term = rest,
+                    None => {
+                        elements.push("..".into());
+                        break;
+                    }
+                }
+            }
+            format!("[{}]", elements.join(", "))
+        }
+    }
+}
+
+/// Compiles the rows of a `case` expression or `let` assignment into a
+/// decision tree, recording whether any values are left unmatched and which
+/// rows can be reached.
+///
+pub struct Compiler<'a, 'b> {
+    environment: &'a Environment<'b>,
+    variable_id: usize,
+    missing: bool,
+    reachable: HashSet<usize>,
+    shadowed_by: HashMap<usize, usize>,
+}
+
+impl<'a, 'b> Compiler<'a, 'b> {
+    pub fn new(environment: &'a Environment<'b>) -> Self {
+        Self {
+            environment,
+            variable_id: 0,
+            missing: false,
+            reachable: HashSet::new(),
+            shadowed_by: HashMap::new(),
+        }
+    }
+
+    /// Create a variable standing in for a value of the given type.
+    ///
+    pub fn new_variable(&mut self, type_: Arc<Type>) -> Variable {
+        let variable = Variable {
+            id: self.variable_id,
+            type_,
+        };
+        self.variable_id += 1;
+        variable
+    }
+
+    fn new_variables(&mut self, types: &[Arc<Type>]) -> Vec<Variable> {
+        types
+            .iter()
+            .map(|type_| self.new_variable(type_.clone()))
+            .collect()
+    }
+
+    /// Compile the rows into a decision tree. The same compiler can be used
+    /// to compile different selections of rows for the same subjects.
+    ///
+    pub fn compile(&mut self, subjects: Vec<Variable>, rows: Vec<Row>) -> Match {
+        let rows = rows
+            .into_iter()
+            .enumerate()
+            .map(|(index, row)| Row { index, ..row })
+            .collect();
+        let tree = self.compile_rows(rows);
+        Match {
+            subjects,
+            tree,
+            missing: std::mem::take(&mut self.missing),
+            reachable: std::mem::take(&mut self.reachable),
+            shadowed_by: std::mem::take(&mut self.shadowed_by),
+        }
+    }
+
+    fn compile_rows(&mut self, rows: Vec<Row>) -> Decision {
+        if rows.is_empty() {
+            self.missing = true;
+            return Decision::Failure;
+        }
+
+        // Patterns that match any value don't need to be tested.
+        let mut rows = rows
+            .into_iter()
+            .map(|mut row| {
+                row.columns
+                    .retain(|column| column.pattern != Pattern::Discard);
+                row
+            })
+            .collect_vec();
+
+        // If the first row has nothing left to test then it always matches,
+        // and any rows after it are only reachable if it has a guard.
+        if rows.first().map(|row| row.columns.is_empty()) == Some(true) {
+            let row = rows.remove(0);
+            let _ = self.reachable.insert(row.index);
+            if row.has_guard {
+                return Decision::Guard(Box::new(self.compile_rows(rows)));
+            }
+            // The rows left would have matched the values that got here, so
+            // they are shadowed by this row on this branch of the tree.
+            for shadowed in rows {
+                let shadowed_by = self.shadowed_by.entry(shadowed.index).or_insert(row.index);
+                *shadowed_by = (*shadowed_by).max(row.index);
+            }
+            return Decision::Success;
+        }
+
+        let branch_variable = self.branch_variable(&rows);
+        match self.branch(&branch_variable.type_) {
+            Branch::Tuple(elements) => {
+                let cases = vec![(Constructor::Tuple, self.new_variables(&elements))];
+                let cases = self.compile_constructor_cases(rows, &branch_variable, cases);
+                Decision::Switch(branch_variable, cases, None)
+            }
+
+            Branch::List(element) => {
+                let tail = list(element.clone());
+                let cases = vec![
+                    (Constructor::EmptyList, vec![]),
+                    (
+                        Constructor::NonEmptyList,
+                        self.new_variables(&[element, tail]),
+                    ),
+                ];
+                let cases = self.compile_constructor_cases(rows, &branch_variable, cases);
+                Decision::Switch(branch_variable, cases, None)
+            }
+
+            Branch::Variants(variants) => {
+                let cases = variants
+                    .into_iter()
+                    .map(|(name, arguments)| {
+                        (
+                            Constructor::Variant { name },
+                            self.new_variables(&arguments),
+                        )
+                    })
+                    .collect();
+                let cases = self.compile_constructor_cases(rows, &branch_variable, cases);
+                Decision::Switch(branch_variable, cases, None)
+            }
+
+            Branch::Infinite => {
+                let (cases, fallback) = self.compile_literal_cases(rows, &branch_variable);
+                Decision::Switch(branch_variable, cases, Some(Box::new(fallback)))
+            }
+        }
+    }
+
+    /// Compiles the cases for a variable of a type with a fixed number of
+    /// constructors. Rows that test the variable are given to the case for
+    /// the constructor they match, with the constructor's arguments as new
+    /// columns, while rows that don't test it are given to every case.
+    ///
+    fn compile_constructor_cases(
+        &mut self,
+        rows: Vec<Row>,
+        branch_variable: &Variable,
+        cases: Vec<(Constructor, Vec<Variable>)>,
+    ) -> Vec<Case> {
+        let mut case_rows: Vec<Vec<Row>> = cases.iter().map(|_| Vec::new()).collect();
+
+        for mut row in rows {
+            let column = match row.remove_column(branch_variable) {
+                Some(column) => column,
+                None => {
+                    for rows in &mut case_rows {
+                        rows.push(row.clone());
+                    }
+                    continue;
+                }
+            };
+
+            let (index, arguments) = match column.pattern {
+                Pattern::Tuple { elements } => (Some(0), elements),
+                Pattern::EmptyList => (Some(0), vec![]),
+                Pattern::NonEmptyList { first, rest } => (Some(1), vec![*first, *rest]),
+                Pattern::Constructor { name, arguments } => {
+                    let index = cases.iter().position(|(constructor, _)| {
+                        matches!(constructor, Constructor::Variant { name: n } if *n == name)
+                    });
+                    (index, arguments)
+                }
+                Pattern::Discard | Pattern::Literal(_) => (None, vec![]),
+            };
+
+            // The pattern type checked against the variable so it will always
+            // be one of the cases, but should that somehow not be so the row
+            // is treated as matching any value. This may hide a missing
+            // pattern but never reports one that isn't.
+            let index = match index {
+                Some(index) => index,
+                None => {
+                    for rows in &mut case_rows {
+                        rows.push(row.clone());
+                    }
+                    continue;
+                }
+            };
+
+            if let (Some((_, variables)), Some(rows)) = (cases.get(index), case_rows.get_mut(index))
+            {
+                row.columns
+                    .extend(
+                        variables
+                            .iter()
+                            .zip(arguments)
+                            .map(|(variable, pattern)| Column {
+                                variable: variable.clone(),
+                                pattern,
+                            }),
+                    );
+                rows.push(row);
+            }
+        }
+
+        cases
+            .into_iter()
+            .zip(case_rows)
+            .map(|((constructor, arguments), rows)| Case {
+                constructor,
+                arguments,
+                body: self.compile_rows(rows),
+            })
+            .collect()
+    }
+
+    /// Compiles the cases for a variable of a type with infinitely many
+    /// values, such as `Int` or `String`. There is a case for each distinct
+    /// literal tested, and a fallback for all the other values which only the
+    /// rows that don't test the variable can match.
+    ///
+    fn compile_literal_cases(
+        &mut self,
+        rows: Vec<Row>,
+        branch_variable: &Variable,
+    ) -> (Vec<Case>, Decision) {
+        let mut literals: Vec<(Literal, Vec<Row>)> = Vec::new();
+        let mut fallback_rows: Vec<Row> = Vec::new();
+
+        for mut row in rows {
+            match row.remove_column(branch_variable) {
+                Some(Column {
+                    pattern: Pattern::Literal(literal),
+                    ..
+                }) => match literals.iter_mut().find(|(tested, _)| *tested == literal) {
+                    Some((_, rows)) => rows.push(row),
+                    None => {
+                        // The rows that matched any value before this one come
+                        // first, so a new case starts with them.
+                        let mut rows = fallback_rows.clone();
+                        rows.push(row);
+                        literals.push((literal, rows));
+                    }
+                },
+
+                // The pattern type checked against the variable so it will
+                // always be a literal, but should that somehow not be so the
+                // row is treated as matching any value.
+                Some(_) | None => {
+                    for (_, rows) in &mut literals {
+                        rows.push(row.clone());
+                    }
+                    fallback_rows.push(row);
+                }
+            }
+        }
+
+        let cases = literals
+            .into_iter()
+            .map(|(literal, rows)| Case {
+                constructor: Constructor::Literal(literal),
+                arguments: vec![],
+                body: self.compile_rows(rows),
+            })
+            .collect();
+        (cases, self.compile_rows(fallback_rows))
+    }
+
+    /// Returns the variable of the first row that is tested by the most rows.
+    ///
+    fn branch_variable(&self, rows: &[Row]) -> Variable {
+        let mut counts: HashMap<usize, usize> = HashMap::new();
+        for row in rows {
+            for column in &row.columns {
+                *counts.entry(column.variable.id).or_insert(0) += 1;
+            }
+        }
+
+        rows.first()
+            .and_then(|row| {
+                // `max_by_key` returns the last of equally tested variables, so
+                // the columns are reversed to prefer the leftmost one.
+                row.columns
+                    .iter()
+                    .rev()
+                    .max_by_key(|column| counts.get(&column.variable.id))
+            })
+            .map(|column| column.variable.clone())
+            .expect("Exhaustiveness branch variable chosen from a row without columns")
+    }
+
+    /// Works out how a variable of the given type can be branched on.
+    ///
+    fn branch(&self, type_: &Arc<Type>) -> Branch {
+        match collapse_links(type_.clone()).as_ref() {
+            Type::Tuple { elems } => Branch::Tuple(elems.clone()),
+
+            Type::App {
+                module, name, args, ..
+            } if module.is_empty() && name == "List" => match args.first() {
+                Some(element) => Branch::List(element.clone()),
+                None => Branch::Infinite,
+            },
+
+            Type::App {
+                module, name, args, ..
+            } => match self.variants(module, name, args) {
+                Some(variants) => Branch::Variants(variants),
+                None => Branch::Infinite,
+            },
+
+            Type::Fn { .. } | Type::Var { .. } => Branch::Infinite,
+        }
+    }
+
+    /// Returns the constructors of a custom type along with the types of
+    /// their arguments, or `None` if they are not known to this module, for
+    /// example because the type is opaque or built in.
+    ///
+    fn variants(
+        &self,
+        module: &SmolStr,
+        name: &SmolStr,
+        type_arguments: &[Arc<Type>],
+    ) -> Option<Vec<(SmolStr, Vec<Arc<Type>>)>> {
+        let environment = self.environment;
+        let (constructor_names, values) = if module == environment.current_module {
+            (environment.module_types_constructors.get(name)?, None)
+        } else {
+            let module_name = if module.is_empty() { "gleam" } else { module };
+            let module = environment.importable_modules.get(module_name)?;
+            (module.types_constructors.get(name)?, Some(&module.values))
+        };
+
+        constructor_names
+            .iter()
+            .map(|constructor_name| {
+                // The constructors of the current module are looked up in
+                // scope as opaque types don't register them as module values.
+                let constructor = match values {
+                    Some(values) => values.get(constructor_name)?,
+                    None => environment.scope.get(constructor_name)?,
+                };
+                if !matches!(constructor.variant, ValueConstructorVariant::Record { .. }) {
+                    return None;
+                }
+
+                let (arguments, return_type) = match constructor.type_.as_ref() {
+                    Type::Fn { args, retrn } => (args.clone(), retrn.clone()),
+                    _ => (vec![], constructor.type_.clone()),
+                };
+
+                // The constructor is generic over the parameters of the type,
+                // so they are replaced with the arguments of the type being
+                // matched on.
+                let mut parameters = HashMap::new();
+                if let Type::App { args, .. } = return_type.as_ref() {
+                    for (parameter, argument) in args.iter().zip(type_arguments) {
+                        if let Some(id) = type_variable_id(parameter) {
+                            let _ = parameters.insert(id, argument.clone());
+                        }
+                    }
+                }
+                let arguments = arguments
+                    .iter()
+                    .map(|argument| substitute(argument, &parameters))
+                    .collect();
+
+                Some((constructor_name.clone(), arguments))
+            })
+            .collect()
+    }
+}
+
+fn type_variable_id(type_: &Arc<Type>) -> Option<u64> {
+    match collapse_links(type_.clone()).as_ref() {
+        Type::Var { type_ } => match &*type_.borrow() {
+            TypeVar::Unbound { id } | TypeVar::Generic { id } => Some(*id),
+            TypeVar::Link { .. } => None,
+        },
+        _ => None,
+    }
+}
+
+fn substitute(type_: &Arc<Type>, parameters: &HashMap<u64, Arc<Type>>) -> Arc<Type> {
+    let type_ = collapse_links(type_.clone());
+    match type_.as_ref() {
+        Type::Var { .. } => type_variable_id(&type_)
+            .and_then(|id| parameters.get(&id).cloned())
+            .unwrap_or(type_),
+
+        Type::App {
+            public,
+            module,
+            name,
+            args,
+        } => Arc::new(Type::App {
+            public: *public,
+            module: module.clone(),
+            name: name.clone(),
+            args: args.iter().map(|arg| substitute(arg, parameters)).collect(),
+        }),
+
+        Type::Fn { args, retrn } => Arc::new(Type::Fn {
+            args: args.iter().map(|arg| substitute(arg, parameters)).collect(),
+            retrn: substitute(retrn, parameters),
+        }),
+
+        Type::Tuple { elems } => Arc::new(Type::Tuple {
+            elems: elems
+                .iter()
+                .map(|elem| substitute(elem, parameters))
+                .collect(),
+        }),
+    }
+}
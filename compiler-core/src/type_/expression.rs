@@ -6,7 +6,7 @@ use crate::{
     analyse::infer_bit_string_segment_option,
     ast::{
         Arg, Assignment, AssignmentKind, BinOp, BitStringSegment, BitStringSegmentOption, CallArg,
-        Clause, ClauseGuard, Constant, HasLocation, RecordUpdateSpread, SrcSpan, Statement,
+        Clause, ClauseGuard, Constant, HasLocation, Layer, RecordUpdateSpread, SrcSpan, Statement,
         TodoKind, TypeAst, TypedArg, TypedAssignment, TypedClause, TypedClauseGuard, TypedConstant,
         TypedExpr, TypedMultiPattern, TypedStatement, UntypedArg, UntypedAssignment, UntypedClause,
         UntypedClauseGuard, UntypedConstant, UntypedConstantBitStringSegment, UntypedExpr,
@@ -770,14 +770,19 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
                 .map_err(|e| convert_unify_error(e, value.type_defining_location()))?;
         }
 
-        // We currently only do only limited exhaustiveness checking of custom types
-        // at the top level of patterns.
         // Do not perform exhaustiveness checking if user explicitly used `let assert ... = ...`.
         if kind.performs_exhaustiveness_check() {
-            if let Err(unmatched) = self
-                .environment
-                .check_exhaustiveness(vec![pattern.clone()], collapse_links(value_typ))
-            {
+            let mut compiler = exhaustiveness::Compiler::new(self.environment);
+            let subject = compiler.new_variable(value_typ);
+            let row = exhaustiveness::Row::new(
+                std::slice::from_ref(&subject),
+                std::slice::from_ref(&pattern),
+                false,
+            );
+            let unmatched = compiler
+                .compile(vec![subject], vec![row])
+                .missing_patterns();
+            if !unmatched.is_empty() {
                 return Err(Error::NotExhaustivePatternMatch {
                     location,
                     unmatched,
@@ -826,9 +831,8 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
             typed_clauses.push(typed_clause);
         }
 
-        if let Err(unmatched) =
-            self.check_case_exhaustiveness(subjects_count, &subject_types, &typed_clauses)
-        {
+        let unmatched = self.check_case_patterns(&subject_types, &typed_clauses);
+        if !unmatched.is_empty() {
             return Err(Error::NotExhaustivePatternMatch {
                 location,
                 unmatched,
@@ -1243,18 +1247,31 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
             (module.name.clone(), constructor.clone())
         };
 
+        self.environment.warn_if_deprecated(
+            &constructor.deprecation,
+            Layer::Value,
+            select_location,
+        );
+
         let type_ = self.instantiate(constructor.type_, &mut hashmap![]);
 
         let constructor = match &constructor.variant {
-            variant @ ValueConstructorVariant::ModuleFn { name, module, .. } => {
-                variant.to_module_value_constructor(Arc::clone(&type_), module, name)
-            }
+            variant @ ValueConstructorVariant::ModuleFn { name, module, .. } => variant
+                .to_module_value_constructor(
+                    Arc::clone(&type_),
+                    module,
+                    name,
+                    &constructor.deprecation,
+                ),
 
             variant @ (ValueConstructorVariant::LocalVariable { .. }
             | ValueConstructorVariant::ModuleConstant { .. }
-            | ValueConstructorVariant::Record { .. }) => {
-                variant.to_module_value_constructor(Arc::clone(&type_), &module_name, &label)
-            }
+            | ValueConstructorVariant::Record { .. }) => variant.to_module_value_constructor(
+                Arc::clone(&type_),
+                &module_name,
+                &label,
+                &constructor.deprecation,
+            ),
         };
 
         Ok(TypedExpr::ModuleSelect {
@@ -1360,14 +1377,15 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
         args: Vec<UntypedRecordUpdateArg>,
         location: SrcSpan,
     ) -> Result<TypedExpr, Error> {
-        let (module, name) = match self.infer(constructor.clone())? {
+        let typed_constructor = self.infer(constructor.clone())?;
+        let (module, name) = match &typed_constructor {
             TypedExpr::ModuleSelect {
                 module_alias,
                 label,
                 ..
-            } => (Some(module_alias), label),
+            } => (Some(module_alias.clone()), label.clone()),
 
-            TypedExpr::Var { name, .. } => (None, name),
+            TypedExpr::Var { name, .. } => (None, name.clone()),
 
             constructor => {
                 return Err(Error::RecordUpdateInvalidConstructor {
@@ -1475,6 +1493,7 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
         Ok(TypedExpr::RecordUpdate {
             location,
             typ: spread.type_(),
+            constructor: Box::new(typed_constructor),
             spread: Box::new(spread),
             args,
         })
@@ -1548,14 +1567,19 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
 
         let ValueConstructor {
             public,
+            deprecation,
             variant,
             type_: typ,
         } = constructor;
 
+        self.environment
+            .warn_if_deprecated(&deprecation, Layer::Value, *location);
+
         // Instantiate generic variables into unbound variables for this usage
         let typ = self.instantiate(typ, &mut hashmap![]);
         Ok(ValueConstructor {
             public,
+            deprecation,
             variant,
             type_: typ,
         })
@@ -1691,6 +1715,7 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
                             type_: Arc::clone(&typ),
                             location: constructor.variant.definition_location(),
                             documentation: None,
+                            deprecation: constructor.deprecation.clone(),
                         };
 
                         TypedExpr::ModuleSelect {
@@ -2050,45 +2075,53 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
         Ok((args, body))
     }
 
-    fn check_case_exhaustiveness(
+    /// Emits a warning for each clause pattern of a case expression that can
+    /// never match, and returns the patterns that would need to be added for
+    /// the case expression to match all possible values of its subjects.
+    fn check_case_patterns(
         &mut self,
-        subjects_count: usize,
-        subjects: &[Arc<Type>],
-        typed_clauses: &[Clause<TypedExpr, Arc<Type>, SmolStr>],
-    ) -> Result<(), Vec<SmolStr>> {
-        // Because exhaustiveness checking in presence of multiple subjects is similar
-        // to full exhaustiveness checking of tuples or other nested record patterns,
-        // and we currently only do only limited exhaustiveness checking of custom types
-        // at the top level of patterns, only consider case expressions with one subject.
-        if subjects_count != 1 {
-            return Ok(());
-        }
-        let subject_type = subjects
-            .get(0)
-            .expect("Asserted there's one case subject but found none");
-        let value_typ = collapse_links(subject_type.clone());
-
-        // Currently guards in exhaustiveness checking are assumed that they can fail,
-        // so we go through all clauses and pluck out only the patterns
-        // for clauses that don't have guards.
-        let mut patterns = Vec::new();
+        subject_types: &[Arc<Type>],
+        typed_clauses: &[TypedClause],
+    ) -> Vec<SmolStr> {
+        let mut compiler = exhaustiveness::Compiler::new(self.environment);
+        let subjects = subject_types
+            .iter()
+            .map(|type_| compiler.new_variable(type_.clone()))
+            .collect_vec();
+
+        // A clause with alternative patterns, e.g. `Audio(_) | Text(_) ->`,
+        // becomes one row per pattern. Guards are assumed to be able to fail.
+        let mut rows = Vec::with_capacity(typed_clauses.len());
+        let mut locations = Vec::with_capacity(typed_clauses.len());
         for clause in typed_clauses {
-            if let Clause { guard: None, .. } = clause {
-                // clause.pattern is a list of patterns for all subjects
-                if let Some(pattern) = clause.pattern.get(0) {
-                    patterns.push(pattern.clone());
-                }
-                // A clause can be built with alternative patterns as well, e.g. `Audio(_) | Text(_) ->`.
-                // We're interested in all patterns so we build a flattened list.
-                for alternative_pattern in &clause.alternative_patterns {
-                    // clause.alternative_pattern is a list of patterns for all subjects
-                    if let Some(pattern) = alternative_pattern.get(0) {
-                        patterns.push(pattern.clone());
-                    }
-                }
+            let has_guard = clause.guard.is_some();
+            for patterns in std::iter::once(&clause.pattern).chain(&clause.alternative_patterns) {
+                rows.push(exhaustiveness::Row::new(&subjects, patterns, has_guard));
+                locations.push(multi_pattern_location(patterns));
             }
         }
-        self.environment.check_exhaustiveness(patterns, value_typ)
+        let compiled = compiler.compile(subjects, rows);
+
+        for (index, location) in locations.iter().enumerate() {
+            if compiled.is_reachable(index) {
+                continue;
+            }
+            // The row is shadowed by the one after which it can no longer be
+            // reached, which is at the latest the row right before it.
+            let shadowing_index = compiled
+                .shadowed_by(index)
+                .unwrap_or_else(|| index.saturating_sub(1));
+            if let Some(shadowed_by) = locations.get(shadowing_index) {
+                self.environment
+                    .warnings
+                    .emit(Warning::UnreachableCaseClause {
+                        location: *location,
+                        shadowed_by: *shadowed_by,
+                    });
+            }
+        }
+
+        compiled.missing_patterns()
     }
 
     fn infer_block(
@@ -2131,6 +2164,19 @@ fn get_use_expression_call(call: UntypedExpr) -> Result<UseCall, Error> {
     }
 }
 
+/// The span covering the patterns for each of the subjects of a case clause.
+fn multi_pattern_location(patterns: &[TypedPattern]) -> SrcSpan {
+    let start = patterns
+        .first()
+        .map(|p| p.location().start)
+        .unwrap_or_default();
+    let end = patterns
+        .last()
+        .map(|p| p.location().end)
+        .unwrap_or_default();
+    SrcSpan { start, end }
+}
+
 #[derive(Debug, Default)]
 struct UseAssignments {
     /// With sugar
@@ -7,7 +7,7 @@ use itertools::Itertools;
 use super::*;
 use crate::{
     analyse::Inferred,
-    ast::{AssignName, UntypedPatternBitStringSegment},
+    ast::{AssignName, Layer, UntypedPatternBitStringSegment},
 };
 use std::sync::Arc;
 
@@ -228,24 +228,27 @@ impl<'a, 'b> PatternTyper<'a, 'b> {
             }
 
             Pattern::VarUsage { name, location, .. } => {
-                let ValueConstructor { type_, .. } = self
-                    .environment
-                    .get_variable(&name)
-                    .cloned()
-                    .ok_or_else(|| Error::UnknownVariable {
-                        location,
-                        name: name.clone(),
-                        variables: self.environment.local_value_names(),
-                    })?;
+                let constructor =
+                    self.environment
+                        .get_variable(&name)
+                        .cloned()
+                        .ok_or_else(|| Error::UnknownVariable {
+                            location,
+                            name: name.clone(),
+                            variables: self.environment.local_value_names(),
+                        })?;
                 self.environment.increment_usage(&name);
-                let typ = self
-                    .environment
-                    .instantiate(type_, &mut hashmap![], self.hydrator);
+                let typ = self.environment.instantiate(
+                    constructor.type_.clone(),
+                    &mut hashmap![],
+                    self.hydrator,
+                );
                 unify(int(), typ.clone()).map_err(|e| convert_unify_error(e, location))?;
 
                 Ok(Pattern::VarUsage {
                     name,
                     location,
+                    constructor: Some(Box::new(constructor)),
                     type_: typ,
                 })
             }
@@ -495,6 +498,7 @@ impl<'a, 'b> PatternTyper<'a, 'b> {
                 }
 
                 let constructor_typ = cons.type_.clone();
+                let deprecation = cons.deprecation.clone();
                 let constructor = match &cons.variant {
                     ValueConstructorVariant::Record {
                         name,
@@ -516,6 +520,9 @@ impl<'a, 'b> PatternTyper<'a, 'b> {
                     }
                 };
 
+                self.environment
+                    .warn_if_deprecated(&deprecation, Layer::Value, location);
+
                 let instantiated_constructor_type =
                     self.environment
                         .instantiate(constructor_typ, &mut hashmap![], self.hydrator);
@@ -1,4 +1,8 @@
-use crate::{ast::SrcSpan, build::Origin, uid::UniqueIdGenerator};
+use crate::{
+    ast::{Deprecation, SrcSpan},
+    build::Origin,
+    uid::UniqueIdGenerator,
+};
 
 use super::{Module, Type, TypeConstructor, TypeVar, ValueConstructor, ValueConstructorVariant};
 use std::{cell::RefCell, collections::HashMap, sync::Arc};
@@ -126,6 +130,7 @@ pub fn link(type_: Arc<Type>) -> Arc<Type> {
 pub fn build_prelude(ids: &UniqueIdGenerator) -> Module {
     let value = |variant, type_| ValueConstructor {
         public: true,
+        deprecation: Deprecation::NotDeprecated,
         variant,
         type_,
     };
@@ -148,6 +153,7 @@ pub fn build_prelude(ids: &UniqueIdGenerator) -> Module {
             origin: Default::default(),
             module: "".into(),
             public: true,
+            deprecation: Deprecation::NotDeprecated,
         },
     );
 
@@ -193,6 +199,7 @@ pub fn build_prelude(ids: &UniqueIdGenerator) -> Module {
             typ: bool(),
             module: "".into(),
             public: true,
+            deprecation: Deprecation::NotDeprecated,
         },
     );
 
@@ -205,6 +212,7 @@ pub fn build_prelude(ids: &UniqueIdGenerator) -> Module {
             typ: list(list_parameter),
             module: "".into(),
             public: true,
+            deprecation: Deprecation::NotDeprecated,
         },
     );
 
@@ -216,6 +224,7 @@ pub fn build_prelude(ids: &UniqueIdGenerator) -> Module {
             typ: float(),
             module: "".into(),
             public: true,
+            deprecation: Deprecation::NotDeprecated,
         },
     );
 
@@ -227,6 +236,7 @@ pub fn build_prelude(ids: &UniqueIdGenerator) -> Module {
             typ: string(),
             module: "".into(),
             public: true,
+            deprecation: Deprecation::NotDeprecated,
         },
     );
 
@@ -240,6 +250,7 @@ pub fn build_prelude(ids: &UniqueIdGenerator) -> Module {
             typ: result(result_value, result_error),
             module: "".into(),
             public: true,
+            deprecation: Deprecation::NotDeprecated,
         },
     );
 
@@ -270,9 +281,14 @@ pub fn build_prelude(ids: &UniqueIdGenerator) -> Module {
             typ: nil(),
             module: "".into(),
             public: true,
+            deprecation: Deprecation::NotDeprecated,
         },
     );
 
+    let _ = prelude
+        .types_constructors
+        .insert(NIL.into(), vec![NIL.into()]);
+
     let _ = prelude.types.insert(
         "BitString".into(),
         TypeConstructor {
@@ -281,6 +297,7 @@ pub fn build_prelude(ids: &UniqueIdGenerator) -> Module {
             typ: bit_string(),
             module: "".into(),
             public: true,
+            deprecation: Deprecation::NotDeprecated,
         },
     );
 
@@ -292,6 +309,7 @@ pub fn build_prelude(ids: &UniqueIdGenerator) -> Module {
             typ: utf_codepoint(),
             module: "".into(),
             public: true,
+            deprecation: Deprecation::NotDeprecated,
         },
     );
 
@@ -10,11 +10,12 @@ use crate::{
 use itertools::Itertools;
 use smol_str::SmolStr;
 use std::{path::PathBuf, sync::Arc};
-use vec1::Vec1;
+use vec1::{vec1, Vec1};
 
 mod assert;
 mod assignments;
 mod errors;
+mod exhaustiveness;
 mod functions;
 mod imports;
 mod pretty;
@@ -78,7 +79,7 @@ macro_rules! assert_error {
         let error = $crate::error::Error::Type {
             src: $src.into(),
             path: std::path::PathBuf::from("/src/one/two.gleam"),
-            error,
+            errors: vec1::vec1![error],
         };
         let output = error.pretty_string();
         insta::assert_snapshot!(insta::internals::AutoName, output, $src);
@@ -214,7 +215,7 @@ pub fn compile_module(
     src: &str,
     warnings: Option<Arc<dyn WarningEmitterIO>>,
     dep: Vec<(&str, &str)>,
-) -> Result<TypedModule, crate::type_::Error> {
+) -> Result<TypedModule, Vec1<crate::type_::Error>> {
     let ids = UniqueIdGenerator::new();
     let mut modules = im::HashMap::new();
     let warnings = TypeWarningEmitter::new(
@@ -260,11 +261,11 @@ pub fn compile_module(
 }
 
 pub fn module_error(src: &str, deps: Vec<(&str, &str)>) -> String {
-    let error = compile_module(src, None, deps).expect_err("should infer an error");
+    let errors = compile_module(src, None, deps).expect_err("should infer an error");
     let error = Error::Type {
         src: src.into(),
         path: PathBuf::from("/src/one/two.gleam"),
-        error,
+        errors,
     };
     error.pretty_string()
 }
@@ -274,7 +275,7 @@ pub fn syntax_error(src: &str) -> String {
     let error = Error::Parse {
         src: src.into(),
         path: PathBuf::from("/src/one/two.gleam"),
-        error,
+        errors: vec1![error],
     };
     error.pretty_string()
 }
@@ -447,7 +448,8 @@ fn infer_module_type_retention_test() {
             types: HashMap::new(),
             types_constructors: HashMap::from([
                 ("Bool".into(), vec!["True".into(), "False".into()]),
-                ("Result".into(), vec!["Ok".into(), "Error".into()])
+                ("Result".into(), vec!["Ok".into(), "Error".into()]),
+                ("Nil".into(), vec!["Nil".into()])
             ]),
             values: HashMap::new(),
             accessors: HashMap::new(),
@@ -652,26 +654,29 @@ fn pipe() {
 
 #[test]
 fn bit_strings() {
-    assert_infer!("let <<x>> = <<1>> x", "Int");
-    assert_infer!("let <<x>> = <<1>> x", "Int");
-    assert_infer!("let <<x:float>> = <<1>> x", "Float");
-    assert_infer!("let <<x:binary>> = <<1>> x", "BitString");
-    assert_infer!("let <<x:bytes>> = <<1>> x", "BitString");
-    assert_infer!("let <<x:bit_string>> = <<1>> x", "BitString");
-    assert_infer!("let <<x:bits>> = <<1>> x", "BitString");
-
-    assert_infer!("let <<x:utf8_codepoint>> = <<128013:32>> x", "UtfCodepoint");
+    assert_infer!("let assert <<x>> = <<1>> x", "Int");
+    assert_infer!("let assert <<x>> = <<1>> x", "Int");
+    assert_infer!("let assert <<x:float>> = <<1>> x", "Float");
+    assert_infer!("let assert <<x:binary>> = <<1>> x", "BitString");
+    assert_infer!("let assert <<x:bytes>> = <<1>> x", "BitString");
+    assert_infer!("let assert <<x:bit_string>> = <<1>> x", "BitString");
+    assert_infer!("let assert <<x:bits>> = <<1>> x", "BitString");
+
+    assert_infer!(
+        "let assert <<x:utf8_codepoint>> = <<128013:32>> x",
+        "UtfCodepoint"
+    );
     assert_infer!(
-        "let <<x:utf16_codepoint>> = <<128013:32>> x",
+        "let assert <<x:utf16_codepoint>> = <<128013:32>> x",
         "UtfCodepoint"
     );
     assert_infer!(
-        "let <<x:utf32_codepoint>> = <<128013:32>> x",
+        "let assert <<x:utf32_codepoint>> = <<128013:32>> x",
         "UtfCodepoint"
     );
 
     assert_infer!(
-        "let a = <<1>> let <<x:binary>> = <<1, a:2-bit_string>> x",
+        "let a = <<1>> let assert <<x:binary>> = <<1, a:2-bit_string>> x",
         "BitString"
     );
     assert_infer!(
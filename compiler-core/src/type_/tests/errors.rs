@@ -1865,3 +1865,53 @@ fn() {
 "
     );
 }
+
+#[test]
+fn errors_in_many_functions() {
+    assert_module_error!(
+        r#"
+pub fn one() -> Int { "one" }
+pub fn two() { one() + 2 }
+pub fn three() { 3 +. 3.0 }"#
+    );
+}
+
+#[test]
+fn function_with_error_does_not_cause_more_errors() {
+    // The function with an error takes its most general type, so it can be
+    // called with different types.
+    assert_module_error!(
+        r#"
+fn id(x) {
+  let _ = unknown
+  x
+}
+pub fn main() { #(id(1), id("one")) }"#
+    );
+}
+
+#[test]
+fn errors_in_many_definitions() {
+    // Errors are reported in the order they appear in the module, whichever
+    // pass over the module finds them.
+    assert_module_error!(
+        r#"
+import unknown
+type Thing { Thing(Unknown) }
+type Alias = List(Missing)
+pub fn main() -> Int { "main" }
+const one: Int = "one"
+fn main() { 1 }"#
+    );
+}
+
+#[test]
+fn constant_with_error_does_not_cause_more_errors() {
+    // The constant with an error takes a fresh type, so it can be used as any
+    // type.
+    assert_module_error!(
+        r#"
+const one: Int = "one"
+pub fn main() { #(one + 1, one <> "two") }"#
+    );
+}
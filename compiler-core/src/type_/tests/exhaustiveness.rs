@@ -0,0 +1,298 @@
+use crate::{
+    assert_infer_with_module, assert_module_error, assert_module_infer, assert_with_module_error,
+};
+
+#[test]
+fn nested_custom_types() {
+    assert_module_infer!(
+        r#"
+pub type Option(a) {
+  Some(a)
+  None
+}
+
+pub fn main(x) {
+  case x {
+    Ok(Some(_)) -> 1
+    Ok(None) -> 2
+    Error(_) -> 3
+  }
+}
+"#,
+        vec![
+            ("None", "Option(a)"),
+            ("Some", "fn(a) -> Option(a)"),
+            ("main", "fn(Result(Option(a), b)) -> Int")
+        ],
+    );
+}
+
+#[test]
+fn nested_custom_types_missing() {
+    assert_module_error!(
+        r#"
+pub type Option(a) {
+  Some(a)
+  None
+}
+
+pub fn main(x) {
+  case x {
+    Ok(Some(_)) -> 1
+    Error(_) -> 3
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn nested_bools_missing() {
+    assert_module_error!(
+        r#"
+pub fn main(x) {
+  case x {
+    Ok(True) -> 1
+    Error(False) -> 2
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn tuples() {
+    assert_module_infer!(
+        r#"
+pub fn main(x) {
+  case x {
+    #(True, _) -> 1
+    #(_, True) -> 2
+    #(False, False) -> 3
+  }
+}
+"#,
+        vec![("main", "fn(#(Bool, Bool)) -> Int")],
+    );
+}
+
+#[test]
+fn tuples_missing() {
+    assert_module_error!(
+        r#"
+pub fn main(x) {
+  case x {
+    #(True, _) -> 1
+    #(_, True) -> 2
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn lists() {
+    assert_module_infer!(
+        r#"
+pub fn main(x) {
+  case x {
+    [] -> 0
+    [_] -> 1
+    [_, _, ..] -> 2
+  }
+}
+"#,
+        vec![("main", "fn(List(a)) -> Int")],
+    );
+}
+
+#[test]
+fn lists_missing() {
+    assert_module_error!(
+        r#"
+pub fn main(x) {
+  case x {
+    [] -> 0
+    [_] -> 1
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn list_elements_missing() {
+    assert_module_error!(
+        r#"
+pub fn main(x) {
+  case x {
+    [] -> 0
+    [Ok(_), ..] -> 1
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn multiple_subjects() {
+    assert_module_infer!(
+        r#"
+pub fn main(x, y) {
+  case x, y {
+    True, _ -> 1
+    _, Ok(_) -> 2
+    False, Error(_) -> 3
+  }
+}
+"#,
+        vec![("main", "fn(Bool, Result(a, b)) -> Int")],
+    );
+}
+
+#[test]
+fn multiple_subjects_missing() {
+    assert_module_error!(
+        r#"
+pub fn main(x, y) {
+  case x, y {
+    True, _ -> 1
+    _, Ok(_) -> 2
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn alternative_patterns() {
+    assert_module_infer!(
+        r#"
+pub fn main(x) {
+  case x {
+    Ok(True) | Error(_) -> 1
+    Ok(False) -> 2
+  }
+}
+"#,
+        vec![("main", "fn(Result(Bool, a)) -> Int")],
+    );
+}
+
+#[test]
+fn ints_missing() {
+    assert_module_error!(
+        r#"
+pub fn main(x) {
+  case x {
+    0 -> "zero"
+    1 -> "one"
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn strings_missing() {
+    assert_module_error!(
+        r#"
+pub fn main(x) {
+  case x {
+    "hello" -> 1
+    "hi" <> _ -> 2
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn strings_with_fallback() {
+    assert_module_infer!(
+        r#"
+pub fn main(x) {
+  case x {
+    "hello" -> 1
+    "hi" <> _ -> 2
+    _ -> 3
+  }
+}
+"#,
+        vec![("main", "fn(String) -> Int")],
+    );
+}
+
+#[test]
+fn guards_can_fail() {
+    assert_module_error!(
+        r#"
+pub fn main(x) {
+  case x {
+    Ok(n) if n > 0 -> 1
+    Error(_) -> 2
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn let_nested_missing() {
+    assert_module_error!(
+        r#"
+pub fn main(x) {
+  let #(Ok(a), b) = x
+  a + b
+}
+"#
+    );
+}
+
+#[test]
+fn let_list_missing() {
+    assert_module_error!(
+        r#"
+pub fn main(x) {
+  let [a, b] = x
+  a + b
+}
+"#
+    );
+}
+
+#[test]
+fn imported_custom_types() {
+    assert_infer_with_module!(
+        ("option", "pub type Option(a) { Some(a) None }"),
+        r#"
+import option.{Some, None}
+
+pub fn main(x) {
+  case x {
+    Some(Ok(_)) -> 1
+    Some(Error(_)) -> 2
+    None -> 3
+  }
+}
+"#,
+        vec![("main", "fn(Option(Result(a, b))) -> Int")],
+    );
+}
+
+#[test]
+fn imported_custom_types_missing() {
+    assert_with_module_error!(
+        ("option", "pub type Option(a) { Some(a) None }"),
+        r#"
+import option
+
+pub fn main(x) {
+  case x {
+    option.Some(True) -> 1
+    option.None -> 2
+  }
+}
+"#,
+    );
+}
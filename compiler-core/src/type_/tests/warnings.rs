@@ -1,5 +1,5 @@
 use super::*;
-use crate::ast::TodoKind;
+use crate::ast::{Layer, TodoKind};
 use crate::{assert_no_warnings, assert_warning};
 
 #[test]
@@ -480,7 +480,7 @@ fn bit_pattern_var_use() {
     assert_no_warnings!(
         "
 pub fn main(x) {
-  let <<name_size:8, name:binary-size(name_size)>> = x
+  let assert <<name_size:8, name:binary-size(name_size)>> = x
   name
 }",
     );
@@ -563,3 +563,239 @@ fn double_unary_bool_variable() {
         "#
     );
 }
+
+#[test]
+fn unreachable_clause_after_discard() {
+    assert_warning!(
+        r#"
+pub fn main(x) {
+  case x {
+    _ -> 1
+    Ok(_) -> 2
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn unreachable_duplicate_empty_list_clause() {
+    assert_warning!(
+        r#"
+pub fn main(x) {
+  case x {
+    [] -> 1
+    [_, ..] -> 2
+    [] -> 3
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn unreachable_alternative_pattern() {
+    assert_warning!(
+        r#"
+pub fn main(x) {
+  case x {
+    Ok(_) | Ok(1) -> 1
+    Error(_) -> 2
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn unreachable_clause_shadowed_by_several_clauses() {
+    assert_warning!(
+        r#"
+pub fn main(x) {
+  case x {
+    Ok(_) -> 1
+    Error(_) -> 2
+    _ -> 3
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn unreachable_clause_with_multiple_subjects() {
+    assert_warning!(
+        r#"
+pub fn main(x, y) {
+  case x, y {
+    True, _ -> 1
+    False, "" -> 2
+    True, "" -> 3
+    _, _ -> 4
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn unreachable_duplicate_literal_clause() {
+    assert_warning!(
+        r#"
+pub fn main(x) {
+  case x {
+    1 -> 1
+    1 -> 2
+    _ -> 3
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn unreachable_int_clause_written_differently() {
+    assert_warning!(
+        r#"
+pub fn main(x) {
+  case x {
+    1000 -> 1
+    1_000 -> 2
+    0x3E8 -> 3
+    _ -> 4
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn unreachable_float_clause_written_differently() {
+    assert_warning!(
+        r#"
+pub fn main(x) {
+  case x {
+    1000.0 -> 1
+    1.0e3 -> 2
+    _ -> 3
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn clause_after_guard_is_reachable() {
+    assert_no_warnings!(
+        r#"
+pub fn main(x) {
+  case x {
+    _ if x > 1 -> 1
+    _ -> 2
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn deprecated_function_used_from_another_module() {
+    assert_warning!(
+        (
+            "gleam/foo",
+            r#"@deprecated("Use baz instead") pub fn bar() { 1 }"#
+        ),
+        "import gleam/foo pub fn main() { foo.bar() }",
+        Warning::DeprecatedItem {
+            location: SrcSpan { start: 33, end: 40 },
+            message: "Use baz instead".into(),
+            layer: Layer::Value,
+        }
+    );
+}
+
+#[test]
+fn deprecated_unqualified_function_used_from_another_module() {
+    assert_warning!(
+        (
+            "gleam/foo",
+            r#"@deprecated("Use baz instead") pub fn bar() { 1 }"#
+        ),
+        "import gleam/foo.{bar} pub fn main() { bar() }",
+        Warning::DeprecatedItem {
+            location: SrcSpan { start: 39, end: 42 },
+            message: "Use baz instead".into(),
+            layer: Layer::Value,
+        }
+    );
+}
+
+#[test]
+fn deprecated_type_used_from_another_module() {
+    assert_warning!(
+        (
+            "gleam/foo",
+            r#"@deprecated("Use Other instead") pub type Thing { Thing }"#
+        ),
+        "import gleam/foo pub fn main(x: foo.Thing) { x }",
+        Warning::DeprecatedItem {
+            location: SrcSpan { start: 32, end: 41 },
+            message: "Use Other instead".into(),
+            layer: Layer::Type,
+        }
+    );
+}
+
+#[test]
+fn deprecated_constructor_pattern_used_from_another_module() {
+    assert_warning!(
+        (
+            "gleam/foo",
+            r#"@deprecated("Use Other instead") pub type Thing { Thing }"#
+        ),
+        "import gleam/foo pub fn main(x) { case x { foo.Thing -> 1 } }",
+        Warning::DeprecatedItem {
+            location: SrcSpan { start: 43, end: 52 },
+            message: "Use Other instead".into(),
+            layer: Layer::Value,
+        }
+    );
+}
+
+#[test]
+fn deprecated_constant_used_from_another_module() {
+    assert_warning!(
+        (
+            "gleam/foo",
+            r#"@deprecated("Use y instead") pub const x = 1"#
+        ),
+        "import gleam/foo pub const z = foo.x",
+        Warning::DeprecatedItem {
+            location: SrcSpan { start: 31, end: 36 },
+            message: "Use y instead".into(),
+            layer: Layer::Value,
+        }
+    );
+}
+
+#[test]
+fn deprecated_items_used_in_their_own_module() {
+    assert_no_warnings!(
+        r#"
+@deprecated("Use baz instead")
+pub fn bar() { Thing }
+
+@deprecated("Use Other instead")
+pub type Thing { Thing }
+
+@deprecated("Use bar instead")
+pub external fn ext() -> Thing = "a" "b"
+
+pub fn main(x: Thing) {
+  case x {
+    Thing -> #(bar(), ext())
+  }
+}
+"#
+    );
+}
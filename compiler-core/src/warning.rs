@@ -1,5 +1,5 @@
 use crate::{
-    ast::TodoKind,
+    ast::{Layer, TodoKind},
     diagnostic::{self, Diagnostic, Location},
     type_,
 };
@@ -126,8 +126,16 @@ pub enum Warning {
 }
 
 impl Warning {
-    pub fn to_diagnostic(&self) -> Diagnostic {
+    /// The stable code identifying this kind of warning. See
+    /// `type_::Warning::code`.
+    pub fn code(&self) -> &'static str {
         match self {
+            Self::Type { warning, .. } => warning.code(),
+        }
+    }
+
+    pub fn to_diagnostic(&self) -> Diagnostic {
+        let diagnostic = match self {
             Self::Type { path, warning, src } => match warning {
                 type_::Warning::Todo {
                     kind,
@@ -153,12 +161,14 @@ expression.",
                         }
                     }
                     .into();
-                    if !typ.is_variable() {
-                        text.push_str(&format!(
-                            "\n\nHint: I think its type is `{}`.\n",
+                    let hint = if typ.is_variable() {
+                        None
+                    } else {
+                        Some(format!(
+                            "I think its type is `{}`.",
                             type_::pretty::Printer::new().pretty_print(typ, 0)
-                        ));
-                    }
+                        ))
+                    };
 
                     Diagnostic {
                         title,
@@ -173,7 +183,8 @@ expression.",
                             },
                             extra_labels: Vec::new(),
                         }),
-                        hint: None,
+                        hint,
+                        code: None,
                     }
                 }
 
@@ -191,6 +202,7 @@ expression.",
                         },
                         extra_labels: Vec::new(),
                     }),
+                    code: None,
                 },
 
                 type_::Warning::UnusedLiteral { location } => Diagnostic {
@@ -207,6 +219,7 @@ expression.",
                         },
                         extra_labels: Vec::new(),
                     }),
+                    code: None,
                 },
 
                 type_::Warning::NoFieldsRecordUpdate { location } => Diagnostic {
@@ -225,6 +238,7 @@ expression.",
                         },
                         extra_labels: Vec::new(),
                     }),
+                    code: None,
                 },
 
                 type_::Warning::AllFieldsRecordUpdate { location } => Diagnostic {
@@ -241,6 +255,7 @@ expression.",
                         },
                         extra_labels: Vec::new(),
                     }),
+                    code: None,
                 },
 
                 type_::Warning::UnusedType {
@@ -270,6 +285,7 @@ expression.",
                             },
                             extra_labels: Vec::new(),
                         }),
+                        code: None,
                     }
                 }
 
@@ -300,6 +316,7 @@ expression.",
                             },
                             extra_labels: Vec::new(),
                         }),
+                        code: None,
                     }
                 }
 
@@ -317,6 +334,7 @@ expression.",
                         },
                         extra_labels: Vec::new(),
                     }),
+                    code: None,
                 },
 
                 type_::Warning::UnusedImportedValue { location, .. } => Diagnostic {
@@ -333,6 +351,7 @@ expression.",
                         },
                         extra_labels: Vec::new(),
                     }),
+                    code: None,
                 },
 
                 type_::Warning::UnusedPrivateModuleConstant { location, .. } => Diagnostic {
@@ -349,6 +368,7 @@ expression.",
                         },
                         extra_labels: Vec::new(),
                     }),
+                    code: None,
                 },
 
                 type_::Warning::UnusedPrivateFunction { location, .. } => Diagnostic {
@@ -365,6 +385,7 @@ expression.",
                         },
                         extra_labels: Vec::new(),
                     }),
+                    code: None,
                 },
 
                 type_::Warning::UnusedVariable { location, name, .. } => Diagnostic {
@@ -381,6 +402,7 @@ expression.",
                         },
                         extra_labels: Vec::new(),
                     }),
+                    code: None,
                 },
                 type_::Warning::UnnecessaryDoubleIntNegation { location } => Diagnostic {
                     title: "Unnecessary double negation (--) on integer.".into(),
@@ -396,6 +418,7 @@ expression.",
                         },
                         extra_labels: Vec::new(),
                     }),
+                    code: None,
                 },
                 type_::Warning::UnnecessaryDoubleBoolNegation { location } => Diagnostic {
                     title: "Unnecessary double negation (!!) on bool.".into(),
@@ -411,8 +434,65 @@ expression.",
                         },
                         extra_labels: Vec::new(),
                     }),
+                    code: None,
+                },
+
+                type_::Warning::UnreachableCaseClause {
+                    location,
+                    shadowed_by,
+                } => Diagnostic {
+                    title: "Unreachable case clause".into(),
+                    text: "This pattern can never match as all the values it matches
+are already matched by the clauses before it."
+                        .into(),
+                    hint: Some("You can safely remove it.".into()),
+                    level: diagnostic::Level::Warning,
+                    location: Some(Location {
+                        src: src.clone(),
+                        path: path.to_path_buf(),
+                        label: diagnostic::Label {
+                            text: Some("This pattern is never matched".into()),
+                            span: *location,
+                        },
+                        extra_labels: vec![diagnostic::Label {
+                            text: Some("Its values are matched by this clause".into()),
+                            span: *shadowed_by,
+                        }],
+                    }),
+                    code: None,
                 },
+
+                type_::Warning::DeprecatedItem {
+                    location,
+                    message,
+                    layer,
+                } => {
+                    let kind = match layer {
+                        Layer::Value => "value",
+                        Layer::Type => "type",
+                    };
+                    Diagnostic {
+                        title: format!("Deprecated {kind} used"),
+                        text: format!("This {kind} has been deprecated: {message}"),
+                        hint: None,
+                        level: diagnostic::Level::Warning,
+                        location: Some(Location {
+                            src: src.clone(),
+                            path: path.to_path_buf(),
+                            label: diagnostic::Label {
+                                text: Some("This has been deprecated".into()),
+                                span: *location,
+                            },
+                            extra_labels: Vec::new(),
+                        }),
+                        code: None,
+                    }
+                }
             },
+        };
+        Diagnostic {
+            code: Some(self.code()),
+            ..diagnostic
         }
     }
 
@@ -0,0 +1,79 @@
+-module('{{ application }}@@test').
+
+-export([run/1]).
+
+run(Tests) ->
+    io:setopts(standard_io, [binary, {encoding, utf8}]),
+    io:setopts(standard_error, [{encoding, utf8}]),
+    try
+        {ok, _} = application:ensure_all_started('{{ application }}'),
+        lists:foreach(fun run_test/1, Tests),
+        erlang:halt(0)
+    catch
+        Class:Reason:StackTrace ->
+            io:put_chars(format_error(Class, Reason, StackTrace)),
+            erlang:halt(127, [{flush, true}])
+    end.
+
+run_test({Module, Function}) ->
+    Start = erlang:monotonic_time(microsecond),
+    {Status, Message} =
+        try Module:Function() of
+            _ -> {<<"passed">>, <<>>}
+        catch
+            Class:Reason:StackTrace ->
+                {status(Class, Reason), format_error(Class, Reason, StackTrace)}
+        end,
+    Duration = erlang:monotonic_time(microsecond) - Start,
+    io:put_chars([
+        <<16#1E/utf8>>, Status, $\t,
+        atom_to_binary(Module, utf8), $\t,
+        atom_to_binary(Function, utf8), $\t,
+        integer_to_binary(Duration), $\t,
+        escape(Message), $\n
+    ]).
+
+% Gleam's `let assert` and the eunit assertion macros used by test libraries
+% such as gleeunit fail a test, any other exception means the test crashed.
+status(error, #{gleam_error := assert}) ->
+    <<"failed">>;
+status(error, {Assertion, _}) when
+    Assertion =:= assert;
+    Assertion =:= assertNot;
+    Assertion =:= assertEqual;
+    Assertion =:= assertNotEqual;
+    Assertion =:= assertMatch;
+    Assertion =:= assertNotMatch;
+    Assertion =:= assertException;
+    Assertion =:= assertNotException
+->
+    <<"failed">>;
+status(_, _) ->
+    <<"crashed">>.
+
+escape(Message) ->
+    lists:foldl(
+        fun({Pattern, Replacement}, Acc) ->
+            binary:replace(Acc, Pattern, Replacement, [global])
+        end,
+        unicode:characters_to_binary(Message),
+        [{<<"\\">>, <<"\\\\">>}, {<<"\n">>, <<"\\n">>}, {<<"\t">>, <<"\\t">>}]
+    ).
+
+format_error(Class, Reason, StackTrace) ->
+    erl_error:format_exception(
+        1, Class, Reason, StackTrace, fun stack_filter/3,
+        fun print_stack_frame/2, unicode
+    ).
+
+stack_filter(Module, _F, _A) ->
+    case Module of
+        ?MODULE -> true;
+        erl_eval -> true;
+        init -> true;
+        lists -> true;
+        _ -> false
+    end.
+
+print_stack_frame(Term, I) ->
+    io_lib:format("~." ++ integer_to_list(I) ++ "tP", [Term, 50]).
@@ -0,0 +1,41 @@
+{% for test in tests -%}
+import { {{ test.name }} as test{{ loop.index0 }} } from "./{{ test.module }}.mjs";
+{% endfor %}
+const tests = [
+{%- for test in tests %}
+  ["{{ test.module }}", "{{ test.name }}", test{{ loop.index0 }}],
+{%- endfor %}
+];
+
+// Gleam's `let assert` fails a test, any other exception means the test
+// crashed.
+function statusOf(error) {
+  return error?.gleam_error === "assignment_no_match" ? "failed" : "crashed";
+}
+
+function format(error) {
+  return error instanceof globalThis.Error ? String(error.stack) : String(error);
+}
+
+function escape(message) {
+  return message
+    .replaceAll("\\", "\\\\")
+    .replaceAll("\n", "\\n")
+    .replaceAll("\t", "\\t");
+}
+
+for (const [module, name, test] of tests) {
+  const start = performance.now();
+  let status = "passed";
+  let message = "";
+  try {
+    await test();
+  } catch (error) {
+    status = statusOf(error);
+    message = format(error);
+  }
+  const duration = Math.round((performance.now() - start) * 1000);
+  console.log(
+    `\x1e${status}\t${module}\t${name}\t${duration}\t${escape(message)}`
+  );
+}
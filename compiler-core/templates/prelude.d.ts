@@ -31,8 +31,20 @@ export interface BitString {
   inspect(): string;
   byteAt(index: number): number;
   floatAt(index: number): number;
-  intFromSlice(start: number, end: number): number;
+  floatFromSlice(start: number, end: number, isBigEndian: boolean): number;
+  intFromSlice(
+    start: number,
+    end: number,
+    isBigEndian?: boolean,
+    isSigned?: boolean
+  ): number;
+  binaryFromSlice(start: number, end: number): BitString;
   sliceAfter(index: number): BitString;
+  utf8CodepointAt(index: number): Utf8Codepoint | undefined;
+  utf8CodepointSize(index: number): number;
+  utf16CodepointAt(index: number): Utf8Codepoint | undefined;
+  utf16CodepointSize(index: number): number;
+  utf32CodepointAt(index: number): Utf8Codepoint | undefined;
 }
 
 export interface Utf8Codepoint {
@@ -42,12 +54,38 @@ export interface Utf8Codepoint {
 
 export function toBitString(segments: Array<number | Uint8Array>): BitString;
 
-export function sizedInt(number: number, size: number): BitString;
+export function sizedInt(
+  number: number,
+  size: number,
+  isBigEndian?: boolean
+): Uint8Array;
+
+export function sizedFloat(
+  float: number,
+  size: number,
+  isBigEndian?: boolean
+): Uint8Array;
+
+export function sizedBitString(bitString: BitString, size: number): Uint8Array;
 
 export function stringBits(string: string): Uint8Array;
 
 export function codepointBits(codepoint: Utf8Codepoint): Uint8Array;
 
+export function stringToUtf16(string: string, isBigEndian?: boolean): Uint8Array;
+
+export function codepointToUtf16(
+  codepoint: Utf8Codepoint,
+  isBigEndian?: boolean
+): Uint8Array;
+
+export function stringToUtf32(string: string, isBigEndian?: boolean): Uint8Array;
+
+export function codepointToUtf32(
+  codepoint: Utf8Codepoint,
+  isBigEndian?: boolean
+): Uint8Array;
+
 export function float64Bits(float: number): Uint8Array;
 
 export interface Result<T, E> {
@@ -139,13 +139,77 @@ export class BitString {
     return byteArrayToFloat(this.buffer.slice(index, index + 8));
   }
 
-  intFromSlice(start, end) {
-    return byteArrayToInt(this.buffer.slice(start, end));
+  floatFromSlice(start, end, isBigEndian) {
+    return byteArrayToFloat(this.buffer.slice(start, end), isBigEndian);
+  }
+
+  intFromSlice(start, end, isBigEndian = true, isSigned = false) {
+    return byteArrayToInt(this.buffer.slice(start, end), isBigEndian, isSigned);
+  }
+
+  binaryFromSlice(start, end) {
+    return new BitString(this.buffer.slice(start, end));
   }
 
   sliceAfter(index) {
     return new BitString(this.buffer.slice(index));
   }
+
+  // The codepoint encoded at the index, or undefined if the bytes there are
+  // not a valid encoding of one. Codepoint segments are always big endian.
+  utf8CodepointAt(index) {
+    const size = this.utf8CodepointSize(index);
+    let value = this.buffer[index] & (size === 1 ? 0x7f : 0xff >> (size + 1));
+    for (let i = index + 1; i < index + size; i++) {
+      const byte = this.buffer[i];
+      if (byte === undefined || (byte & 0xc0) !== 0x80) return undefined;
+      value = (value << 6) | (byte & 0x3f);
+    }
+    const smallest = [0, 0, 0x80, 0x800, 0x10000][size];
+    if (size === 0 || value < smallest) return undefined;
+    return codepointAt(value);
+  }
+
+  // The number of bytes the codepoint at the index is encoded with, taken
+  // from its first byte.
+  utf8CodepointSize(index) {
+    const byte = this.buffer[index];
+    if (byte === undefined) return 0;
+    if (byte < 0x80) return 1;
+    if ((byte & 0xe0) === 0xc0) return 2;
+    if ((byte & 0xf0) === 0xe0) return 3;
+    if ((byte & 0xf8) === 0xf0) return 4;
+    return 0;
+  }
+
+  utf16CodepointAt(index) {
+    if (index + 2 > this.buffer.length) return undefined;
+    const size = this.utf16CodepointSize(index);
+    const high = this.intFromSlice(index, index + 2);
+    if (size === 2) return codepointAt(high);
+    if (index + 4 > this.buffer.length) return undefined;
+    const low = this.intFromSlice(index + 2, index + 4);
+    if (low < 0xdc00 || low > 0xdfff) return undefined;
+    return codepointAt(0x10000 + ((high - 0xd800) << 10) + (low - 0xdc00));
+  }
+
+  // A codepoint takes 4 bytes when it is encoded as a surrogate pair.
+  utf16CodepointSize(index) {
+    const unit = this.intFromSlice(index, index + 2);
+    return unit >= 0xd800 && unit <= 0xdbff ? 4 : 2;
+  }
+
+  utf32CodepointAt(index) {
+    if (index + 4 > this.buffer.length) return undefined;
+    return codepointAt(this.intFromSlice(index, index + 4));
+  }
+}
+
+// Surrogates and values past the last codepoint are not valid codepoints.
+function codepointAt(value) {
+  const isSurrogate = value >= 0xd800 && value <= 0xdfff;
+  if (isNaN(value) || value > 0x10ffff || isSurrogate) return undefined;
+  return new UtfCodepoint(value);
 }
 
 export class UtfCodepoint {
@@ -181,13 +245,13 @@ export function toBitString(segments) {
 }
 
 // Derived from this answer https://stackoverflow.com/questions/8482309/converting-javascript-integer-to-byte-array-and-back
-export function sizedInt(int, size) {
+export function sizedInt(int, size, isBigEndian = true) {
   let value = int;
-  if (size < 0) {
+  if (size <= 0) {
     return new Uint8Array();
   }
   if (size % 8 != 0) {
-    throw "Needs to be a byte size" + size;
+    throw "Needs to be a byte size " + size;
   }
   const byteArray = new Uint8Array(size / 8);
 
@@ -196,20 +260,34 @@ export function sizedInt(int, size) {
     byteArray[index] = byte;
     value = (value - byte) / 256;
   }
-  return byteArray.reverse();
+  return isBigEndian ? byteArray.reverse() : byteArray;
 }
 
-export function byteArrayToInt(byteArray) {
-  byteArray = byteArray.reverse();
+export function byteArrayToInt(byteArray, isBigEndian = true, isSigned = false) {
   let value = 0;
-  for (let i = byteArray.length - 1; i >= 0; i--) {
-    value = value * 256 + byteArray[i];
+  for (let i = 0; i < byteArray.length; i++) {
+    const byte = isBigEndian ? byteArray[i] : byteArray[byteArray.length - 1 - i];
+    value = value * 256 + byte;
+  }
+  if (isSigned && byteArray.length > 0) {
+    const max = Math.pow(256, byteArray.length);
+    if (value >= max / 2) {
+      value -= max;
+    }
   }
   return value;
 }
 
-export function byteArrayToFloat(byteArray) {
-  return new Float64Array(byteArray.reverse().buffer)[0];
+export function byteArrayToFloat(byteArray, isBigEndian = true) {
+  const view = new DataView(byteArray.buffer, byteArray.byteOffset);
+  switch (byteArray.length) {
+    case 4:
+      return view.getFloat32(0, !isBigEndian);
+    case 8:
+      return view.getFloat64(0, !isBigEndian);
+    default:
+      throw "Float segments must be 32 or 64 bits, got " + byteArray.length * 8;
+  }
 }
 
 export function stringBits(string) {
@@ -220,10 +298,60 @@ export function codepointBits(codepoint) {
   return stringBits(String.fromCodePoint(codepoint.value));
 }
 
+export function stringToUtf16(string, isBigEndian = true) {
+  const view = new DataView(new ArrayBuffer(string.length * 2));
+  for (let i = 0; i < string.length; i++) {
+    view.setUint16(i * 2, string.charCodeAt(i), !isBigEndian);
+  }
+  return new Uint8Array(view.buffer);
+}
+
+export function codepointToUtf16(codepoint, isBigEndian = true) {
+  return stringToUtf16(String.fromCodePoint(codepoint.value), isBigEndian);
+}
+
+export function stringToUtf32(string, isBigEndian = true) {
+  const codepoints = Array.from(string, (char) => char.codePointAt(0));
+  const view = new DataView(new ArrayBuffer(codepoints.length * 4));
+  codepoints.forEach((codepoint, i) =>
+    view.setUint32(i * 4, codepoint, !isBigEndian)
+  );
+  return new Uint8Array(view.buffer);
+}
+
+export function codepointToUtf32(codepoint, isBigEndian = true) {
+  return stringToUtf32(String.fromCodePoint(codepoint.value), isBigEndian);
+}
+
 export function float64Bits(float) {
   return new Uint8Array(Float64Array.from([float]).buffer).reverse();
 }
 
+export function sizedFloat(float, size, isBigEndian = true) {
+  const view = new DataView(new ArrayBuffer(size / 8));
+  switch (size) {
+    case 32:
+      view.setFloat32(0, float, !isBigEndian);
+      break;
+    case 64:
+      view.setFloat64(0, float, !isBigEndian);
+      break;
+    default:
+      throw "Float segments must be 32 or 64 bits, got " + size;
+  }
+  return new Uint8Array(view.buffer);
+}
+
+export function sizedBitString(bitString, size) {
+  if (size % 8 != 0) {
+    throw "Needs to be a byte size " + size;
+  }
+  if (bitString.length * 8 < size) {
+    throw "Bit string is smaller than the segment size " + size;
+  }
+  return bitString.buffer.slice(0, size / 8);
+}
+
 export class Result extends CustomType {
   static isResult(data) {
     let variant = data?.__gleam_prelude_variant__;
@@ -80,6 +80,7 @@ fn manifest_from_name(name: &str) -> ManifestPackage {
         requirements: vec![],
         source: ManifestPackageSource::Hex {
             outer_checksum: Base16Checksum(vec![]),
+            repository: "hexpm".into(),
         },
     }
 }
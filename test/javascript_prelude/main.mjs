@@ -8,11 +8,18 @@ import {
   Ok,
   UtfCodepoint,
   codepointBits,
+  codepointToUtf16,
+  codepointToUtf32,
   divideFloat,
   divideInt,
   inspect,
   isEqual,
+  sizedBitString,
+  sizedFloat,
+  sizedInt,
   stringBits,
+  stringToUtf16,
+  stringToUtf32,
   toBitString,
   toList,
 } from "./prelude.mjs";
@@ -430,6 +437,122 @@ assertEqual(
   new BitString(new Uint8Array([1, 2, 3])).sliceAfter(1),
   new BitString(new Uint8Array([2, 3]))
 );
+assertEqual(
+  new BitString(new Uint8Array([1, 2, 3])).intFromSlice(0, 2, false, false),
+  513
+);
+assertEqual(
+  new BitString(new Uint8Array([255, 254])).intFromSlice(0, 2, true, true),
+  -2
+);
+assertEqual(
+  new BitString(new Uint8Array([254, 255])).intFromSlice(0, 2, false, true),
+  -2
+);
+
+assertEqual(
+  new BitString(new Uint8Array([97, 240, 159, 144, 141])).utf8CodepointAt(0),
+  new UtfCodepoint(97)
+);
+assertEqual(
+  new BitString(new Uint8Array([97, 240, 159, 144, 141])).utf8CodepointAt(1),
+  new UtfCodepoint(128013)
+);
+assertEqual(
+  new BitString(new Uint8Array([240, 159, 144, 141])).utf8CodepointSize(0),
+  4
+);
+assertEqual(
+  new BitString(new Uint8Array([240, 159, 144])).utf8CodepointAt(0),
+  undefined
+);
+assertEqual(new BitString(new Uint8Array([128])).utf8CodepointAt(0), undefined);
+assertEqual(
+  new BitString(new Uint8Array([192, 128])).utf8CodepointAt(0),
+  undefined
+);
+assertEqual(
+  new BitString(new Uint8Array([237, 160, 128])).utf8CodepointAt(0),
+  undefined
+);
+assertEqual(
+  new BitString(new Uint8Array([216, 61, 220, 13])).utf16CodepointAt(0),
+  new UtfCodepoint(128013)
+);
+assertEqual(
+  new BitString(new Uint8Array([216, 61, 220, 13])).utf16CodepointSize(0),
+  4
+);
+assertEqual(
+  new BitString(new Uint8Array([0, 97])).utf16CodepointAt(0),
+  new UtfCodepoint(97)
+);
+assertEqual(
+  new BitString(new Uint8Array([216, 61])).utf16CodepointAt(0),
+  undefined
+);
+assertEqual(
+  new BitString(new Uint8Array([0, 1, 244, 13])).utf32CodepointAt(0),
+  new UtfCodepoint(128013)
+);
+assertEqual(
+  new BitString(new Uint8Array([0, 17, 0, 0])).utf32CodepointAt(0),
+  undefined
+);
+assertEqual(
+  new BitString(new Uint8Array([0, 0, 128, 63])).floatFromSlice(0, 4, false),
+  1.0
+);
+assertEqual(
+  new BitString(new Uint8Array([1, 2, 3])).binaryFromSlice(1, 2),
+  new BitString(new Uint8Array([2]))
+);
+
+// Sized segments
+
+assertEqual(
+  new BitString(new Uint8Array([1, 2])),
+  toBitString([sizedInt(258, 16, true)])
+);
+assertEqual(
+  new BitString(new Uint8Array([2, 1])),
+  toBitString([sizedInt(258, 16, false)])
+);
+assertEqual(
+  new BitString(new Uint8Array([255, 254])),
+  toBitString([sizedInt(-2, 16, true)])
+);
+assertEqual(
+  new BitString(new Uint8Array([63, 128, 0, 0])),
+  toBitString([sizedFloat(1.0, 32, true)])
+);
+assertEqual(
+  new BitString(new Uint8Array([0, 0, 0, 0, 0, 0, 240, 63])),
+  toBitString([sizedFloat(1.0, 64, false)])
+);
+assertEqual(
+  new BitString(new Uint8Array([1, 2])),
+  toBitString([sizedBitString(new BitString(new Uint8Array([1, 2, 3])), 16)])
+);
+
+// UTF16 and UTF32 segments
+
+assertEqual(
+  new BitString(new Uint8Array([0, 97, 216, 61, 220, 13])),
+  toBitString([stringToUtf16("a🐍", true)])
+);
+assertEqual(
+  new BitString(new Uint8Array([97, 0])),
+  toBitString([codepointToUtf16(new UtfCodepoint(97), false)])
+);
+assertEqual(
+  new BitString(new Uint8Array([0, 0, 0, 97, 0, 1, 244, 13])),
+  toBitString([stringToUtf32("a🐍", true)])
+);
+assertEqual(
+  new BitString(new Uint8Array([13, 244, 1, 0])),
+  toBitString([codepointToUtf32(new UtfCodepoint(128013), false)])
+);
 
 assertEqual(inspect(new UtfCodepoint(128013)), "//utfcodepoint(🐍)");
 